@@ -1,10 +1,12 @@
 use crate::{
     error::{ParseMessageError, Result, error::StreamClosedSnafu},
     models::{AisMessage, AisMessageType, AisPosition, AisStatic, MessageType},
+    nmea::NmeaDecoder,
 };
 use async_channel::Sender;
+use chrono::Utc;
 use futures::StreamExt;
-use kyogre_core::{DataMessage, NewAisStatic};
+use kyogre_core::{AisRejectionCategory, DataMessage, NewAisRejectedMessage, NewAisStatic};
 use tokio::io::AsyncRead;
 use tokio_util::codec::{FramedRead, LinesCodec, LinesCodecError};
 use tracing::{error, instrument};
@@ -13,6 +15,20 @@ pub struct Consumer {
     commit_interval: std::time::Duration,
 }
 
+/// The format of the lines read from an ais source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageFormat {
+    /// Line-delimited json messages from Barentswatch.
+    Json,
+    /// Raw NMEA 0183 `!AIVDM` sentences.
+    Nmea,
+}
+
+enum Decoder {
+    Json,
+    Nmea(NmeaDecoder),
+}
+
 impl Consumer {
     pub fn new(commit_interval: std::time::Duration) -> Consumer {
         Consumer { commit_interval }
@@ -21,8 +37,11 @@ impl Consumer {
     pub async fn run(
         &self,
         source: impl AsyncRead + Unpin,
+        format: MessageFormat,
         sender: Sender<DataMessage>,
     ) -> Result<()> {
+        let mut decoder = Decoder::new(format);
+
         let codec = LinesCodec::new_with_max_length(1000);
         let mut framed_read = FramedRead::new(source, codec);
 
@@ -38,13 +57,13 @@ impl Consumer {
                     match message {
                         Some(message) => buffer.push(message),
                         None => {
-                            process_messages(buffer.drain(..), &sender).await?;
+                            process_messages(buffer.drain(..), &mut decoder, &sender).await?;
                             return StreamClosedSnafu{}.fail();
                         },
                     }
                 }
                 _ = interval.tick() => {
-                    process_messages(buffer.drain(..), &sender).await?;
+                    process_messages(buffer.drain(..), &mut decoder, &sender).await?;
                 }
             }
         }
@@ -52,28 +71,64 @@ impl Consumer {
 }
 
 // Only returns an error if the receiver half of the sender closes, or if we get an io::Error
-#[instrument(skip(messages, sender), fields(app.num_messages))]
-async fn process_messages<T>(messages: T, sender: &Sender<DataMessage>) -> Result<()>
+#[instrument(
+    skip(messages, decoder, sender),
+    fields(app.num_messages, app.num_rejected_messages)
+)]
+async fn process_messages<T>(
+    messages: T,
+    decoder: &mut Decoder,
+    sender: &Sender<DataMessage>,
+) -> Result<()>
 where
     T: IntoIterator<Item = std::result::Result<String, LinesCodecError>>,
 {
     let mut data_message = DataMessage::default();
     let mut num_messages = 0;
+    let received_at = Utc::now();
+    let rejected = |category, message, error| NewAisRejectedMessage {
+        category,
+        message,
+        error,
+        received_at,
+    };
+
     for message in messages {
         num_messages += 1;
         match message {
             Err(e) => match e {
                 LinesCodecError::MaxLineLengthExceeded => {
-                    error!("failed to consume ais message: {e:?}")
+                    error!("failed to consume ais message: {e:?}");
+                    // The codec discards the content of lines that are too long
+                    data_message.rejected_messages.push(rejected(
+                        AisRejectionCategory::MaxLineLengthExceeded,
+                        String::new(),
+                        format!("{e:?}"),
+                    ));
                 }
                 LinesCodecError::Io(error) => return Err(error.into()),
             },
-            Ok(original) => match parse_message(&original) {
-                Err(e) => error!("failed to parse message: {e:?}, message: '{original}'"),
-                Ok(message) => match message {
+            Ok(original) => match decoder.decode(&original) {
+                Err(e) => {
+                    error!("failed to parse message: {e:?}, message: '{original}'");
+                    data_message.rejected_messages.push(rejected(
+                        e.rejection_category(),
+                        original,
+                        format!("{e:?}"),
+                    ));
+                }
+                Ok(None) => {}
+                Ok(Some(message)) => match message {
                     AisMessage::Static(m) => match NewAisStatic::try_from(m) {
                         Err(e) => {
-                            error!("failed to convert static message: {e:?}, message: '{original}'")
+                            error!(
+                                "failed to convert static message: {e:?}, message: '{original}'"
+                            );
+                            data_message.rejected_messages.push(rejected(
+                                AisRejectionCategory::InvalidStatic,
+                                original,
+                                format!("{e:?}"),
+                            ));
                         }
                         Ok(d) => data_message.static_messages.push(d),
                     },
@@ -91,14 +146,37 @@ where
         return Ok(());
     }
 
+    let num_rejected_messages = data_message.rejected_messages.len();
+
     // Can only fail if the channel is closed.
     sender.send(data_message).await?;
 
-    tracing::Span::current().record("app.num_messages", num_messages);
+    let span = tracing::Span::current();
+    span.record("app.num_messages", num_messages);
+    span.record("app.num_rejected_messages", num_rejected_messages);
 
     Ok(())
 }
 
+impl Decoder {
+    fn new(format: MessageFormat) -> Decoder {
+        match format {
+            MessageFormat::Json => Decoder::Json,
+            MessageFormat::Nmea => Decoder::Nmea(NmeaDecoder::new()),
+        }
+    }
+
+    fn decode(
+        &mut self,
+        message: &str,
+    ) -> std::result::Result<Option<AisMessage>, ParseMessageError> {
+        match self {
+            Decoder::Json => parse_message(message).map(Some),
+            Decoder::Nmea(decoder) => Ok(decoder.decode(message)?),
+        }
+    }
+}
+
 fn parse_message(message: &str) -> std::result::Result<AisMessage, ParseMessageError> {
     let message_type: MessageType = serde_json::from_str(message)?;
 
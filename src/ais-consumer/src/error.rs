@@ -1,5 +1,5 @@
 use async_channel::{RecvError, SendError};
-use kyogre_core::{DataMessage, IsTimeout, OauthError};
+use kyogre_core::{AisRejectionCategory, DataMessage, IsTimeout, OauthError};
 use reqwest::StatusCode;
 use snafu::{Location, Snafu};
 use stack_error::StackError;
@@ -31,6 +31,25 @@ impl IsTimeout for Error {
                 error: _,
             } => false,
             Error::Io { location: _, error } => error.is_timeout(),
+            Error::InvalidReplaySpeed {
+                location: _,
+                speed: _,
+            } => false,
+        }
+    }
+}
+
+impl ParseMessageError {
+    pub fn rejection_category(&self) -> AisRejectionCategory {
+        match self {
+            ParseMessageError::Json {
+                location: _,
+                error: _,
+            } => AisRejectionCategory::InvalidJson,
+            ParseMessageError::Nmea {
+                location: _,
+                source: _,
+            } => AisRejectionCategory::InvalidNmea,
         }
     }
 }
@@ -45,6 +64,58 @@ pub enum ParseMessageError {
         #[snafu(source)]
         error: serde_json::Error,
     },
+    #[snafu(display("Nmea error"))]
+    Nmea {
+        #[snafu(implicit)]
+        location: Location,
+        source: NmeaError,
+    },
+}
+
+#[derive(Snafu, StackError)]
+#[snafu(module, visibility(pub))]
+pub enum NmeaError {
+    #[snafu(display("Encountered an invalid nmea sentence '{sentence}'"))]
+    InvalidSentence {
+        #[snafu(implicit)]
+        location: Location,
+        sentence: String,
+    },
+    #[snafu(display("Nmea checksum mismatch, expected: '{expected}', actual: '{actual}'"))]
+    Checksum {
+        #[snafu(implicit)]
+        location: Location,
+        expected: u8,
+        actual: u8,
+    },
+    #[snafu(display("Encountered an out of order or orphaned fragment '{sentence}'"))]
+    InvalidFragment {
+        #[snafu(implicit)]
+        location: Location,
+        sentence: String,
+    },
+    #[snafu(display("Encountered an invalid payload character '{character}'"))]
+    InvalidPayloadCharacter {
+        #[snafu(implicit)]
+        location: Location,
+        character: char,
+    },
+    #[snafu(display("Encountered an invalid message type '{message_type}'"))]
+    InvalidMessageType {
+        #[snafu(implicit)]
+        location: Location,
+        message_type: u32,
+    },
+    #[snafu(display(
+        "Payload of message type '{message_type}' was too short, expected: '{expected}' bits, actual: '{actual}'"
+    ))]
+    PayloadTooShort {
+        #[snafu(implicit)]
+        location: Location,
+        message_type: u32,
+        expected: usize,
+        actual: usize,
+    },
 }
 
 #[derive(Snafu, StackError)]
@@ -97,6 +168,12 @@ pub enum Error {
         #[snafu(source)]
         error: SendError<DataMessage>,
     },
+    #[snafu(display("Replay speed must be a positive number, got: '{speed}'"))]
+    InvalidReplaySpeed {
+        #[snafu(implicit)]
+        location: Location,
+        speed: f64,
+    },
 }
 
 #[derive(Snafu, StackError)]
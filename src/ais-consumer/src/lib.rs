@@ -1,12 +1,14 @@
 #![deny(warnings)]
 #![deny(rust_2018_idioms)]
 
-//! Implements a binary that continously consume an input ais-stream from Kysteverket and adds it to our postgres
-//! database.
+//! Implements a binary that continously consume an input ais-stream from Kysteverket, or raw NMEA
+//! sentences from our own receivers, and adds it to our postgres database.
 
 pub mod barentswatch;
 pub mod consumer;
 pub mod error;
 pub mod models;
+pub mod nmea;
+pub mod replay;
 pub mod settings;
 pub mod startup;
@@ -7,7 +7,7 @@ async fn main() {
 
     let settings = Settings::new(settings).unwrap();
 
-    let app = App::build(settings).await;
+    let app = App::build(settings).await.unwrap();
 
     app.run().await;
 }
@@ -0,0 +1,676 @@
+//! Decoder for raw NMEA 0183 `!AIVDM`/`!AIVDO` sentences.
+//! See https://gpsd.gitlab.io/gpsd/AIVDM.html for a description of the format.
+use crate::{
+    error::{
+        NmeaError,
+        nmea_error::{
+            ChecksumSnafu, InvalidFragmentSnafu, InvalidMessageTypeSnafu,
+            InvalidPayloadCharacterSnafu, InvalidSentenceSnafu, PayloadTooShortSnafu,
+        },
+    },
+    models::{AisMessage, AisMessageType, AisPosition, AisStatic},
+};
+use chrono::{DateTime, Utc};
+use kyogre_core::{AisClass, Mmsi, NavigationStatus};
+use num_traits::FromPrimitive;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+const POSITION_BITS: usize = 168;
+const STATIC_AND_VOYAGE_BITS: usize = 424;
+const EXTENDED_CLASS_B_BITS: usize = 312;
+const STATIC_DATA_REPORT_A_BITS: usize = 160;
+const STATIC_DATA_REPORT_B_BITS: usize = 162;
+
+const LONGITUDE_NOT_AVAILABLE: i64 = 181 * 600_000;
+const LATITUDE_NOT_AVAILABLE: i64 = 91 * 600_000;
+const SPEED_NOT_AVAILABLE: u32 = 1023;
+const COURSE_NOT_AVAILABLE: u32 = 3600;
+const HEADING_NOT_AVAILABLE: u32 = 511;
+const RATE_OF_TURN_NOT_AVAILABLE: i64 = -128;
+
+/// The highest message type defined by ITU-R M.1371, higher (and zero) message types can only
+/// stem from corrupted payloads.
+const MAX_MESSAGE_TYPE: u32 = 27;
+
+/// How long the first fragments of a multi-fragment message are kept while waiting for the
+/// remaining fragments.
+const FRAGMENT_TIMEOUT: Duration = Duration::from_secs(60);
+/// The maximum number of incomplete multi-fragment messages that are buffered, the oldest
+/// message is discarded when exceeded.
+const MAX_PENDING_FRAGMENTS: usize = 1024;
+
+/// Stateful decoder of NMEA sentences, multi-fragment messages (e.g. type 5 static and voyage
+/// data) are buffered until all their fragments have been received.
+/// Messages that are not completed within `FRAGMENT_TIMEOUT` are discarded.
+#[derive(Debug, Default)]
+pub struct NmeaDecoder {
+    fragments: HashMap<FragmentKey, Fragments>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct FragmentKey {
+    sequential_id: String,
+    channel: String,
+}
+
+#[derive(Debug)]
+struct Fragments {
+    total: u32,
+    next: u32,
+    payload: String,
+    msgtime: DateTime<Utc>,
+    received: Instant,
+}
+
+struct Sentence<'a> {
+    fragment_count: u32,
+    fragment_number: u32,
+    sequential_id: &'a str,
+    channel: &'a str,
+    payload: &'a str,
+    fill_bits: u32,
+}
+
+struct Payload {
+    bits: Vec<bool>,
+}
+
+impl NmeaDecoder {
+    pub fn new() -> NmeaDecoder {
+        NmeaDecoder::default()
+    }
+
+    /// Decodes a single line, returns `None` if the line is a fragment of a message that is not
+    /// yet complete or a well-formed message of a type we do not handle.
+    pub fn decode(&mut self, line: &str) -> Result<Option<AisMessage>, NmeaError> {
+        let (tag_time, sentence) = split_tag_block(line.trim());
+        let sentence = parse_sentence(sentence)?;
+
+        let msgtime = tag_time.unwrap_or_else(Utc::now);
+
+        if sentence.fragment_count == 1 {
+            let payload = Payload::new(sentence.payload, sentence.fill_bits)?;
+            return payload.decode(msgtime);
+        }
+
+        if sentence.fragment_number == 0 || sentence.fragment_number > sentence.fragment_count {
+            return InvalidFragmentSnafu {
+                sentence: sentence.payload,
+            }
+            .fail();
+        }
+
+        let key = FragmentKey {
+            sequential_id: sentence.sequential_id.to_string(),
+            channel: sentence.channel.to_string(),
+        };
+
+        if sentence.fragment_number == 1 {
+            let received = Instant::now();
+            self.evict(received);
+            self.fragments.insert(
+                key,
+                Fragments {
+                    total: sentence.fragment_count,
+                    next: 2,
+                    payload: sentence.payload.to_string(),
+                    msgtime,
+                    received,
+                },
+            );
+            return Ok(None);
+        }
+
+        let Some(fragments) = self.fragments.get_mut(&key) else {
+            return InvalidFragmentSnafu {
+                sentence: sentence.payload,
+            }
+            .fail();
+        };
+
+        if fragments.next != sentence.fragment_number || fragments.total != sentence.fragment_count
+        {
+            self.fragments.remove(&key);
+            return InvalidFragmentSnafu {
+                sentence: sentence.payload,
+            }
+            .fail();
+        }
+
+        fragments.payload.push_str(sentence.payload);
+        fragments.next += 1;
+
+        if sentence.fragment_number < sentence.fragment_count {
+            return Ok(None);
+        }
+
+        let fragments = self.fragments.remove(&key).unwrap();
+        let payload = Payload::new(&fragments.payload, sentence.fill_bits)?;
+        payload.decode(fragments.msgtime)
+    }
+
+    /// Discards incomplete messages that have timed out, and the oldest incomplete message if
+    /// there is no room for another one.
+    fn evict(&mut self, now: Instant) {
+        self.fragments
+            .retain(|_, f| now.duration_since(f.received) < FRAGMENT_TIMEOUT);
+
+        if self.fragments.len() >= MAX_PENDING_FRAGMENTS
+            && let Some(oldest) = self
+                .fragments
+                .iter()
+                .min_by_key(|(_, f)| f.received)
+                .map(|(k, _)| k.clone())
+        {
+            self.fragments.remove(&oldest);
+        }
+    }
+}
+
+/// Splits an optional IEC 61162-450 tag block (`\s:source,c:1700000000*hh\`) from the
+/// sentence and returns the unix timestamp it contains, if any.
+fn split_tag_block(line: &str) -> (Option<DateTime<Utc>>, &str) {
+    let Some(rest) = line.strip_prefix('\\') else {
+        return (None, line);
+    };
+    let Some((tag_block, sentence)) = rest.split_once('\\') else {
+        return (None, line);
+    };
+
+    let tag_block = tag_block
+        .split_once('*')
+        .map(|(v, _)| v)
+        .unwrap_or(tag_block);
+
+    let timestamp = tag_block
+        .split(',')
+        .find_map(|v| v.strip_prefix("c:"))
+        .and_then(|v| v.parse::<i64>().ok())
+        .and_then(|v| {
+            // Some receivers report milliseconds rather than seconds
+            if v > 10_000_000_000 {
+                DateTime::from_timestamp_millis(v)
+            } else {
+                DateTime::from_timestamp(v, 0)
+            }
+        });
+
+    (timestamp, sentence)
+}
+
+fn parse_sentence(sentence: &str) -> Result<Sentence<'_>, NmeaError> {
+    let invalid = || InvalidSentenceSnafu { sentence }.build();
+
+    let body = sentence
+        .strip_prefix('!')
+        .or_else(|| sentence.strip_prefix('$'))
+        .ok_or_else(invalid)?;
+
+    let (body, checksum) = body.split_once('*').ok_or_else(invalid)?;
+
+    let expected =
+        u8::from_str_radix(checksum.get(..2).ok_or_else(invalid)?, 16).map_err(|_| invalid())?;
+    let actual = body.bytes().fold(0, |acc, b| acc ^ b);
+    if expected != actual {
+        return ChecksumSnafu { expected, actual }.fail();
+    }
+
+    let mut fields = body.split(',');
+
+    let talker = fields.next().ok_or_else(invalid)?;
+    if !(talker.ends_with("VDM") || talker.ends_with("VDO")) {
+        return Err(invalid());
+    }
+
+    let fragment_count = fields
+        .next()
+        .and_then(|v| v.parse().ok())
+        .ok_or_else(invalid)?;
+    let fragment_number = fields
+        .next()
+        .and_then(|v| v.parse().ok())
+        .ok_or_else(invalid)?;
+    let sequential_id = fields.next().ok_or_else(invalid)?;
+    let channel = fields.next().ok_or_else(invalid)?;
+    let payload = fields.next().ok_or_else(invalid)?;
+    let fill_bits = fields
+        .next()
+        .and_then(|v| v.parse().ok())
+        .ok_or_else(invalid)?;
+
+    Ok(Sentence {
+        fragment_count,
+        fragment_number,
+        sequential_id,
+        channel,
+        payload,
+        fill_bits,
+    })
+}
+
+impl Payload {
+    fn new(payload: &str, fill_bits: u32) -> Result<Payload, NmeaError> {
+        let mut bits = Vec::with_capacity(payload.len() * 6);
+
+        for c in payload.chars() {
+            let value = match c {
+                '0'..='W' => c as u8 - 48,
+                '`'..='w' => c as u8 - 56,
+                _ => return InvalidPayloadCharacterSnafu { character: c }.fail(),
+            };
+            for i in (0..6).rev() {
+                bits.push((value >> i) & 1 == 1);
+            }
+        }
+
+        bits.truncate(bits.len().saturating_sub(fill_bits as usize));
+
+        Ok(Payload { bits })
+    }
+
+    /// Returns `None` for valid message types that we do not handle (e.g. base station reports
+    /// and binary messages).
+    fn decode(&self, msgtime: DateTime<Utc>) -> Result<Option<AisMessage>, NmeaError> {
+        let message_type = self.unsigned(0, 6);
+
+        let message = match message_type {
+            1..=3 => {
+                self.ensure_len(message_type, POSITION_BITS)?;
+                AisMessage::Position(self.class_a_position(message_type, msgtime))
+            }
+            18 => {
+                self.ensure_len(message_type, POSITION_BITS)?;
+                AisMessage::Position(self.class_b_position(message_type, msgtime))
+            }
+            19 => {
+                self.ensure_len(message_type, EXTENDED_CLASS_B_BITS)?;
+                // The static part of type 19 messages is covered by type 24 messages, which are
+                // sent alongside them.
+                AisMessage::Position(self.class_b_position(message_type, msgtime))
+            }
+            5 => {
+                self.ensure_len(message_type, STATIC_AND_VOYAGE_BITS)?;
+                AisMessage::Static(self.static_and_voyage(msgtime))
+            }
+            24 => AisMessage::Static(self.static_data_report(msgtime)?),
+            1..=MAX_MESSAGE_TYPE => return Ok(None),
+            _ => return InvalidMessageTypeSnafu { message_type }.fail(),
+        };
+
+        Ok(Some(message))
+    }
+
+    fn class_a_position(&self, message_type: u32, msgtime: DateTime<Utc>) -> AisPosition {
+        let (latitude, longitude) = self.coordinates(89, 61);
+
+        AisPosition {
+            message_type_id: Some(message_type as i32),
+            message_type: Some(AisMessageType::Position),
+            mmsi: self.mmsi(),
+            msgtime,
+            altitude: None,
+            course_over_ground: self.course(116),
+            latitude,
+            longitude,
+            navigational_status: NavigationStatus::from_u32(self.unsigned(38, 4))
+                .unwrap_or(NavigationStatus::NotDefined),
+            ais_class: Some(AisClass::A),
+            rate_of_turn: Some(self.signed(42, 8))
+                .filter(|v| *v != RATE_OF_TURN_NOT_AVAILABLE)
+                .map(|v| v as f64),
+            speed_over_ground: self.speed(50),
+            true_heading: self.heading(128),
+        }
+    }
+
+    fn class_b_position(&self, message_type: u32, msgtime: DateTime<Utc>) -> AisPosition {
+        let (latitude, longitude) = self.coordinates(85, 57);
+
+        AisPosition {
+            message_type_id: Some(message_type as i32),
+            message_type: Some(AisMessageType::Position),
+            mmsi: self.mmsi(),
+            msgtime,
+            altitude: None,
+            course_over_ground: self.course(112),
+            latitude,
+            longitude,
+            navigational_status: NavigationStatus::NotDefined,
+            ais_class: Some(AisClass::B),
+            rate_of_turn: None,
+            speed_over_ground: self.speed(46),
+            true_heading: self.heading(124),
+        }
+    }
+
+    fn static_and_voyage(&self, msgtime: DateTime<Utc>) -> AisStatic {
+        let (dimension_a, dimension_b, dimension_c, dimension_d) = self.dimensions(240);
+
+        let eta = format!(
+            "{:02}{:02}{:02}{:02}",
+            self.unsigned(274, 4),
+            self.unsigned(278, 5),
+            self.unsigned(283, 5),
+            self.unsigned(288, 6),
+        );
+
+        AisStatic {
+            message_type: Some(AisMessageType::Static),
+            message_type_id: 5,
+            mmsi: self.mmsi(),
+            msgtime,
+            imo_number: Some(self.unsigned(40, 30) as i32).filter(|v| *v != 0),
+            call_sign: self.text(70, 7),
+            destination: self.text(302, 20),
+            eta: Some(eta),
+            name: self.text(112, 20),
+            draught: Some(self.unsigned(294, 8) as i32).filter(|v| *v != 0),
+            ship_length: sum_dimensions(dimension_a, dimension_b),
+            ship_width: sum_dimensions(dimension_c, dimension_d),
+            ship_type: Some(self.unsigned(232, 8) as i32).filter(|v| *v != 0),
+            dimension_a,
+            dimension_b,
+            dimension_c,
+            dimension_d,
+            position_fixing_device_type: Some(self.unsigned(270, 4) as i32).filter(|v| *v != 0),
+            report_class: None,
+        }
+    }
+
+    /// Type 24 messages are sent in two parts, part A containing the vessel name and part B
+    /// containing the call sign, ship type and dimensions.
+    /// Each part is emitted as its own static message as existing static fields are not
+    /// replaced by missing values when persisted.
+    fn static_data_report(&self, msgtime: DateTime<Utc>) -> Result<AisStatic, NmeaError> {
+        let mut ais_static = AisStatic {
+            message_type: Some(AisMessageType::Static),
+            message_type_id: 24,
+            mmsi: self.mmsi(),
+            msgtime,
+            imo_number: None,
+            call_sign: None,
+            destination: None,
+            eta: None,
+            name: None,
+            draught: None,
+            ship_length: None,
+            ship_width: None,
+            ship_type: None,
+            dimension_a: None,
+            dimension_b: None,
+            dimension_c: None,
+            dimension_d: None,
+            position_fixing_device_type: None,
+            report_class: None,
+        };
+
+        self.ensure_len(24, STATIC_DATA_REPORT_A_BITS)?;
+
+        if self.unsigned(38, 2) == 0 {
+            ais_static.name = self.text(40, 20);
+        } else {
+            self.ensure_len(24, STATIC_DATA_REPORT_B_BITS)?;
+
+            let (dimension_a, dimension_b, dimension_c, dimension_d) = self.dimensions(132);
+
+            ais_static.ship_type = Some(self.unsigned(40, 8) as i32).filter(|v| *v != 0);
+            ais_static.call_sign = self.text(90, 7);
+            ais_static.ship_length = sum_dimensions(dimension_a, dimension_b);
+            ais_static.ship_width = sum_dimensions(dimension_c, dimension_d);
+            ais_static.dimension_a = dimension_a;
+            ais_static.dimension_b = dimension_b;
+            ais_static.dimension_c = dimension_c;
+            ais_static.dimension_d = dimension_d;
+        }
+
+        Ok(ais_static)
+    }
+
+    fn ensure_len(&self, message_type: u32, bits: usize) -> Result<(), NmeaError> {
+        if self.bits.len() < bits {
+            PayloadTooShortSnafu {
+                message_type,
+                expected: bits,
+                actual: self.bits.len(),
+            }
+            .fail()
+        } else {
+            Ok(())
+        }
+    }
+
+    fn mmsi(&self) -> Mmsi {
+        Mmsi::from(self.unsigned(8, 30) as i32)
+    }
+
+    fn coordinates(&self, lat_start: usize, lon_start: usize) -> (Option<f64>, Option<f64>) {
+        let latitude = self.signed(lat_start, 27);
+        let longitude = self.signed(lon_start, 28);
+
+        if latitude == LATITUDE_NOT_AVAILABLE || longitude == LONGITUDE_NOT_AVAILABLE {
+            (None, None)
+        } else {
+            (
+                Some(latitude as f64 / 600_000.0),
+                Some(longitude as f64 / 600_000.0),
+            )
+        }
+    }
+
+    fn speed(&self, start: usize) -> Option<f64> {
+        Some(self.unsigned(start, 10))
+            .filter(|v| *v != SPEED_NOT_AVAILABLE)
+            .map(|v| v as f64 / 10.0)
+    }
+
+    fn course(&self, start: usize) -> Option<f64> {
+        Some(self.unsigned(start, 12))
+            .filter(|v| *v < COURSE_NOT_AVAILABLE)
+            .map(|v| v as f64 / 10.0)
+    }
+
+    fn heading(&self, start: usize) -> Option<i32> {
+        Some(self.unsigned(start, 9))
+            .filter(|v| *v != HEADING_NOT_AVAILABLE)
+            .map(|v| v as i32)
+    }
+
+    fn dimensions(&self, start: usize) -> (Option<i32>, Option<i32>, Option<i32>, Option<i32>) {
+        let dimension = |start, len| Some(self.unsigned(start, len) as i32).filter(|v| *v != 0);
+        (
+            dimension(start, 9),
+            dimension(start + 9, 9),
+            dimension(start + 18, 6),
+            dimension(start + 24, 6),
+        )
+    }
+
+    fn text(&self, start: usize, chars: usize) -> Option<String> {
+        let text: String = (0..chars)
+            .map(|i| {
+                let v = self.unsigned(start + i * 6, 6) as u8;
+                if v < 32 { (v + 64) as char } else { v as char }
+            })
+            .collect();
+
+        let text = text.trim_end_matches(['@', ' ']).trim();
+        (!text.is_empty()).then(|| text.to_string())
+    }
+
+    fn unsigned(&self, start: usize, len: usize) -> u32 {
+        self.bits
+            .iter()
+            .skip(start)
+            .take(len)
+            .fold(0, |acc, bit| (acc << 1) | *bit as u32)
+    }
+
+    fn signed(&self, start: usize, len: usize) -> i64 {
+        let value = self.unsigned(start, len) as i64;
+        if value & (1 << (len - 1)) != 0 {
+            value - (1 << len)
+        } else {
+            value
+        }
+    }
+}
+
+fn sum_dimensions(a: Option<i32>, b: Option<i32>) -> Option<i32> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a + b),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decodes_class_a_position() {
+        let mut decoder = NmeaDecoder::new();
+        let message = decoder
+            .decode("!AIVDM,1,1,,B,15M67FC000G?ufbE`FepT@3n00Sa,0*5C")
+            .unwrap()
+            .unwrap();
+
+        let AisMessage::Position(position) = message else {
+            panic!("expected position message");
+        };
+
+        assert_eq!(position.mmsi, Mmsi::from(366053209));
+        assert_eq!(position.message_type_id, Some(1));
+        assert_eq!(position.speed_over_ground, Some(0.0));
+        assert!((position.latitude.unwrap() - 37.802118).abs() < 0.0001);
+        assert!((position.longitude.unwrap() + 122.341618).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_decodes_multi_fragment_static_and_voyage_message() {
+        let mut decoder = NmeaDecoder::new();
+        let first = decoder
+            .decode(
+                "!AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*1C",
+            )
+            .unwrap();
+        assert!(first.is_none());
+
+        let message = decoder
+            .decode("!AIVDM,2,2,1,A,88888888880,2*25")
+            .unwrap()
+            .unwrap();
+
+        let AisMessage::Static(ais_static) = message else {
+            panic!("expected static message");
+        };
+
+        assert_eq!(ais_static.mmsi, Mmsi::from(351759000));
+        assert_eq!(ais_static.imo_number, Some(9134270));
+        assert_eq!(ais_static.call_sign.as_deref(), Some("3FOF8"));
+        assert_eq!(ais_static.name.as_deref(), Some("EVER DIADEM"));
+        assert_eq!(ais_static.destination.as_deref(), Some("NEW YORK"));
+        assert_eq!(ais_static.ship_length, Some(295));
+        assert_eq!(ais_static.ship_width, Some(32));
+    }
+
+    #[test]
+    fn test_uses_tag_block_timestamp() {
+        let mut decoder = NmeaDecoder::new();
+        let message = decoder
+            .decode("\\s:2573135,c:1700000000*0E\\!AIVDM,1,1,,B,15M67FC000G?ufbE`FepT@3n00Sa,0*5C")
+            .unwrap()
+            .unwrap();
+
+        let AisMessage::Position(position) = message else {
+            panic!("expected position message");
+        };
+
+        assert_eq!(position.msgtime.timestamp(), 1700000000);
+    }
+
+    #[test]
+    fn test_rejects_invalid_checksum() {
+        let mut decoder = NmeaDecoder::new();
+        assert!(
+            decoder
+                .decode("!AIVDM,1,1,,B,15M67FC000G?ufbE`FepT@3n00Sa,0*5D")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_rejects_fragment_without_first_part() {
+        let mut decoder = NmeaDecoder::new();
+        assert!(decoder.decode("!AIVDM,2,2,1,A,88888888880,2*25").is_err());
+    }
+
+    #[test]
+    fn test_ignores_unsupported_message_type() {
+        let mut decoder = NmeaDecoder::new();
+        // Type 4 base station report
+        let message = decoder
+            .decode("!AIVDM,1,1,,A,403OviQuMGCqWrRO9>E6fE700@GO,0*4D")
+            .unwrap();
+        assert!(message.is_none());
+    }
+
+    #[test]
+    fn test_rejects_invalid_message_type() {
+        let mut decoder = NmeaDecoder::new();
+        assert!(
+            decoder
+                .decode("!AIVDM,1,1,,B,o5M67FC000G?ufbE`FepT@3n00Sa,0*02")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_evicts_timed_out_fragments() {
+        let mut decoder = NmeaDecoder::new();
+        decoder
+            .decode(
+                "!AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*1C",
+            )
+            .unwrap();
+
+        for f in decoder.fragments.values_mut() {
+            f.received -= FRAGMENT_TIMEOUT;
+        }
+
+        decoder
+            .decode(
+                "!AIVDM,2,1,2,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*1F",
+            )
+            .unwrap();
+
+        assert_eq!(decoder.fragments.len(), 1);
+        assert!(decoder.decode("!AIVDM,2,2,1,A,88888888880,2*25").is_err());
+    }
+
+    #[test]
+    fn test_caps_pending_fragments() {
+        let mut decoder = NmeaDecoder::new();
+        for i in 0..MAX_PENDING_FRAGMENTS + 10 {
+            decoder.evict(Instant::now());
+            decoder.fragments.insert(
+                FragmentKey {
+                    sequential_id: i.to_string(),
+                    channel: "A".into(),
+                },
+                Fragments {
+                    total: 2,
+                    next: 2,
+                    payload: String::new(),
+                    msgtime: Utc::now(),
+                    received: Instant::now(),
+                },
+            );
+        }
+
+        assert_eq!(decoder.fragments.len(), MAX_PENDING_FRAGMENTS);
+        assert!(!decoder.fragments.keys().any(|k| k.sequential_id == "0"));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::error::{Result, error::InvalidReplaySpeedSnafu};
+use async_compression::tokio::bufread::GzipDecoder;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::{
+    future::Future,
+    io,
+    path::{Path, PathBuf},
+    pin::Pin,
+    task::{Context, Poll, ready},
+};
+use tokio::{
+    fs::File,
+    io::{
+        AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncWriteExt, BufReader, DuplexStream, ReadBuf,
+    },
+    task::JoinHandle,
+    time::Instant,
+};
+
+const REPLAY_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Replays a recorded capture of line-delimited Barentswatch json messages, optionally gzipped,
+/// while honouring the `msgtime` of each message.
+pub struct Replayer {
+    path: PathBuf,
+    speed: Option<f64>,
+}
+
+/// The replayed capture, reading fails if the replay fails such that a failed or truncated replay
+/// is not mistaken for the end of the capture.
+pub struct ReplaySource {
+    stream: DuplexStream,
+    replay: Option<JoinHandle<io::Result<()>>>,
+}
+
+#[derive(Deserialize)]
+struct MessageTime {
+    msgtime: DateTime<Utc>,
+}
+
+impl Replayer {
+    /// `speed` is the replay rate relative to the recorded pace, e.g. `1.0` replays in real-time
+    /// and `10.0` replays ten times faster. `None` replays as fast as possible.
+    /// Fails if `speed` is not a positive finite number.
+    pub fn new(path: PathBuf, speed: Option<f64>) -> Result<Replayer> {
+        if let Some(speed) = speed
+            && !(speed.is_finite() && speed > 0.0)
+        {
+            return InvalidReplaySpeedSnafu { speed }.fail();
+        }
+        Ok(Replayer { path, speed })
+    }
+
+    /// Returns the capture as a stream which yields each line at the time it should be replayed,
+    /// the stream is closed once the whole capture has been replayed.
+    pub async fn source(&self) -> Result<ReplaySource> {
+        let file = BufReader::new(File::open(&self.path).await?);
+
+        let reader: Box<dyn AsyncBufRead + Unpin + Send> = if is_gzip(&self.path) {
+            Box::new(BufReader::new(GzipDecoder::new(file)))
+        } else {
+            Box::new(file)
+        };
+
+        let (writer, source) = tokio::io::duplex(REPLAY_BUFFER_SIZE);
+
+        let speed = self.speed;
+        let replay = tokio::spawn(replay(reader, writer, speed));
+
+        Ok(ReplaySource {
+            stream: source,
+            replay: Some(replay),
+        })
+    }
+}
+
+impl AsyncRead for ReplaySource {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let filled = buf.filled().len();
+        ready!(Pin::new(&mut self.stream).poll_read(cx, buf))?;
+
+        if buf.filled().len() > filled || buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        // The stream has ended, which is only the end of the capture if the replay succeeded
+        if let Some(replay) = self.replay.as_mut() {
+            let result = ready!(Pin::new(replay).poll(cx));
+            self.replay = None;
+            result.map_err(io::Error::other)??;
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+async fn replay(
+    reader: impl AsyncBufRead + Unpin,
+    mut writer: DuplexStream,
+    speed: Option<f64>,
+) -> io::Result<()> {
+    let mut lines = reader.lines();
+    let mut start: Option<(DateTime<Utc>, Instant)> = None;
+
+    while let Some(line) = lines.next_line().await? {
+        // Lines without a valid `msgtime` are passed through immediately and left for the
+        // consumer to reject.
+        if let Some(speed) = speed
+            && let Ok(message) = serde_json::from_str::<MessageTime>(&line)
+        {
+            match start {
+                None => start = Some((message.msgtime, Instant::now())),
+                Some((first_msgtime, started)) => {
+                    // Messages that are out of order are replayed immediately
+                    let offset = (message.msgtime - first_msgtime)
+                        .to_std()
+                        .unwrap_or_default();
+                    tokio::time::sleep_until(started + offset.div_f64(speed)).await;
+                }
+            }
+        }
+
+        writer.write_all(line.as_bytes()).await?;
+        writer.write_all(b"\n").await?;
+    }
+
+    writer.shutdown().await?;
+
+    Ok(())
+}
+
+fn is_gzip(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == "gz")
+}
@@ -2,6 +2,7 @@ use config::ConfigError;
 use kyogre_core::OauthConfig;
 use orca_core::{Environment, PsqlSettings};
 use serde::Deserialize;
+use std::path::PathBuf;
 
 #[derive(Deserialize, Debug)]
 pub struct Settings {
@@ -12,6 +13,27 @@ pub struct Settings {
     pub broadcast_buffer_size: usize,
     pub oauth: Option<OauthConfig>,
     pub api_address: Option<String>,
+    /// If set, raw NMEA sentences are consumed from this source instead of the Barentswatch
+    /// stream.
+    pub nmea_source: Option<NmeaSource>,
+    /// If set, a recorded capture is replayed instead of consuming a live stream.
+    pub replay: Option<ReplaySettings>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ReplaySettings {
+    /// Line-delimited Barentswatch json messages, gzipped if the file ends with `.gz`.
+    pub path: PathBuf,
+    /// Replay rate relative to the recorded pace, replays as fast as possible if not set.
+    pub speed: Option<f64>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub enum NmeaSource {
+    /// A shore receiver emitting NMEA sentences over tcp, e.g. `"10.0.0.12:4001"`.
+    Tcp { address: String },
+    /// A recorded NMEA log, the consumer stops once the whole file has been read.
+    File { path: PathBuf },
 }
 
 impl Settings {
@@ -2,14 +2,18 @@ use core::panic;
 use std::{str::FromStr, time::Duration};
 
 use crate::{
-    barentswatch::BarentswatchAisClient, consumer::Consumer, error::Result, settings::Settings,
+    barentswatch::BarentswatchAisClient,
+    consumer::{Consumer, MessageFormat},
+    error::{Error, Result},
+    replay::Replayer,
+    settings::{NmeaSource, Settings},
 };
 use async_channel::{Receiver, Sender};
 use kyogre_core::{DataMessage, IsTimeout};
 use orca_core::Environment;
 use postgres::PostgresAdapter;
 use reqwest::Url;
-use tokio::{io::AsyncRead, task::JoinSet};
+use tokio::{fs::File, io::AsyncRead, net::TcpStream, task::JoinSet};
 use tracing::{info, info_span};
 
 pub struct App {
@@ -17,41 +21,63 @@ pub struct App {
     postgres: PostgresAdapter,
     sender: Sender<DataMessage>,
     receiver: Receiver<DataMessage>,
-    ais_source: Option<BarentswatchAisClient>,
+    ais_source: Option<AisSource>,
+}
+
+enum AisSource {
+    Barentswatch(BarentswatchAisClient),
+    Nmea(NmeaSource),
+    Replay(Replayer),
+}
+
+impl AisSource {
+    /// Whether the source is exhausted once read rather than being a continuous stream.
+    fn is_finite(&self) -> bool {
+        match self {
+            AisSource::Barentswatch(_) | AisSource::Nmea(NmeaSource::Tcp { .. }) => false,
+            AisSource::Nmea(NmeaSource::File { .. }) | AisSource::Replay(_) => true,
+        }
+    }
 }
 
 impl App {
-    pub async fn build(settings: Settings) -> App {
+    pub async fn build(settings: Settings) -> Result<App> {
         let (sender, receiver) =
             async_channel::bounded::<DataMessage>(settings.broadcast_buffer_size);
         let postgres = PostgresAdapter::new(&settings.postgres).await.unwrap();
 
         let ais_source = if let Environment::Test = settings.environment {
             None
+        } else if let Some(replay) = settings.replay {
+            Some(AisSource::Replay(Replayer::new(replay.path, replay.speed)?))
+        } else if let Some(nmea_source) = settings.nmea_source {
+            Some(AisSource::Nmea(nmea_source))
         } else {
-            Some(BarentswatchAisClient::new(
+            Some(AisSource::Barentswatch(BarentswatchAisClient::new(
                 settings.oauth.unwrap(),
                 Url::from_str(&settings.api_address.unwrap()).unwrap(),
-            ))
+            )))
         };
 
         if settings.environment == Environment::Local {
             postgres.do_migrations().await;
         }
 
-        App {
+        Ok(App {
             postgres,
             sender,
             consumer: Consumer::new(settings.commit_interval),
             ais_source,
             receiver,
-        }
+        })
     }
 
     pub async fn run(self) {
         let receiver = self.receiver.clone();
         let postgres = self.postgres.clone();
 
+        let is_finite = self.ais_source.as_ref().is_some_and(AisSource::is_finite);
+
         let mut set = JoinSet::new();
 
         set.spawn(async move { postgres.consume_loop(receiver, None).await });
@@ -60,7 +86,12 @@ impl App {
                 if let Err(e) = self.run_impl().await {
                     let span = info_span!("ais_consumer_run");
                     let _guard = span.enter();
-                    if e.is_timeout() {
+                    if is_finite && matches!(e, Error::StreamClosed { .. }) {
+                        // Dropping `self` closes the channel which lets the postgres loop exit
+                        // once all remaining messages have been persisted.
+                        info!("finished reading ais source");
+                        return;
+                    } else if e.is_timeout() {
                         info!("retryable error encountered: '{e:?}', retrying");
                     } else {
                         // We assume the error is unrecoverable and requires a restart
@@ -72,6 +103,11 @@ impl App {
             }
         });
 
+        if is_finite {
+            set.join_all().await;
+            return;
+        }
+
         let out = set.join_next().await;
         panic!(
             "incoming ais consume loop or ais postgres loop exited unexpectedly: {:?}",
@@ -80,13 +116,38 @@ impl App {
     }
 
     async fn run_impl(&self) -> Result<()> {
-        let ais_source = self.ais_source.as_ref().unwrap().streamer().await?;
-        self.consumer.run(ais_source, self.sender.clone()).await
+        match self.ais_source.as_ref().unwrap() {
+            AisSource::Barentswatch(client) => {
+                let source = client.streamer().await?;
+                self.consumer
+                    .run(source, MessageFormat::Json, self.sender.clone())
+                    .await
+            }
+            AisSource::Nmea(NmeaSource::Tcp { address }) => {
+                let source = TcpStream::connect(address).await?;
+                self.consumer
+                    .run(source, MessageFormat::Nmea, self.sender.clone())
+                    .await
+            }
+            AisSource::Nmea(NmeaSource::File { path }) => {
+                let source = File::open(path).await?;
+                self.consumer
+                    .run(source, MessageFormat::Nmea, self.sender.clone())
+                    .await
+            }
+            AisSource::Replay(replayer) => {
+                let source = replayer.source().await?;
+                self.consumer
+                    .run(source, MessageFormat::Json, self.sender.clone())
+                    .await
+            }
+        }
     }
 
     pub async fn run_test(
         self,
         source: impl AsyncRead + Unpin,
+        format: MessageFormat,
         postgres_process_confirmation: tokio::sync::mpsc::Sender<()>,
     ) -> Result<()> {
         let receiver = self.receiver.clone();
@@ -95,6 +156,6 @@ impl App {
                 .consume_loop(receiver, Some(postgres_process_confirmation))
                 .await
         });
-        self.consumer.run(source, self.sender).await
+        self.consumer.run(source, format, self.sender).await
     }
 }
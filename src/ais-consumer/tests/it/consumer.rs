@@ -1,5 +1,6 @@
 use ais_consumer::models::{AisPosition, AisStatic, create_eta_string_value};
 use chrono::{Duration, TimeZone, Utc};
+use kyogre_core::{AisRejectionCategory, NewAisRejectedMessage};
 
 use crate::helper::test;
 
@@ -200,3 +201,63 @@ async fn test_does_not_store_duplicates_of_static_messages() {
     })
     .await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_unparseable_messages_are_stored_as_rejected() {
+    test(|mut helper| async move {
+        let pos = AisPosition::test_default(None);
+        helper.ais_source.send_raw("{\"type\": \"Unknown\"}").await;
+        helper.ais_source.send_position(&pos).await;
+
+        helper.postgres_process_confirmation.recv().await.unwrap();
+
+        let rejected = helper.db.all_ais_rejected_messages().await;
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].category, AisRejectionCategory::InvalidJson);
+        assert_eq!(rejected[0].message, "{\"type\": \"Unknown\"}");
+        assert_eq!(vec![pos], helper.db.all_ais_positions().await);
+    })
+    .await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rejected_messages_are_counted_per_category() {
+    test(|mut helper| async move {
+        helper.ais_source.send_raw("not json").await;
+        helper.postgres_process_confirmation.recv().await.unwrap();
+        helper.ais_source.send_raw("{}").await;
+        helper.postgres_process_confirmation.recv().await.unwrap();
+
+        let counts = helper.db.ais_rejection_counts().await;
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts[0].category, AisRejectionCategory::InvalidJson);
+        assert_eq!(counts[0].num_rejected, 2);
+    })
+    .await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rejected_messages_past_retention_are_deleted() {
+    test(|mut helper| async move {
+        helper
+            .db
+            .add_ais_rejected_messages(&[NewAisRejectedMessage {
+                category: AisRejectionCategory::InvalidJson,
+                message: "old".into(),
+                error: "old".into(),
+                received_at: Utc::now() - Duration::days(31),
+            }])
+            .await;
+
+        helper.ais_source.send_raw("not json").await;
+        helper.postgres_process_confirmation.recv().await.unwrap();
+
+        let rejected = helper.db.all_ais_rejected_messages().await;
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].message, "not json");
+
+        let counts = helper.db.ais_rejection_counts().await;
+        assert_eq!(counts[0].num_rejected, 2);
+    })
+    .await;
+}
@@ -1,4 +1,5 @@
 use ais_consumer::{
+    consumer::MessageFormat,
     models::{AisPosition, AisStatic},
     settings::Settings,
     startup::App,
@@ -24,6 +25,23 @@ pub struct AisSource {
 impl TestHelper {}
 
 pub async fn test<T, Fut>(test_closure: T)
+where
+    T: FnOnce(TestHelper) -> Fut + panic::UnwindSafe + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    test_with_format(MessageFormat::Json, test_closure).await
+}
+
+/// Same as `test` but the source is read as raw NMEA sentences, which are sent with `send_raw`.
+pub async fn test_nmea<T, Fut>(test_closure: T)
+where
+    T: FnOnce(TestHelper) -> Fut + panic::UnwindSafe + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    test_with_format(MessageFormat::Nmea, test_closure).await
+}
+
+async fn test_with_format<T, Fut>(format: MessageFormat, test_closure: T)
 where
     T: FnOnce(TestHelper) -> Fut + panic::UnwindSafe + Send + Sync + 'static,
     Fut: Future<Output = ()> + Send + 'static,
@@ -62,12 +80,14 @@ where
                 broadcast_buffer_size: 10,
                 oauth: None,
                 api_address: None,
+                nmea_source: None,
+                replay: None,
             };
 
             let test_db = TestDb { db };
 
             let (postgres_sender, postgres_recveiver) = tokio::sync::mpsc::channel(100);
-            let app = App::build(app_settings).await;
+            let app = App::build(app_settings).await.unwrap();
 
             let (sender, recv) = tokio::sync::mpsc::channel(100);
 
@@ -76,7 +96,7 @@ where
                 receiver_stream.into_async_read(),
             );
 
-            tokio::spawn(app.run_test(compat, postgres_sender));
+            tokio::spawn(app.run_test(compat, format, postgres_sender));
 
             let helper = TestHelper {
                 ais_source: AisSource { out: sender },
@@ -103,6 +123,10 @@ impl AisSource {
         self.send_string(string).await
     }
 
+    pub async fn send_raw(&self, val: &str) {
+        self.send_string(val.to_string()).await
+    }
+
     async fn send_string(&self, mut val: String) {
         val.push('\n');
         self.out.send(Ok(val)).await.unwrap();
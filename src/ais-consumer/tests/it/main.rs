@@ -6,6 +6,8 @@ use config::{Config, File};
 
 pub mod consumer;
 pub mod helper;
+pub mod nmea;
+pub mod replay;
 
 #[test]
 fn test_local_settings_are_valid() {
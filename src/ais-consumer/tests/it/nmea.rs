@@ -0,0 +1,50 @@
+use kyogre_core::Mmsi;
+
+use crate::helper::test_nmea;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_nmea_position_sentences_are_persisted_to_postgres() {
+    test_nmea(|mut helper| async move {
+        helper
+            .ais_source
+            .send_raw(
+                "\\s:2573135,c:1700000000*0E\\!AIVDM,1,1,,B,15M67FC000G?ufbE`FepT@3n00Sa,0*5C",
+            )
+            .await;
+
+        helper.postgres_process_confirmation.recv().await.unwrap();
+
+        let positions = helper.db.all_ais_positions().await;
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].mmsi, Mmsi::from(366053209));
+        assert_eq!(positions[0].msgtime.timestamp(), 1700000000);
+        assert!((positions[0].latitude - 37.802118).abs() < 0.0001);
+        assert!((positions[0].longitude + 122.341618).abs() < 0.0001);
+    })
+    .await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_nmea_multi_fragment_static_sentences_are_persisted_to_postgres() {
+    test_nmea(|mut helper| async move {
+        helper
+            .ais_source
+            .send_raw(
+                "!AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*1C",
+            )
+            .await;
+        helper
+            .ais_source
+            .send_raw("!AIVDM,2,2,1,A,88888888880,2*25")
+            .await;
+
+        helper.postgres_process_confirmation.recv().await.unwrap();
+
+        let vessels = helper.db.all_ais_vessels().await;
+        assert_eq!(vessels.len(), 1);
+        assert_eq!(vessels[0].mmsi, Mmsi::from(351759000));
+        assert_eq!(vessels[0].name.as_deref(), Some("EVER DIADEM"));
+        assert_eq!(vessels[0].length, Some(295));
+    })
+    .await;
+}
@@ -0,0 +1,131 @@
+use ais_consumer::{models::AisPosition, replay::Replayer};
+use async_compression::tokio::write::GzipEncoder;
+use chrono::Duration;
+use std::path::PathBuf;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+fn capture() -> (Vec<String>, String) {
+    let pos = AisPosition::test_default(None);
+    let mut pos2 = pos.clone();
+    pos2.msgtime += Duration::milliseconds(200);
+
+    let lines = vec![
+        serde_json::to_string(&pos).unwrap(),
+        serde_json::to_string(&pos2).unwrap(),
+    ];
+    let mut expected = lines.join("\n");
+    expected.push('\n');
+
+    (lines, expected)
+}
+
+fn capture_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("{}_{name}", std::process::id()))
+}
+
+async fn replay_to_string(path: PathBuf, speed: Option<f64>) -> String {
+    let mut source = Replayer::new(path, speed).unwrap().source().await.unwrap();
+    let mut out = String::new();
+    source.read_to_string(&mut out).await.unwrap();
+    out
+}
+
+#[tokio::test]
+async fn test_replay_yields_all_lines_of_capture() {
+    let (lines, expected) = capture();
+    let path = capture_path("ais_capture.jsonl");
+    tokio::fs::write(&path, lines.join("\n")).await.unwrap();
+
+    let out = replay_to_string(path.clone(), None).await;
+    tokio::fs::remove_file(path).await.unwrap();
+
+    assert_eq!(expected, out);
+}
+
+#[tokio::test]
+async fn test_replay_decompresses_gzipped_capture() {
+    let (lines, expected) = capture();
+    let path = capture_path("ais_capture.jsonl.gz");
+
+    let mut encoder = GzipEncoder::new(Vec::new());
+    encoder
+        .write_all(lines.join("\n").as_bytes())
+        .await
+        .unwrap();
+    encoder.shutdown().await.unwrap();
+    tokio::fs::write(&path, encoder.into_inner()).await.unwrap();
+
+    let out = replay_to_string(path.clone(), None).await;
+    tokio::fs::remove_file(path).await.unwrap();
+
+    assert_eq!(expected, out);
+}
+
+#[tokio::test]
+async fn test_replay_honours_msgtime_at_given_speed() {
+    let (lines, expected) = capture();
+    let path = capture_path("ais_capture_paced.jsonl");
+    tokio::fs::write(&path, lines.join("\n")).await.unwrap();
+
+    let start = std::time::Instant::now();
+    let out = replay_to_string(path.clone(), Some(2.0)).await;
+    let elapsed = start.elapsed();
+    tokio::fs::remove_file(path).await.unwrap();
+
+    assert_eq!(expected, out);
+    assert!(elapsed >= std::time::Duration::from_millis(100));
+}
+
+#[tokio::test]
+async fn test_replay_fails_on_truncated_gzipped_capture() {
+    let (lines, _) = capture();
+    let path = capture_path("ais_capture_truncated.jsonl.gz");
+
+    let mut encoder = GzipEncoder::new(Vec::new());
+    encoder
+        .write_all(lines.join("\n").as_bytes())
+        .await
+        .unwrap();
+    encoder.shutdown().await.unwrap();
+    let mut compressed = encoder.into_inner();
+    compressed.truncate(compressed.len() - 8);
+    tokio::fs::write(&path, compressed).await.unwrap();
+
+    let mut source = Replayer::new(path.clone(), None)
+        .unwrap()
+        .source()
+        .await
+        .unwrap();
+    let mut out = String::new();
+    let result = source.read_to_string(&mut out).await;
+    tokio::fs::remove_file(path).await.unwrap();
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_replay_fails_on_missing_gzip_header() {
+    let (lines, _) = capture();
+    let path = capture_path("ais_capture_not_gzipped.jsonl.gz");
+    tokio::fs::write(&path, lines.join("\n")).await.unwrap();
+
+    let mut source = Replayer::new(path.clone(), None)
+        .unwrap()
+        .source()
+        .await
+        .unwrap();
+    let mut out = String::new();
+    let result = source.read_to_string(&mut out).await;
+    tokio::fs::remove_file(path).await.unwrap();
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_replayer_rejects_invalid_speed() {
+    let path = capture_path("ais_capture_invalid_speed.jsonl");
+    for speed in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        assert!(Replayer::new(path.clone(), Some(speed)).is_err());
+    }
+    assert!(Replayer::new(path, Some(2.0)).is_ok());
+}
@@ -1,21 +1,27 @@
 use crate::{
-    api::matrix_cache::LandingMatrix,
+    api::matrix_cache::{DataVersions, LandingMatrix, TimeSeriesPoint},
     error::{Error, Result, error::RefreshCommunictionSnafu},
     filter::{HaulFilters, LandingFilters},
-    refresher::{DuckdbRefresher, RefreshRequest},
+    refresher::{DuckdbRefresher, RefreshRequest, RefreshStats},
 };
+use arrow::ipc::writer::StreamWriter;
 use duckdb::DuckdbConnectionManager;
 use kyogre_core::{
     HaulMatrixQueryOutput, HaulMatrixXFeature, HaulMatrixYFeature, HaulsMatrix, HaulsMatrixQuery,
-    LandingMatrixQuery, LandingMatrixQueryOutput, LandingMatrixXFeature, LandingMatrixYFeature,
+    HaulsTimeSeriesQuery, LandingMatrixQuery, LandingMatrixQueryOutput, LandingMatrixXFeature,
+    LandingMatrixYFeature, LandingTimeSeriesQuery, TimeBucket, TimeSeriesGroup,
     calculate_haul_sum_area_table, calculate_landing_sum_area_table,
 };
 use orca_core::PsqlSettings;
 use serde::Deserialize;
 use std::path::PathBuf;
-use tokio::sync::mpsc::{self, Sender};
+use tokio::sync::mpsc::{self, Receiver, Sender};
 use tracing::{error, info};
 
+/// The number of encoded record batches to buffer for each row stream before waiting on the
+/// receiver.
+const ROW_STREAM_BUFFER: usize = 4;
+
 #[derive(Clone)]
 pub struct DuckdbAdapter {
     pool: r2d2::Pool<DuckdbConnectionManager>,
@@ -30,6 +36,17 @@ pub struct DuckdbSettings {
     pub storage: CacheStorage,
     #[serde(with = "humantime_serde")]
     pub refresh_interval: std::time::Duration,
+    /// Parquet snapshots of the cache tables written after each refresh, no snapshots are written
+    /// if not set.
+    #[serde(default)]
+    pub parquet_snapshots: Option<ParquetSnapshotSettings>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ParquetSnapshotSettings {
+    pub dir: PathBuf,
+    /// Number of snapshots to keep per cache table, older snapshots are deleted after each write.
+    pub retention: usize,
 }
 
 #[derive(Clone, Debug, Copy, Deserialize)]
@@ -75,6 +92,7 @@ impl DuckdbAdapter {
             pool.clone(),
             postgres_settings,
             settings.refresh_interval,
+            settings.parquet_snapshots.clone(),
             recv,
         );
 
@@ -89,7 +107,7 @@ impl DuckdbAdapter {
         Ok((adapter, refresher))
     }
 
-    pub async fn refresh(&self) -> Result<()> {
+    pub async fn refresh(&self) -> Result<RefreshStats> {
         let (sender, mut recv) = mpsc::channel(1);
         self.refresh_queue.send(RefreshRequest(sender)).await?;
         match recv.recv().await {
@@ -126,6 +144,156 @@ impl DuckdbAdapter {
         }
     }
 
+    /// Returns the local data versions along with the current versions in Postgres.
+    pub fn data_versions(&self) -> Result<DataVersions> {
+        let conn = self.pool.get()?;
+
+        conn.execute(
+            r"
+LOAD postgres;
+            ",
+            [],
+        )?;
+
+        let versions = conn.query_row(
+            r#"
+SELECT
+    (
+        SELECT
+            "version"
+        FROM
+            data_versions
+        WHERE
+            source = 'hauls'
+    ),
+    (
+        SELECT
+            "version"
+        FROM
+            data_versions
+        WHERE
+            source = 'landings'
+    ),
+    (
+        SELECT
+            "version"
+        FROM
+            postgres_db.duckdb_data_version
+        WHERE
+            duckdb_data_version_id = 'hauls'
+    ),
+    (
+        SELECT
+            "version"
+        FROM
+            postgres_db.duckdb_data_version
+        WHERE
+            duckdb_data_version_id = 'landings'
+    )
+            "#,
+            [],
+            |row| {
+                Ok(DataVersions {
+                    hauls: row.get(0)?,
+                    landings: row.get(1)?,
+                    postgres_hauls: row.get(2)?,
+                    postgres_landings: row.get(3)?,
+                })
+            },
+        )?;
+
+        Ok(versions)
+    }
+
+    pub fn hauls_time_series(&self, query: &HaulsTimeSeriesQuery) -> Result<Vec<TimeSeriesPoint>> {
+        let conn = self.pool.get()?;
+        get_time_series(
+            &conn,
+            "hauls_matrix_cache",
+            "start_timestamp",
+            &HaulFilters::all(&query.filter).query_string(),
+            query.bucket,
+            &query.group_by,
+        )
+    }
+
+    pub fn landing_time_series(
+        &self,
+        query: &LandingTimeSeriesQuery,
+    ) -> Result<Vec<TimeSeriesPoint>> {
+        let conn = self.pool.get()?;
+        get_time_series(
+            &conn,
+            "landing_matrix_cache",
+            "landing_timestamp",
+            &LandingFilters::all(&query.filter).query_string(),
+            query.bucket,
+            &query.group_by,
+        )
+    }
+
+    /// Streams the rows of `hauls_matrix_cache` matching all filters of the query as Arrow IPC
+    /// encoded record batches.
+    pub fn haul_rows(&self, query: &HaulsMatrixQuery) -> Receiver<Result<Vec<u8>>> {
+        let mut sql = "
+SELECT
+    catch_location_id,
+    matrix_month_bucket,
+    vessel_length_group,
+    fiskeridir_vessel_id,
+    gear_group_id,
+    species_group_id,
+    living_weight,
+    species_group_weight_percentage_of_haul,
+    is_majority_species_group_of_haul
+FROM
+    hauls_matrix_cache
+            "
+        .to_string();
+
+        sql.push_str(&HaulFilters::all(query).query_string());
+
+        self.stream_arrow(sql)
+    }
+
+    /// Streams the rows of `landing_matrix_cache` matching all filters of the query as Arrow IPC
+    /// encoded record batches.
+    pub fn landing_rows(&self, query: &LandingMatrixQuery) -> Receiver<Result<Vec<u8>>> {
+        let mut sql = "
+SELECT
+    landing_id,
+    catch_location_id,
+    matrix_month_bucket,
+    vessel_length_group,
+    fiskeridir_vessel_id,
+    gear_group_id,
+    species_group_id,
+    living_weight
+FROM
+    landing_matrix_cache
+            "
+        .to_string();
+
+        sql.push_str(&LandingFilters::all(query).query_string());
+
+        self.stream_arrow(sql)
+    }
+
+    fn stream_arrow(&self, sql: String) -> Receiver<Result<Vec<u8>>> {
+        let (sender, recv) = mpsc::channel(ROW_STREAM_BUFFER);
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = stream_arrow_impl(&pool, &sql, &sender) {
+                // Only fails if the receiver has been dropped, in which case there is no one left
+                // to report the error to.
+                let _ = sender.blocking_send(Err(e));
+            }
+        });
+
+        recv
+    }
+
     fn landing_matrix_impl(&self, params: &LandingMatrixQuery) -> Result<Option<LandingMatrix>> {
         let conn = self.pool.get()?;
         let dates = self.get_landing_matrix(&conn, LandingMatrixXFeature::Date, params)?;
@@ -257,6 +425,100 @@ FROM
     }
 }
 
+/// Sums the living weight of all rows matching the filter per time bucket and group, the columns
+/// that are not grouped by are selected as `NULL` to keep the output columns fixed.
+fn get_time_series(
+    conn: &r2d2::PooledConnection<DuckdbConnectionManager>,
+    table: &str,
+    timestamp_column: &str,
+    filter: &str,
+    bucket: TimeBucket,
+    group_by: &[TimeSeriesGroup],
+) -> Result<Vec<TimeSeriesPoint>> {
+    let group = |group: TimeSeriesGroup| {
+        if group_by.contains(&group) {
+            group.column_name()
+        } else {
+            "NULL"
+        }
+    };
+
+    let sql = format!(
+        "
+SELECT
+    epoch_ms(date_trunc('{bucket}', {timestamp_column})),
+    {},
+    {},
+    {},
+    {},
+    {},
+    SUM(living_weight)
+FROM
+    {table}
+{filter}
+GROUP BY
+    1,
+    2,
+    3,
+    4,
+    5,
+    6
+ORDER BY
+    1,
+    2,
+    3,
+    4,
+    5,
+    6
+        ",
+        group(TimeSeriesGroup::GearGroup),
+        group(TimeSeriesGroup::SpeciesGroup),
+        group(TimeSeriesGroup::VesselLength),
+        group(TimeSeriesGroup::CatchLocation),
+        group(TimeSeriesGroup::Vessel),
+    );
+
+    let mut stmt = conn.prepare(&sql)?;
+    let mut rows = stmt.query([])?;
+
+    let mut points = Vec::new();
+    while let Some(row) = rows.next()? {
+        points.push(TimeSeriesPoint {
+            bucket: row.get(0)?,
+            gear_group_id: row.get::<_, Option<i32>>(1)?.map(|v| v as u32),
+            species_group_id: row.get::<_, Option<i32>>(2)?.map(|v| v as u32),
+            vessel_length_group: row.get::<_, Option<i32>>(3)?.map(|v| v as u32),
+            catch_location_id: row.get(4)?,
+            fiskeridir_vessel_id: row.get::<_, Option<i32>>(5)?.map(i64::from),
+            living_weight: row.get(6)?,
+        });
+    }
+
+    Ok(points)
+}
+
+fn stream_arrow_impl(
+    pool: &r2d2::Pool<DuckdbConnectionManager>,
+    sql: &str,
+    sender: &Sender<Result<Vec<u8>>>,
+) -> Result<()> {
+    let conn = pool.get()?;
+    let mut stmt = conn.prepare(sql)?;
+
+    for batch in stmt.query_arrow([])? {
+        let mut writer = StreamWriter::try_new(Vec::new(), &batch.schema())?;
+        writer.write(&batch)?;
+        writer.finish()?;
+
+        if sender.blocking_send(Ok(writer.into_inner()?)).is_err() {
+            // The receiver has been dropped and is no longer interested in the remaining rows
+            return Ok(());
+        }
+    }
+
+    Ok(())
+}
+
 fn get_landing_matrix_output(mut rows: duckdb::Rows<'_>) -> Result<Vec<LandingMatrixQueryOutput>> {
     let mut data = Vec::new();
     while let Some(row) = rows.next()? {
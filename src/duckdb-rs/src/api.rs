@@ -4,57 +4,161 @@ pub mod matrix_cache {
 
 use crate::{
     adapter::DuckdbAdapter,
-    error::{Error, Result, error::InvalidParametersSnafu},
+    error::{
+        Error, Result,
+        error::{InvalidParametersSnafu, InvalidTimestampSnafu},
+    },
+    refresher::{RefreshMode, RefreshStats, SourceRefreshStats},
+    replica::{ReplicaSet, spawn_version_checks},
 };
+use arrow::{array::RecordBatch, ipc::reader::StreamReader};
 use async_trait::async_trait;
+use chrono::DateTime;
 use fiskeridir_rs::{GearGroup, SpeciesGroup, VesselLengthGroup};
+use futures::TryStreamExt;
 use kyogre_core::retry;
 use kyogre_core::{
     ActiveHaulsFilter, ActiveLandingFilter, CatchLocationId, CoreResult, FiskeridirVesselId,
-    HaulsMatrixQuery, LandingMatrixQuery, MatrixCacheOutbound,
+    HaulsMatrixQuery, HaulsTimeSeriesQuery, LandingMatrixQuery, LandingTimeSeriesQuery,
+    MatrixCacheOutbound, TimeBucket, TimeSeriesGroup,
 };
-use matrix_cache::matrix_cache_client::MatrixCacheClient;
 use matrix_cache::matrix_cache_server::MatrixCache;
 use matrix_cache::*;
 use num_traits::FromPrimitive;
-use std::time::Duration;
-use tonic::codegen::CompressionEncoding;
+use std::{io::Cursor, pin::Pin, sync::Arc, time::Duration};
+use tokio_stream::{Stream, StreamExt, wrappers::ReceiverStream};
 use tonic::{Request, Response, Status};
 use tracing::{error, instrument};
 
+type RecordBatchStream =
+    Pin<Box<dyn Stream<Item = std::result::Result<ArrowRecordBatch, Status>> + Send>>;
+
+/// Decoded record batches streamed from a matrix cache replica.
+pub type RowStream = Pin<Box<dyn Stream<Item = Result<RecordBatch>> + Send>>;
+
 #[derive(Clone)]
 pub struct MatrixCacheService {
     adapter: DuckdbAdapter,
 }
 
+/// Client of one or more matrix cache replicas.
+///
+/// Requests are balanced across the replicas that are the least behind Postgres and retried on
+/// the remaining replicas, ordered by how far behind they are, if one fails.
 #[derive(Clone)]
 pub struct Client {
-    inner: MatrixCacheClient<tonic::transport::Channel>,
+    replicas: Arc<ReplicaSet>,
 }
 
 impl Client {
     pub async fn new(ip: impl AsRef<str>, port: u16) -> Result<Client> {
-        let addr = tonic::transport::Uri::try_from(format!("http://{}:{port}", ip.as_ref()))?;
+        Self::with_replicas([(ip, port)]).await
+    }
 
-        let channel = tonic::transport::Channel::builder(addr)
-            .timeout(Duration::from_secs(5))
-            .http2_keep_alive_interval(Duration::from_secs(5))
-            .keep_alive_while_idle(true)
-            .connect_lazy();
+    pub async fn with_replicas<I, S>(replicas: I) -> Result<Client>
+    where
+        I: IntoIterator<Item = (S, u16)>,
+        S: AsRef<str>,
+    {
+        let replicas = Arc::new(ReplicaSet::new(replicas)?);
 
-        Ok(Client {
-            inner: MatrixCacheClient::new(channel).accept_compressed(CompressionEncoding::Gzip),
-        })
+        // A single replica is used regardless of its version, so there is no need to check it.
+        if replicas.len() > 1 {
+            spawn_version_checks(&replicas);
+        }
+
+        Ok(Client { replicas })
+    }
+
+    /// Compares the data versions of all replicas with Postgres, this is done periodically in the
+    /// background and is only exposed for test purposes.
+    pub async fn check_replicas(&self) {
+        self.replicas.check_versions().await
+    }
+
+    // Only used for test purposes, refreshes a single replica.
+    pub async fn refresh(&self) -> Result<RefreshStats> {
+        self.replicas
+            .call(|mut client| async move {
+                Ok(RefreshStats::from(
+                    client.refresh(EmptyMessage {}).await?.into_inner(),
+                ))
+            })
+            .await
     }
 
-    // Only used for test purposes
-    pub async fn refresh(&self) -> Result<()> {
-        // Cloning a channel is cheap see
-        // https://docs.rs/tonic/latest/tonic/transport/struct.Channel.html for more
-        // explanation.
-        let mut client = self.inner.clone();
+    /// Streams the rows behind the haul matrix matching all filters of the query, the active
+    /// filter is ignored.
+    /// Only establishing the stream is retried on another replica, errors while streaming are
+    /// returned to the caller.
+    pub async fn haul_rows(&self, query: HaulsMatrixQuery) -> Result<RowStream> {
+        self.replicas
+            .call(|mut client| {
+                let features = HaulFeatures::from(query.clone());
+                async move {
+                    let stream = client.get_haul_rows(features).await?.into_inner();
+                    Ok(decode_record_batches(stream))
+                }
+            })
+            .await
+    }
+
+    /// Streams the rows behind the landing matrix matching all filters of the query, the active
+    /// filter is ignored.
+    /// Only establishing the stream is retried on another replica, errors while streaming are
+    /// returned to the caller.
+    pub async fn landing_rows(&self, query: LandingMatrixQuery) -> Result<RowStream> {
+        self.replicas
+            .call(|mut client| {
+                let features = LandingFeatures::from(query.clone());
+                async move {
+                    let stream = client.get_landing_rows(features).await?.into_inner();
+                    Ok(decode_record_batches(stream))
+                }
+            })
+            .await
+    }
 
-        Ok(client.refresh(EmptyMessage {}).await.map(|_| ())?)
+    async fn hauls_time_series_impl(
+        &self,
+        query: HaulsTimeSeriesQuery,
+    ) -> Result<Vec<kyogre_core::TimeSeriesPoint>> {
+        self.replicas
+            .call(|mut client| {
+                let features = HaulTimeSeriesFeatures::from(query.clone());
+                async move {
+                    client
+                        .get_haul_time_series(features)
+                        .await?
+                        .into_inner()
+                        .points
+                        .into_iter()
+                        .map(kyogre_core::TimeSeriesPoint::try_from)
+                        .collect()
+                }
+            })
+            .await
+    }
+
+    async fn landing_time_series_impl(
+        &self,
+        query: LandingTimeSeriesQuery,
+    ) -> Result<Vec<kyogre_core::TimeSeriesPoint>> {
+        self.replicas
+            .call(|mut client| {
+                let features = LandingTimeSeriesFeatures::from(query.clone());
+                async move {
+                    client
+                        .get_landing_time_series(features)
+                        .await?
+                        .into_inner()
+                        .points
+                        .into_iter()
+                        .map(kyogre_core::TimeSeriesPoint::try_from)
+                        .collect()
+                }
+            })
+            .await
     }
 
     async fn landing_matrix_impl(
@@ -64,12 +168,13 @@ impl Client {
         let active_filter = query.active_filter;
         let parameters = LandingFeatures::from(query);
 
-        // Cloning a channel is cheap see
-        // https://docs.rs/tonic/latest/tonic/transport/struct.Channel.html for more
-        // explanation.
-        let mut client = self.inner.clone();
-
-        let matrix = client.get_landing_matrix(parameters).await?.into_inner();
+        let matrix = self
+            .replicas
+            .call(|mut client| {
+                let parameters = parameters.clone();
+                async move { Ok(client.get_landing_matrix(parameters).await?.into_inner()) }
+            })
+            .await?;
 
         if matrix.dates.is_empty()
             || matrix.gear_group.is_empty()
@@ -86,12 +191,13 @@ impl Client {
         let active_filter = query.active_filter;
         let parameters = HaulFeatures::from(query);
 
-        // Cloning a channel is cheap see
-        // https://docs.rs/tonic/latest/tonic/transport/struct.Channel.html for more
-        // explanation.
-        let mut client = self.inner.clone();
-
-        let matrix = client.get_haul_matrix(parameters).await?.into_inner();
+        let matrix = self
+            .replicas
+            .call(|mut client| {
+                let parameters = parameters.clone();
+                async move { Ok(client.get_haul_matrix(parameters).await?.into_inner()) }
+            })
+            .await?;
 
         if matrix.dates.is_empty()
             || matrix.gear_group.is_empty()
@@ -118,10 +224,41 @@ impl MatrixCacheOutbound for Client {
     async fn hauls_matrix(&self, query: &HaulsMatrixQuery) -> CoreResult<kyogre_core::HaulsMatrix> {
         Ok(retry(|| self.hauls_matrix_impl(query.clone())).await?)
     }
+    #[instrument(name = "cache_hauls_time_series", skip(self))]
+    async fn hauls_time_series(
+        &self,
+        query: &HaulsTimeSeriesQuery,
+    ) -> CoreResult<Vec<kyogre_core::TimeSeriesPoint>> {
+        Ok(retry(|| self.hauls_time_series_impl(query.clone())).await?)
+    }
+    #[instrument(name = "cache_landing_time_series", skip(self))]
+    async fn landing_time_series(
+        &self,
+        query: &LandingTimeSeriesQuery,
+    ) -> CoreResult<Vec<kyogre_core::TimeSeriesPoint>> {
+        Ok(retry(|| self.landing_time_series_impl(query.clone())).await?)
+    }
+}
+
+fn decode_record_batches(stream: tonic::Streaming<ArrowRecordBatch>) -> RowStream {
+    Box::pin(
+        stream
+            .map_err(Error::from)
+            .and_then(|batch| async move {
+                StreamReader::try_new(Cursor::new(batch.ipc_stream), None)?
+                    .map(|b| b.map_err(Error::from))
+                    .collect::<Result<Vec<_>>>()
+            })
+            .map_ok(|batches| futures::stream::iter(batches.into_iter().map(Ok)))
+            .try_flatten(),
+    )
 }
 
 #[tonic::async_trait]
 impl MatrixCache for MatrixCacheService {
+    type GetHaulRowsStream = RecordBatchStream;
+    type GetLandingRowsStream = RecordBatchStream;
+
     #[instrument(skip(self))]
     async fn get_landing_matrix(
         &self,
@@ -157,16 +294,90 @@ impl MatrixCache for MatrixCacheService {
         Ok(Response::new(HaulMatrix::from(matrix.unwrap_or_default())))
     }
     #[instrument(skip(self))]
+    async fn get_haul_time_series(
+        &self,
+        request: Request<HaulTimeSeriesFeatures>,
+    ) -> std::result::Result<Response<TimeSeries>, Status> {
+        let parameters = HaulsTimeSeriesQuery::try_from(request.into_inner()).map_err(|e| {
+            error!("{e:?}");
+            Status::invalid_argument(format!("{e:?}"))
+        })?;
+
+        let points = self.adapter.hauls_time_series(&parameters).map_err(|e| {
+            error!("failed to retrive haul time series: {e:?}");
+            Status::internal(format!("{e:?}"))
+        })?;
+
+        Ok(Response::new(TimeSeries { points }))
+    }
+    #[instrument(skip(self))]
+    async fn get_landing_time_series(
+        &self,
+        request: Request<LandingTimeSeriesFeatures>,
+    ) -> std::result::Result<Response<TimeSeries>, Status> {
+        let parameters = LandingTimeSeriesQuery::try_from(request.into_inner()).map_err(|e| {
+            error!("{e:?}");
+            Status::invalid_argument(format!("{e:?}"))
+        })?;
+
+        let points = self.adapter.landing_time_series(&parameters).map_err(|e| {
+            error!("failed to retrive landing time series: {e:?}");
+            Status::internal(format!("{e:?}"))
+        })?;
+
+        Ok(Response::new(TimeSeries { points }))
+    }
+    #[instrument(skip(self))]
+    async fn get_data_versions(
+        &self,
+        _request: Request<EmptyMessage>,
+    ) -> std::result::Result<Response<DataVersions>, Status> {
+        let versions = self.adapter.data_versions().map_err(|e| {
+            error!("failed to retrive data versions: {e:?}");
+            Status::internal(format!("{e:?}"))
+        })?;
+
+        Ok(Response::new(versions))
+    }
+    #[instrument(skip(self))]
+    async fn get_haul_rows(
+        &self,
+        request: Request<HaulFeatures>,
+    ) -> std::result::Result<Response<Self::GetHaulRowsStream>, Status> {
+        let parameters = HaulsMatrixQuery::try_from(request.into_inner()).map_err(|e| {
+            error!("{e:?}");
+            Status::invalid_argument(format!("{e:?}"))
+        })?;
+
+        Ok(Response::new(record_batch_stream(
+            self.adapter.haul_rows(&parameters),
+        )))
+    }
+    #[instrument(skip(self))]
+    async fn get_landing_rows(
+        &self,
+        request: Request<LandingFeatures>,
+    ) -> std::result::Result<Response<Self::GetLandingRowsStream>, Status> {
+        let parameters = LandingMatrixQuery::try_from(request.into_inner()).map_err(|e| {
+            error!("{e:?}");
+            Status::invalid_argument(format!("{e:?}"))
+        })?;
+
+        Ok(Response::new(record_batch_stream(
+            self.adapter.landing_rows(&parameters),
+        )))
+    }
+    #[instrument(skip(self))]
     async fn refresh(
         &self,
         _request: Request<EmptyMessage>,
-    ) -> std::result::Result<Response<EmptyMessage>, Status> {
-        self.adapter.refresh().await.map_err(|e| {
+    ) -> std::result::Result<Response<RefreshSummary>, Status> {
+        let stats = self.adapter.refresh().await.map_err(|e| {
             error!("failed to refresh matrix cache: {e:?}");
             Status::internal(format!("{e:?}"))
         })?;
 
-        Ok(Response::new(EmptyMessage {}))
+        Ok(Response::new(RefreshSummary::from(stats)))
     }
 }
 
@@ -176,6 +387,16 @@ impl MatrixCacheService {
     }
 }
 
+fn record_batch_stream(rows: tokio::sync::mpsc::Receiver<Result<Vec<u8>>>) -> RecordBatchStream {
+    Box::pin(ReceiverStream::new(rows).map(|v| match v {
+        Ok(ipc_stream) => Ok(ArrowRecordBatch { ipc_stream }),
+        Err(e) => {
+            error!("failed to retrive matrix rows: {e:?}");
+            Err(Status::internal(format!("{e:?}")))
+        }
+    }))
+}
+
 impl From<LandingMatrix> for kyogre_core::LandingMatrix {
     fn from(value: LandingMatrix) -> Self {
         let LandingMatrix {
@@ -425,3 +646,190 @@ impl TryFrom<HaulFeatures> for HaulsMatrixQuery {
         })
     }
 }
+
+impl From<HaulsTimeSeriesQuery> for HaulTimeSeriesFeatures {
+    fn from(value: HaulsTimeSeriesQuery) -> Self {
+        let HaulsTimeSeriesQuery {
+            filter,
+            bucket,
+            group_by,
+        } = value;
+
+        HaulTimeSeriesFeatures {
+            features: Some(HaulFeatures::from(filter)),
+            bucket: bucket as u32,
+            group_by: group_by.into_iter().map(|v| v as u32).collect(),
+        }
+    }
+}
+
+impl TryFrom<HaulTimeSeriesFeatures> for HaulsTimeSeriesQuery {
+    type Error = Error;
+
+    fn try_from(value: HaulTimeSeriesFeatures) -> Result<Self> {
+        let HaulTimeSeriesFeatures {
+            features,
+            bucket,
+            group_by,
+        } = value;
+
+        Ok(Self {
+            filter: HaulsMatrixQuery::try_from(features.unwrap_or_default())?,
+            bucket: time_bucket(bucket)?,
+            group_by: time_series_groups(group_by)?,
+        })
+    }
+}
+
+impl From<LandingTimeSeriesQuery> for LandingTimeSeriesFeatures {
+    fn from(value: LandingTimeSeriesQuery) -> Self {
+        let LandingTimeSeriesQuery {
+            filter,
+            bucket,
+            group_by,
+        } = value;
+
+        LandingTimeSeriesFeatures {
+            features: Some(LandingFeatures::from(filter)),
+            bucket: bucket as u32,
+            group_by: group_by.into_iter().map(|v| v as u32).collect(),
+        }
+    }
+}
+
+impl TryFrom<LandingTimeSeriesFeatures> for LandingTimeSeriesQuery {
+    type Error = Error;
+
+    fn try_from(value: LandingTimeSeriesFeatures) -> Result<Self> {
+        let LandingTimeSeriesFeatures {
+            features,
+            bucket,
+            group_by,
+        } = value;
+
+        Ok(Self {
+            filter: LandingMatrixQuery::try_from(features.unwrap_or_default())?,
+            bucket: time_bucket(bucket)?,
+            group_by: time_series_groups(group_by)?,
+        })
+    }
+}
+
+fn time_bucket(value: u32) -> Result<TimeBucket> {
+    TimeBucket::from_u32(value).ok_or_else(|| InvalidParametersSnafu { value }.build())
+}
+
+fn time_series_groups(values: Vec<u32>) -> Result<Vec<TimeSeriesGroup>> {
+    values
+        .into_iter()
+        .map(|v| {
+            TimeSeriesGroup::from_u32(v).ok_or_else(|| InvalidParametersSnafu { value: v }.build())
+        })
+        .collect()
+}
+
+impl TryFrom<TimeSeriesPoint> for kyogre_core::TimeSeriesPoint {
+    type Error = Error;
+
+    fn try_from(value: TimeSeriesPoint) -> Result<Self> {
+        let TimeSeriesPoint {
+            bucket,
+            gear_group_id,
+            species_group_id,
+            vessel_length_group,
+            catch_location_id,
+            fiskeridir_vessel_id,
+            living_weight,
+        } = value;
+
+        Ok(Self {
+            bucket: DateTime::from_timestamp_millis(bucket)
+                .ok_or_else(|| InvalidTimestampSnafu { value: bucket }.build())?,
+            gear_group_id: gear_group_id
+                .map(|v| {
+                    GearGroup::from_u32(v)
+                        .ok_or_else(|| InvalidParametersSnafu { value: v }.build())
+                })
+                .transpose()?,
+            species_group_id: species_group_id
+                .map(|v| {
+                    SpeciesGroup::from_u32(v)
+                        .ok_or_else(|| InvalidParametersSnafu { value: v }.build())
+                })
+                .transpose()?,
+            vessel_length_group: vessel_length_group
+                .map(|v| {
+                    VesselLengthGroup::from_u32(v)
+                        .ok_or_else(|| InvalidParametersSnafu { value: v }.build())
+                })
+                .transpose()?,
+            catch_location_id: catch_location_id
+                .map(|v| v.parse::<CatchLocationId>())
+                .transpose()?,
+            fiskeridir_vessel_id: fiskeridir_vessel_id.map(FiskeridirVesselId::new),
+            living_weight,
+        })
+    }
+}
+
+impl From<RefreshStats> for RefreshSummary {
+    fn from(value: RefreshStats) -> Self {
+        let RefreshStats { hauls, landings } = value;
+
+        RefreshSummary {
+            hauls: hauls.map(SourceRefreshSummary::from),
+            landings: landings.map(SourceRefreshSummary::from),
+        }
+    }
+}
+
+impl From<SourceRefreshStats> for SourceRefreshSummary {
+    fn from(value: SourceRefreshStats) -> Self {
+        let SourceRefreshStats {
+            mode,
+            deleted_rows,
+            inserted_rows,
+            duration,
+        } = value;
+
+        SourceRefreshSummary {
+            full: mode == RefreshMode::Full,
+            deleted_rows,
+            inserted_rows,
+            duration_ms: duration.as_millis() as u64,
+        }
+    }
+}
+
+impl From<RefreshSummary> for RefreshStats {
+    fn from(value: RefreshSummary) -> Self {
+        let RefreshSummary { hauls, landings } = value;
+
+        RefreshStats {
+            hauls: hauls.map(SourceRefreshStats::from),
+            landings: landings.map(SourceRefreshStats::from),
+        }
+    }
+}
+
+impl From<SourceRefreshSummary> for SourceRefreshStats {
+    fn from(value: SourceRefreshSummary) -> Self {
+        let SourceRefreshSummary {
+            full,
+            deleted_rows,
+            inserted_rows,
+            duration_ms,
+        } = value;
+
+        SourceRefreshStats {
+            mode: if full {
+                RefreshMode::Full
+            } else {
+                RefreshMode::Incremental
+            },
+            deleted_rows,
+            inserted_rows,
+            duration: Duration::from_millis(duration_ms),
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::refresher::RefreshRequest;
-use kyogre_core::{IsTimeout, MatrixIndexError};
+use kyogre_core::{CatchLocationIdError, IsTimeout, MatrixIndexError};
 use snafu::{Location, Snafu};
 use stack_error::{OpaqueError, StackError};
 use tokio::sync::mpsc::error::SendError;
@@ -12,6 +12,18 @@ impl IsTimeout for Error {
     }
 }
 
+impl Error {
+    /// Whether the error was caused by the replica serving the request rather than the request
+    /// itself, in which case it should be retried on another replica.
+    pub fn is_replica_failure(&self) -> bool {
+        match self {
+            Error::Timeout { .. } => true,
+            Error::Grpc { error, .. } => error.code() != tonic::Code::InvalidArgument,
+            _ => false,
+        }
+    }
+}
+
 #[derive(Snafu, StackError)]
 #[snafu(module, visibility(pub))]
 pub enum Error {
@@ -71,6 +83,30 @@ pub enum Error {
         location: Location,
         value: u32,
     },
+    #[snafu(display("Arrow error"))]
+    Arrow {
+        #[snafu(implicit)]
+        location: Location,
+        #[snafu(source)]
+        error: arrow::error::ArrowError,
+    },
+    #[snafu(display("Invalid timestamp '{value}'"))]
+    InvalidTimestamp {
+        #[snafu(implicit)]
+        location: Location,
+        value: i64,
+    },
+    #[snafu(display("Catch location id error"))]
+    CatchLocationId {
+        #[snafu(implicit)]
+        location: Location,
+        source: CatchLocationIdError,
+    },
+    #[snafu(display("No matrix cache replicas where provided"))]
+    NoReplicas {
+        #[snafu(implicit)]
+        location: Location,
+    },
     #[snafu(display("Matrix index error"))]
     MatrixIndex {
         #[snafu(implicit)]
@@ -116,6 +116,20 @@ impl<'a> HaulFilters<'a> {
         x_feature: HaulMatrixXFeature,
         y_feature: HaulMatrixYFeature,
     ) -> Self {
+        Self::with_features(query, Some((x_feature, y_feature)))
+    }
+    /// Applies all filters of the query regardless of its active filter, used when retrieving the
+    /// rows behind the matrix.
+    pub fn all(query: &'a HaulsMatrixQuery) -> Self {
+        Self::with_features(query, None)
+    }
+    fn with_features(
+        query: &'a HaulsMatrixQuery,
+        features: Option<(HaulMatrixXFeature, HaulMatrixYFeature)>,
+    ) -> Self {
+        let is_x = |x: HaulMatrixXFeature| features.is_some_and(|(v, _)| v == x);
+        let is_y = |y: HaulMatrixYFeature| features.is_some_and(|(_, v)| v == y);
+
         let mut filters = Vec::new();
         let HaulsMatrixQuery {
             months,
@@ -137,31 +151,29 @@ impl<'a> HaulFilters<'a> {
             filters.push(HaulFilter::BycatchPercentage(*bycatch_percentage));
         }
 
-        if !months.is_empty()
-            && y_feature != HaulMatrixYFeature::Date
-            && x_feature != HaulMatrixXFeature::Date
+        if !months.is_empty() && !is_y(HaulMatrixYFeature::Date) && !is_x(HaulMatrixXFeature::Date)
         {
             filters.push(HaulFilter::MonthBuckets(months));
         }
 
-        if !catch_locations.is_empty() && y_feature != HaulMatrixYFeature::CatchLocation {
+        if !catch_locations.is_empty() && !is_y(HaulMatrixYFeature::CatchLocation) {
             filters.push(HaulFilter::CatchLocations(catch_locations));
         }
         if !gear_group_ids.is_empty()
-            && y_feature != HaulMatrixYFeature::GearGroup
-            && x_feature != HaulMatrixXFeature::GearGroup
+            && !is_y(HaulMatrixYFeature::GearGroup)
+            && !is_x(HaulMatrixXFeature::GearGroup)
         {
             filters.push(HaulFilter::GearGroup(gear_group_ids));
         }
         if !species_group_ids.is_empty()
-            && y_feature != HaulMatrixYFeature::SpeciesGroup
-            && x_feature != HaulMatrixXFeature::SpeciesGroup
+            && !is_y(HaulMatrixYFeature::SpeciesGroup)
+            && !is_x(HaulMatrixXFeature::SpeciesGroup)
         {
             filters.push(HaulFilter::SpeciesGroup(species_group_ids));
         }
         if !vessel_length_groups.is_empty()
-            && y_feature != HaulMatrixYFeature::VesselLength
-            && x_feature != HaulMatrixXFeature::VesselLength
+            && !is_y(HaulMatrixYFeature::VesselLength)
+            && !is_x(HaulMatrixXFeature::VesselLength)
         {
             filters.push(HaulFilter::VesselLengthGroups(vessel_length_groups));
         }
@@ -182,6 +194,22 @@ impl<'a> LandingFilters<'a> {
         x_feature: LandingMatrixXFeature,
         y_feature: LandingMatrixYFeature,
     ) -> Self {
+        Self::with_features(query, Some((x_feature, y_feature)))
+    }
+
+    /// Applies all filters of the query regardless of its active filter, used when retrieving the
+    /// rows behind the matrix.
+    pub fn all(query: &'a LandingMatrixQuery) -> Self {
+        Self::with_features(query, None)
+    }
+
+    fn with_features(
+        query: &'a LandingMatrixQuery,
+        features: Option<(LandingMatrixXFeature, LandingMatrixYFeature)>,
+    ) -> Self {
+        let is_x = |x: LandingMatrixXFeature| features.is_some_and(|(v, _)| v == x);
+        let is_y = |y: LandingMatrixYFeature| features.is_some_and(|(_, v)| v == y);
+
         let mut filters = Vec::new();
         let LandingMatrixQuery {
             months,
@@ -194,30 +222,30 @@ impl<'a> LandingFilters<'a> {
         } = query;
 
         if !months.is_empty()
-            && y_feature != LandingMatrixYFeature::Date
-            && x_feature != LandingMatrixXFeature::Date
+            && !is_y(LandingMatrixYFeature::Date)
+            && !is_x(LandingMatrixXFeature::Date)
         {
             filters.push(LandingFilter::MonthBuckets(months));
         }
 
-        if !catch_locations.is_empty() && y_feature != LandingMatrixYFeature::CatchLocation {
+        if !catch_locations.is_empty() && !is_y(LandingMatrixYFeature::CatchLocation) {
             filters.push(LandingFilter::CatchLocations(catch_locations));
         }
         if !gear_group_ids.is_empty()
-            && y_feature != LandingMatrixYFeature::GearGroup
-            && x_feature != LandingMatrixXFeature::GearGroup
+            && !is_y(LandingMatrixYFeature::GearGroup)
+            && !is_x(LandingMatrixXFeature::GearGroup)
         {
             filters.push(LandingFilter::GearGroup(gear_group_ids));
         }
         if !species_group_ids.is_empty()
-            && y_feature != LandingMatrixYFeature::SpeciesGroup
-            && x_feature != LandingMatrixXFeature::SpeciesGroup
+            && !is_y(LandingMatrixYFeature::SpeciesGroup)
+            && !is_x(LandingMatrixXFeature::SpeciesGroup)
         {
             filters.push(LandingFilter::SpeciesGroup(species_group_ids));
         }
         if !vessel_length_groups.is_empty()
-            && y_feature != LandingMatrixYFeature::VesselLength
-            && x_feature != LandingMatrixXFeature::VesselLength
+            && !is_y(LandingMatrixYFeature::VesselLength)
+            && !is_x(LandingMatrixXFeature::VesselLength)
         {
             filters.push(LandingFilter::VesselLengthGroups(vessel_length_groups));
         }
@@ -6,6 +6,7 @@ pub mod api;
 pub mod error;
 pub mod filter;
 pub mod refresher;
+mod replica;
 pub mod settings;
 pub mod startup;
 
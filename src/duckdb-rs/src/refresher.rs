@@ -1,15 +1,18 @@
-use crate::error::Result;
+use crate::{adapter::ParquetSnapshotSettings, error::Result};
+use chrono::Utc;
 use duckdb::DuckdbConnectionManager;
 use duckdb::{Transaction, params};
 use kyogre_core::retry;
 use orca_core::PsqlSettings;
 use r2d2::PooledConnection;
+use std::{path::Path, time::Instant};
 use tokio::sync::mpsc::{Receiver, Sender};
 use tracing::{error, info, instrument};
 
 const POSTGRES_DUCKDB_VERSION_TABLE: &str = "duckdb_data_version";
 const HAULS_SCHEMA: &str = "CREATE TABLE
     hauls_matrix_cache (
+        haul_id BIGINT NOT NULL,
         catch_location_matrix_index INT NOT NULL,
         catch_location_id TEXT NOT NULL,
         matrix_month_bucket INT NOT NULL,
@@ -19,7 +22,8 @@ const HAULS_SCHEMA: &str = "CREATE TABLE
         species_group_id INT NOT NULL,
         living_weight DOUBLE NOT NULL,
         species_group_weight_percentage_of_haul DOUBLE,
-        is_majority_species_group_of_haul BOOLEAN
+        is_majority_species_group_of_haul BOOLEAN,
+        start_timestamp TIMESTAMP NOT NULL
     )";
 const LANDING_SCHEMA: &str = "CREATE TABLE
     landing_matrix_cache (
@@ -32,19 +36,45 @@ const LANDING_SCHEMA: &str = "CREATE TABLE
         gear_group_id INT NOT NULL,
         species_group_id INT NOT NULL,
         living_weight DOUBLE NOT NULL,
+        landing_timestamp TIMESTAMP NOT NULL,
         PRIMARY KEY (landing_id, species_group_id)
     )";
 
 pub struct RefreshRequest(pub Sender<RefreshResponse>);
-pub struct RefreshResponse(pub Result<()>);
+pub struct RefreshResponse(pub Result<RefreshStats>);
+
+/// The outcome of a refresh, sources that did not need a refresh are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RefreshStats {
+    pub hauls: Option<SourceRefreshStats>,
+    pub landings: Option<SourceRefreshStats>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceRefreshStats {
+    pub mode: RefreshMode,
+    pub deleted_rows: u64,
+    pub inserted_rows: u64,
+    pub duration: std::time::Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefreshMode {
+    /// All rows are reloaded from Postgres.
+    Full,
+    /// Only rows changed since the local version are reloaded from Postgres.
+    Incremental,
+}
 
 pub struct DuckdbRefresher {
     pool: r2d2::Pool<DuckdbConnectionManager>,
     postgres_credentials: String,
     refresh_interval: std::time::Duration,
+    parquet_snapshots: Option<ParquetSnapshotSettings>,
     refresh_queue: Receiver<RefreshRequest>,
 }
 
+#[derive(Debug, Clone, Copy)]
 pub enum DataSource {
     Hauls,
     Landings,
@@ -68,6 +98,76 @@ impl DataSource {
             DataSource::Landings => "landings",
         }
     }
+    fn cache_table(&self) -> &'static str {
+        match self {
+            DataSource::Hauls => "hauls_matrix_cache",
+            DataSource::Landings => "landing_matrix_cache",
+        }
+    }
+    fn staging_table(&self) -> &'static str {
+        match self {
+            DataSource::Hauls => "hauls_matrix_staging",
+            DataSource::Landings => "landing_matrix_staging",
+        }
+    }
+    fn id_column(&self) -> &'static str {
+        match self {
+            DataSource::Hauls => "haul_id",
+            DataSource::Landings => "landing_id",
+        }
+    }
+    /// Selects the matrix rows of all entries changed at or after the given version from Postgres,
+    /// entries that have been deleted are returned with only their changed id set.
+    fn postgres_changes_query(&self, since_version: u64) -> String {
+        match self {
+            DataSource::Hauls => format!(
+                "
+SELECT
+    c.haul_id AS changed_id,
+    m.haul_id,
+    m.catch_location_matrix_index,
+    m.catch_location,
+    m.matrix_month_bucket,
+    m.vessel_length_group,
+    m.fiskeridir_vessel_id,
+    m.gear_group_id,
+    m.species_group_id,
+    m.living_weight,
+    m.species_group_weight_percentage_of_haul,
+    m.is_majority_species_group_of_haul,
+    h.start_timestamp
+FROM
+    hauls_matrix_changes c
+    LEFT JOIN hauls_matrix m ON c.haul_id = m.haul_id
+    LEFT JOIN hauls h ON m.haul_id = h.haul_id
+WHERE
+    c.version >= {since_version}
+                "
+            ),
+            DataSource::Landings => format!(
+                "
+SELECT
+    c.landing_id AS changed_id,
+    m.landing_id,
+    m.catch_location_matrix_index,
+    m.catch_location_id,
+    m.matrix_month_bucket,
+    m.vessel_length_group,
+    m.fiskeridir_vessel_id,
+    m.gear_group_id,
+    m.species_group_id,
+    m.living_weight,
+    l.landing_timestamp
+FROM
+    landing_matrix_changes c
+    LEFT JOIN landing_matrix m ON c.landing_id = m.landing_id
+    LEFT JOIN landings l ON m.landing_id = l.landing_id
+WHERE
+    c.version >= {since_version}
+                "
+            ),
+        }
+    }
 }
 
 pub struct RefreshStatus {
@@ -77,14 +177,29 @@ pub struct RefreshStatus {
 
 pub struct SourceStatus {
     version: u64,
+    local_version: u64,
+    min_change_version: u64,
     should_refresh: bool,
 }
 
+impl SourceStatus {
+    /// Changes older than `min_change_version` have been pruned from Postgres, or were never
+    /// tracked, so caches behind it can't be refreshed incrementally.
+    fn refresh_mode(&self) -> RefreshMode {
+        if self.local_version < self.min_change_version {
+            RefreshMode::Full
+        } else {
+            RefreshMode::Incremental
+        }
+    }
+}
+
 impl DuckdbRefresher {
     pub fn new(
         pool: r2d2::Pool<DuckdbConnectionManager>,
         postgres_settings: PsqlSettings,
         refresh_interval: std::time::Duration,
+        parquet_snapshots: Option<ParquetSnapshotSettings>,
         refresh_queue: Receiver<RefreshRequest>,
     ) -> DuckdbRefresher {
         let postgres_credentials = format!(
@@ -110,6 +225,7 @@ impl DuckdbRefresher {
             pool,
             postgres_credentials,
             refresh_interval,
+            parquet_snapshots,
             refresh_queue,
         }
     }
@@ -169,6 +285,11 @@ SELECT
             self.create_hauls(CreateMode::Initial, &tx)?;
             self.create_landings(CreateMode::Initial, &tx)?;
             self.add_data_versions(&tx)?;
+        } else if !self.has_current_schema(&tx)? {
+            // Caches persisted to disk prior to the addition of the timestamp and id columns have
+            // to be recreated as the columns can't be backfilled without a full scan anyway.
+            self.create_hauls(CreateMode::Initial, &tx)?;
+            self.create_landings(CreateMode::Initial, &tx)?;
         }
 
         tx.commit()?;
@@ -176,6 +297,30 @@ SELECT
         Ok(())
     }
 
+    fn has_current_schema(&self, tx: &Transaction<'_>) -> Result<bool> {
+        let columns: u64 = tx.query_row(
+            "
+SELECT
+    COUNT(*)
+FROM
+    information_schema.columns
+WHERE
+    (
+        table_name = 'hauls_matrix_cache'
+        AND column_name IN ('start_timestamp', 'haul_id')
+    )
+    OR (
+        table_name = 'landing_matrix_cache'
+        AND column_name = 'landing_timestamp'
+    )
+            ",
+            params![],
+            |row| row.get(0),
+        )?;
+
+        Ok(columns == 3)
+    }
+
     pub async fn refresh_loop(mut self) {
         let mut interval = tokio::time::interval(self.refresh_interval);
 
@@ -213,30 +358,34 @@ SELECT
         }
     }
 
-    fn do_periodic_refresh_impl(&self, refresh_override: bool) -> Result<()> {
+    fn do_periodic_refresh_impl(&self, refresh_override: bool) -> Result<RefreshStats> {
         let status = self.refresh_status()?;
 
-        let res = if refresh_override || status.hauls.should_refresh {
+        let hauls = if refresh_override || status.hauls.should_refresh {
             info!("hauls have been modified, starting refresh...");
-            self.refresh_hauls(Some(status.hauls.version))
+            Some(self.refresh(DataSource::Hauls, &status.hauls))
         } else {
-            Ok(())
+            None
         };
-        let res2 = if refresh_override || status.landings.should_refresh {
+        let landings = if refresh_override || status.landings.should_refresh {
             info!("landings have been modified, starting refresh...");
-            self.refresh_landings(Some(status.landings.version))
+            Some(self.refresh(DataSource::Landings, &status.landings))
         } else {
-            Ok(())
+            None
         };
 
-        res.and(res2)
+        Ok(RefreshStats {
+            hauls: hauls.transpose()?,
+            landings: landings.transpose()?,
+        })
     }
 
-    #[instrument(skip(self))]
-    fn refresh_landings(&self, new_version: Option<u64>) -> Result<()> {
-        let mut conn = self.pool.get()?;
+    #[instrument(skip(self, status))]
+    fn refresh(&self, source: DataSource, status: &SourceStatus) -> Result<SourceRefreshStats> {
+        let start = Instant::now();
+        let mode = status.refresh_mode();
 
-        conn.execute("DELETE FROM landing_matrix_cache", [])?;
+        let mut conn = self.pool.get()?;
 
         conn.execute(
             r"
@@ -245,41 +394,122 @@ LOAD postgres;
             [],
         )?;
 
+        // Rows are deleted outside of the transaction as duckdb fails on re-inserting primary
+        // keys deleted within the same transaction.
+        let deleted_rows = match mode {
+            RefreshMode::Full => {
+                conn.execute(&format!("DELETE FROM {}", source.cache_table()), [])?
+            }
+            RefreshMode::Incremental => {
+                self.stage_changes(&conn, source, status.local_version)?;
+                conn.execute(
+                    &format!(
+                        "DELETE FROM {} WHERE {} IN (SELECT changed_id FROM {})",
+                        source.cache_table(),
+                        source.id_column(),
+                        source.staging_table(),
+                    ),
+                    [],
+                )?
+            }
+        };
+
         let tx = conn.transaction()?;
 
-        self.create_landings(CreateMode::Refresh, &tx)?;
+        let rows = match (mode, source) {
+            (RefreshMode::Full, DataSource::Hauls) => {
+                self.create_hauls(CreateMode::Refresh, &tx)?
+            }
+            (RefreshMode::Full, DataSource::Landings) => {
+                self.create_landings(CreateMode::Refresh, &tx)?
+            }
+            (RefreshMode::Incremental, DataSource::Hauls) => self.insert_staged_hauls(&tx)?,
+            (RefreshMode::Incremental, DataSource::Landings) => self.insert_staged_landings(&tx)?,
+        };
 
-        if let Some(new_version) = new_version {
-            self.set_data_source_version(DataSource::Landings, new_version, &tx)?;
-        }
+        self.set_data_source_version(source, status.version, &tx)?;
 
         tx.commit()?;
-        Ok(())
-    }
 
-    #[instrument(skip(self))]
-    fn refresh_hauls(&self, new_version: Option<u64>) -> Result<()> {
-        let mut conn = self.pool.get()?;
+        if mode == RefreshMode::Incremental {
+            conn.execute(&format!("DROP TABLE {}", source.staging_table()), [])?;
+        }
 
-        conn.execute("DELETE FROM hauls_matrix_cache", [])?;
+        let stats = SourceRefreshStats {
+            mode,
+            deleted_rows: deleted_rows as u64,
+            inserted_rows: rows as u64,
+            duration: start.elapsed(),
+        };
+
+        info!(
+            source = source.row_value_name(),
+            mode = ?stats.mode,
+            deleted_rows = stats.deleted_rows,
+            inserted_rows = stats.inserted_rows,
+            duration_ms = stats.duration.as_millis() as u64,
+            "refreshed matrix cache"
+        );
 
+        if let Err(e) = self.write_parquet_snapshot(&conn, source) {
+            error!("failed to write {source:?} parquet snapshot: {e:?}");
+        }
+
+        Ok(stats)
+    }
+
+    /// Copies all changes since the given version from Postgres into a temporary staging table.
+    /// The query is executed in Postgres to be able to use its indices, and a single query is used
+    /// to get a consistent snapshot of both the changed ids and their rows.
+    fn stage_changes(
+        &self,
+        conn: &PooledConnection<DuckdbConnectionManager>,
+        source: DataSource,
+        since_version: u64,
+    ) -> Result<()> {
         conn.execute(
-            r"
-LOAD postgres;
-            ",
+            &format!(
+                "CREATE OR REPLACE TEMP TABLE {} AS SELECT * FROM postgres_query('postgres_db', '{}')",
+                source.staging_table(),
+                source
+                    .postgres_changes_query(since_version)
+                    .replace('\'', "''"),
+            ),
             [],
         )?;
 
-        let tx = conn.transaction()?;
+        Ok(())
+    }
 
-        self.create_hauls(CreateMode::Refresh, &tx)?;
+    /// Writes the cache table of the given source to a timestamped Parquet file in the snapshot
+    /// directory, if configured, and deletes its snapshots beyond the configured retention.
+    /// A failed snapshot does not fail the refresh as the cache itself is up to date.
+    #[instrument(skip_all)]
+    fn write_parquet_snapshot(
+        &self,
+        conn: &PooledConnection<DuckdbConnectionManager>,
+        source: DataSource,
+    ) -> Result<()> {
+        let Some(ParquetSnapshotSettings { dir, retention }) = &self.parquet_snapshots else {
+            return Ok(());
+        };
 
-        if let Some(new_version) = new_version {
-            self.set_data_source_version(DataSource::Hauls, new_version, &tx)?;
-        }
+        std::fs::create_dir_all(dir)?;
 
-        tx.commit()?;
-        Ok(())
+        let table = source.cache_table();
+        let path = dir.join(format!(
+            "{table}_{}.parquet",
+            Utc::now().format("%Y%m%dT%H%M%SZ")
+        ));
+
+        conn.execute_batch(&format!(
+            "COPY {table} TO '{}' (FORMAT PARQUET);",
+            path.display()
+        ))?;
+
+        info!("wrote parquet snapshot to {}", path.display());
+
+        prune_parquet_snapshots(dir, table, *retention)
     }
 
     fn refresh_status(&self) -> Result<RefreshStatus> {
@@ -292,27 +522,45 @@ LOAD postgres;
             [],
         )?;
 
-        let postgres_haul_version = self.postgres_data_source_version(&conn, DataSource::Hauls)?;
-        let local_haul_version = self.data_source_version(&conn, DataSource::Hauls)?;
-
-        let postgres_landing_version =
-            self.postgres_data_source_version(&conn, DataSource::Landings)?;
-        let local_landing_version = self.data_source_version(&conn, DataSource::Landings)?;
-
         let status = RefreshStatus {
-            hauls: SourceStatus {
-                version: postgres_haul_version,
-                should_refresh: postgres_haul_version > local_haul_version,
-            },
-            landings: SourceStatus {
-                version: postgres_landing_version,
-                should_refresh: postgres_landing_version > local_landing_version,
-            },
+            hauls: self.source_status(&conn, DataSource::Hauls)?,
+            landings: self.source_status(&conn, DataSource::Landings)?,
         };
 
         Ok(status)
     }
 
+    fn source_status(
+        &self,
+        conn: &PooledConnection<DuckdbConnectionManager>,
+        source: DataSource,
+    ) -> Result<SourceStatus> {
+        let version = self.postgres_data_source_version(conn, source)?;
+        let local_version = self.data_source_version(conn, source)?;
+
+        let min_change_version: u64 = conn.query_row(
+            &format!(
+                "
+SELECT
+    min_change_version
+FROM
+    postgres_db.{POSTGRES_DUCKDB_VERSION_TABLE}
+WHERE
+    duckdb_data_version_id = ?
+                "
+            ),
+            params![source.postgres_version_table_id()],
+            |row| row.get(0),
+        )?;
+
+        Ok(SourceStatus {
+            version,
+            local_version,
+            min_change_version,
+            should_refresh: version > local_version,
+        })
+    }
+
     fn postgres_data_source_version(
         &self,
         conn: &PooledConnection<DuckdbConnectionManager>,
@@ -440,7 +688,7 @@ ON CONFLICT (source) DO NOTHING;
     }
 
     #[instrument(skip_all)]
-    fn create_landings(&self, mode: CreateMode, tx: &Transaction<'_>) -> Result<()> {
+    fn create_landings(&self, mode: CreateMode, tx: &Transaction<'_>) -> Result<usize> {
         let postgres_scan_command = "
 INSERT INTO
     landing_matrix_cache (
@@ -452,38 +700,123 @@ INSERT INTO
         fiskeridir_vessel_id,
         gear_group_id,
         species_group_id,
-        living_weight
+        living_weight,
+        landing_timestamp
     )
 SELECT
-   landing_id,
-   catch_location_matrix_index,
-   catch_location_id,
-   matrix_month_bucket,
-   vessel_length_group,
-   fiskeridir_vessel_id,
-   gear_group_id,
-   species_group_id,
-   living_weight
+   m.landing_id,
+   m.catch_location_matrix_index,
+   m.catch_location_id,
+   m.matrix_month_bucket,
+   m.vessel_length_group,
+   m.fiskeridir_vessel_id,
+   m.gear_group_id,
+   m.species_group_id,
+   m.living_weight,
+   make_timestamp(epoch_us(l.landing_timestamp))
 FROM
-    postgres_db.landing_matrix"
+    postgres_db.landing_matrix m
+    INNER JOIN postgres_db.landings l ON m.landing_id = l.landing_id"
             .to_string();
 
-        let queries = match mode {
-            CreateMode::Initial => {
-                format!(
-                    "DROP TABLE IF EXISTS landing_matrix_cache;{LANDING_SCHEMA};{postgres_scan_command};"
-                )
-            }
-            CreateMode::Refresh => postgres_scan_command,
-        };
-        Ok(tx.execute_batch(&queries)?)
+        if let CreateMode::Initial = mode {
+            tx.execute_batch(&format!(
+                "DROP TABLE IF EXISTS landing_matrix_cache;{LANDING_SCHEMA};"
+            ))?;
+        }
+
+        Ok(tx.execute(&postgres_scan_command, [])?)
+    }
+
+    #[instrument(skip_all)]
+    fn insert_staged_landings(&self, tx: &Transaction<'_>) -> Result<usize> {
+        Ok(tx.execute(
+            "
+INSERT INTO
+    landing_matrix_cache (
+        landing_id,
+        catch_location_matrix_index,
+        catch_location_id,
+        matrix_month_bucket,
+        vessel_length_group,
+        fiskeridir_vessel_id,
+        gear_group_id,
+        species_group_id,
+        living_weight,
+        landing_timestamp
+    )
+SELECT
+    landing_id,
+    catch_location_matrix_index,
+    catch_location_id,
+    matrix_month_bucket,
+    vessel_length_group,
+    fiskeridir_vessel_id,
+    gear_group_id,
+    species_group_id,
+    living_weight,
+    make_timestamp(epoch_us(landing_timestamp))
+FROM
+    landing_matrix_staging
+WHERE
+    landing_id IS NOT NULL
+            ",
+            [],
+        )?)
     }
 
     #[instrument(skip_all)]
-    fn create_hauls(&self, mode: CreateMode, tx: &Transaction<'_>) -> Result<()> {
+    fn create_hauls(&self, mode: CreateMode, tx: &Transaction<'_>) -> Result<usize> {
         let postgres_scan_command = "
 INSERT INTO
     hauls_matrix_cache (
+        haul_id,
+        catch_location_matrix_index,
+        catch_location_id,
+        matrix_month_bucket,
+        vessel_length_group,
+        fiskeridir_vessel_id,
+        gear_group_id,
+        species_group_id,
+        living_weight,
+        species_group_weight_percentage_of_haul,
+        is_majority_species_group_of_haul,
+        start_timestamp
+    )
+SELECT
+    m.haul_id,
+    m.catch_location_matrix_index,
+    m.catch_location,
+    m.matrix_month_bucket,
+    m.vessel_length_group,
+    m.fiskeridir_vessel_id,
+    m.gear_group_id,
+    m.species_group_id,
+    m.living_weight,
+    m.species_group_weight_percentage_of_haul,
+    m.is_majority_species_group_of_haul,
+    make_timestamp(epoch_us(h.start_timestamp))
+FROM
+    postgres_db.hauls_matrix m
+    INNER JOIN postgres_db.hauls h ON m.haul_id = h.haul_id"
+            .to_string();
+
+        if let CreateMode::Initial = mode {
+            tx.execute_batch(&format!(
+                "DROP TABLE IF EXISTS hauls_matrix_cache;{HAULS_SCHEMA};"
+            ))?;
+        }
+
+        Ok(tx.execute(&postgres_scan_command, [])?)
+    }
+
+    #[instrument(skip_all)]
+    fn insert_staged_hauls(&self, tx: &Transaction<'_>) -> Result<usize> {
+        Ok(tx.execute(
+            "
+INSERT INTO
+    hauls_matrix_cache (
+        haul_id,
         catch_location_matrix_index,
         catch_location_id,
         matrix_month_bucket,
@@ -493,9 +826,11 @@ INSERT INTO
         species_group_id,
         living_weight,
         species_group_weight_percentage_of_haul,
-        is_majority_species_group_of_haul
+        is_majority_species_group_of_haul,
+        start_timestamp
     )
 SELECT
+    haul_id,
     catch_location_matrix_index,
     catch_location,
     matrix_month_bucket,
@@ -505,19 +840,122 @@ SELECT
     species_group_id,
     living_weight,
     species_group_weight_percentage_of_haul,
-    is_majority_species_group_of_haul
+    is_majority_species_group_of_haul,
+    make_timestamp(epoch_us(start_timestamp))
 FROM
-    postgres_db.hauls_matrix"
-            .to_string();
+    hauls_matrix_staging
+WHERE
+    haul_id IS NOT NULL
+            ",
+            [],
+        )?)
+    }
+}
 
-        let queries = match mode {
-            CreateMode::Initial => {
-                format!(
-                    "DROP TABLE IF EXISTS hauls_matrix_cache;{HAULS_SCHEMA};{postgres_scan_command};"
-                )
-            }
-            CreateMode::Refresh => postgres_scan_command,
-        };
-        Ok(tx.execute_batch(&queries)?)
+/// Deletes all but the `retention` most recent Parquet snapshots of the given table.
+/// Snapshot file names end with a fixed-width UTC timestamp, so sorting them by name orders them
+/// chronologically.
+fn prune_parquet_snapshots(dir: &Path, table: &str, retention: usize) -> Result<()> {
+    let prefix = format!("{table}_");
+
+    let mut snapshots = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_snapshot = path.extension().is_some_and(|e| e == "parquet")
+            && path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(&prefix));
+
+        if is_snapshot {
+            snapshots.push(path);
+        }
+    }
+
+    snapshots.sort();
+
+    let num_expired = snapshots.len().saturating_sub(retention);
+    for path in &snapshots[..num_expired] {
+        std::fs::remove_file(path)?;
+        info!("deleted expired parquet snapshot {}", path.display());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn snapshot_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "kyogre_parquet_snapshots_{name}_{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn file_names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<_> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_prune_parquet_snapshots_keeps_the_most_recent_snapshots() {
+        let dir = snapshot_dir("keeps_most_recent");
+        for ts in [
+            "20260101T000000Z",
+            "20260103T000000Z",
+            "20260102T000000Z",
+            "20260104T000000Z",
+        ] {
+            std::fs::write(dir.join(format!("hauls_matrix_cache_{ts}.parquet")), []).unwrap();
+        }
+
+        prune_parquet_snapshots(&dir, "hauls_matrix_cache", 2).unwrap();
+
+        assert_eq!(
+            file_names(&dir),
+            vec![
+                "hauls_matrix_cache_20260103T000000Z.parquet",
+                "hauls_matrix_cache_20260104T000000Z.parquet",
+            ]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_prune_parquet_snapshots_ignores_other_tables_and_files() {
+        let dir = snapshot_dir("ignores_other_files");
+        for name in [
+            "hauls_matrix_cache_20260101T000000Z.parquet",
+            "hauls_matrix_cache_20260102T000000Z.parquet",
+            "landing_matrix_cache_20260101T000000Z.parquet",
+            "hauls_matrix_cache_20260101T000000Z.csv",
+        ] {
+            std::fs::write(dir.join(name), []).unwrap();
+        }
+
+        prune_parquet_snapshots(&dir, "hauls_matrix_cache", 1).unwrap();
+
+        assert_eq!(
+            file_names(&dir),
+            vec![
+                "hauls_matrix_cache_20260101T000000Z.csv",
+                "hauls_matrix_cache_20260102T000000Z.parquet",
+                "landing_matrix_cache_20260101T000000Z.parquet",
+            ]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 }
@@ -0,0 +1,208 @@
+use crate::{
+    api::matrix_cache::{DataVersions, EmptyMessage, matrix_cache_client::MatrixCacheClient},
+    error::{Result, error::NoReplicasSnafu},
+};
+use std::{
+    future::Future,
+    sync::{
+        Arc, Weak,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+use tonic::{codegen::CompressionEncoding, transport::Channel};
+use tracing::{instrument, warn};
+
+/// How often the data versions of the replicas are compared with Postgres.
+const VERSION_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+/// The lag of replicas that failed their last request or version check.
+const UNAVAILABLE: u64 = u64::MAX;
+
+pub(crate) struct Replica {
+    address: String,
+    client: MatrixCacheClient<Channel>,
+    /// The number of versions the replica is behind Postgres summed over all data sources, as of
+    /// the last version check.
+    lag: AtomicU64,
+}
+
+/// Balances requests across all replicas that are the least behind Postgres, falling back to
+/// replicas that are further behind if they fail.
+///
+/// Replicas are considered current until their first version check.
+pub(crate) struct ReplicaSet {
+    replicas: Vec<Replica>,
+    next: AtomicUsize,
+}
+
+impl ReplicaSet {
+    pub fn new<I, S>(addresses: I) -> Result<ReplicaSet>
+    where
+        I: IntoIterator<Item = (S, u16)>,
+        S: AsRef<str>,
+    {
+        let replicas = addresses
+            .into_iter()
+            .map(|(ip, port)| Replica::new(ip.as_ref(), port))
+            .collect::<Result<Vec<_>>>()?;
+
+        if replicas.is_empty() {
+            return NoReplicasSnafu.fail();
+        }
+
+        Ok(ReplicaSet {
+            replicas,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.replicas.len()
+    }
+
+    /// Runs `f` against the next eligible replica, falling back to the remaining replicas ordered
+    /// by their lag if it fails.
+    pub async fn call<T, F, Fut>(&self, f: F) -> Result<T>
+    where
+        F: Fn(MatrixCacheClient<Channel>) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut last_error = None;
+
+        for replica in self.candidates() {
+            // Cloning a channel is cheap see
+            // https://docs.rs/tonic/latest/tonic/transport/struct.Channel.html for more
+            // explanation.
+            match f(replica.client.clone()).await {
+                Ok(v) => return Ok(v),
+                Err(e) if e.is_replica_failure() => {
+                    warn!("matrix cache replica '{}' failed: {e:?}", replica.address);
+                    replica.lag.store(UNAVAILABLE, Ordering::Relaxed);
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        match last_error {
+            Some(e) => Err(e),
+            None => NoReplicasSnafu.fail(),
+        }
+    }
+
+    #[instrument(skip_all)]
+    pub async fn check_versions(&self) {
+        for replica in &self.replicas {
+            let lag = match replica
+                .client
+                .clone()
+                .get_data_versions(EmptyMessage {})
+                .await
+            {
+                Ok(versions) => lag(&versions.into_inner()),
+                Err(e) => {
+                    warn!(
+                        "failed to check data versions of matrix cache replica '{}': {e:?}",
+                        replica.address
+                    );
+                    UNAVAILABLE
+                }
+            };
+            replica.lag.store(lag, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns all replicas ordered by their lag, where replicas with the same lag are ordered
+    /// round robin starting at the next replica. Requests are thereby balanced across the replicas
+    /// with the lowest lag, and fall back to replicas that are further behind.
+    fn candidates(&self) -> Vec<&Replica> {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let len = self.replicas.len();
+
+        let mut candidates = (0..len)
+            .map(|i| &self.replicas[(start + i) % len])
+            .collect::<Vec<_>>();
+
+        // The sort is stable, so the round robin order is kept among replicas with the same lag.
+        candidates.sort_by_key(|r| r.lag.load(Ordering::Relaxed));
+        candidates
+    }
+}
+
+impl Replica {
+    fn new(ip: &str, port: u16) -> Result<Replica> {
+        let address = format!("http://{ip}:{port}");
+        let uri = tonic::transport::Uri::try_from(address.clone())?;
+
+        let channel = Channel::builder(uri)
+            .timeout(Duration::from_secs(5))
+            .http2_keep_alive_interval(Duration::from_secs(5))
+            .keep_alive_while_idle(true)
+            .connect_lazy();
+
+        Ok(Replica {
+            address,
+            client: MatrixCacheClient::new(channel).accept_compressed(CompressionEncoding::Gzip),
+            lag: AtomicU64::new(0),
+        })
+    }
+}
+
+/// Periodically checks the data versions of the replicas until the set is dropped.
+pub(crate) fn spawn_version_checks(replicas: &Arc<ReplicaSet>) {
+    let replicas: Weak<ReplicaSet> = Arc::downgrade(replicas);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(VERSION_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            match replicas.upgrade() {
+                Some(replicas) => replicas.check_versions().await,
+                None => break,
+            }
+        }
+    });
+}
+
+fn lag(versions: &DataVersions) -> u64 {
+    versions.postgres_hauls.saturating_sub(versions.hauls)
+        + versions.postgres_landings.saturating_sub(versions.landings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replica_set(lags: &[u64]) -> ReplicaSet {
+        let set =
+            ReplicaSet::new(lags.iter().enumerate().map(|(i, _)| ("[::]", i as u16 + 1))).unwrap();
+        for (replica, lag) in set.replicas.iter().zip(lags) {
+            replica.lag.store(*lag, Ordering::Relaxed);
+        }
+        set
+    }
+
+    fn ports(candidates: Vec<&Replica>) -> Vec<&str> {
+        candidates
+            .into_iter()
+            .map(|r| r.address.rsplit(':').next().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_candidates_falls_back_to_replicas_ordered_by_lag() {
+        let set = replica_set(&[2, UNAVAILABLE, 0, 1]);
+
+        assert_eq!(ports(set.candidates()), vec!["3", "4", "1", "2"]);
+    }
+
+    #[tokio::test]
+    async fn test_candidates_balances_replicas_with_the_same_lag() {
+        let set = replica_set(&[0, 1, 0]);
+
+        assert_eq!(ports(set.candidates()), vec!["1", "3", "2"]);
+        assert_eq!(ports(set.candidates()), vec!["3", "1", "2"]);
+        assert_eq!(ports(set.candidates()), vec!["3", "1", "2"]);
+        assert_eq!(ports(set.candidates()), vec!["1", "3", "2"]);
+    }
+}
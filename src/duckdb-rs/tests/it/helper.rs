@@ -1,11 +1,15 @@
 use duckdb_rs::{
     adapter::{CacheMode, CacheStorage, DuckdbSettings},
     api::Client,
+    refresher::RefreshStats,
     settings::Settings,
     startup::App,
 };
+use fiskeridir_rs::ErsDca;
 use futures::Future;
-use kyogre_core::{POSTGRES_TEST_PORT, VerificationOutbound};
+use kyogre_core::{
+    FiskeridirVesselId, MatrixCacheVersion, POSTGRES_TEST_PORT, VerificationOutbound,
+};
 use orca_core::{Environment, PsqlLogStatements, PsqlSettings, TestHelperBuilder};
 use postgres::{PostgresAdapter, TestDb};
 use std::panic;
@@ -13,12 +17,50 @@ use std::panic;
 pub struct TestHelper {
     pub db: TestDb,
     pub cache: Client,
+    pub port: u16,
+    settings: Settings,
 }
 
 impl TestHelper {
     pub fn adapter(&self) -> &PostgresAdapter {
         &self.db.db
     }
+
+    /// Adds a vessel with a single haul that is included in the hauls matrix and returns the id
+    /// of the vessel.
+    pub async fn add_vessel_with_haul(&self) -> FiskeridirVesselId {
+        let vessel_id = FiskeridirVesselId::test_new(1);
+
+        self.db
+            .generate_fiskeridir_vessel(vessel_id, None, None)
+            .await;
+        self.db.add_ers_dca_value(ers_dca(1, vessel_id)).await;
+
+        vessel_id
+    }
+
+    /// Bumps the data version in postgres and refreshes the matrix cache.
+    pub async fn refresh(&self) -> RefreshStats {
+        self.adapter().increment().await.unwrap();
+        self.cache.refresh().await.unwrap()
+    }
+
+    /// Starts another matrix cache instance against the same database and returns its port.
+    pub async fn spawn_replica(&self) -> u16 {
+        let app = App::build(&self.settings).await;
+        let port = app.port();
+        tokio::spawn(app.run());
+        port
+    }
+}
+
+/// Returns a haul of the given vessel located within a catch location, as hauls outside of catch
+/// locations are not included in the hauls matrix.
+pub fn ers_dca(message_id: u64, vessel_id: FiskeridirVesselId) -> ErsDca {
+    let mut ers_dca = ErsDca::test_default(message_id, Some(vessel_id));
+    ers_dca.start_latitude = Some(70.536);
+    ers_dca.start_longitude = Some(21.957);
+    ers_dca
 }
 
 pub async fn test<T, Fut>(test: T)
@@ -58,6 +100,7 @@ where
                     cache_mode: CacheMode::ReturnError,
                     storage: CacheStorage::Memory,
                     refresh_interval: std::time::Duration::from_secs(10000000),
+                    parquet_snapshots: None,
                 },
             };
 
@@ -72,6 +115,8 @@ where
                     db: adapter.clone(),
                 },
                 cache: Client::new("[::]", port).await.unwrap(),
+                port,
+                settings,
             };
 
             test(helper).await;
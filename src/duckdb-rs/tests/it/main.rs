@@ -3,6 +3,9 @@ use duckdb_rs::Settings;
 
 pub mod helper;
 pub mod refresh;
+pub mod replica;
+pub mod rows;
+pub mod time_series;
 
 #[test]
 fn test_local_settings_are_valid() {
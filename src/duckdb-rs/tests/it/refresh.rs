@@ -1,17 +1,18 @@
 use chrono::{TimeZone, Utc};
-use fiskeridir_rs::{ErsDca, Landing};
+use duckdb_rs::refresher::RefreshMode;
+use fiskeridir_rs::Landing;
+use futures::TryStreamExt;
 use kyogre_core::{
-    ActiveHaulsFilter, ActiveLandingFilter, FiskeridirVesselId, HaulsMatrixQuery,
-    LandingMatrixQuery, MatrixCacheOutbound, MatrixCacheVersion,
+    ActiveHaulsFilter, ActiveLandingFilter, HaulsMatrixQuery, LandingMatrixQuery,
+    MatrixCacheOutbound,
 };
 
-use super::helper::test;
+use super::helper::{ers_dca, test};
 
 #[tokio::test]
 async fn test_haul_refresh_with_no_data_succeeds_and_returns_miss_on_subsequent_request() {
     test(|helper| async move {
-        helper.adapter().increment().await.unwrap();
-        helper.cache.refresh().await.unwrap();
+        helper.refresh().await;
 
         let cache_result = helper
             .cache
@@ -36,20 +37,8 @@ async fn test_haul_refresh_with_no_data_succeeds_and_returns_miss_on_subsequent_
 #[tokio::test]
 async fn test_haul_returns_hit_after_refreshing_with_data() {
     test(|helper| async move {
-        let vessel_id = FiskeridirVesselId::test_new(1);
-
-        helper
-            .db
-            .generate_fiskeridir_vessel(vessel_id, None, None)
-            .await;
-
-        let mut ers_dca = ErsDca::test_default(1, Some(vessel_id));
-        ers_dca.start_latitude = Some(70.536);
-        ers_dca.start_longitude = Some(21.957);
-        helper.db.add_ers_dca_value(ers_dca.clone()).await;
-
-        helper.adapter().increment().await.unwrap();
-        helper.cache.refresh().await.unwrap();
+        helper.add_vessel_with_haul().await;
+        helper.refresh().await;
 
         let cache_result = helper
             .cache
@@ -75,8 +64,7 @@ async fn test_haul_returns_hit_after_refreshing_with_data() {
 #[tokio::test]
 async fn test_landing_refresh_with_no_data_succeeds_and_returns_miss_on_subsequent_request() {
     test(|helper| async move {
-        helper.adapter().increment().await.unwrap();
-        helper.cache.refresh().await.unwrap();
+        helper.refresh().await;
 
         let cache_result = helper
             .cache
@@ -104,8 +92,7 @@ async fn test_landing_returns_hit_after_refreshing_with_data() {
 
         helper.db.add_landings(vec![landing.clone()]).await;
 
-        helper.adapter().increment().await.unwrap();
-        helper.cache.refresh().await.unwrap();
+        helper.refresh().await;
 
         let cache_result = helper
             .cache
@@ -125,3 +112,110 @@ async fn test_landing_returns_hit_after_refreshing_with_data() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn test_first_refresh_is_full_and_subsequent_refreshes_are_incremental() {
+    test(|helper| async move {
+        let vessel_id = helper.add_vessel_with_haul().await;
+        let stats = helper.refresh().await;
+
+        let hauls = stats.hauls.unwrap();
+        assert_eq!(hauls.mode, RefreshMode::Full);
+        assert!(hauls.inserted_rows > 0);
+
+        helper.db.add_ers_dca_value(ers_dca(2, vessel_id)).await;
+
+        let stats = helper.refresh().await;
+
+        let incremental = stats.hauls.unwrap();
+        assert_eq!(incremental.mode, RefreshMode::Incremental);
+        assert_eq!(incremental.deleted_rows, 0);
+        assert_eq!(incremental.inserted_rows, hauls.inserted_rows);
+
+        let rows = helper
+            .cache
+            .haul_rows(HaulsMatrixQuery {
+                months: vec![],
+                catch_locations: vec![],
+                gear_group_ids: vec![],
+                species_group_ids: vec![],
+                vessel_length_groups: vec![],
+                vessel_ids: vec![],
+                active_filter: ActiveHaulsFilter::Date,
+                bycatch_percentage: None,
+                majority_species_group: false,
+            })
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            rows.iter().map(|b| b.num_rows() as u64).sum::<u64>(),
+            hauls.inserted_rows * 2
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_incremental_refresh_replaces_rows_of_modified_landings() {
+    test(|helper| async move {
+        let mut landing = Landing::test_default(1, None);
+        landing.landing_timestamp = Utc.with_ymd_and_hms(2001, 1, 1, 0, 0, 0).unwrap();
+        landing.document_info.version_number = 1;
+
+        helper.db.add_landings(vec![landing.clone()]).await;
+
+        helper.refresh().await;
+
+        landing.document_info.version_number = 2;
+        landing.product.living_weight = Some(40.0);
+        helper.db.add_landings(vec![landing]).await;
+
+        let stats = helper.refresh().await;
+
+        let landings = stats.landings.unwrap();
+        assert_eq!(landings.mode, RefreshMode::Incremental);
+        assert_eq!(landings.deleted_rows, 1);
+        assert_eq!(landings.inserted_rows, 1);
+
+        let rows = helper
+            .cache
+            .landing_rows(LandingMatrixQuery {
+                months: vec![],
+                catch_locations: vec![],
+                gear_group_ids: vec![],
+                species_group_ids: vec![],
+                vessel_length_groups: vec![],
+                vessel_ids: vec![],
+                active_filter: ActiveLandingFilter::Date,
+            })
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(rows.iter().map(|b| b.num_rows()).sum::<usize>(), 1);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_refresh_skips_sources_without_new_versions() {
+    test(|helper| async move {
+        helper.refresh().await;
+
+        // Requested refreshes skip the version check, so both sources are refreshed, but
+        // without any changes nothing should be reloaded.
+        let stats = helper.cache.refresh().await.unwrap();
+
+        let hauls = stats.hauls.unwrap();
+        assert_eq!(hauls.mode, RefreshMode::Incremental);
+        assert_eq!(hauls.deleted_rows, 0);
+        assert_eq!(hauls.inserted_rows, 0);
+    })
+    .await;
+}
@@ -0,0 +1,99 @@
+use duckdb_rs::Client;
+use kyogre_core::{ActiveHaulsFilter, HaulsMatrixQuery, MatrixCacheOutbound, MatrixCacheVersion};
+
+use super::helper::test;
+
+fn hauls_query() -> HaulsMatrixQuery {
+    HaulsMatrixQuery {
+        months: vec![],
+        catch_locations: vec![],
+        gear_group_ids: vec![],
+        species_group_ids: vec![],
+        vessel_length_groups: vec![],
+        vessel_ids: vec![],
+        active_filter: ActiveHaulsFilter::Date,
+        bycatch_percentage: None,
+        majority_species_group: false,
+    }
+}
+
+#[tokio::test]
+async fn test_client_retries_on_another_replica_if_one_is_unavailable() {
+    test(|helper| async move {
+        helper.add_vessel_with_haul().await;
+        helper.refresh().await;
+
+        let unavailable_port = std::net::TcpListener::bind("[::]:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let client = Client::with_replicas([("[::]", unavailable_port), ("[::]", helper.port)])
+            .await
+            .unwrap();
+
+        let expected = helper.cache.hauls_matrix(&hauls_query()).await.unwrap();
+        assert!(!expected.is_empty());
+
+        for _ in 0..4 {
+            let matrix = client.hauls_matrix(&hauls_query()).await.unwrap();
+            assert_eq!(matrix, expected);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_client_skips_replicas_that_are_behind_postgres() {
+    test(|helper| async move {
+        let behind_port = helper.spawn_replica().await;
+
+        helper.add_vessel_with_haul().await;
+        helper.refresh().await;
+
+        let behind = Client::new("[::]", behind_port).await.unwrap();
+        assert!(
+            behind
+                .hauls_matrix(&hauls_query())
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let client = Client::with_replicas([("[::]", behind_port), ("[::]", helper.port)])
+            .await
+            .unwrap();
+        client.check_replicas().await;
+
+        let expected = helper.cache.hauls_matrix(&hauls_query()).await.unwrap();
+        assert!(!expected.is_empty());
+
+        for _ in 0..4 {
+            let matrix = client.hauls_matrix(&hauls_query()).await.unwrap();
+            assert_eq!(matrix, expected);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_client_uses_replicas_that_are_behind_if_none_are_current() {
+    test(|helper| async move {
+        helper.add_vessel_with_haul().await;
+        helper.refresh().await;
+
+        let expected = helper.cache.hauls_matrix(&hauls_query()).await.unwrap();
+
+        helper.adapter().increment().await.unwrap();
+
+        let client = Client::with_replicas([("[::]", helper.port)])
+            .await
+            .unwrap();
+        client.check_replicas().await;
+
+        let matrix = client.hauls_matrix(&hauls_query()).await.unwrap();
+        assert_eq!(matrix, expected);
+    })
+    .await;
+}
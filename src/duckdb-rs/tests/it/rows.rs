@@ -0,0 +1,99 @@
+use chrono::{TimeZone, Utc};
+use fiskeridir_rs::Landing;
+use futures::TryStreamExt;
+use kyogre_core::{
+    ActiveHaulsFilter, ActiveLandingFilter, FiskeridirVesselId, HaulsMatrixQuery,
+    LandingMatrixQuery,
+};
+
+use super::helper::test;
+
+fn hauls_query(vessel_ids: Vec<FiskeridirVesselId>) -> HaulsMatrixQuery {
+    HaulsMatrixQuery {
+        months: vec![],
+        catch_locations: vec![],
+        gear_group_ids: vec![],
+        species_group_ids: vec![],
+        vessel_length_groups: vec![],
+        vessel_ids,
+        active_filter: ActiveHaulsFilter::Date,
+        bycatch_percentage: None,
+        majority_species_group: false,
+    }
+}
+
+#[tokio::test]
+async fn test_haul_rows_returns_rows_after_refreshing_with_data() {
+    test(|helper| async move {
+        let vessel_id = helper.add_vessel_with_haul().await;
+        helper.refresh().await;
+
+        let batches = helper
+            .cache
+            .haul_rows(hauls_query(vec![vessel_id]))
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert!(batches.iter().map(|b| b.num_rows()).sum::<usize>() > 0);
+        assert!(
+            batches
+                .iter()
+                .all(|b| b.schema().field_with_name("living_weight").is_ok())
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_haul_rows_applies_vessel_filter() {
+    test(|helper| async move {
+        helper.add_vessel_with_haul().await;
+        helper.refresh().await;
+
+        let batches = helper
+            .cache
+            .haul_rows(hauls_query(vec![FiskeridirVesselId::test_new(2)]))
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 0);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_landing_rows_returns_rows_after_refreshing_with_data() {
+    test(|helper| async move {
+        let mut landing = Landing::test_default(1, None);
+        landing.landing_timestamp = Utc.with_ymd_and_hms(2001, 1, 1, 0, 0, 0).unwrap();
+
+        helper.db.add_landings(vec![landing]).await;
+        helper.refresh().await;
+
+        let batches = helper
+            .cache
+            .landing_rows(LandingMatrixQuery {
+                months: vec![],
+                catch_locations: vec![],
+                gear_group_ids: vec![],
+                species_group_ids: vec![],
+                vessel_length_groups: vec![],
+                vessel_ids: vec![],
+                active_filter: ActiveLandingFilter::Date,
+            })
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert!(batches.iter().map(|b| b.num_rows()).sum::<usize>() > 0);
+    })
+    .await;
+}
@@ -0,0 +1,167 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use fiskeridir_rs::Landing;
+use kyogre_core::{
+    ActiveHaulsFilter, ActiveLandingFilter, FiskeridirVesselId, HaulsMatrixQuery,
+    HaulsTimeSeriesQuery, LandingMatrixQuery, LandingTimeSeriesQuery, MatrixCacheOutbound,
+    TimeBucket, TimeSeriesGroup,
+};
+
+use super::helper::{TestHelper, ers_dca, test};
+
+fn hauls_query(bucket: TimeBucket, group_by: Vec<TimeSeriesGroup>) -> HaulsTimeSeriesQuery {
+    HaulsTimeSeriesQuery {
+        filter: HaulsMatrixQuery {
+            months: vec![],
+            catch_locations: vec![],
+            gear_group_ids: vec![],
+            species_group_ids: vec![],
+            vessel_length_groups: vec![],
+            vessel_ids: vec![],
+            active_filter: ActiveHaulsFilter::Date,
+            bycatch_percentage: None,
+            majority_species_group: false,
+        },
+        bucket,
+        group_by,
+    }
+}
+
+async fn add_haul(helper: &TestHelper, message_id: u64, start: DateTime<Utc>) {
+    let vessel_id = FiskeridirVesselId::test_new(1);
+
+    let mut ers_dca = ers_dca(message_id, vessel_id);
+    ers_dca.set_start_timestamp(start);
+    ers_dca.set_stop_timestamp(start + Duration::hours(1));
+    helper.db.add_ers_dca_value(ers_dca).await;
+}
+
+#[tokio::test]
+async fn test_hauls_time_series_sums_per_week() {
+    test(|helper| async move {
+        helper
+            .db
+            .generate_fiskeridir_vessel(FiskeridirVesselId::test_new(1), None, None)
+            .await;
+
+        add_haul(
+            &helper,
+            1,
+            Utc.with_ymd_and_hms(2020, 1, 1, 10, 0, 0).unwrap(),
+        )
+        .await;
+        add_haul(
+            &helper,
+            2,
+            Utc.with_ymd_and_hms(2020, 1, 2, 10, 0, 0).unwrap(),
+        )
+        .await;
+        add_haul(
+            &helper,
+            3,
+            Utc.with_ymd_and_hms(2020, 1, 15, 10, 0, 0).unwrap(),
+        )
+        .await;
+
+        helper.refresh().await;
+
+        let series = helper
+            .cache
+            .hauls_time_series(&hauls_query(TimeBucket::Week, vec![]))
+            .await
+            .unwrap();
+
+        assert_eq!(series.len(), 2);
+        assert_eq!(
+            series[0].bucket,
+            Utc.with_ymd_and_hms(2019, 12, 30, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            series[1].bucket,
+            Utc.with_ymd_and_hms(2020, 1, 13, 0, 0, 0).unwrap()
+        );
+        assert!(series[0].living_weight > series[1].living_weight);
+        assert!(series.iter().all(|p| p.species_group_id.is_none()));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_hauls_time_series_groups_by_requested_dimensions() {
+    test(|helper| async move {
+        let vessel_id = FiskeridirVesselId::test_new(1);
+        helper
+            .db
+            .generate_fiskeridir_vessel(vessel_id, None, None)
+            .await;
+
+        add_haul(
+            &helper,
+            1,
+            Utc.with_ymd_and_hms(2020, 1, 1, 10, 0, 0).unwrap(),
+        )
+        .await;
+
+        helper.refresh().await;
+
+        let series = helper
+            .cache
+            .hauls_time_series(&hauls_query(
+                TimeBucket::Year,
+                vec![TimeSeriesGroup::SpeciesGroup, TimeSeriesGroup::Vessel],
+            ))
+            .await
+            .unwrap();
+
+        assert!(!series.is_empty());
+        for point in series {
+            assert_eq!(
+                point.bucket,
+                Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap()
+            );
+            assert!(point.species_group_id.is_some());
+            assert_eq!(point.fiskeridir_vessel_id, Some(vessel_id));
+            assert!(point.gear_group_id.is_none());
+            assert!(point.catch_location_id.is_none());
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_landing_time_series_sums_per_month() {
+    test(|helper| async move {
+        let mut landing = Landing::test_default(1, None);
+        landing.landing_timestamp = Utc.with_ymd_and_hms(2001, 1, 20, 0, 0, 0).unwrap();
+
+        helper.db.add_landings(vec![landing]).await;
+
+        helper.refresh().await;
+
+        let series = helper
+            .cache
+            .landing_time_series(&LandingTimeSeriesQuery {
+                filter: LandingMatrixQuery {
+                    months: vec![],
+                    catch_locations: vec![],
+                    gear_group_ids: vec![],
+                    species_group_ids: vec![],
+                    vessel_length_groups: vec![],
+                    vessel_ids: vec![],
+                    active_filter: ActiveLandingFilter::Date,
+                },
+                bucket: TimeBucket::Month,
+                group_by: vec![TimeSeriesGroup::GearGroup],
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(series.len(), 1);
+        assert_eq!(
+            series[0].bucket,
+            Utc.with_ymd_and_hms(2001, 1, 1, 0, 0, 0).unwrap()
+        );
+        assert!(series[0].gear_group_id.is_some());
+        assert!(series[0].living_weight > 0.0);
+    })
+    .await;
+}
@@ -82,6 +82,7 @@ pub enum Fishery {
 pub struct SharedState {
     pub num_workers: u32,
     pub fuel_mode: FuelImplDiscriminants,
+    pub incremental_trip_assembly: bool,
     pub local_processing_vessels: Option<Vec<FiskeridirVesselId>>,
     pub trip_assembler_outbound_port: Box<dyn TripAssemblerOutboundPort>,
     pub trips_precision_outbound_port: Box<dyn TripPrecisionOutboundPort>,
@@ -103,18 +104,11 @@ pub struct SharedState {
 
 impl SharedState {
     pub fn assembler_id_to_impl(&self, id: TripAssemblerId) -> &dyn TripAssembler {
-        let ers_idx = self
-            .trip_assemblers
+        self.trip_assemblers
             .iter()
-            .position(|v| v.assembler_id() == id);
-        let landings_idx = self
-            .trip_assemblers
-            .iter()
-            .position(|v| v.assembler_id() == id);
-        match id {
-            TripAssemblerId::Landings => self.trip_assemblers[ers_idx.unwrap()].as_ref(),
-            TripAssemblerId::Ers => self.trip_assemblers[landings_idx.unwrap()].as_ref(),
-        }
+            .find(|v| v.assembler_id() == id)
+            .unwrap_or_else(|| panic!("missing trip assembler: {id:?}"))
+            .as_ref()
     }
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -137,6 +131,7 @@ impl SharedState {
         trip_distancer: Box<dyn TripDistancer>,
         trip_position_layers: Vec<Box<dyn TripPositionLayer>>,
         fuel_mode: FuelImplDiscriminants,
+        incremental_trip_assembly: bool,
     ) -> SharedState {
         SharedState {
             num_workers,
@@ -158,6 +153,7 @@ impl SharedState {
             catch_location_weather,
             fuel_estimation,
             fuel_mode,
+            incremental_trip_assembly,
         }
     }
 }
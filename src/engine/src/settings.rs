@@ -1,4 +1,7 @@
-use crate::{AisVms, Cluster, ErsTripAssembler, FisheryDiscriminants, LandingTripAssembler};
+use crate::{
+    AisVms, AisVmsTripAssembler, Cluster, ErsTripAssembler, FisheryDiscriminants,
+    LandingTripAssembler,
+};
 use config::ConfigError;
 use kyogre_core::*;
 use orca_core::{Environment, PsqlSettings};
@@ -9,6 +12,10 @@ use serde::Deserialize;
 pub struct Settings {
     pub num_trip_state_workers: u32,
     pub fuel_estimation_mode: FuelImplDiscriminants,
+    /// Only replaces the trips that changed when re-assembling trips after a conflict, instead of
+    /// all trips after the conflict.
+    #[serde(default)]
+    pub incremental_trip_assembly: bool,
     pub local_processing_vessels: Option<Vec<FiskeridirVesselId>>,
     pub postgres: PsqlSettings,
     pub environment: Environment,
@@ -30,10 +37,12 @@ impl Settings {
     pub fn trip_assemblers(&self) -> Vec<Box<dyn TripAssembler>> {
         let landings_assembler = Box::<LandingTripAssembler>::default();
         let ers_assembler = Box::<ErsTripAssembler>::default();
+        let ais_vms_assembler = Box::<AisVmsTripAssembler>::default();
 
         let vec = vec![
             ers_assembler as Box<dyn TripAssembler>,
             landings_assembler as Box<dyn TripAssembler>,
+            ais_vms_assembler as Box<dyn TripAssembler>,
         ];
 
         vec
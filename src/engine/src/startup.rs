@@ -69,6 +69,7 @@ impl App {
             trip_distancer,
             trip_position_layers,
             settings.fuel_estimation_mode,
+            settings.incremental_trip_assembly,
         );
 
         App {
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use processors::{FuelImpl, VesselFuelInfo, estimate_fuel};
+use processors::{FuelImpl, estimate_fuel, vessel_fuel_info};
 
 use super::*;
 
@@ -21,18 +21,22 @@ impl TripComputationStep for TripFuelConsumption {
             return Ok(unit);
         }
 
-        let max_cargo_weight = match shared.fuel_mode {
-            FuelImplDiscriminants::Maru => Some(
-                shared
-                    .fuel_estimation
-                    .vessel_max_cargo_weight(vessel.fiskeridir.id)
-                    .await?,
-            ),
-            FuelImplDiscriminants::Holtrop => None,
-        };
+        let empirical_model = shared
+            .fuel_estimation
+            .empirical_fuel_model(vessel.fiskeridir.id)
+            .await?;
 
-        let vessel = VesselFuelInfo::from_core(vessel, max_cargo_weight, shared.fuel_mode);
+        let vessel = vessel_fuel_info(
+            shared.fuel_estimation.as_ref(),
+            shared.fuel_mode,
+            vessel,
+            empirical_model,
+        )
+        .await?;
         let mut fuel_impl = FuelImpl::new(&vessel);
+        fuel_impl
+            .load_weather(shared.fuel_estimation.as_ref(), &unit.positions)
+            .await?;
 
         estimate_fuel(&mut fuel_impl, &mut unit.positions, &vessel);
 
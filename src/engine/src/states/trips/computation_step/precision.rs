@@ -6,6 +6,7 @@ use super::*;
 pub struct TripPrecisionStep {
     landing: LandingTripAssembler,
     ers: ErsTripAssembler,
+    ais_vms: AisVmsTripAssembler,
 }
 
 #[async_trait]
@@ -24,6 +25,7 @@ impl TripComputationStep for TripPrecisionStep {
         let precision = match vessel.preferred_trip_assembler {
             TripAssemblerId::Landings => self.landing.calculate_precision(vessel, adapter, &unit),
             TripAssemblerId::Ers => self.ers.calculate_precision(vessel, adapter, &unit),
+            TripAssemblerId::AisVms => self.ais_vms.calculate_precision(vessel, adapter, &unit),
         }
         .await?;
 
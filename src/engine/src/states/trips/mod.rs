@@ -2,7 +2,7 @@ use std::{cmp::min, collections::HashMap, sync::Arc};
 
 use async_channel::bounded;
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use machine::Schedule;
 use tokio::{select, sync::mpsc::channel, task::JoinSet};
 use tracing::{error, info};
@@ -20,6 +20,8 @@ pub struct TripsState;
 #[derive(Debug, Default)]
 pub struct TripsReport {
     pub num_trips: u32,
+    pub num_trips_replaced: u32,
+    pub num_trips_unchanged: u32,
     pub num_conflicts: u32,
     pub num_no_prior_state: u32,
     pub num_vessels: u32,
@@ -30,6 +32,11 @@ pub struct TripsReport {
 #[derive(Debug)]
 pub struct TripProcessingOutcome {
     pub num_trips: u32,
+    /// Number of existing trips that were replaced, only counted by incremental trip assembly.
+    pub num_trips_replaced: u32,
+    /// Number of re-assembled trips that were identical to existing trips and therefore kept,
+    /// only counted by incremental trip assembly.
+    pub num_trips_unchanged: u32,
     pub state: AssemblerState,
 }
 
@@ -50,6 +57,8 @@ impl std::ops::Add<TripProcessingOutcome> for TripsReport {
 
     fn add(mut self, rhs: TripProcessingOutcome) -> Self::Output {
         self.num_trips += rhs.num_trips;
+        self.num_trips_replaced += rhs.num_trips_replaced;
+        self.num_trips_unchanged += rhs.num_trips_unchanged;
         self.num_vessels += 1;
         match rhs.state {
             AssemblerState::Conflict(_) => self.num_conflicts += 1,
@@ -73,11 +82,14 @@ impl machine::State for TripsState {
             Ok(r) => {
                 info!(
                     "num_conflicts: {}, num_vessels: {}, num_no_prior_state: {}
-                       num_trips: {}, num_failed: {}, num_reset: {}",
+                       num_trips: {}, num_trips_replaced: {}, num_trips_unchanged: {},
+                       num_failed: {}, num_reset: {}",
                     r.num_conflicts,
                     r.num_vessels,
                     r.num_no_prior_state,
                     r.num_trips,
+                    r.num_trips_replaced,
+                    r.num_trips_unchanged,
                     r.num_failed,
                     r.num_reset
                 );
@@ -225,7 +237,7 @@ async fn run_state(shared_state: Arc<SharedState>) -> Result<TripsReport> {
                 match task {
                     MasterTask::New(vessel, result) => {
                         match result {
-                            Ok((TripProcessingOutcome { num_trips: 0, state: AssemblerState::QueuedReset }, None)) => {
+                            Ok((TripProcessingOutcome { num_trips: 0, state: AssemblerState::QueuedReset, .. }, None)) => {
                                     if let Err(e) =
                                         shared_state.trip_pipeline_inbound.nuke_trips(vessel.fiskeridir.id).await
                                     {
@@ -250,7 +262,7 @@ async fn run_state(shared_state: Arc<SharedState>) -> Result<TripsReport> {
                                     // trip to add any new hauls or fishing facilites that might have
                                     // been added.
                                     match vessel.preferred_trip_assembler {
-                                        TripAssemblerId::Landings => (),
+                                        TripAssemblerId::Landings | TripAssemblerId::AisVms => (),
                                         TripAssemblerId::Ers => {
                                             if let Err(e) = shared_state.trip_pipeline_inbound.set_current_trip(vessel.fiskeridir.id).await {
                                                 error!(
@@ -333,17 +345,77 @@ async fn run_state(shared_state: Arc<SharedState>) -> Result<TripsReport> {
     Ok(trips_report)
 }
 
+/// Detects port calls from the vessel's AIS/VMS positions since the last time it was processed,
+/// which the [`TripAssemblerId::AisVms`] trip assembler creates trips from.
+async fn detect_port_calls(
+    shared: &SharedState,
+    vessel: &Vessel,
+    ports: &HashMap<String, Port>,
+    dock_points: &HashMap<String, Vec<PortDockPoint>>,
+) -> Result<()> {
+    if vessel.mmsi().is_none() && vessel.fiskeridir_call_sign().is_none() {
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    let timer = shared
+        .trip_assembler_outbound_port
+        .ais_vms_port_call_timer(vessel.id())
+        .await?
+        .unwrap_or_else(|| AisVmsPortCallTimer {
+            timestamp: port_call_search_start(now),
+            port_id: None,
+        });
+
+    let locations = PortLocations::new(ports, dock_points);
+    let mut detector = PortCallDetector::new(&locations, vessel.id(), timer.clone());
+
+    let mut start = timer.timestamp;
+    while start < now {
+        let end = min(start + Duration::days(PORT_CALL_SEARCH_WINDOW_DAYS), now);
+        let positions = shared
+            .trips_precision_outbound_port
+            .ais_vms_positions(
+                vessel.mmsi(),
+                vessel.fiskeridir_call_sign(),
+                &DateRange::new(start, end)?,
+            )
+            .await?;
+
+        for p in &positions {
+            detector.advance(p);
+        }
+
+        start = end;
+    }
+
+    let (port_calls, new_timer) = detector.finalize();
+    if new_timer != timer {
+        shared
+            .trip_pipeline_inbound
+            .add_ais_vms_port_calls(vessel.id(), port_calls, new_timer)
+            .await?;
+    }
+
+    Ok(())
+}
+
 async fn process_vessel(
     shared: &SharedState,
     vessel: &Vessel,
     ports: &HashMap<String, Port>,
     dock_points: &HashMap<String, Vec<PortDockPoint>>,
 ) -> Result<(TripProcessingOutcome, Option<TripSet>)> {
+    if vessel.preferred_trip_assembler == TripAssemblerId::AisVms {
+        detect_port_calls(shared, vessel, ports, dock_points).await?;
+    }
+
     let assembler_impl = shared.assembler_id_to_impl(vessel.preferred_trip_assembler);
     let (outcome, trips) = run_trip_assembler(
         vessel,
         shared.trip_assembler_outbound_port.as_ref(),
         assembler_impl,
+        shared.incremental_trip_assembly,
     )
     .await?;
 
@@ -451,6 +523,7 @@ async fn run_trip_assembler(
     vessel: &Vessel,
     adapter: &dyn TripAssemblerOutboundPort,
     assembler: &dyn TripAssembler,
+    incremental: bool,
 ) -> Result<(TripProcessingOutcome, Option<TripAssembly>)> {
     let timer = adapter
         .trip_calculation_timer(vessel.fiskeridir.id, assembler.assembler_id())
@@ -528,14 +601,41 @@ async fn run_trip_assembler(
             (AssemblerState::QueuedReset, _) => TripsConflictStrategy::ReplaceAll,
         };
 
+        let replaced_from = match conflict_strategy {
+            TripsConflictStrategy::Replace { conflict } => Some(Some(conflict)),
+            TripsConflictStrategy::ReplaceAll => Some(None),
+            TripsConflictStrategy::Error | TripsConflictStrategy::ReplaceRange { .. } => None,
+        };
+
+        let assembly = match (&state, replaced_from) {
+            (
+                AssemblerState::Conflict(_) | AssemblerState::TripCalculationTimer(_),
+                Some(end_from),
+            ) if incremental => {
+                let existing = adapter
+                    .assembled_trips(vessel.fiskeridir.id, assembler.assembler_id(), end_from)
+                    .await?;
+                ChangedTrips::new(&existing, trips.new_trips)
+            }
+            // Resets and first time assembly always re-create all trips.
+            _ => ChangedTrips {
+                num_trips_replaced: 0,
+                num_trips_unchanged: 0,
+                trips: trips.new_trips,
+                conflict_strategy,
+            },
+        };
+
         Ok((
             TripProcessingOutcome {
-                num_trips: trips.new_trips.len() as u32,
+                num_trips: assembly.trips.len() as u32,
+                num_trips_replaced: assembly.num_trips_replaced,
+                num_trips_unchanged: assembly.num_trips_unchanged,
                 state,
             },
             Some(TripAssembly {
-                trips: trips.new_trips,
-                conflict_strategy,
+                trips: assembly.trips,
+                conflict_strategy: assembly.conflict_strategy,
                 trip_assembler_id: assembler.assembler_id(),
                 prior_trip_calculation_time,
                 conflict,
@@ -548,6 +648,8 @@ async fn run_trip_assembler(
         Ok((
             TripProcessingOutcome {
                 num_trips: 0,
+                num_trips_replaced: 0,
+                num_trips_unchanged: 0,
                 state,
             },
             None,
@@ -555,6 +657,78 @@ async fn run_trip_assembler(
     }
 }
 
+/// The difference between the trips that would be replaced by a conflict strategy and the
+/// re-assembled trips.
+/// Late arriving events usually only affect the trips surrounding them, the leading and trailing
+/// trips that are identical to the existing trips are kept instead of being re-created.
+#[derive(Debug)]
+struct ChangedTrips {
+    trips: Vec<NewTrip>,
+    conflict_strategy: TripsConflictStrategy,
+    num_trips_replaced: u32,
+    num_trips_unchanged: u32,
+}
+
+impl ChangedTrips {
+    /// Assumes both `existing` and `new_trips` are ordered by their period and that `existing`
+    /// are all the trips the original conflict strategy would have replaced.
+    fn new(existing: &[NewTrip], mut new_trips: Vec<NewTrip>) -> Self {
+        let num_leading = existing
+            .iter()
+            .zip(&new_trips)
+            .take_while(|(a, b)| is_identical(a, b))
+            .count();
+
+        let num_trailing = existing[num_leading..]
+            .iter()
+            .rev()
+            .zip(new_trips[num_leading..].iter().rev())
+            .take_while(|(a, b)| is_identical(a, b))
+            .count();
+
+        let replaced = &existing[num_leading..existing.len() - num_trailing];
+        new_trips.truncate(new_trips.len() - num_trailing);
+        let trips = new_trips.split_off(num_leading);
+
+        // Trips do not overlap, all trips starting within the range are therefore the trips
+        // between the unchanged leading and trailing trips.
+        let start = [replaced.first(), trips.first()]
+            .into_iter()
+            .flatten()
+            .map(|t| t.period.start())
+            .min();
+        let end = [replaced.last(), trips.last()]
+            .into_iter()
+            .flatten()
+            .map(|t| t.period.start())
+            .max();
+
+        let conflict_strategy = match (start, end) {
+            (Some(start), Some(end)) => TripsConflictStrategy::ReplaceRange { start, end },
+            // Nothing changed and nothing will be replaced
+            _ => TripsConflictStrategy::Error,
+        };
+
+        Self {
+            num_trips_replaced: replaced.len() as u32,
+            num_trips_unchanged: (num_leading + num_trailing) as u32,
+            trips,
+            conflict_strategy,
+        }
+    }
+}
+
+/// `DateRange`'s `PartialEq` only compares timestamps with second precision, which is not enough
+/// to decide whether a trip can be kept.
+fn is_identical(a: &NewTrip, b: &NewTrip) -> bool {
+    let exact = |a: &DateRange, b: &DateRange| a.start() == b.start() && a.end() == b.end();
+
+    a == b
+        && exact(&a.period, &b.period)
+        && exact(&a.period_extended, &b.period_extended)
+        && exact(&a.landing_coverage, &b.landing_coverage)
+}
+
 async fn new_vessel_events(
     vessel_id: FiskeridirVesselId,
     adapter: &dyn TripAssemblerOutboundPort,
@@ -1,14 +1,15 @@
 use crate::{
-    AisConsumeLoop, AisPosition, AisVms, Arrival, Cluster, DataMessage, Departure,
-    ErsTripAssembler, FisheryEngine, FishingFacilities, FishingFacilitiesQuery, FishingFacility,
-    Haul, HaulsQuery, Landing, LandingTripAssembler, LandingsQuery, LandingsSorting, Mmsi,
-    NewAisPosition, NewAisStatic, OceanClimate, Ordering, Pagination, PrecisionId, ScrapeState,
-    SharedState, Step, TripDetailed, Trips, TripsQuery, Vessel, VmsPosition, Weather,
+    AisConsumeLoop, AisPosition, AisVms, AisVmsTripAssembler, Arrival, Cluster, DataMessage,
+    Departure, ErsTripAssembler, FisheryEngine, FishingFacilities, FishingFacilitiesQuery,
+    FishingFacility, Haul, HaulsQuery, Landing, LandingTripAssembler, LandingsQuery,
+    LandingsSorting, Mmsi, NewAisPosition, NewAisStatic, OceanClimate, Ordering, Pagination,
+    PrecisionId, ScrapeState, SharedState, Step, TripDetailed, Trips, TripsQuery, Vessel,
+    VmsPosition, Weather,
     test_helper::user_haul::{UserHaulBuilder, UserHaulConstructor},
 };
 use async_channel::Sender;
 use chrono::{DateTime, Duration, TimeZone, Utc};
-use fiskeridir_rs::{CallSign, DeliveryPointId, LandingMonth, RegisterVessel};
+use fiskeridir_rs::{CallSign, DeliveryPointId, LandingMonth, RegisterVessel, SpeciesFiskeridirId};
 use futures::TryStreamExt;
 use kyogre_core::{
     BarentswatchUserId, BuyerLocation, FisheryId, FiskeridirVesselId, NewVesselConflict,
@@ -174,13 +175,18 @@ enum TripPrecisonStartPoint {
     },
 }
 
-pub async fn engine(adapter: PostgresAdapter, db_settings: &PsqlSettings) -> FisheryEngine {
+pub async fn engine(
+    adapter: PostgresAdapter,
+    db_settings: &PsqlSettings,
+    incremental_trip_assembly: bool,
+) -> FisheryEngine {
     let transition_log = Box::new(machine::PostgresAdapter::new(db_settings).await.unwrap());
     let db_arc = Arc::new(adapter.clone());
     let db = Box::new(adapter);
     let trip_assemblers = vec![
         Box::<LandingTripAssembler>::default() as Box<dyn TripAssembler>,
         Box::<ErsTripAssembler>::default() as Box<dyn TripAssembler>,
+        Box::<AisVmsTripAssembler>::default() as Box<dyn TripAssembler>,
     ];
     let trip_distancer = Box::<AisVms>::default() as Box<dyn TripDistancer>;
     let trip_layers = vec![
@@ -209,6 +215,7 @@ pub async fn engine(adapter: PostgresAdapter, db_settings: &PsqlSettings) -> Fis
         trip_distancer,
         trip_layers,
         FuelImplDiscriminants::Maru,
+        incremental_trip_assembly,
     );
     let step = Step::initial(ScrapeState, shared_state, transition_log, random());
     FisheryEngine::Scrape(step)
@@ -590,6 +597,25 @@ impl TestStateBuilder {
         }
     }
 
+    /// Adds a single `WeeklySale` in the week of `timestamp` matching the gear group, length group
+    /// and species of the first catch of the given haul, which gives the haul an estimated value.
+    pub fn haul_weekly_sale(mut self, haul: &Haul, timestamp: DateTime<Utc>) -> WeeklySaleBuilder {
+        let mut id = WeeklySaleId::test_new(timestamp);
+        id.vessel_length_group = haul.vessel_length_group;
+        id.gear_group = haul.gear_group_id;
+        id.species = SpeciesFiskeridirId::test_new(haul.catches[0].species_fiskeridir_id);
+
+        self.weekly_sales.push(WeeklySaleContructor {
+            cycle: self.cycle,
+            weekly_sale: WeeklySale::test_new(id),
+        });
+
+        WeeklySaleBuilder {
+            current_index: self.weekly_sales.len() - 1,
+            state: self,
+        }
+    }
+
     pub async fn build(mut self) -> TestState {
         // TODO: get weather/climate from db and not conversion.
         let mut weather = Vec::new();
@@ -629,6 +655,7 @@ impl TestStateBuilder {
                             }
                         }))
                         .collect(),
+                    rejected_messages: vec![],
                 })
                 .await
                 .unwrap();
@@ -682,6 +709,8 @@ impl TestStateBuilder {
                                             engine_rpm: None,
                                             degree_of_electrification: None,
                                             service_speed: None,
+                                            fuel_type: None,
+                                            fuel_estimation_model: None,
                                         },
                                     ));
                                 }
@@ -1019,6 +1048,7 @@ impl TestStateBuilder {
                         })
                         .collect(),
                     static_messages: vec![],
+                    rejected_messages: vec![],
                 })
                 .await
                 .unwrap();
@@ -0,0 +1,369 @@
+use super::{
+    DockPointPrecision, PortPrecision, PrecisionConfig, StartSearchPoint,
+    precision::TripPrecisionCalculator,
+};
+use crate::error::Result;
+use crate::{DistanceToShorePrecision, ers_last_trip_landing_coverage_end};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use kyogre_core::{
+    AisVmsPortCallType, Bound, CoreResult, DateRange, NewTrip, PrecisionOutcome, TripAssembler,
+    TripAssemblerId, TripAssemblerState, TripPrecisionOutboundPort, TripProcessingUnit,
+    TripsConflictStrategy, Vessel, VesselEventData, VesselEventDetailed,
+};
+
+mod port_calls;
+
+pub use port_calls::*;
+
+/// Creates trips for vessels that report neither ERS nor landings based on port calls detected
+/// from their AIS/VMS positions (see [`PortCallDetector`]).
+/// A trip starts at a departure and ends at the succeeding arrival.
+///
+/// Landing coverage for a trip is defined as `DEP -> DEP(N)` where `DEP(N)` is the next trip's
+/// departure, the latest trip which has no next trip has the end `ARR + 3 days`.
+pub struct AisVmsTripAssembler {
+    precision_calculator: TripPrecisionCalculator,
+}
+
+impl Default for AisVmsTripAssembler {
+    fn default() -> Self {
+        // Port calls are registered at the last/first position within the port call distance
+        // threshold, the actual departure from and arrival at the dock is therefore searched for
+        // by extending the trip period.
+        let config = PrecisionConfig::default();
+        let port_start = Box::new(PortPrecision::new(
+            config.clone(),
+            PrecisionDirection::Extending,
+            StartSearchPoint::Start,
+        ));
+        let port_end = Box::new(PortPrecision::new(
+            config.clone(),
+            PrecisionDirection::Extending,
+            StartSearchPoint::End,
+        ));
+        let dock_point_start = Box::new(DockPointPrecision::new(
+            config.clone(),
+            PrecisionDirection::Extending,
+            StartSearchPoint::Start,
+        ));
+        let dock_point_end = Box::new(DockPointPrecision::new(
+            config.clone(),
+            PrecisionDirection::Extending,
+            StartSearchPoint::End,
+        ));
+        let distance_to_shore_start = Box::new(DistanceToShorePrecision::new(
+            config.clone(),
+            PrecisionDirection::Extending,
+            StartSearchPoint::Start,
+        ));
+        let distance_to_shore_end = Box::new(DistanceToShorePrecision::new(
+            config,
+            PrecisionDirection::Extending,
+            StartSearchPoint::End,
+        ));
+        AisVmsTripAssembler {
+            precision_calculator: TripPrecisionCalculator::new(
+                vec![port_start, dock_point_start, distance_to_shore_start],
+                vec![port_end, dock_point_end, distance_to_shore_end],
+            ),
+        }
+    }
+}
+
+impl AisVmsTripAssembler {
+    pub fn new(precision_calculator: TripPrecisionCalculator) -> AisVmsTripAssembler {
+        AisVmsTripAssembler {
+            precision_calculator,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PortCallEvent {
+    vessel_event_id: i64,
+    timestamp: DateTime<Utc>,
+    port_id: Option<String>,
+    port_call_type: AisVmsPortCallType,
+}
+
+impl PortCallEvent {
+    fn from_detailed_vessel_event(v: VesselEventDetailed) -> Option<PortCallEvent> {
+        match v.event_data {
+            VesselEventData::AisVmsDeparture { port_id, timestamp } => {
+                Some((AisVmsPortCallType::Departure, port_id, timestamp))
+            }
+            VesselEventData::AisVmsArrival { port_id, timestamp } => {
+                Some((AisVmsPortCallType::Arrival, port_id, timestamp))
+            }
+            VesselEventData::ErsDep { .. }
+            | VesselEventData::ErsPor { .. }
+            | VesselEventData::Landing
+            | VesselEventData::ErsDca
+            | VesselEventData::ErsTra
+            | VesselEventData::Haul => None,
+        }
+        .map(|(port_call_type, port_id, timestamp)| PortCallEvent {
+            vessel_event_id: v.event_id as i64,
+            timestamp,
+            port_id,
+            port_call_type,
+        })
+    }
+}
+
+#[async_trait]
+impl TripAssembler for AisVmsTripAssembler {
+    fn assembler_id(&self) -> TripAssemblerId {
+        TripAssemblerId::AisVms
+    }
+    async fn calculate_precision(
+        &self,
+        vessel: &Vessel,
+        adapter: &dyn TripPrecisionOutboundPort,
+        trip: &TripProcessingUnit,
+    ) -> CoreResult<PrecisionOutcome> {
+        Ok(self
+            .precision_calculator
+            .calculate_precision(vessel, adapter, trip)
+            .await?)
+    }
+
+    async fn assemble(
+        &self,
+        prior_trip_events: Vec<VesselEventDetailed>,
+        vessel_events: Vec<VesselEventDetailed>,
+    ) -> CoreResult<Option<TripAssemblerState>> {
+        Ok(assemble_impl(prior_trip_events, vessel_events)?)
+    }
+}
+
+fn assemble_impl(
+    prior_trip_events: Vec<VesselEventDetailed>,
+    vessel_events: Vec<VesselEventDetailed>,
+) -> Result<Option<TripAssemblerState>> {
+    let mut vessel_events: Vec<PortCallEvent> = vessel_events
+        .into_iter()
+        .filter_map(PortCallEvent::from_detailed_vessel_event)
+        .collect();
+
+    let mut prior_trip_events: Vec<PortCallEvent> = prior_trip_events
+        .into_iter()
+        .filter_map(PortCallEvent::from_detailed_vessel_event)
+        .collect();
+
+    // Only departures will not generate any new trips
+    if vessel_events
+        .iter()
+        .all(|v| v.port_call_type == AisVmsPortCallType::Departure)
+    {
+        return Ok(None);
+    }
+
+    // Adding a new trip always requires recalculating the previous trip due to landing coverage
+    // semantics.
+    let conflict_strategy = prior_trip_events
+        .last()
+        .map(|last| TripsConflictStrategy::Replace {
+            conflict: last.timestamp,
+        });
+
+    prior_trip_events.append(&mut vessel_events);
+
+    let new_trips = trips_from_port_calls(prior_trip_events)?;
+
+    if new_trips.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(TripAssemblerState {
+            new_trips,
+            conflict_strategy,
+        }))
+    }
+}
+
+fn trips_from_port_calls(events: Vec<PortCallEvent>) -> Result<Vec<NewTrip>> {
+    let mut new_trips: Vec<NewTrip> = Vec::new();
+    let mut departure: Option<PortCallEvent> = None;
+
+    for e in events {
+        match e.port_call_type {
+            AisVmsPortCallType::Departure => {
+                // Port calls are detected in order and should always alternate, but if they
+                // do not we keep the earliest departure.
+                if departure.is_none() {
+                    departure = Some(e);
+                }
+            }
+            AisVmsPortCallType::Arrival => {
+                let Some(departure) = departure.take() else {
+                    continue;
+                };
+
+                let period = DateRange::new(departure.timestamp, e.timestamp)?;
+
+                if let Some(prior_trip) = new_trips.last_mut() {
+                    let mut landing_coverage =
+                        DateRange::new(prior_trip.landing_coverage.start(), period.start())?;
+                    landing_coverage.set_end_bound(Bound::Exclusive);
+                    prior_trip.landing_coverage = landing_coverage;
+                }
+
+                let mut landing_coverage = DateRange::new(
+                    period.start(),
+                    ers_last_trip_landing_coverage_end(&period.end()),
+                )?;
+                landing_coverage.set_end_bound(Bound::Exclusive);
+
+                new_trips.push(NewTrip {
+                    period_extended: period.clone(),
+                    period,
+                    landing_coverage,
+                    first_arrival: None,
+                    start_port_code: departure.port_id,
+                    end_port_code: e.port_id,
+                    start_vessel_event_id: Some(departure.vessel_event_id),
+                    end_vessel_event_id: Some(e.vessel_event_id),
+                });
+            }
+        }
+    }
+
+    Ok(new_trips)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use kyogre_core::{FiskeridirVesselId, VesselEventType};
+
+    fn event(
+        event_id: u64,
+        timestamp: DateTime<Utc>,
+        port_call_type: AisVmsPortCallType,
+    ) -> VesselEventDetailed {
+        let port_id = Some("NOTOS".to_string());
+        let (event_type, event_data) = match port_call_type {
+            AisVmsPortCallType::Departure => (
+                VesselEventType::AisVmsDeparture,
+                VesselEventData::AisVmsDeparture { port_id, timestamp },
+            ),
+            AisVmsPortCallType::Arrival => (
+                VesselEventType::AisVmsArrival,
+                VesselEventData::AisVmsArrival { port_id, timestamp },
+            ),
+        };
+        VesselEventDetailed {
+            event_id,
+            vessel_id: FiskeridirVesselId::new(1),
+            reported_timestamp: timestamp,
+            event_type,
+            event_data,
+        }
+    }
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_trips_from_alternating_port_calls() {
+        let t = start();
+        let state = assemble_impl(
+            vec![],
+            vec![
+                event(1, t, AisVmsPortCallType::Departure),
+                event(2, t + Duration::hours(10), AisVmsPortCallType::Arrival),
+                event(3, t + Duration::days(1), AisVmsPortCallType::Departure),
+                event(4, t + Duration::days(2), AisVmsPortCallType::Arrival),
+            ],
+        )
+        .unwrap()
+        .unwrap();
+
+        assert!(state.conflict_strategy.is_none());
+        assert_eq!(state.new_trips.len(), 2);
+
+        let first = &state.new_trips[0];
+        assert_eq!(first.period.start(), t);
+        assert_eq!(first.period.end(), t + Duration::hours(10));
+        assert_eq!(first.landing_coverage.start(), t);
+        assert_eq!(first.landing_coverage.end(), t + Duration::days(1));
+        assert_eq!(first.start_vessel_event_id, Some(1));
+        assert_eq!(first.end_vessel_event_id, Some(2));
+
+        let second = &state.new_trips[1];
+        assert_eq!(second.period.start(), t + Duration::days(1));
+        assert_eq!(second.period.end(), t + Duration::days(2));
+        assert_eq!(
+            second.landing_coverage.end(),
+            ers_last_trip_landing_coverage_end(&(t + Duration::days(2)))
+        );
+    }
+
+    #[test]
+    fn test_keeps_earliest_departure_and_skips_leading_arrival() {
+        let t = start();
+        let state = assemble_impl(
+            vec![],
+            vec![
+                event(1, t, AisVmsPortCallType::Arrival),
+                event(2, t + Duration::hours(1), AisVmsPortCallType::Departure),
+                event(3, t + Duration::hours(2), AisVmsPortCallType::Departure),
+                event(4, t + Duration::hours(3), AisVmsPortCallType::Arrival),
+            ],
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(state.new_trips.len(), 1);
+        assert_eq!(state.new_trips[0].period.start(), t + Duration::hours(1));
+        assert_eq!(state.new_trips[0].period.end(), t + Duration::hours(3));
+    }
+
+    #[test]
+    fn test_only_departures_produce_no_trips() {
+        let t = start();
+        let state = assemble_impl(
+            vec![event(1, t, AisVmsPortCallType::Arrival)],
+            vec![event(
+                2,
+                t + Duration::hours(1),
+                AisVmsPortCallType::Departure,
+            )],
+        )
+        .unwrap();
+
+        assert!(state.is_none());
+    }
+
+    #[test]
+    fn test_replaces_prior_trip_to_update_its_landing_coverage() {
+        let t = start();
+        let state = assemble_impl(
+            vec![
+                event(1, t, AisVmsPortCallType::Departure),
+                event(2, t + Duration::hours(10), AisVmsPortCallType::Arrival),
+            ],
+            vec![
+                event(3, t + Duration::days(1), AisVmsPortCallType::Departure),
+                event(4, t + Duration::days(2), AisVmsPortCallType::Arrival),
+            ],
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(
+            state.conflict_strategy,
+            Some(TripsConflictStrategy::Replace {
+                conflict: t + Duration::hours(10)
+            })
+        );
+        assert_eq!(state.new_trips.len(), 2);
+        assert_eq!(
+            state.new_trips[0].landing_coverage.end(),
+            t + Duration::days(1)
+        );
+    }
+}
@@ -0,0 +1,371 @@
+use std::{cmp::max, collections::HashMap};
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use geoutils::Location;
+use kyogre_core::{
+    AisVmsPortCallTimer, AisVmsPortCallType, AisVmsPosition, FiskeridirVesselId, NewAisVmsPortCall,
+    Port, PortDockPoint,
+};
+
+/// Positions within this distance (in meters) of a port or one of its dock points are
+/// considered to be in that port.
+pub static PORT_CALL_DISTANCE_THRESHOLD: f64 = 1500.0;
+/// Stays outside of port shorter than this are considered noise in the positions and do not
+/// produce any port calls.
+pub static PORT_CALL_MIN_TRIP_DURATION_MINUTES: i64 = 60;
+/// How many days of positions to fetch at a time when searching for port calls.
+pub static PORT_CALL_SEARCH_WINDOW_DAYS: i64 = 30;
+/// How many days back in time to search for port calls for vessels that have never been
+/// processed.
+pub static PORT_CALL_MAX_SEARCH_DAYS: i64 = 365 * 3;
+
+/// Where to start searching for port calls for vessels that have never been processed.
+/// Bounded by [`PORT_CALL_MAX_SEARCH_DAYS`] to avoid scanning the entire position history on
+/// first run, and never earlier than the start of the VMS data.
+pub fn port_call_search_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    max(
+        Utc.with_ymd_and_hms(2011, 1, 1, 0, 0, 0).unwrap(),
+        now - Duration::days(PORT_CALL_MAX_SEARCH_DAYS),
+    )
+}
+
+// Used to discard locations that are obviously too far away before computing the actual
+// distance, roughly 2 km in latitude and at least 2 km in longitude in Norwegian waters.
+static LATITUDE_PREFILTER_DEGREES: f64 = 0.02;
+static LONGITUDE_PREFILTER_DEGREES: f64 = 0.06;
+
+/// The locations of all ports and their dock points.
+pub struct PortLocations(Vec<(String, Location)>);
+
+impl PortLocations {
+    pub fn new(
+        ports: &HashMap<String, Port>,
+        dock_points: &HashMap<String, Vec<PortDockPoint>>,
+    ) -> Self {
+        let ports = ports.values().filter_map(|p| {
+            p.coordinates
+                .as_ref()
+                .map(|c| (p.id.clone(), Location::new(c.latitude, c.longitude)))
+        });
+        let dock_points = dock_points
+            .values()
+            .flatten()
+            .map(|d| (d.port_id.clone(), Location::new(d.latitude, d.longitude)));
+
+        Self(ports.chain(dock_points).collect())
+    }
+
+    /// Returns the closest port within [`PORT_CALL_DISTANCE_THRESHOLD`] of the given position.
+    fn port_of(&self, position: &AisVmsPosition) -> Option<&str> {
+        // Ports lie on the shore, positions further from shore than the threshold can therefore
+        // never be in port.
+        if position.distance_to_shore > PORT_CALL_DISTANCE_THRESHOLD {
+            return None;
+        }
+
+        let location = Location::new(position.latitude, position.longitude);
+
+        self.0
+            .iter()
+            .filter(|(_, l)| {
+                (l.latitude() - position.latitude).abs() <= LATITUDE_PREFILTER_DEGREES
+                    && (l.longitude() - position.longitude).abs() <= LONGITUDE_PREFILTER_DEGREES
+            })
+            .map(|(id, l)| (id, location.haversine_distance_to(l).meters()))
+            .filter(|(_, distance)| *distance <= PORT_CALL_DISTANCE_THRESHOLD)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(id, _)| id.as_str())
+    }
+}
+
+/// Detects departures and arrivals from a vessel's positions.
+///
+/// A vessel is in port when it is within [`PORT_CALL_DISTANCE_THRESHOLD`] of a port or one of its
+/// dock points.
+/// - A departure is placed at the last position in port before leaving.
+/// - An arrival is placed at the first position in port after having been at sea.
+///
+/// A departure is only emitted together with its arrival, and both are discarded if the vessel
+/// was at sea for less than [`PORT_CALL_MIN_TRIP_DURATION_MINUTES`].
+/// The returned timer never moves past an unmatched departure so that it is re-detected once the
+/// vessel arrives at a port.
+pub struct PortCallDetector<'a> {
+    locations: &'a PortLocations,
+    vessel_id: FiskeridirVesselId,
+    current_port: Option<String>,
+    last_in_port: DateTime<Utc>,
+    pending_departure: Option<(DateTime<Utc>, String)>,
+    timer: AisVmsPortCallTimer,
+    port_calls: Vec<NewAisVmsPortCall>,
+}
+
+impl<'a> PortCallDetector<'a> {
+    pub fn new(
+        locations: &'a PortLocations,
+        vessel_id: FiskeridirVesselId,
+        timer: AisVmsPortCallTimer,
+    ) -> Self {
+        Self {
+            locations,
+            vessel_id,
+            current_port: timer.port_id.clone(),
+            last_in_port: timer.timestamp,
+            pending_departure: None,
+            timer,
+            port_calls: vec![],
+        }
+    }
+
+    pub fn advance(&mut self, position: &AisVmsPosition) {
+        if position.timestamp <= self.timer.timestamp {
+            return;
+        }
+
+        match self.locations.port_of(position) {
+            Some(port) => {
+                if let Some((departure, departure_port)) = self.pending_departure.take()
+                    && position.timestamp - departure
+                        >= Duration::minutes(PORT_CALL_MIN_TRIP_DURATION_MINUTES)
+                {
+                    self.port_calls.push(NewAisVmsPortCall {
+                        fiskeridir_vessel_id: self.vessel_id,
+                        timestamp: departure,
+                        port_id: Some(departure_port),
+                        port_call_type: AisVmsPortCallType::Departure,
+                    });
+                    self.port_calls.push(NewAisVmsPortCall {
+                        fiskeridir_vessel_id: self.vessel_id,
+                        timestamp: position.timestamp,
+                        port_id: Some(port.to_string()),
+                        port_call_type: AisVmsPortCallType::Arrival,
+                    });
+                }
+
+                self.current_port = Some(port.to_string());
+                self.last_in_port = position.timestamp;
+                self.timer = AisVmsPortCallTimer {
+                    timestamp: position.timestamp,
+                    port_id: Some(port.to_string()),
+                };
+            }
+            None => {
+                if let Some(port) = self.current_port.take() {
+                    self.pending_departure = Some((self.last_in_port, port));
+                }
+                if self.pending_departure.is_none() {
+                    self.timer = AisVmsPortCallTimer {
+                        timestamp: position.timestamp,
+                        port_id: None,
+                    };
+                }
+            }
+        }
+    }
+
+    pub fn finalize(self) -> (Vec<NewAisVmsPortCall>, AisVmsPortCallTimer) {
+        (self.port_calls, self.timer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kyogre_core::{Coordinates, PositionType};
+
+    static PORT_ID: &str = "NOTOS";
+    static PORT_LATITUDE: f64 = 69.65;
+    static PORT_LONGITUDE: f64 = 18.96;
+
+    fn locations() -> PortLocations {
+        let port = Port {
+            id: PORT_ID.into(),
+            coordinates: Some(Coordinates {
+                latitude: PORT_LATITUDE,
+                longitude: PORT_LONGITUDE,
+            }),
+        };
+        PortLocations::new(&HashMap::from([(PORT_ID.into(), port)]), &HashMap::new())
+    }
+
+    fn position(timestamp: DateTime<Utc>, in_port: bool) -> AisVmsPosition {
+        let (latitude, distance_to_shore) = if in_port {
+            (PORT_LATITUDE, 0.)
+        } else {
+            (PORT_LATITUDE + 0.5, 10_000.)
+        };
+        AisVmsPosition {
+            latitude,
+            longitude: PORT_LONGITUDE,
+            timestamp,
+            course_over_ground: None,
+            speed: None,
+            navigational_status: None,
+            rate_of_turn: None,
+            true_heading: None,
+            distance_to_shore,
+            position_type: PositionType::Ais,
+            pruned_by: None,
+            trip_cumulative_cargo_weight: 0.,
+            trip_cumulative_fuel_consumption_liter: 0.,
+            active_gear: None,
+        }
+    }
+
+    fn detect(
+        timer: AisVmsPortCallTimer,
+        positions: &[AisVmsPosition],
+    ) -> (Vec<NewAisVmsPortCall>, AisVmsPortCallTimer) {
+        let locations = locations();
+        let mut detector = PortCallDetector::new(&locations, FiskeridirVesselId::new(1), timer);
+        for p in positions {
+            detector.advance(p);
+        }
+        detector.finalize()
+    }
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    fn timer() -> AisVmsPortCallTimer {
+        AisVmsPortCallTimer {
+            timestamp: start() - Duration::hours(1),
+            port_id: None,
+        }
+    }
+
+    #[test]
+    fn test_detects_departure_at_last_and_arrival_at_first_position_in_port() {
+        let t = start();
+        let (port_calls, timer) = detect(
+            timer(),
+            &[
+                position(t, true),
+                position(t + Duration::minutes(10), true),
+                position(t + Duration::minutes(30), false),
+                position(t + Duration::hours(2), false),
+                position(t + Duration::hours(3), true),
+                position(t + Duration::hours(4), true),
+            ],
+        );
+
+        assert_eq!(
+            port_calls,
+            vec![
+                NewAisVmsPortCall {
+                    fiskeridir_vessel_id: FiskeridirVesselId::new(1),
+                    timestamp: t + Duration::minutes(10),
+                    port_id: Some(PORT_ID.into()),
+                    port_call_type: AisVmsPortCallType::Departure,
+                },
+                NewAisVmsPortCall {
+                    fiskeridir_vessel_id: FiskeridirVesselId::new(1),
+                    timestamp: t + Duration::hours(3),
+                    port_id: Some(PORT_ID.into()),
+                    port_call_type: AisVmsPortCallType::Arrival,
+                },
+            ]
+        );
+        assert_eq!(
+            timer,
+            AisVmsPortCallTimer {
+                timestamp: t + Duration::hours(4),
+                port_id: Some(PORT_ID.into()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_discards_stays_at_sea_shorter_than_min_trip_duration() {
+        let t = start();
+        let (port_calls, _) = detect(
+            timer(),
+            &[
+                position(t, true),
+                position(t + Duration::minutes(10), false),
+                position(
+                    t + Duration::minutes(PORT_CALL_MIN_TRIP_DURATION_MINUTES - 1),
+                    true,
+                ),
+            ],
+        );
+
+        assert!(port_calls.is_empty());
+    }
+
+    #[test]
+    fn test_timer_does_not_move_past_unmatched_departure() {
+        let t = start();
+        let (port_calls, timer) = detect(
+            timer(),
+            &[
+                position(t, true),
+                position(t + Duration::hours(1), false),
+                position(t + Duration::hours(5), false),
+            ],
+        );
+
+        assert!(port_calls.is_empty());
+        assert_eq!(
+            timer,
+            AisVmsPortCallTimer {
+                timestamp: t,
+                port_id: Some(PORT_ID.into()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_resumes_from_timer_in_port() {
+        let t = start();
+        let (port_calls, _) = detect(
+            AisVmsPortCallTimer {
+                timestamp: t,
+                port_id: Some(PORT_ID.into()),
+            },
+            &[
+                position(t, true),
+                position(t + Duration::hours(1), false),
+                position(t + Duration::hours(3), true),
+            ],
+        );
+
+        assert_eq!(port_calls.len(), 2);
+        assert_eq!(port_calls[0].timestamp, t);
+        assert_eq!(port_calls[0].port_call_type, AisVmsPortCallType::Departure);
+        assert_eq!(port_calls[1].timestamp, t + Duration::hours(3));
+    }
+
+    #[test]
+    fn test_ignores_positions_far_from_shore() {
+        let t = start();
+        let mut far_from_shore = position(t + Duration::hours(3), true);
+        far_from_shore.distance_to_shore = PORT_CALL_DISTANCE_THRESHOLD + 1.;
+
+        let (port_calls, _) = detect(
+            timer(),
+            &[
+                position(t, true),
+                position(t + Duration::hours(1), false),
+                far_from_shore,
+            ],
+        );
+
+        assert!(port_calls.is_empty());
+    }
+
+    #[test]
+    fn test_port_call_search_start_is_bounded() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            port_call_search_start(now),
+            now - Duration::days(PORT_CALL_MAX_SEARCH_DAYS)
+        );
+
+        let now = Utc.with_ymd_and_hms(2012, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            port_call_search_start(now),
+            Utc.with_ymd_and_hms(2011, 1, 1, 0, 0, 0).unwrap()
+        );
+    }
+}
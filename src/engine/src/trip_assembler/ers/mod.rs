@@ -108,6 +108,8 @@ impl ErsEvent {
             VesselEventData::ErsDca => None,
             VesselEventData::ErsTra => None,
             VesselEventData::Haul => None,
+            VesselEventData::AisVmsDeparture { .. } => None,
+            VesselEventData::AisVmsArrival { .. } => None,
         }
         .map(|(event_type, port_id, estimated_timestamp)| ErsEvent {
             event_id: v.event_id,
@@ -106,6 +106,8 @@ impl LandingEvent {
             VesselEventType::ErsDep => None,
             VesselEventType::ErsTra => None,
             VesselEventType::Haul => None,
+            VesselEventType::AisVmsDeparture => None,
+            VesselEventType::AisVmsArrival => None,
         }
     }
 
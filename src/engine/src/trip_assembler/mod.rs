@@ -1,9 +1,11 @@
 use chrono::{DateTime, Duration, Utc};
 
+mod ais_vms;
 mod ers;
 mod landings;
 mod precision;
 
+pub use ais_vms::*;
 pub use ers::*;
 pub use landings::*;
 pub use precision::*;
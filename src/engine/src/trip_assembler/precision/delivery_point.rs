@@ -23,6 +23,10 @@ pub struct DeliveryPointPrecision {
 
 #[async_trait]
 impl TripPrecision for DeliveryPointPrecision {
+    fn id(&self) -> PrecisionId {
+        PrecisionId::DeliveryPoint
+    }
+
     async fn precision(
         &self,
         adapter: &dyn TripPrecisionOutboundPort,
@@ -17,6 +17,10 @@ pub struct DistanceToShorePrecision {
 
 #[async_trait]
 impl TripPrecision for DistanceToShorePrecision {
+    fn id(&self) -> PrecisionId {
+        PrecisionId::DistanceToShore
+    }
+
     async fn precision(
         &self,
         adapter: &dyn TripPrecisionOutboundPort,
@@ -19,6 +19,10 @@ pub struct DockPointPrecision {
 
 #[async_trait]
 impl TripPrecision for DockPointPrecision {
+    fn id(&self) -> PrecisionId {
+        PrecisionId::DockPoint
+    }
+
     async fn precision(
         &self,
         adapter: &dyn TripPrecisionOutboundPort,
@@ -20,6 +20,10 @@ pub struct FirstMovedPoint {
 
 #[async_trait]
 impl TripPrecision for FirstMovedPoint {
+    fn id(&self) -> PrecisionId {
+        PrecisionId::FirstMovedPoint
+    }
+
     async fn precision(
         &self,
         _adapter: &dyn TripPrecisionOutboundPort,
@@ -3,8 +3,9 @@ use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
 use geoutils::Location;
 use kyogre_core::{
-    AisVmsPosition, DateRange, PrecisionDirection, PrecisionId, PrecisionOutcome, PrecisionUpdate,
-    TripPrecisionOutboundPort, TripProcessingUnit, Vessel,
+    AisVmsPosition, DateRange, PrecisionAttempt, PrecisionBoundary, PrecisionDirection,
+    PrecisionId, PrecisionOutcome, PrecisionUpdate, TripPrecisionOutboundPort, TripProcessingUnit,
+    Vessel,
 };
 use num_traits::ToPrimitive;
 
@@ -40,6 +41,7 @@ pub struct PrecisionConfig {
 
 #[async_trait]
 pub trait TripPrecision: Send + Sync {
+    fn id(&self) -> PrecisionId;
     async fn precision(
         &self,
         adapter: &dyn TripPrecisionOutboundPort,
@@ -116,25 +118,29 @@ impl TripPrecisionCalculator {
         trip: &TripProcessingUnit,
     ) -> Result<PrecisionOutcome> {
         if trip.positions.is_empty() {
-            return Ok(PrecisionOutcome::Failed);
+            return Ok(PrecisionOutcome::Failed { attempts: vec![] });
         }
 
-        let mut end_precision = None;
-        let mut start_precision = None;
-
-        for f in &self.start_precisions {
-            if let Some(s) = f.precision(adapter, trip, vessel).await? {
-                start_precision = Some(s);
-                break;
-            }
-        }
-
-        for f in &self.end_precisions {
-            if let Some(s) = f.precision(adapter, trip, vessel).await? {
-                end_precision = Some(s);
-                break;
-            }
-        }
+        let mut attempts = Vec::new();
+
+        let start_precision = run_precisions(
+            &self.start_precisions,
+            PrecisionBoundary::Start,
+            &mut attempts,
+            vessel,
+            adapter,
+            trip,
+        )
+        .await?;
+        let end_precision = run_precisions(
+            &self.end_precisions,
+            PrecisionBoundary::End,
+            &mut attempts,
+            vessel,
+            adapter,
+            trip,
+        )
+        .await?;
 
         let trip_start = trip.trip.period_extended.start();
         let trip_end = trip.trip.period_extended.end();
@@ -153,13 +159,42 @@ impl TripPrecisionCalculator {
                 new_period: DateRange::new(start, end)?,
                 start_precision: start_precision.map(PrecisionUpdate::from),
                 end_precision: end_precision.map(PrecisionUpdate::from),
+                attempts,
             })
         } else {
-            Ok(PrecisionOutcome::Failed)
+            Ok(PrecisionOutcome::Failed { attempts })
         }
     }
 }
 
+/// Runs the given precision implementations in order until one of them succeeds, recording the
+/// outcome of each implementation that was run.
+async fn run_precisions(
+    precisions: &[Box<dyn TripPrecision>],
+    boundary: PrecisionBoundary,
+    attempts: &mut Vec<PrecisionAttempt>,
+    vessel: &Vessel,
+    adapter: &dyn TripPrecisionOutboundPort,
+    trip: &TripProcessingUnit,
+) -> Result<Option<PrecisionStop>> {
+    for f in precisions {
+        let stop = f.precision(adapter, trip, vessel).await?;
+
+        attempts.push(PrecisionAttempt {
+            id: f.id(),
+            boundary,
+            timestamp: stop.as_ref().map(|s| s.timestamp),
+            direction: stop.as_ref().map(|s| s.direction),
+        });
+
+        if stop.is_some() {
+            return Ok(stop);
+        }
+    }
+
+    Ok(None)
+}
+
 fn find_close_point<'a, T>(
     target: &Location,
     iter: T,
@@ -25,6 +25,10 @@ pub struct PortPrecision {
 
 #[async_trait]
 impl TripPrecision for PortPrecision {
+    fn id(&self) -> PrecisionId {
+        PrecisionId::Port
+    }
+
     async fn precision(
         &self,
         adapter: &dyn TripPrecisionOutboundPort,
@@ -0,0 +1,134 @@
+use crate::helper::test;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use engine::*;
+use kyogre_core::*;
+
+fn start() -> DateTime<Utc> {
+    // Port calls are only searched for within a bounded period back in time
+    Utc.timestamp_opt((Utc::now() - Duration::days(10)).timestamp(), 0)
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_ais_vms_assembler_creates_trips_from_port_calls() {
+    test(|helper, builder| async move {
+        let dock_point = helper
+            .adapter()
+            .dock_points_of_port("NOTOS")
+            .await
+            .remove(0);
+        let start = start();
+
+        // (offset from start, in port)
+        let positions = [
+            (Duration::zero(), true),
+            (Duration::minutes(10), true),
+            (Duration::hours(1), false),
+            (Duration::hours(3), false),
+            (Duration::hours(5), true),
+            (Duration::hours(6), true),
+        ];
+
+        let state = builder
+            .vessels(1)
+            .ais_positions(positions.len())
+            .modify_idx(|i, p| {
+                let (offset, in_port) = positions[i];
+                p.position.msgtime = start + offset;
+                p.position.latitude = dock_point.latitude + if in_port { 0. } else { 0.5 };
+                p.position.longitude = dock_point.longitude;
+                p.position.distance_to_shore = if in_port { 0. } else { 10_000. };
+            })
+            .build()
+            .await;
+
+        assert_eq!(state.trips.len(), 1);
+
+        let trip = &state.trips[0];
+        assert_eq!(trip.assembler_id, TripAssemblerId::AisVms);
+        assert_eq!(trip.period.start(), start + Duration::minutes(10));
+        assert_eq!(trip.period.end(), start + Duration::hours(5));
+        assert_eq!(trip.start_port_id.as_deref(), Some("NOTOS"));
+        assert_eq!(trip.end_port_id.as_deref(), Some("NOTOS"));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_ais_vms_assembler_does_not_create_trips_for_short_stays_outside_port() {
+    test(|helper, builder| async move {
+        let dock_point = helper
+            .adapter()
+            .dock_points_of_port("NOTOS")
+            .await
+            .remove(0);
+        let start = start();
+
+        let positions = [
+            (Duration::zero(), true),
+            (Duration::minutes(10), false),
+            (Duration::minutes(20), true),
+        ];
+
+        let state = builder
+            .vessels(1)
+            .ais_positions(positions.len())
+            .modify_idx(|i, p| {
+                let (offset, in_port) = positions[i];
+                p.position.msgtime = start + offset;
+                p.position.latitude = dock_point.latitude + if in_port { 0. } else { 0.5 };
+                p.position.longitude = dock_point.longitude;
+                p.position.distance_to_shore = if in_port { 0. } else { 10_000. };
+            })
+            .build()
+            .await;
+
+        assert!(state.trips.is_empty());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_vessels_with_sparse_landings_use_the_landings_assembler() {
+    test(|helper, builder| async move {
+        let dock_point = helper
+            .adapter()
+            .dock_points_of_port("NOTOS")
+            .await
+            .remove(0);
+        let start = start();
+
+        // (offset from start, in port)
+        let positions = [
+            (Duration::zero(), true),
+            (Duration::minutes(10), true),
+            (Duration::hours(1), false),
+            (Duration::hours(3), false),
+            (Duration::hours(5), true),
+            (Duration::hours(6), true),
+        ];
+
+        let state = builder
+            .vessels(1)
+            .ais_positions(positions.len())
+            .modify_idx(|i, p| {
+                let (offset, in_port) = positions[i];
+                p.position.msgtime = start + offset;
+                p.position.latitude = dock_point.latitude + if in_port { 0. } else { 0.5 };
+                p.position.longitude = dock_point.longitude;
+                p.position.distance_to_shore = if in_port { 0. } else { 10_000. };
+            })
+            .landings(1)
+            .modify(|l| l.landing.landing_timestamp = start + Duration::days(2))
+            .build()
+            .await;
+
+        assert_eq!(state.trips.len(), 1);
+        assert_eq!(state.trips[0].assembler_id, TripAssemblerId::Landings);
+        assert_eq!(
+            state.trips[0].period.end(),
+            state.landings[0].landing_timestamp
+        );
+    })
+    .await;
+}
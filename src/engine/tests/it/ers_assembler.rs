@@ -346,6 +346,69 @@ async fn test_handles_conflict_correctly() {
     .await;
 }
 
+#[tokio::test]
+async fn test_incremental_assembly_only_replaces_trips_affected_by_conflict() {
+    test_with_trip_assembly(true, |helper, builder| async move {
+        let departures = [10, 30, 50].map(|v| Utc.timestamp_opt(v, 0).unwrap());
+        let arrivals = [20, 40, 60].map(|v| Utc.timestamp_opt(v, 0).unwrap());
+        let departure4 = Utc.timestamp_opt(22, 0).unwrap();
+        let arrival4 = Utc.timestamp_opt(27, 0).unwrap();
+
+        let state = builder
+            .vessels(1)
+            .dep(3)
+            .modify_idx(|i, v| {
+                v.dep.set_departure_timestamp(departures[i]);
+                v.dep.message_info.set_message_timestamp(departures[i]);
+            })
+            .por(3)
+            .modify_idx(|i, v| {
+                v.por.set_arrival_timestamp(arrivals[i]);
+                v.por.message_info.set_message_timestamp(arrivals[i]);
+            })
+            .new_cycle()
+            .dep(1)
+            .modify(|v| {
+                v.dep.set_departure_timestamp(departure4);
+                v.dep.message_info.set_message_timestamp(departure4);
+            })
+            .por(1)
+            .modify(|v| {
+                v.por.set_arrival_timestamp(arrival4);
+                v.por.message_info.set_message_timestamp(arrival4);
+            })
+            .build()
+            .await;
+
+        assert_eq!(state.trips.len(), 4);
+
+        // The trip prior to the conflict has its landing coverage changed and is replaced
+        // together with the new trip, while the trips after the conflict are kept.
+        let trip_ids = state
+            .trips
+            .iter()
+            .map(|t| t.trip_id.into_inner())
+            .collect::<Vec<_>>();
+        assert_eq!(trip_ids, vec![4, 5, 2, 3]);
+
+        assert_eq!(state.trips[0].period.start(), departures[0]);
+        assert_eq!(state.trips[0].landing_coverage.end(), arrival4);
+        assert_eq!(state.trips[1].period.start(), departure4);
+        assert_eq!(state.trips[1].period.end(), arrival4);
+        assert_eq!(state.trips[1].landing_coverage.end(), arrivals[1]);
+
+        let timer = helper
+            .adapter()
+            .trip_calculation_timer(state.vessels[0].fiskeridir.id, TripAssemblerId::Ers)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(timer.conflict.is_none());
+        assert_eq!(timer.timestamp, arrivals[2]);
+    })
+    .await;
+}
+
 #[tokio::test]
 async fn test_is_not_affected_of_other_vessels_trips() {
     test(|_helper, builder| async move {
@@ -9,11 +9,17 @@ use std::panic;
 pub struct TestHelper {
     pub db: TestDb,
     db_settings: PsqlSettings,
+    incremental_trip_assembly: bool,
 }
 
 impl TestHelper {
     pub async fn builder(&self) -> TestStateBuilder {
-        let engine = engine(self.adapter().clone(), &self.db_settings).await;
+        let engine = engine(
+            self.adapter().clone(),
+            &self.db_settings,
+            self.incremental_trip_assembly,
+        )
+        .await;
 
         TestStateBuilder::new(
             Box::new(self.adapter().clone()),
@@ -28,7 +34,23 @@ impl TestHelper {
     }
 }
 
+/// Runs the test with both full and incremental trip assembly, which should produce the same
+/// trips.
 pub async fn test<T, Fut>(test: T)
+where
+    T: FnOnce(TestHelper, TestStateBuilder) -> Fut
+        + Clone
+        + panic::UnwindSafe
+        + Send
+        + Sync
+        + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    test_with_trip_assembly(false, test.clone()).await;
+    test_with_trip_assembly(true, test).await;
+}
+
+pub async fn test_with_trip_assembly<T, Fut>(incremental_trip_assembly: bool, test: T)
 where
     T: FnOnce(TestHelper, TestStateBuilder) -> Fut + panic::UnwindSafe + Send + Sync + 'static,
     Fut: Future<Output = ()> + Send + 'static,
@@ -62,7 +84,11 @@ where
                 db: adapter.clone(),
             };
 
-            let helper = TestHelper { db, db_settings };
+            let helper = TestHelper {
+                db,
+                db_settings,
+                incremental_trip_assembly,
+            };
             let builder = helper.builder().await;
 
             test(helper, builder).await;
@@ -4,6 +4,7 @@
 use config::{Config, File};
 use engine::Settings;
 
+pub mod ais_vms_assembler;
 pub mod ers_assembler;
 pub mod haul_distributor;
 pub mod helper;
@@ -50,3 +50,62 @@ async fn test_preferred_assembler_set_to_landings_after_one_year_of_no_ers() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn test_replace_range_without_new_trips_deletes_removed_trip() {
+    test(|helper, builder| async move {
+        let state = builder.vessels(1).dep(3).por(3).build().await;
+        assert_eq!(state.trips.len(), 3);
+
+        let adapter = helper.adapter();
+        let vessel_id = state.vessels[0].fiskeridir.id;
+        let removed = &state.trips[1];
+
+        let timer = adapter
+            .trip_calculation_timer(vessel_id, TripAssemblerId::Ers)
+            .await
+            .unwrap()
+            .unwrap();
+        let id = adapter.new_trip_assembler_processing_id().await.unwrap();
+
+        // Incremental assembly of [A, B, C] into [A, C] keeps A and C and yields no new trips
+        adapter
+            .add_trip_set(
+                TripSet {
+                    fiskeridir_vessel_id: vessel_id,
+                    conflict_strategy: TripsConflictStrategy::ReplaceRange {
+                        start: removed.period.start(),
+                        end: removed.period.start(),
+                    },
+                    trip_assembler_id: TripAssemblerId::Ers,
+                    values: vec![],
+                    conflict: None,
+                    queued_reset: false,
+                    new_trip_events: vec![],
+                    prior_trip_events: vec![],
+                    prior_trip_calculation_time: Some(timer.timestamp),
+                    processed_event_ids: vec![],
+                },
+                id,
+            )
+            .await
+            .unwrap();
+
+        let trips = adapter
+            .assembled_trips(vessel_id, TripAssemblerId::Ers, None)
+            .await
+            .unwrap();
+
+        assert_eq!(trips.len(), 2);
+        assert_eq!(trips[0].period, state.trips[0].period);
+        assert_eq!(trips[1].period, state.trips[2].period);
+
+        let new_timer = adapter
+            .trip_calculation_timer(vessel_id, TripAssemblerId::Ers)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(new_timer.timestamp, timer.timestamp);
+    })
+    .await;
+}
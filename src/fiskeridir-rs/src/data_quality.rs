@@ -0,0 +1,135 @@
+use std::{cell::Cell, ops::AddAssign};
+
+/// Number of fields the lenient deserializers could not take as is.
+///
+/// - `coerced`: the value was accepted after being converted, e.g. a decimal comma or a
+///   timestamp truncated to a date.
+/// - `defaulted`: a placeholder value such as `-` or `*` was replaced by `None`.
+/// - `rejected`: the value could not be parsed, failing the row.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FieldQuality {
+    pub coerced: u64,
+    pub defaulted: u64,
+    pub rejected: u64,
+}
+
+thread_local! {
+    static FIELD_QUALITY: Cell<FieldQuality> = const {
+        Cell::new(FieldQuality {
+            coerced: 0,
+            defaulted: 0,
+            rejected: 0,
+        })
+    };
+}
+
+impl AddAssign for FieldQuality {
+    fn add_assign(&mut self, rhs: Self) {
+        self.coerced += rhs.coerced;
+        self.defaulted += rhs.defaulted;
+        self.rejected += rhs.rejected;
+    }
+}
+
+/// Runs `f` and returns the field quality registered by the deserializers while doing so.
+/// Deserialization is synchronous, so a thread local is enough to attribute the counts to `f`.
+pub(crate) fn track<T>(f: impl FnOnce() -> T) -> (T, FieldQuality) {
+    FIELD_QUALITY.set(FieldQuality::default());
+    let value = f();
+    (value, FIELD_QUALITY.take())
+}
+
+fn update(f: impl FnOnce(&mut FieldQuality)) {
+    let mut quality = FIELD_QUALITY.get();
+    f(&mut quality);
+    FIELD_QUALITY.set(quality);
+}
+
+pub(crate) fn coerced<T>(v: T) -> T {
+    update(|q| q.coerced = q.coerced.saturating_add(1));
+    v
+}
+
+pub(crate) fn defaulted<T>(v: T) -> T {
+    update(|q| q.defaulted = q.defaulted.saturating_add(1));
+    v
+}
+
+pub(crate) fn rejected<T>(v: T) -> T {
+    update(|q| q.rejected = q.rejected.saturating_add(1));
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        NonEmptyString,
+        deserialize_utils::{opt_naive_time_from_str, opt_str_with_hyphen, opt_u32_with_hyphen},
+    };
+    use chrono::NaiveTime;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Row {
+        #[serde(deserialize_with = "opt_str_with_hyphen")]
+        name: Option<NonEmptyString>,
+        #[serde(deserialize_with = "opt_naive_time_from_str")]
+        time: Option<NaiveTime>,
+        #[serde(deserialize_with = "opt_u32_with_hyphen")]
+        count: Option<u32>,
+    }
+
+    fn deserialize(row: &str) -> (csv::Result<Row>, FieldQuality) {
+        let data = format!("name;time;count\n{row}\n");
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(b';')
+            .from_reader(data.as_bytes());
+        let headers = reader.byte_headers().unwrap().clone();
+        let record = reader.byte_records().next().unwrap().unwrap();
+
+        track(|| record.deserialize(Some(&headers)))
+    }
+
+    #[test]
+    fn test_track_counts_coerced_and_defaulted_fields() {
+        let (row, quality) = deserialize("-;12:30;-");
+        let row = row.unwrap();
+
+        assert!(row.name.is_none());
+        assert_eq!(row.time, NaiveTime::from_hms_opt(12, 30, 0));
+        assert!(row.count.is_none());
+        assert_eq!(
+            quality,
+            FieldQuality {
+                coerced: 1,
+                defaulted: 2,
+                rejected: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_track_counts_rejected_fields() {
+        let (row, quality) = deserialize("name;12:30:00;many");
+
+        assert!(row.is_err());
+        assert_eq!(
+            quality,
+            FieldQuality {
+                coerced: 0,
+                defaulted: 0,
+                rejected: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_track_only_counts_fields_deserialized_by_the_closure() {
+        let _ = deserialize("-;-;-");
+
+        let (_, quality) = deserialize("name;12:30:00;1");
+
+        assert_eq!(quality, FieldQuality::default());
+    }
+}
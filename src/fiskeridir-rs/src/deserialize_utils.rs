@@ -14,7 +14,10 @@ use serde::{
 };
 use serde_with::{DeserializeAs, SerializeAs};
 
-use crate::string_new_types::NonEmptyString;
+use crate::{
+    data_quality::{coerced, defaulted, rejected},
+    string_new_types::NonEmptyString,
+};
 
 pub fn opt_str_with_hyphen<'de, D>(deserializer: D) -> Result<Option<NonEmptyString>, D::Error>
 where
@@ -22,7 +25,8 @@ where
 {
     let v = String::deserialize(deserializer)?;
     match v.as_str() {
-        "" | "-" => Ok(None),
+        "" => Ok(None),
+        "-" => Ok(defaulted(None)),
         _ => Ok(Some(NonEmptyString::new_unchecked(v))),
     }
 }
@@ -33,8 +37,9 @@ where
 {
     let v = String::deserialize(deserializer)?;
     match v.as_str() {
-        "" | "-" => Ok(None),
-        _ => v.parse().map(Some).map_err(Error::custom),
+        "" => Ok(None),
+        "-" => Ok(defaulted(None)),
+        _ => v.parse().map(Some).map_err(Error::custom).map_err(rejected),
     }
 }
 
@@ -63,7 +68,7 @@ where
         {
             T::from_i64(v)
                 .map(Some)
-                .ok_or_else(|| Error::invalid_value(Unexpected::Signed(v), &self))
+                .ok_or_else(|| rejected(Error::invalid_value(Unexpected::Signed(v), &self)))
         }
 
         fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
@@ -72,19 +77,21 @@ where
         {
             T::from_u64(v)
                 .map(Some)
-                .ok_or_else(|| Error::invalid_value(Unexpected::Unsigned(v), &self))
+                .ok_or_else(|| rejected(Error::invalid_value(Unexpected::Unsigned(v), &self)))
         }
 
         fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
         where
             E: Error,
         {
-            if v.is_empty() || v.starts_with("*") {
+            if v.is_empty() {
                 Ok(None)
+            } else if v.starts_with("*") {
+                Ok(defaulted(None))
             } else {
                 v.parse()
                     .map(Some)
-                    .map_err(|_| Error::invalid_value(Unexpected::Str(v), &self))
+                    .map_err(|_| rejected(Error::invalid_value(Unexpected::Str(v), &self)))
             }
         }
 
@@ -111,7 +118,9 @@ where
     D: Deserializer<'de>,
 {
     let s = String::deserialize(deserializer)?;
-    parse_date_time_utc_from_str(&s, ':', false).map_err(Error::custom)
+    parse_date_time_utc_from_str(&s, ':', false)
+        .map_err(Error::custom)
+        .map_err(rejected)
 }
 
 pub fn date_time_utc_from_non_iso_local_date_time_str<'de, D>(
@@ -125,10 +134,10 @@ where
 
     match parse_date_time_utc_from_local_date_time_str(&formatted, '.', true) {
         Ok(Some(v)) => Ok(v),
-        Ok(None) => Err(Error::custom(
+        Ok(None) => Err(rejected(Error::custom(
             "could not construct oslo time from timestamp",
-        )),
-        Err(e) => Err(Error::custom(e)),
+        ))),
+        Err(e) => Err(rejected(Error::custom(e))),
     }
 }
 
@@ -140,7 +149,9 @@ where
 {
     let s = String::deserialize(deserializer)?;
     let formatted = s.replacen([',', ':'], ".", 4);
-    parse_date_time_utc_from_str(&formatted, '.', true).map_err(Error::custom)
+    parse_date_time_utc_from_str(&formatted, '.', true)
+        .map_err(Error::custom)
+        .map_err(rejected)
 }
 
 /// Deserialize a NaiveDate that could be a NaiveDateTime
@@ -149,7 +160,9 @@ where
     D: Deserializer<'de>,
 {
     let s = String::deserialize(deserializer)?;
-    parse_date_from_str(&s).map_err(Error::custom)
+    parse_date_from_str(&s)
+        .map_err(Error::custom)
+        .map_err(rejected)
 }
 
 /// Deserialize an Optional NaiveDate that could be a NaiveDateTime
@@ -161,7 +174,10 @@ where
     if v.is_empty() {
         Ok(None)
     } else {
-        parse_date_from_str(&v).map(Some).map_err(Error::custom)
+        parse_date_from_str(&v)
+            .map(Some)
+            .map_err(Error::custom)
+            .map_err(rejected)
     }
 }
 
@@ -170,7 +186,9 @@ where
     D: Deserializer<'de>,
 {
     let s = String::deserialize(deserializer)?;
-    parse_time_from_str(&s).map_err(Error::custom)
+    parse_time_from_str(&s)
+        .map_err(Error::custom)
+        .map_err(rejected)
 }
 
 pub fn opt_naive_time_from_str<'de, D>(deserializer: D) -> Result<Option<NaiveTime>, D::Error>
@@ -184,8 +202,9 @@ where
         match parse_time_from_str(&v) {
             Ok(v) => Ok(Some(v)),
             Err(_) => parse_hour_minue_time_from_str(&v)
-                .map(Some)
-                .map_err(Error::custom),
+                .map(|v| Some(coerced(v)))
+                .map_err(Error::custom)
+                .map_err(rejected),
         }
     }
 }
@@ -195,7 +214,9 @@ where
     D: Deserializer<'de>,
 {
     let s = String::deserialize(deserializer)?;
-    parse_hour_minue_time_from_str(&s).map_err(Error::custom)
+    parse_hour_minue_time_from_str(&s)
+        .map_err(Error::custom)
+        .map_err(rejected)
 }
 
 fn parse_naive_date_time_from_str(
@@ -257,7 +278,7 @@ fn parse_date_time_utc_from_local_date_time_str(
         chrono::LocalResult::Single(d) => Some(d),
         // As we have no way of knowing if the timestamp is before or after winter/summer
         // time shift we simply have to pick one.
-        chrono::LocalResult::Ambiguous(_, max) => Some(max),
+        chrono::LocalResult::Ambiguous(_, max) => Some(coerced(max)),
     };
 
     Ok(oslo_timestamp.map(|tz| tz.with_timezone(&Utc)))
@@ -269,7 +290,7 @@ fn parse_date_from_str(s: &str) -> Result<NaiveDate, chrono::ParseError> {
         Ok(d) => Ok(d),
         Err(_) => {
             let date_time = chrono::NaiveDateTime::parse_from_str(&s, "%d.%m.%Y %H:%M:%S")?;
-            Ok(date_time.date())
+            Ok(coerced(date_time.date()))
         }
     }
 }
@@ -308,14 +329,16 @@ where
             where
                 E: Error,
             {
-                S::from_i64(v).ok_or_else(|| Error::invalid_value(Unexpected::Signed(v), &self))
+                S::from_i64(v)
+                    .ok_or_else(|| rejected(Error::invalid_value(Unexpected::Signed(v), &self)))
             }
 
             fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
             where
                 E: Error,
             {
-                S::from_u64(v).ok_or_else(|| Error::invalid_value(Unexpected::Unsigned(v), &self))
+                S::from_u64(v)
+                    .ok_or_else(|| rejected(Error::invalid_value(Unexpected::Unsigned(v), &self)))
             }
 
             fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
@@ -324,7 +347,7 @@ where
             {
                 let v = v
                     .parse()
-                    .map_err(|_| Error::invalid_value(Unexpected::Str(v), &self))?;
+                    .map_err(|_| rejected(Error::invalid_value(Unexpected::Str(v), &self)))?;
                 self.visit_i64(v)
             }
         }
@@ -373,7 +396,7 @@ where
             {
                 S::from_i64(v)
                     .map(Some)
-                    .ok_or_else(|| Error::invalid_value(Unexpected::Signed(v), &self))
+                    .ok_or_else(|| rejected(Error::invalid_value(Unexpected::Signed(v), &self)))
             }
 
             fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
@@ -382,7 +405,7 @@ where
             {
                 S::from_u64(v)
                     .map(Some)
-                    .ok_or_else(|| Error::invalid_value(Unexpected::Unsigned(v), &self))
+                    .ok_or_else(|| rejected(Error::invalid_value(Unexpected::Unsigned(v), &self)))
             }
 
             fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
@@ -394,7 +417,7 @@ where
                 } else {
                     let v = v
                         .parse()
-                        .map_err(|_| Error::invalid_value(Unexpected::Str(v), &self))?;
+                        .map_err(|_| rejected(Error::invalid_value(Unexpected::Str(v), &self)))?;
                     self.visit_i64(v)
                 }
             }
@@ -439,21 +462,24 @@ where
             where
                 E: Error,
             {
-                S::from_i64(v).ok_or_else(|| Error::invalid_value(Unexpected::Signed(v), &self))
+                S::from_i64(v)
+                    .ok_or_else(|| rejected(Error::invalid_value(Unexpected::Signed(v), &self)))
             }
 
             fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
             where
                 E: Error,
             {
-                S::from_u64(v).ok_or_else(|| Error::invalid_value(Unexpected::Unsigned(v), &self))
+                S::from_u64(v)
+                    .ok_or_else(|| rejected(Error::invalid_value(Unexpected::Unsigned(v), &self)))
             }
 
             fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
             where
                 E: Error,
             {
-                S::from_f64(v).ok_or_else(|| Error::invalid_value(Unexpected::Float(v), &self))
+                S::from_f64(v)
+                    .ok_or_else(|| rejected(Error::invalid_value(Unexpected::Float(v), &self)))
             }
 
             fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
@@ -462,7 +488,7 @@ where
             {
                 v.replacen(",", ".", 1)
                     .parse()
-                    .map_err(|_| Error::invalid_value(Unexpected::Str(v), &self))
+                    .map_err(|_| rejected(Error::invalid_value(Unexpected::Str(v), &self)))
             }
         }
 
@@ -500,7 +526,7 @@ where
             {
                 S::from_i64(v)
                     .map(Some)
-                    .ok_or_else(|| Error::invalid_value(Unexpected::Signed(v), &self))
+                    .ok_or_else(|| rejected(Error::invalid_value(Unexpected::Signed(v), &self)))
             }
 
             fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
@@ -509,7 +535,7 @@ where
             {
                 S::from_u64(v)
                     .map(Some)
-                    .ok_or_else(|| Error::invalid_value(Unexpected::Unsigned(v), &self))
+                    .ok_or_else(|| rejected(Error::invalid_value(Unexpected::Unsigned(v), &self)))
             }
 
             fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
@@ -518,7 +544,7 @@ where
             {
                 S::from_f64(v)
                     .map(Some)
-                    .ok_or_else(|| Error::invalid_value(Unexpected::Float(v), &self))
+                    .ok_or_else(|| rejected(Error::invalid_value(Unexpected::Float(v), &self)))
             }
 
             fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
@@ -531,7 +557,7 @@ where
                     v.replacen(",", ".", 1)
                         .parse()
                         .map(Some)
-                        .map_err(|_| Error::invalid_value(Unexpected::Str(v), &self))
+                        .map_err(|_| rejected(Error::invalid_value(Unexpected::Str(v), &self)))
                 }
             }
 
@@ -577,7 +603,7 @@ where
             {
                 v.to_string()
                     .parse()
-                    .map_err(|_| Error::invalid_value(Unexpected::Signed(v), &self))
+                    .map_err(|_| rejected(Error::invalid_value(Unexpected::Signed(v), &self)))
             }
 
             fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
@@ -586,7 +612,7 @@ where
             {
                 v.to_string()
                     .parse()
-                    .map_err(|_| Error::invalid_value(Unexpected::Unsigned(v), &self))
+                    .map_err(|_| rejected(Error::invalid_value(Unexpected::Unsigned(v), &self)))
             }
 
             fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
@@ -594,7 +620,7 @@ where
                 E: Error,
             {
                 v.parse()
-                    .map_err(|_| Error::invalid_value(Unexpected::Str(v), &self))
+                    .map_err(|_| rejected(Error::invalid_value(Unexpected::Str(v), &self)))
             }
         }
 
@@ -633,7 +659,7 @@ where
                 v.to_string()
                     .parse()
                     .map(Some)
-                    .map_err(|_| Error::invalid_value(Unexpected::Signed(v), &self))
+                    .map_err(|_| rejected(Error::invalid_value(Unexpected::Signed(v), &self)))
             }
 
             fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
@@ -643,7 +669,7 @@ where
                 v.to_string()
                     .parse()
                     .map(Some)
-                    .map_err(|_| Error::invalid_value(Unexpected::Unsigned(v), &self))
+                    .map_err(|_| rejected(Error::invalid_value(Unexpected::Unsigned(v), &self)))
             }
 
             fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
@@ -655,7 +681,7 @@ where
                 } else {
                     v.parse()
                         .map(Some)
-                        .map_err(|_| Error::invalid_value(Unexpected::Str(v), &self))
+                        .map_err(|_| rejected(Error::invalid_value(Unexpected::Str(v), &self)))
                 }
             }
 
@@ -1,13 +1,19 @@
 use chrono::Datelike;
-use std::{fmt::Display, io::Write, path::PathBuf, time::Duration};
+use std::{
+    fmt::Display,
+    io::{Read, Write},
+    marker::PhantomData,
+    path::PathBuf,
+    time::Duration,
+};
 
 use chrono::Utc;
-use csv::DeserializeRecordsIntoIter;
+use csv::{ByteRecord, DeserializeRecordsIntoIter};
 use futures_util::StreamExt;
 use http_client::HttpClient;
 use serde::de::DeserializeOwned;
 
-use crate::{ApiDownloader, Result, utils::hash_file};
+use crate::{ApiDownloader, FieldQuality, Result, data_quality::track, utils::hash_file};
 
 #[derive(Debug, Clone)]
 pub struct DataDownloader {
@@ -33,6 +39,64 @@ impl<R: std::io::Read, D: DeserializeOwned> Iterator for FiskeridirRecordIter<R,
     }
 }
 
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// A row of a file together with its line number, a hash of its raw fields and the quality of
+/// its fields.
+#[derive(Debug)]
+pub struct FiskeridirRecord<T> {
+    pub line: u64,
+    pub hash: u64,
+    pub quality: FieldQuality,
+    pub value: Result<T>,
+}
+
+pub struct FiskeridirRecordsIter<R, D> {
+    reader: csv::Reader<R>,
+    headers: ByteRecord,
+    record: ByteRecord,
+    _marker: PhantomData<D>,
+}
+
+impl<T> FiskeridirRecord<T> {
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> FiskeridirRecord<U> {
+        FiskeridirRecord {
+            line: self.line,
+            hash: self.hash,
+            quality: self.quality,
+            value: self.value.map(f),
+        }
+    }
+}
+
+impl<R: Read, D: DeserializeOwned> Iterator for FiskeridirRecordsIter<R, D> {
+    type Item = FiskeridirRecord<D>;
+    fn next(&mut self) -> Option<FiskeridirRecord<D>> {
+        match self.reader.read_byte_record(&mut self.record) {
+            Ok(false) => None,
+            Ok(true) => {
+                let hash = record_hash(&self.record);
+
+                let (value, quality) = track(|| self.record.deserialize::<D>(Some(&self.headers)));
+
+                Some(FiskeridirRecord {
+                    line: self.record.position().map(|p| p.line()).unwrap_or_default(),
+                    hash,
+                    quality,
+                    value: value.map_err(|e| e.into()),
+                })
+            }
+            Err(e) => Some(FiskeridirRecord {
+                line: e.position().map(|p| p.line()).unwrap_or_default(),
+                hash: 0,
+                quality: FieldQuality::default(),
+                value: Err(e.into()),
+            }),
+        }
+    }
+}
+
 impl DataDir {
     pub fn into_deserialize<T: DeserializeOwned + 'static>(
         self,
@@ -50,6 +114,29 @@ impl DataDir {
         })
     }
 
+    /// Same as `into_deserialize`, but each row is returned with its line number and a hash of
+    /// its contents.
+    pub fn into_records<T: DeserializeOwned + 'static>(
+        self,
+        file: &DataFile,
+    ) -> Result<FiskeridirRecordsIter<std::fs::File, T>> {
+        let file = std::fs::File::open(self.file_name(file))?;
+
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(b';')
+            .flexible(true)
+            .from_reader(file);
+
+        let headers = reader.byte_headers()?.clone();
+
+        Ok(FiskeridirRecordsIter {
+            reader,
+            headers,
+            record: ByteRecord::new(),
+            _marker: PhantomData,
+        })
+    }
+
     pub fn hash(&self, file: &DataFile) -> Result<String> {
         hash_file(&self.file_name(file))
     }
@@ -315,3 +402,35 @@ impl Default for ApiDownloader {
         Self::new()
     }
 }
+
+/// Hashes the raw fields of a row with FNV-1a, as row hashes are persisted in file checkpoints
+/// they must be stable across builds and platforms.
+fn record_hash(record: &ByteRecord) -> u64 {
+    record.iter().fold(FNV_OFFSET_BASIS, |hash, field| {
+        fnv1a(fnv1a(hash, field), b";")
+    })
+}
+
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(hash, |hash, b| (hash ^ *b as u64).wrapping_mul(FNV_PRIME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a_matches_reference_values() {
+        assert_eq!(fnv1a(FNV_OFFSET_BASIS, b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(FNV_OFFSET_BASIS, b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(fnv1a(FNV_OFFSET_BASIS, b"foobar"), 0x85944171f73967e8);
+    }
+
+    #[test]
+    fn test_record_hash_is_fnv1a_over_semicolon_terminated_fields() {
+        let record = ByteRecord::from(vec!["a", "b"]);
+        assert_eq!(record_hash(&record), fnv1a(FNV_OFFSET_BASIS, b"a;b;"));
+    }
+}
@@ -4,6 +4,7 @@
 //! Implements a library for downloading and reading data sources from Fiskeridir
 
 mod api_downloader;
+mod data_quality;
 mod deserialize_utils;
 mod error;
 mod file_downloader;
@@ -12,6 +13,7 @@ mod string_new_types;
 mod utils;
 
 pub use api_downloader::*;
+pub use data_quality::FieldQuality;
 pub use error::{Error, ErrorDiscriminants, LandingIdError, ParseStringError, Result};
 pub use file_downloader::*;
 pub use models::*;
@@ -194,6 +194,40 @@ async fn download_and_read_landings() {
     assert_eq!(result.len(), 499);
 }
 
+#[tokio::test]
+async fn read_landing_records_with_line_numbers() {
+    let test_helper = TestHelper::new().await;
+    let path = test_helper.temp_dir.path();
+    let mock_server_uri = test_helper.mock_server.uri();
+
+    let downloader = DataDownloader::new(path.to_path_buf());
+    let source = FileSource::Landings {
+        year: 2021,
+        url: Some(format!("{mock_server_uri}/landings")),
+    };
+
+    let datafile = downloader.download(&source).await.unwrap();
+
+    let records = datafile
+        .clone()
+        .into_records::<LandingRaw>(&source.files()[0])
+        .unwrap()
+        .collect::<Vec<_>>();
+    let hashes = datafile
+        .into_records::<LandingRaw>(&source.files()[0])
+        .unwrap()
+        .map(|r| r.hash)
+        .collect::<Vec<_>>();
+
+    test_helper.temp_dir.close().unwrap();
+
+    assert_eq!(records.len(), 500);
+    assert_eq!(records[0].line, 2);
+    assert!(records.windows(2).all(|w| w[0].line < w[1].line));
+    assert_eq!(records.iter().filter(|r| r.value.is_err()).count(), 1);
+    assert_eq!(records.iter().map(|r| r.hash).collect::<Vec<_>>(), hashes);
+}
+
 #[tokio::test]
 async fn download_and_read_register_vessels() {
     let test_helper = TestHelper::new().await;
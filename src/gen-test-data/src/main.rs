@@ -43,7 +43,7 @@ mod all {
 
         let adapter = PostgresAdapter::new(&db_settings).await.unwrap();
 
-        let engine = engine::engine(adapter.clone(), &db_settings).await;
+        let engine = engine::engine(adapter.clone(), &db_settings, false).await;
 
         let builder = TestStateBuilder::new(
             Box::new(adapter.clone()),
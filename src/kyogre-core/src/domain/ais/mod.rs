@@ -31,6 +31,7 @@ pub enum AisPermission {
 pub struct DataMessage {
     pub positions: Vec<NewAisPosition>,
     pub static_messages: Vec<NewAisStatic>,
+    pub rejected_messages: Vec<NewAisRejectedMessage>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -111,6 +112,47 @@ pub struct NewAisStatic {
     pub report_class: Option<String>,
 }
 
+/// A message from the ais stream that could not be consumed, stored so that we can audit changes
+/// in the upstream feed and replay the message once the cause has been fixed.
+#[derive(Debug, Clone)]
+pub struct NewAisRejectedMessage {
+    pub category: AisRejectionCategory,
+    pub message: String,
+    pub error: String,
+    pub received_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AisRejectedMessage {
+    pub category: AisRejectionCategory,
+    pub message: String,
+    pub error: String,
+    pub received_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AisRejectionCount {
+    pub category: AisRejectionCategory,
+    pub num_rejected: i64,
+    pub last_rejected_at: DateTime<Utc>,
+}
+
+#[repr(i32)]
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, Hash, FromPrimitive, strum::Display, AsRefStr, EnumString,
+)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+pub enum AisRejectionCategory {
+    /// The message exceeded the maximum line length of the consumer.
+    MaxLineLengthExceeded = 1,
+    /// The message was not a valid Barentswatch json message.
+    InvalidJson = 2,
+    /// The message was not a valid NMEA sentence.
+    InvalidNmea = 3,
+    /// The message was parsed but could not be converted to a static message.
+    InvalidStatic = 4,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct AisPositionMinimal {
     pub latitude: f64,
@@ -223,7 +265,15 @@ pub struct AisVesselMigrate {
 
 impl DataMessage {
     pub fn is_empty(&self) -> bool {
-        self.static_messages.is_empty() && self.positions.is_empty()
+        self.static_messages.is_empty()
+            && self.positions.is_empty()
+            && self.rejected_messages.is_empty()
+    }
+}
+
+impl From<AisRejectionCategory> for i32 {
+    fn from(value: AisRejectionCategory) -> Self {
+        value as i32
     }
 }
 
@@ -241,6 +291,12 @@ impl FromStr for Mmsi {
     }
 }
 
+impl From<i32> for Mmsi {
+    fn from(value: i32) -> Self {
+        Self(value)
+    }
+}
+
 impl From<Mmsi> for i32 {
     fn from(value: Mmsi) -> Self {
         value.0
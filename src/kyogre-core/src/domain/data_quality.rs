@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+use fiskeridir_rs::{DataFileId, FieldQuality};
+use serde::{Deserialize, Serialize};
+
+/// The number of rows and fields of a file that the lenient Fiskeridir deserializers had to
+/// coerce, default or reject, see `FieldQuality`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewFileDataQuality {
+    pub file_id: DataFileId,
+    pub file_hash: String,
+    pub dataset: String,
+    pub year: u32,
+    pub rows: u64,
+    pub rejected_rows: u64,
+    pub fields: FieldQuality,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+pub struct FileDataQuality {
+    pub dataset: String,
+    pub year: u32,
+    pub rows: u64,
+    pub rejected_rows: u64,
+    pub coerced_fields: u64,
+    pub defaulted_fields: u64,
+    pub rejected_fields: u64,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DataQualityQuery {
+    pub datasets: Option<Vec<String>>,
+    pub years: Option<Vec<u32>>,
+}
@@ -0,0 +1,210 @@
+use fiskeridir_rs::FiskeridirVesselId;
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, Display, EnumString};
+
+/// The fuel a vessel runs on, which determines the emission factors used for its emission reports.
+/// Vessels without a registered fuel type are assumed to run on [`FuelType::Diesel`].
+#[repr(i32)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Deserialize,
+    Serialize,
+    Display,
+    AsRefStr,
+    EnumString,
+)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+pub enum FuelType {
+    /// Marine Diesel/Gas Oil
+    #[default]
+    Diesel = 1,
+    /// Light Fuel Oil
+    LightFuelOil = 2,
+    /// Heavy Fuel Oil, assumed to be compliant with the global 0.5% sulphur cap
+    HeavyFuelOil = 3,
+    /// Liquefied Natural Gas
+    Lng = 4,
+}
+
+/// Emission factors given as kg emitted per tonn of fuel consumed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EmissionFactors {
+    pub co2: f64,
+    pub nox: f64,
+    pub sox: f64,
+}
+
+/// Fuel consumption and the resulting emissions, NOx and SOx (as SO2) are given in kg and CO2 in
+/// tonn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+pub struct Emissions {
+    #[serde(rename = "fuel")]
+    pub fuel_liter: f64,
+    #[serde(rename = "co2")]
+    pub co2_tonn: f64,
+    #[serde(rename = "nox")]
+    pub nox_kg: f64,
+    #[serde(rename = "sox")]
+    pub sox_kg: f64,
+}
+
+/// The yearly emissions of a single vessel.
+/// `measured` is derived from the fuel measurements reported by the vessel and `estimated` from
+/// our fuel estimates for the periods not covered by any fuel measurement.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+pub struct VesselEmissions {
+    pub fiskeridir_vessel_id: FiskeridirVesselId,
+    pub fuel_type: FuelType,
+    pub measured: Emissions,
+    pub estimated: Emissions,
+    pub total: Emissions,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+pub struct EmissionReport {
+    pub year: i32,
+    pub vessels: Vec<VesselEmissions>,
+    pub measured: Emissions,
+    pub estimated: Emissions,
+    pub total: Emissions,
+}
+
+/// The measured and estimated fuel consumption of a vessel within a year.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VesselYearlyFuel {
+    pub fiskeridir_vessel_id: FiskeridirVesselId,
+    pub fuel_type: FuelType,
+    pub measured_fuel_liter: f64,
+    pub estimated_fuel_liter: f64,
+}
+
+impl FuelType {
+    /// Sources:
+    /// - CO2: <https://www.classnk.or.jp/hp/pdf/activities/statutory/eedi/mepc_1-circ_684.pdf>
+    ///   Appendix, section 3
+    /// - NOx: EMEP/EEA air pollutant emission inventory guidebook 2023, 1.A.3.d Navigation,
+    ///   Tier 1 emission factors
+    /// - SOx: 20 kg per tonn of fuel per percent of sulphur content, assuming 0.1% for distillates
+    ///   and 0.5% for residual fuels
+    pub fn emission_factors(&self) -> EmissionFactors {
+        match self {
+            FuelType::Diesel => EmissionFactors {
+                co2: 3206.,
+                nox: 78.5,
+                sox: 2.,
+            },
+            FuelType::LightFuelOil => EmissionFactors {
+                co2: 3151.,
+                nox: 79.3,
+                sox: 10.,
+            },
+            FuelType::HeavyFuelOil => EmissionFactors {
+                co2: 3114.,
+                nox: 79.3,
+                sox: 10.,
+            },
+            FuelType::Lng => EmissionFactors {
+                co2: 2750.,
+                nox: 13.,
+                sox: 0.,
+            },
+        }
+    }
+
+    pub fn density_kg_per_liter(&self) -> f64 {
+        match self {
+            FuelType::Diesel => 0.86,
+            FuelType::LightFuelOil => 0.92,
+            FuelType::HeavyFuelOil => 0.98,
+            FuelType::Lng => 0.45,
+        }
+    }
+}
+
+impl Emissions {
+    pub fn new(fuel_liter: f64, fuel_type: FuelType) -> Self {
+        let fuel_tonn = fuel_liter * fuel_type.density_kg_per_liter() / 1000.;
+        let EmissionFactors { co2, nox, sox } = fuel_type.emission_factors();
+
+        Self {
+            fuel_liter,
+            co2_tonn: fuel_tonn * co2 / 1000.,
+            nox_kg: fuel_tonn * nox,
+            sox_kg: fuel_tonn * sox,
+        }
+    }
+}
+
+impl std::ops::Add for Emissions {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            fuel_liter: self.fuel_liter + rhs.fuel_liter,
+            co2_tonn: self.co2_tonn + rhs.co2_tonn,
+            nox_kg: self.nox_kg + rhs.nox_kg,
+            sox_kg: self.sox_kg + rhs.sox_kg,
+        }
+    }
+}
+
+impl EmissionReport {
+    pub fn new(year: i32, fuel: Vec<VesselYearlyFuel>) -> Self {
+        let vessels: Vec<_> = fuel.into_iter().map(VesselEmissions::from).collect();
+
+        let (measured, estimated) = vessels
+            .iter()
+            .fold(Default::default(), |(m, e): (Emissions, Emissions), v| {
+                (m + v.measured, e + v.estimated)
+            });
+
+        Self {
+            year,
+            vessels,
+            measured,
+            estimated,
+            total: measured + estimated,
+        }
+    }
+}
+
+impl From<VesselYearlyFuel> for VesselEmissions {
+    fn from(value: VesselYearlyFuel) -> Self {
+        let VesselYearlyFuel {
+            fiskeridir_vessel_id,
+            fuel_type,
+            measured_fuel_liter,
+            estimated_fuel_liter,
+        } = value;
+
+        let measured = Emissions::new(measured_fuel_liter, fuel_type);
+        let estimated = Emissions::new(estimated_fuel_liter, fuel_type);
+
+        Self {
+            fiskeridir_vessel_id,
+            fuel_type,
+            measured,
+            estimated,
+            total: measured + estimated,
+        }
+    }
+}
+
+impl From<FuelType> for i32 {
+    fn from(value: FuelType) -> Self {
+        value as i32
+    }
+}
@@ -0,0 +1,20 @@
+use fiskeridir_rs::DataFileId;
+
+/// Progress of a file that has only been partially added, the rows before `row_offset` have been
+/// added and `chunk_hash` is the hash of all those rows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileCheckpoint {
+    pub file_id: DataFileId,
+    pub file_hash: String,
+    pub row_offset: u64,
+    pub chunk_hash: String,
+}
+
+/// A row of a file that could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileParseError {
+    pub file_id: DataFileId,
+    pub file_hash: String,
+    pub line: u64,
+    pub error: String,
+}
@@ -56,6 +56,21 @@ pub struct LiveFuel {
     pub entries: Vec<LiveFuelEntry>,
 }
 
+/// A vessel specific fuel model fitted from the vessel's own fuel measurements.
+/// The consumption of a position segment is modelled as a linear combination of the time spent,
+/// the engine load derived from the speed and the additional load of active gear while hauling.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EmpiricalFuelModel {
+    /// Consumption independent of speed, such as auxiliary engines and idling.
+    pub idle_liter_per_hour: f64,
+    /// Additional consumption when running at full engine load.
+    pub load_liter_per_hour: f64,
+    /// Additional consumption per unit of haul load factor above one.
+    pub haul_liter_per_hour: f64,
+    /// Number of fuel measurement ranges the model was fitted from.
+    pub num_measurements: u32,
+}
+
 pub fn live_fuel_year_day_hour(ts: DateTime<Utc>) -> (i32, u32, u32) {
     (ts.year(), ts.ordinal(), ts.hour())
 }
@@ -1,7 +1,9 @@
 use super::DateRange;
 use chrono::{DateTime, Utc};
 use fiskeridir_rs::FiskeridirVesselId;
+use num_derive::FromPrimitive;
 use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, EnumString};
 
 #[cfg(feature = "oasgen")]
 use oasgen::OaSchema;
@@ -54,6 +56,72 @@ pub struct TripOverlappingFuelMeasurement {
     pub percentage_of_trip_covered_by_measurements: f64,
 }
 
+/// The fuel estimation models that can be back-tested against fuel measurements.
+#[repr(i32)]
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    FromPrimitive,
+    Deserialize,
+    Serialize,
+    strum::Display,
+    AsRefStr,
+    EnumString,
+)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+pub enum FuelEstimationModel {
+    Maru = 1,
+    Holtrop = 2,
+    Empirical = 3,
+}
+
+/// The estimated fuel of a single fuel measurement range for one of the estimation models.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewFuelBacktestResult {
+    pub fuel_range: DateRange,
+    pub model: FuelEstimationModel,
+    pub measured_liter: f64,
+    pub estimated_liter: f64,
+}
+
+/// Error metrics of the estimated fuel compared to the measured fuel, `fiskeridir_vessel_id` is
+/// `None` for the metrics across all vessels.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct FuelBacktestMetrics {
+    pub fiskeridir_vessel_id: Option<FiskeridirVesselId>,
+    pub model: FuelEstimationModel,
+    pub num_ranges: i64,
+    #[serde(rename = "measuredFuel")]
+    pub measured_liter: f64,
+    #[serde(rename = "estimatedFuel")]
+    pub estimated_liter: f64,
+    /// Mean absolute error in liters.
+    pub mae_liter: f64,
+    /// Mean of the estimated minus the measured fuel in liters, positive values means that the
+    /// model overestimates.
+    pub bias_liter: f64,
+    /// Mean absolute percentage error.
+    pub mape: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct FuelBacktestQuery {
+    pub fiskeridir_vessel_id: Option<FiskeridirVesselId>,
+}
+
+impl From<FuelEstimationModel> for i32 {
+    fn from(value: FuelEstimationModel) -> Self {
+        value as i32
+    }
+}
+
 impl From<FuelMeasurementId> for i64 {
     fn from(value: FuelMeasurementId) -> Self {
         value.0
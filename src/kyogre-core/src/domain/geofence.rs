@@ -0,0 +1,216 @@
+use std::fmt::{self, Display};
+
+use chrono::{DateTime, Utc};
+use fiskeridir_rs::{CallSign, FiskeridirVesselId};
+use geo::{Contains, Geometry, Point, Polygon};
+use num_derive::FromPrimitive;
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, EnumString};
+use uuid::Uuid;
+
+#[cfg(feature = "oasgen")]
+use oasgen::OaSchema;
+
+use crate::{BarentswatchUserId, CatchLocationId, Mmsi, OptionalDateTimeRange};
+
+/// Fishing facilities are mostly reported as points or lines, so geofences created from them
+/// cover the area within this distance of the reported geometry.
+pub const FISHING_FACILITY_GEOFENCE_BUFFER_METERS: f64 = 500.;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type), sqlx(transparent))]
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+pub struct GeofenceId(i64);
+
+#[repr(i32)]
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    FromPrimitive,
+    Deserialize,
+    Serialize,
+    strum::Display,
+    AsRefStr,
+    EnumString,
+)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+pub enum GeofenceAreaType {
+    /// A polygon drawn by the user.
+    Polygon = 1,
+    /// A copy of the polygon of an existing catch location.
+    CatchLocation = 2,
+    /// A buffered area around the geometry of a fishing facility.
+    FishingFacility = 3,
+}
+
+#[repr(i32)]
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    FromPrimitive,
+    Deserialize,
+    Serialize,
+    strum::Display,
+    AsRefStr,
+    EnumString,
+)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+pub enum GeofenceEventType {
+    Enter = 1,
+    Exit = 2,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Geofence {
+    pub id: GeofenceId,
+    pub name: String,
+    pub area_type: GeofenceAreaType,
+    pub catch_location_id: Option<CatchLocationId>,
+    pub fishing_facility_tool_id: Option<Uuid>,
+    pub geometry: Geometry<f64>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeofenceArea {
+    Polygon(Polygon<f64>),
+    CatchLocation(CatchLocationId),
+    FishingFacility(Uuid),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewGeofence {
+    pub name: String,
+    pub area: GeofenceArea,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+pub struct GeofenceEvent {
+    pub geofence_id: GeofenceId,
+    pub fiskeridir_vessel_id: FiskeridirVesselId,
+    pub event_type: GeofenceEventType,
+    pub timestamp: DateTime<Utc>,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct GeofenceEventsQuery {
+    pub user_id: BarentswatchUserId,
+    pub geofence_id: Option<GeofenceId>,
+    pub fiskeridir_vessel_id: Option<FiskeridirVesselId>,
+    pub range: OptionalDateTimeRange,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+/// The processing state of a vessel followed by the owner of a geofence.
+#[derive(Debug, Clone)]
+pub struct GeofenceVessel {
+    pub geofence_id: GeofenceId,
+    pub fiskeridir_vessel_id: FiskeridirVesselId,
+    pub mmsi: Option<Mmsi>,
+    pub call_sign: Option<CallSign>,
+    /// Whether the latest processed position was inside the geofence, `None` if the vessel has
+    /// not been processed for this geofence yet.
+    pub inside: Option<bool>,
+    pub processed_until: Option<DateTime<Utc>>,
+    /// The timestamp of the earliest VMS position added since the vessel was last processed. VMS
+    /// positions are often added late, if this is at or before `processed_until` the vessel is
+    /// processed again from this timestamp.
+    pub earliest_vms_insertion: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GeofenceVesselUpdate {
+    pub geofence_id: GeofenceId,
+    pub fiskeridir_vessel_id: FiskeridirVesselId,
+    pub inside: bool,
+    pub processed_until: DateTime<Utc>,
+    pub events: Vec<NewGeofenceEvent>,
+    /// Existing events at or after this timestamp are replaced by `events`.
+    pub replace_events_from: Option<DateTime<Utc>>,
+    pub call_sign: Option<CallSign>,
+    /// The `earliest_vms_insertion` of the vessel that was handled by this update.
+    pub earliest_vms_insertion: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewGeofenceEvent {
+    pub geofence_id: GeofenceId,
+    pub fiskeridir_vessel_id: FiskeridirVesselId,
+    pub event_type: GeofenceEventType,
+    pub timestamp: DateTime<Utc>,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Geofence {
+    pub fn contains(&self, latitude: f64, longitude: f64) -> bool {
+        self.geometry.contains(&Point::new(longitude, latitude))
+    }
+}
+
+impl GeofenceArea {
+    pub fn area_type(&self) -> GeofenceAreaType {
+        match self {
+            GeofenceArea::Polygon(_) => GeofenceAreaType::Polygon,
+            GeofenceArea::CatchLocation(_) => GeofenceAreaType::CatchLocation,
+            GeofenceArea::FishingFacility(_) => GeofenceAreaType::FishingFacility,
+        }
+    }
+}
+
+impl GeofenceEventType {
+    pub fn from_transition(inside: bool) -> Self {
+        if inside { Self::Enter } else { Self::Exit }
+    }
+}
+
+impl From<GeofenceAreaType> for i32 {
+    fn from(value: GeofenceAreaType) -> Self {
+        value as i32
+    }
+}
+
+impl From<GeofenceEventType> for i32 {
+    fn from(value: GeofenceEventType) -> Self {
+        value as i32
+    }
+}
+
+impl From<GeofenceId> for i64 {
+    fn from(value: GeofenceId) -> Self {
+        value.0
+    }
+}
+
+impl Display for GeofenceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(feature = "test")]
+mod test {
+    use super::*;
+
+    impl GeofenceId {
+        pub fn test_new(value: i64) -> Self {
+            Self(value)
+        }
+    }
+}
@@ -47,6 +47,9 @@ pub struct TripsDetailedHaul {
     pub start_fuel_liter: Option<u32>,
     #[serde(default)]
     pub end_fuel_liter: Option<u32>,
+    /// Only set for the hauls of the current trip, see [`Haul::estimated_value`].
+    #[serde(default)]
+    pub estimated_value: Option<f64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
@@ -70,6 +73,11 @@ pub struct Haul {
     pub catches: Vec<HaulCatch>,
     pub vessel_name: Option<String>,
     pub call_sign: CallSign,
+    /// Estimated value of the catches based on the Råfisklaget weekly sale prices of the most
+    /// recent week with sales at or before the end of the haul.
+    pub estimated_value: Option<f64>,
+    /// Sea temperature at the haul, only set once haul weather has been processed.
+    pub water_temperature: Option<f64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
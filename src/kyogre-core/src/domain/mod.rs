@@ -2,12 +2,16 @@ mod ais;
 mod ais_vms;
 mod catch_location;
 mod current_position;
+mod data_quality;
 mod date_range;
 mod delivery_points;
+mod emissions;
 mod ers;
+mod file_checkpoint;
 mod fishing_facility;
 mod fuel;
 mod fuel_measurement;
+mod geofence;
 mod hauls;
 mod landing;
 mod min_max_both;
@@ -23,7 +27,12 @@ mod vessels;
 mod vms;
 mod weather;
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(i32)]
+#[derive(
+    Clone, Copy, Debug, PartialEq, serde_repr::Serialize_repr, serde_repr::Deserialize_repr,
+)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
 pub enum ProcessingStatus {
     Unprocessed = 1,
     Attempted = 2,
@@ -72,12 +81,16 @@ pub use ais::*;
 pub use ais_vms::*;
 pub use catch_location::*;
 pub use current_position::*;
+pub use data_quality::*;
 pub use date_range::*;
 pub use delivery_points::*;
+pub use emissions::*;
 pub use ers::*;
+pub use file_checkpoint::*;
 pub use fishing_facility::*;
 pub use fuel::*;
 pub use fuel_measurement::*;
+pub use geofence::*;
 pub use hauls::*;
 pub use landing::*;
 pub use min_max_both::*;
@@ -1,7 +1,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::WeatherLocationId;
+use crate::{CatchLocationId, WeatherLocationId};
 
 #[derive(Debug, Clone)]
 pub struct NewOceanClimate {
@@ -36,6 +36,24 @@ pub struct OceanClimate {
     pub weather_location_id: WeatherLocationId,
 }
 
+/// The ocean climate of a catch location at a single depth, averaged over all weather locations
+/// that overlap the catch location and all timestamps within the queried range.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+pub struct CatchLocationOceanClimate {
+    pub catch_location_id: CatchLocationId,
+    pub depth: i32,
+    pub water_speed: Option<f64>,
+    pub water_direction: Option<f64>,
+    pub upward_sea_velocity: Option<f64>,
+    pub wind_speed: Option<f64>,
+    pub wind_direction: Option<f64>,
+    pub salinity: Option<f64>,
+    pub temperature: Option<f64>,
+    pub sea_floor_depth: f64,
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct HaulOceanClimate {
     pub water_speed: Option<f64>,
@@ -1,3 +1,5 @@
+mod price_forecast;
 mod weekly_sales;
 
+pub use price_forecast::*;
 pub use weekly_sales::*;
@@ -0,0 +1,64 @@
+use chrono::IsoWeek;
+use fiskeridir_rs::{Condition, GearGroup, Quality, SpeciesFiskeridirId, VesselLengthGroup};
+use serde::{Deserialize, Serialize};
+
+/// The combination of attributes Råfisklaget aggregates their weekly sales over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PriceGroup {
+    pub vessel_length_group: VesselLengthGroup,
+    pub gear_group: GearGroup,
+    pub species: SpeciesFiskeridirId,
+    pub condition: Condition,
+    pub quality: Quality,
+}
+
+/// The average price per kilo living weight of a price group within a single week.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeeklyPrice {
+    pub group: PriceGroup,
+    pub iso_week: IsoWeek,
+    pub price: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewPriceForecast {
+    pub group: PriceGroup,
+    pub iso_week: IsoWeek,
+    pub price: f64,
+    pub lower_bound: f64,
+    pub upper_bound: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PricePoint {
+    pub year: i32,
+    pub week: i32,
+    /// Price per kilo living weight.
+    pub price: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PriceForecast {
+    pub year: i32,
+    pub week: i32,
+    /// Forecasted price per kilo living weight.
+    pub price: f64,
+    /// Lower bound of the 95% prediction interval.
+    pub lower_bound: f64,
+    /// Upper bound of the 95% prediction interval.
+    pub upper_bound: f64,
+}
+
+/// The historical weekly prices of a price group ordered ascendingly by week, followed by a
+/// forecast for the succeeding weeks.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PriceSeries {
+    pub prices: Vec<PricePoint>,
+    pub forecast: Vec<PriceForecast>,
+}
@@ -34,6 +34,48 @@ pub struct AverageTripBenchmarks {
     // pub sustainability: f64,
 }
 
+/// Compares the trip benchmarks of a vessel against its most similar peers, which are vessels in
+/// the same length group and fishery sharing at least one gear group, ranked by how close their
+/// engine power is. Peers are only ever exposed as aggregates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+pub struct PeerBenchmarks {
+    pub num_peers: u32,
+    pub weight_per_hour: Option<PeerBenchmark>,
+    pub weight_per_distance: Option<PeerBenchmark>,
+    #[serde(rename = "weightPerFuel")]
+    pub weight_per_fuel_liter: Option<PeerBenchmark>,
+    #[serde(rename = "catchValuePerFuel")]
+    pub catch_value_per_fuel_liter: Option<PeerBenchmark>,
+    pub eeoi: Option<PeerBenchmark>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+pub struct PeerBenchmark {
+    pub value: f64,
+    pub peer_average: f64,
+    /// Percentage of peers the vessel performs better than, where ties count as half.
+    /// For EEOI a lower value is better, for all other benchmarks a higher value is better.
+    pub percentile_rank: f64,
+    /// Number of peers with a value for this benchmark, which is at least
+    /// [`PEER_BENCHMARKS_MIN_PEERS`].
+    pub num_peers: u32,
+}
+
+/// The average trip benchmarks of a single vessel within a peer group.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeerBenchmarkVessel {
+    pub is_self: bool,
+    pub weight_per_hour: Option<f64>,
+    pub weight_per_distance: Option<f64>,
+    pub weight_per_fuel_liter: Option<f64>,
+    pub catch_value_per_fuel_liter: Option<f64>,
+    pub eeoi: Option<f64>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TripWithBenchmark {
     pub id: TripId,
@@ -75,6 +117,66 @@ pub trait TripBenchmark: Send + Sync {
     ) -> CoreResult<()>;
 }
 
+impl PeerBenchmarks {
+    pub fn new(vessels: Vec<PeerBenchmarkVessel>) -> Self {
+        let (own, peers): (Vec<_>, Vec<_>) = vessels.into_iter().partition(|v| v.is_self);
+        let own = own.first();
+
+        let benchmark = |f: fn(&PeerBenchmarkVessel) -> Option<f64>, higher_is_better: bool| {
+            PeerBenchmark::new(
+                own.and_then(f)?,
+                peers.iter().filter_map(f),
+                higher_is_better,
+            )
+        };
+
+        Self {
+            num_peers: peers.len() as u32,
+            weight_per_hour: benchmark(|v| v.weight_per_hour, true),
+            weight_per_distance: benchmark(|v| v.weight_per_distance, true),
+            weight_per_fuel_liter: benchmark(|v| v.weight_per_fuel_liter, true),
+            catch_value_per_fuel_liter: benchmark(|v| v.catch_value_per_fuel_liter, true),
+            eeoi: benchmark(|v| v.eeoi, false),
+        }
+    }
+}
+
+impl PeerBenchmark {
+    /// Returns `None` if fewer than [`PEER_BENCHMARKS_MIN_PEERS`] peers have a value to compare
+    /// against, as the aggregate would otherwise single out individual peers.
+    pub fn new(
+        value: f64,
+        peers: impl IntoIterator<Item = f64>,
+        higher_is_better: bool,
+    ) -> Option<Self> {
+        let mut num_peers = 0;
+        let mut sum = 0.;
+        let mut better = 0.;
+
+        for p in peers {
+            num_peers += 1;
+            sum += p;
+            better += match value.partial_cmp(&p) {
+                Some(std::cmp::Ordering::Equal) => 0.5,
+                Some(std::cmp::Ordering::Greater) if higher_is_better => 1.,
+                Some(std::cmp::Ordering::Less) if !higher_is_better => 1.,
+                _ => 0.,
+            };
+        }
+
+        if num_peers < PEER_BENCHMARKS_MIN_PEERS {
+            return None;
+        }
+
+        Some(Self {
+            value,
+            peer_average: sum / num_peers as f64,
+            percentile_rank: better / num_peers as f64 * 100.,
+            num_peers,
+        })
+    }
+}
+
 impl From<TripBenchmarkId> for i32 {
     fn from(value: TripBenchmarkId) -> Self {
         value as i32
@@ -0,0 +1,31 @@
+use crate::{
+    DateRange, FiskeridirVesselId, PrecisionAttempt, PrecisionUpdate, ProcessingStatus,
+    PrunedTripPosition, TripAssemblerId, TripId, TripsConflictStrategy, VesselEvent,
+};
+
+/// How a trip was assembled and processed, used to investigate trips that look wrong.
+#[derive(Debug, Clone)]
+pub struct TripExplanation {
+    pub trip_id: TripId,
+    pub vessel_id: FiskeridirVesselId,
+    pub assembler_id: TripAssemblerId,
+    pub period: DateRange,
+    pub period_extended: DateRange,
+    pub period_precision: Option<DateRange>,
+    pub landing_coverage: DateRange,
+    /// The conflict strategy used when the trip was created, `None` for trips created before
+    /// we started recording it.
+    pub conflict_strategy: Option<TripsConflictStrategy>,
+    pub start_vessel_event_id: Option<u64>,
+    pub end_vessel_event_id: Option<u64>,
+    /// All vessel events connected to the trip.
+    pub events: Vec<VesselEvent>,
+    pub precision_status: ProcessingStatus,
+    pub start_precision: Option<PrecisionUpdate>,
+    pub end_precision: Option<PrecisionUpdate>,
+    /// The outcome of each precision implementation run on the trip, in the order they were run.
+    pub precision_attempts: Vec<PrecisionAttempt>,
+    pub position_layers_status: ProcessingStatus,
+    pub track_coverage: Option<f64>,
+    pub pruned_positions: Vec<PrunedTripPosition>,
+}
@@ -15,12 +15,16 @@ use oasgen::OaSchema;
 mod assembler;
 mod benchmark;
 mod distancer;
+mod explanation;
 mod layer;
+mod port_call;
 
 pub use assembler::*;
 pub use benchmark::*;
 pub use distancer::*;
+pub use explanation::*;
 pub use layer::*;
+pub use port_call::*;
 use strum::{AsRefStr, Display, EnumString};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
@@ -156,6 +160,8 @@ pub struct CurrentTrip {
     pub target_species_fiskeridir_id: Option<i32>,
     pub hauls: Vec<TripsDetailedHaul>,
     pub fishing_facilities: Vec<FishingFacility>,
+    /// Sum of the estimated value of the trip's hauls.
+    pub estimated_value: Option<f64>,
 }
 
 #[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
@@ -313,9 +319,26 @@ pub enum TripsConflictStrategy {
     Replace {
         conflict: DateTime<Utc>,
     },
+    /// Replaces the trips starting within the range (both inclusive), used by incremental trip
+    /// assembly to only replace the trips that changed.
+    ReplaceRange {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
     ReplaceAll,
 }
 
+impl TripsConflictStrategy {
+    /// The conflict of `Replace` and the start and end of the range of `ReplaceRange`.
+    pub fn timestamps(&self) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+        match *self {
+            TripsConflictStrategy::Replace { conflict } => (Some(conflict), None),
+            TripsConflictStrategy::ReplaceRange { start, end } => (Some(start), Some(end)),
+            TripsConflictStrategy::Error | TripsConflictStrategy::ReplaceAll => (None, None),
+        }
+    }
+}
+
 #[repr(i32)]
 #[derive(
     Debug,
@@ -338,6 +361,7 @@ pub enum TripsConflictStrategy {
 pub enum TripAssemblerId {
     Landings = 1,
     Ers = 2,
+    AisVms = 3,
 }
 
 impl TripProcessingUnit {
@@ -360,8 +384,9 @@ impl TripProcessingUnit {
                 new_period,
                 start_precision: _,
                 end_precision: _,
+                attempts: _,
             } => Some(new_period),
-            PrecisionOutcome::Failed => None,
+            PrecisionOutcome::Failed { attempts: _ } => None,
         })
     }
 }
@@ -398,8 +423,31 @@ pub enum PrecisionOutcome {
         new_period: DateRange,
         start_precision: Option<PrecisionUpdate>,
         end_precision: Option<PrecisionUpdate>,
+        attempts: Vec<PrecisionAttempt>,
+    },
+    Failed {
+        attempts: Vec<PrecisionAttempt>,
     },
-    Failed,
+}
+
+/// The result of running a single precision implementation on a trip.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrecisionAttempt {
+    pub id: PrecisionId,
+    pub boundary: PrecisionBoundary,
+    /// The new start or end of the trip found by the implementation, `None` if it did not find
+    /// anything.
+    pub timestamp: Option<DateTime<Utc>>,
+    pub direction: Option<PrecisionDirection>,
+}
+
+/// Which end of a trip a precision implementation tries to make more precise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "oasgen", derive(OaSchema))]
+pub enum PrecisionBoundary {
+    Start,
+    End,
 }
 
 impl TripUpdate {
@@ -409,8 +457,9 @@ impl TripUpdate {
                 new_period,
                 start_precision: _,
                 end_precision: _,
+                attempts: _,
             } => Some(new_period.clone()),
-            PrecisionOutcome::Failed => None,
+            PrecisionOutcome::Failed { attempts: _ } => None,
         })
     }
 }
@@ -422,8 +471,21 @@ impl PrecisionOutcome {
                 new_period: _,
                 start_precision: _,
                 end_precision: _,
+                attempts: _,
             } => ProcessingStatus::Successful,
-            PrecisionOutcome::Failed => ProcessingStatus::Attempted,
+            PrecisionOutcome::Failed { attempts: _ } => ProcessingStatus::Attempted,
+        }
+    }
+
+    pub fn attempts(&self) -> &[PrecisionAttempt] {
+        match self {
+            PrecisionOutcome::Success {
+                new_period: _,
+                start_precision: _,
+                end_precision: _,
+                attempts,
+            }
+            | PrecisionOutcome::Failed { attempts } => attempts,
         }
     }
 }
@@ -435,7 +497,10 @@ pub struct PrecisionUpdate {
 }
 
 /// What direction a precision implementation have modified a trip.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, EnumString)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "oasgen", derive(OaSchema))]
 pub enum PrecisionDirection {
     /// The trip has been shrinked.
     Shrinking = 1,
@@ -459,7 +524,10 @@ impl PrecisionDirection {
 }
 
 /// All trip precision implementations.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(feature = "oasgen", derive(OaSchema))]
 pub enum PrecisionId {
     /// Tries to find the first moved point.
     FirstMovedPoint = 1,
@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+
+use crate::{FiskeridirVesselId, VesselEventType};
+
+/// A departure from or an arrival at a port detected from a vessel's AIS/VMS positions, used by
+/// the [`crate::TripAssemblerId::AisVms`] trip assembler.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewAisVmsPortCall {
+    pub fiskeridir_vessel_id: FiskeridirVesselId,
+    pub timestamp: DateTime<Utc>,
+    pub port_id: Option<String>,
+    pub port_call_type: AisVmsPortCallType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AisVmsPortCallType {
+    Departure,
+    Arrival,
+}
+
+/// How far port call detection has progressed for a vessel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AisVmsPortCallTimer {
+    /// All positions up to and including this timestamp have been processed.
+    pub timestamp: DateTime<Utc>,
+    /// The port the vessel was in at `timestamp`, `None` if the vessel was at sea.
+    pub port_id: Option<String>,
+}
+
+impl From<AisVmsPortCallType> for VesselEventType {
+    fn from(value: AisVmsPortCallType) -> Self {
+        match value {
+            AisVmsPortCallType::Departure => VesselEventType::AisVmsDeparture,
+            AisVmsPortCallType::Arrival => VesselEventType::AisVmsArrival,
+        }
+    }
+}
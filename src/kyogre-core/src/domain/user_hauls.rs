@@ -29,6 +29,8 @@ pub struct UserHaul {
     pub end_fuel_liter: u32,
     pub total_living_weight_kg: Option<f64>,
     pub config: serde_json::Value,
+    /// Estimated value of the ERS haul the user haul has been matched with, if any.
+    pub estimated_value: Option<f64>,
 }
 
 #[derive(Debug, Clone)]
@@ -1,6 +1,6 @@
 use super::{Draught, VesselCurrentTrip};
 use crate::mean::Mean;
-use crate::{AisVessel, FisheryId, Mmsi, TripAssemblerId};
+use crate::{AisVessel, FisheryId, FuelEstimationModel, FuelType, Mmsi, TripAssemblerId};
 use chrono::{DateTime, Utc};
 use fiskeridir_rs::{
     CallSign, FiskeridirVesselId, GearGroup, RegisterVesselOwner, SpeciesGroup, VesselLengthGroup,
@@ -38,6 +38,12 @@ pub struct UpdateVessel {
     pub engine_rpm: Option<u32>,
     pub degree_of_electrification: Option<f64>,
     pub service_speed: Option<f64>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub fuel_type: Option<FuelType>,
+    /// The model used to estimate the fuel consumption of the vessel, vessels without a selected
+    /// model use the default model of the deployment.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub fuel_estimation_model: Option<FuelEstimationModel>,
 }
 
 #[derive(Debug, Clone)]
@@ -59,6 +65,7 @@ pub struct LiveFuelVessel {
     pub length: Option<f64>,
     pub breadth: Option<f64>,
     pub current_draught: Option<Draught>,
+    pub fuel_estimation_model: Option<FuelEstimationModel>,
 }
 
 impl LiveFuelVessel {
@@ -141,6 +148,14 @@ pub enum VesselEventData {
     ErsDca,
     ErsTra,
     Haul,
+    AisVmsDeparture {
+        port_id: Option<String>,
+        timestamp: DateTime<Utc>,
+    },
+    AisVmsArrival {
+        port_id: Option<String>,
+        timestamp: DateTime<Utc>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -187,6 +202,8 @@ pub enum VesselEventType {
     ErsDep = 4,
     ErsTra = 5,
     Haul = 6,
+    AisVmsDeparture = 7,
+    AisVmsArrival = 8,
 }
 
 impl From<VesselEventType> for i32 {
@@ -217,6 +234,8 @@ pub struct FiskeridirVessel {
     pub engine_version: u32,
     pub degree_of_electrification: Option<f64>,
     pub service_speed: Option<f64>,
+    pub fuel_type: Option<FuelType>,
+    pub fuel_estimation_model: Option<FuelEstimationModel>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -412,6 +431,8 @@ impl VesselEventType {
             VesselEventType::ErsDep => "ers_dep",
             VesselEventType::ErsPor => "ers_por",
             VesselEventType::Haul => "haul",
+            VesselEventType::AisVmsDeparture => "ais_vms_departure",
+            VesselEventType::AisVmsArrival => "ais_vms_arrival",
         }
     }
 }
@@ -433,6 +454,8 @@ mod test {
                 service_speed: Some(15.0),
                 engine_type: Some(EngineType::MSD),
                 engine_rpm: Some(700),
+                fuel_type: Some(FuelType::HeavyFuelOil),
+                fuel_estimation_model: Some(FuelEstimationModel::Maru),
             }
         }
     }
@@ -8,6 +8,7 @@ use serde::Deserialize;
 pub enum EarliestVmsUsedBy {
     TripsState = 1,
     CurrentTripPositionsProcessor = 2,
+    GeofenceProcessor = 3,
 }
 
 #[derive(Deserialize, Debug, Clone)]
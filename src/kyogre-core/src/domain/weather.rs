@@ -155,6 +155,38 @@ pub struct NewWeather {
     pub cloud_area_fraction: CloudAreaFraction,
 }
 
+/// A single time step of a forecast run, `reference_time` is the time the run was issued.
+#[derive(Debug, Clone)]
+pub struct NewWeatherForecast {
+    pub reference_time: DateTime<Utc>,
+    pub timestamp: DateTime<Utc>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub wind_speed_10m: WindSpeed,
+    pub wind_direction_10m: Option<f64>,
+    pub air_temperature_2m: AirTemperature,
+    pub air_pressure_at_sea_level: AirPressureAtSeaLevel,
+    pub precipitation_amount: PrecipitationAmount,
+}
+
+/// The forecast of a catch location at a given hour, averaged over all weather locations that
+/// overlap the catch location.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+pub struct WeatherForecast {
+    pub catch_location_id: CatchLocationId,
+    pub reference_time: DateTime<Utc>,
+    pub timestamp: DateTime<Utc>,
+    /// Whole hours between `reference_time` and `timestamp`.
+    pub horizon_hours: i32,
+    pub wind_speed_10m: Option<f64>,
+    pub wind_direction_10m: Option<f64>,
+    pub air_temperature_2m: Option<f64>,
+    pub air_pressure_at_sea_level: Option<f64>,
+    pub precipitation_amount: Option<f64>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
@@ -196,6 +228,18 @@ pub struct HaulWeather {
     pub cloud_area_fraction: Option<f64>,
 }
 
+/// The wind of a single weather location at a given hour, used to estimate the added resistance
+/// from wind and waves during fuel estimation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FuelWeather {
+    pub timestamp: DateTime<Utc>,
+    pub weather_location_id: WeatherLocationId,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub wind_speed_10m: f64,
+    pub wind_direction_10m: f64,
+}
+
 #[derive(Debug, Clone)]
 pub struct HaulWeatherOutput {
     pub haul_id: HaulId,
@@ -338,4 +382,21 @@ mod test {
             }
         }
     }
+
+    impl NewWeatherForecast {
+        pub fn test_default(reference_time: DateTime<Utc>, timestamp: DateTime<Utc>) -> Self {
+            let (latitude, longitude, _) = WEATHER_LOCATION_LATS_LONS[0];
+            Self {
+                reference_time,
+                timestamp,
+                latitude,
+                longitude,
+                wind_speed_10m: WindSpeed::new(12.0),
+                wind_direction_10m: Some(270.0),
+                air_temperature_2m: AirTemperature::new(275.0),
+                air_pressure_at_sea_level: AirPressureAtSeaLevel::new(101000.0),
+                precipitation_amount: PrecipitationAmount::new(1.5),
+            }
+        }
+    }
 }
@@ -1,9 +1,10 @@
-use crate::{IsTimeout, UserHaulId};
+use crate::{CatchLocationId, GeofenceId, IsTimeout, TripId, UserHaulId};
 use chrono::{DateTime, NaiveDate, Utc};
 use fiskeridir_rs::{CallSign, OrgId};
 use snafu::{Location, Snafu};
 use stack_error::{OpaqueError, StackError};
 use std::num::ParseIntError;
+use uuid::Uuid;
 
 pub type CoreResult<T> = Result<T, Error>;
 pub type WebApiResult<T> = Result<T, WebApiError>;
@@ -53,6 +54,14 @@ pub enum Object {
     Org(OrgId),
     #[strum(to_string = "The vessel with call_sign '{0}' was not found")]
     Vessel(CallSign),
+    #[strum(to_string = "Geofence with id: '{0}' not found")]
+    Geofence(GeofenceId),
+    #[strum(to_string = "The catch location '{0}' was not found")]
+    CatchLocation(CatchLocationId),
+    #[strum(to_string = "The fishing facility '{0}' was not found or has no geometry")]
+    FishingFacility(Uuid),
+    #[strum(to_string = "The trip '{0}' was not found")]
+    Trip(TripId),
 }
 
 #[derive(Snafu, StackError)]
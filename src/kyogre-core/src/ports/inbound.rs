@@ -25,6 +25,26 @@ pub trait LiveFuelInbound: Send + Sync {
         mmsi: Mmsi,
         range: &DateRange,
     ) -> CoreResult<Vec<AisPosition>>;
+    async fn empirical_fuel_model(
+        &self,
+        vessel_id: FiskeridirVesselId,
+    ) -> CoreResult<Option<EmpiricalFuelModel>>;
+}
+
+#[async_trait]
+pub trait FuelBacktestInbound: Send + Sync {
+    /// Replaces all existing back-test results of the vessel.
+    async fn set_fuel_backtest_results(
+        &self,
+        vessel_id: FiskeridirVesselId,
+        results: &[NewFuelBacktestResult],
+    ) -> CoreResult<()>;
+}
+
+#[async_trait]
+pub trait PriceForecastInbound: Send + Sync {
+    /// Replaces all existing price forecasts.
+    async fn set_price_forecasts(&self, forecasts: &[NewPriceForecast]) -> CoreResult<()>;
 }
 
 #[async_trait]
@@ -35,6 +55,11 @@ pub trait CurrentPositionInbound: Send + Sync {
     ) -> CoreResult<()>;
 }
 
+#[async_trait]
+pub trait GeofenceInbound: Send + Sync {
+    async fn update_geofence_vessels(&self, updates: Vec<GeofenceVesselUpdate>) -> CoreResult<()>;
+}
+
 #[async_trait]
 pub trait AisConsumeLoop: Sync + Send {
     async fn consume(
@@ -124,6 +149,16 @@ pub trait WebApiInboundPort {
         measurements: &[DeleteFuelMeasurement],
         call_sign: &CallSign,
     ) -> WebApiResult<()>;
+    async fn add_geofence(
+        &self,
+        geofence: &NewGeofence,
+        user_id: BarentswatchUserId,
+    ) -> WebApiResult<Geofence>;
+    async fn delete_geofence(
+        &self,
+        id: GeofenceId,
+        user_id: BarentswatchUserId,
+    ) -> WebApiResult<()>;
 }
 
 #[async_trait]
@@ -140,6 +175,21 @@ pub trait ScraperInboundPort {
         landings: BoxIterator<fiskeridir_rs::Result<fiskeridir_rs::Landing>>,
         data_year: u32,
     ) -> CoreResult<()>;
+    /// Adds a chunk of a landings file without deleting the landings that are missing from it or
+    /// updating the vessels of the landings, which is done by `delete_removed_landings` once the
+    /// whole file has been added.
+    async fn add_landings_chunk(
+        &self,
+        landings: Vec<fiskeridir_rs::Landing>,
+        data_year: u32,
+    ) -> CoreResult<()>;
+    /// Deletes the landings of the data year that are not in `landing_ids` and updates the
+    /// vessels of the remaining landings.
+    async fn delete_removed_landings(
+        &self,
+        landing_ids: Vec<fiskeridir_rs::LandingId>,
+        data_year: u32,
+    ) -> CoreResult<()>;
     async fn add_ers_dca(
         &self,
         ers_dca: BoxIterator<fiskeridir_rs::Result<fiskeridir_rs::ErsDca>>,
@@ -166,12 +216,23 @@ pub trait ScraperInboundPort {
         delivery_points: Vec<MattilsynetDeliveryPoint>,
     ) -> CoreResult<()>;
     async fn add_weather(&self, weather: Vec<NewWeather>) -> CoreResult<()>;
+    async fn add_weather_forecasts(&self, forecasts: Vec<NewWeatherForecast>) -> CoreResult<()>;
     async fn add_ocean_climate(&self, ocean_climate: Vec<NewOceanClimate>) -> CoreResult<()>;
 }
 
 #[async_trait]
 pub trait ScraperFileHashInboundPort {
+    /// Stores the hash of a fully added file and removes its checkpoint.
     async fn add(&self, id: &DataFileId, hash: String) -> CoreResult<()>;
+    /// Stores the checkpoint of a partially added file together with the rows that failed to
+    /// parse since the previous checkpoint.
+    async fn set_checkpoint(
+        &self,
+        checkpoint: &FileCheckpoint,
+        parse_errors: Vec<FileParseError>,
+    ) -> CoreResult<()>;
+    /// Replaces the data quality of the file.
+    async fn set_data_quality(&self, quality: &NewFileDataQuality) -> CoreResult<()>;
 }
 
 #[async_trait]
@@ -199,6 +260,11 @@ pub trait TripPipelineInbound: Send + Sync {
     /// This has the drawback where a vessel that has operated without ERS messages
     /// for some time and then starts reporting ERS, then all prior [`crate::TripAssemblerId::Landings`] trips for that vessel will be deleted
     /// and replaced with the new ERS based trips (the new ERS based trips will not cover the older landings).
+    /// Vessels without any departures or landings, but with AIS or VMS identifiers, will use the
+    /// [`crate::TripAssemblerId::AisVms`] trip assembler.
+    /// Vessels with any landings keep the [`crate::TripAssemblerId::Landings`] trip assembler
+    /// regardless of how sparse their landings are, as every trip must cover the landings of the
+    /// vessel and AIS/VMS based trips give no such guarantee.
     ///
     /// This method updates all vessels preferred trip assembler.
     async fn update_preferred_trip_assemblers(&self) -> CoreResult<()>;
@@ -234,6 +300,16 @@ pub trait TripPipelineInbound: Send + Sync {
     /// Which should only happen in the landings assembler when all landings for that vessel has
     /// been deleted.
     async fn nuke_trips(&self, vessel_id: FiskeridirVesselId) -> CoreResult<()>;
+
+    /// Adds port calls detected from AIS/VMS positions as vessel events for the
+    /// [`crate::TripAssemblerId::AisVms`] trip assembler and stores how far the detection has
+    /// progressed for the given vessel.
+    async fn add_ais_vms_port_calls(
+        &self,
+        vessel_id: FiskeridirVesselId,
+        port_calls: Vec<NewAisVmsPortCall>,
+        timer: AisVmsPortCallTimer,
+    ) -> CoreResult<()>;
 }
 
 #[cfg(feature = "test")]
@@ -21,12 +21,14 @@ pub type PinBoxStream<'a, T> = Pin<Box<dyn Stream<Item = WebApiResult<T>> + Send
 #[async_trait]
 pub trait WebApiOutboundPort {
     async fn price(&self, query: PriceQuery) -> WebApiResult<Option<f64>>;
+    async fn price_series(&self, query: PriceSeriesQuery) -> WebApiResult<PriceSeries>;
     async fn user_hauls(&self, call_sign: &CallSign) -> WebApiResult<Vec<UserHaul>>;
     async fn current_user_haul(
         &self,
         call_sign: &CallSign,
     ) -> WebApiResult<Option<StartedUserHaul>>;
     async fn selected_vessel(&self, id: BarentswatchUserId) -> WebApiResult<Option<CallSign>>;
+    async fn trip_explanation(&self, trip_id: TripId) -> WebApiResult<Option<TripExplanation>>;
     fn current_positions(
         &self,
         limit: Option<DateTime<Utc>>,
@@ -78,6 +80,7 @@ pub trait WebApiOutboundPort {
     ) -> WebApiResult<Vec<TripWithBenchmark>>;
     async fn eeoi(&self, query: EeoiQuery) -> WebApiResult<Option<f64>>;
     async fn fui(&self, query: FuiQuery) -> WebApiResult<Option<f64>>;
+    async fn peer_benchmarks(&self, query: PeerBenchmarksQuery) -> WebApiResult<PeerBenchmarks>;
     fn detailed_trips(
         &self,
         query: TripsQuery,
@@ -106,6 +109,12 @@ pub trait WebApiOutboundPort {
         query: &FuelQuery,
         org_id: OrgId,
     ) -> WebApiResult<Option<Vec<FuelEntry>>>;
+    async fn emission_report(&self, query: &EmissionReportQuery) -> WebApiResult<EmissionReport>;
+    async fn emission_report_by_org(
+        &self,
+        query: &EmissionReportQuery,
+        org_id: OrgId,
+    ) -> WebApiResult<Option<EmissionReport>>;
     fn fishing_facilities(
         &self,
         query: FishingFacilitiesQuery,
@@ -113,8 +122,21 @@ pub trait WebApiOutboundPort {
     async fn get_user(&self, user_id: BarentswatchUserId) -> WebApiResult<User>;
     fn delivery_points(&self) -> PinBoxStream<'_, DeliveryPoint>;
     fn weather(&self, query: WeatherQuery) -> PinBoxStream<'_, Weather>;
+    fn weather_forecasts(&self, query: WeatherForecastQuery) -> PinBoxStream<'_, WeatherForecast>;
     fn weather_locations(&self) -> PinBoxStream<'_, WeatherLocation>;
+    fn ocean_climate(
+        &self,
+        query: CatchLocationOceanClimateQuery,
+    ) -> PinBoxStream<'_, CatchLocationOceanClimate>;
     fn fuel_measurements(&self, query: FuelMeasurementsQuery) -> PinBoxStream<'_, FuelMeasurement>;
+    fn fuel_backtest(&self, query: FuelBacktestQuery) -> PinBoxStream<'_, FuelBacktestMetrics>;
+    fn data_quality(&self, query: DataQualityQuery) -> PinBoxStream<'_, FileDataQuality>;
+    fn geofences(&self, user_id: BarentswatchUserId) -> PinBoxStream<'_, Geofence>;
+    fn geofence_events(
+        &self,
+        query: GeofenceEventsQuery,
+        user_policy: AisPermission,
+    ) -> PinBoxStream<'_, GeofenceEvent>;
     async fn update_vessel(
         &self,
         call_sign: &CallSign,
@@ -141,8 +163,20 @@ pub trait TripAssemblerOutboundPort: Send + Sync {
         search_timestamp: TripSearchTimestamp,
         trip_assembler: TripAssemblerId,
     ) -> CoreResult<Option<TripAndSucceedingEvents>>;
+    /// Returns the trips of the given vessel ending at or after `end_from` ordered by their
+    /// period, or all trips of the vessel if `end_from` is `None`.
+    async fn assembled_trips(
+        &self,
+        vessel_id: FiskeridirVesselId,
+        trip_assembler: TripAssemblerId,
+        end_from: Option<DateTime<Utc>>,
+    ) -> CoreResult<Vec<NewTrip>>;
     async fn ports(&self) -> CoreResult<Vec<Port>>;
     async fn dock_points(&self) -> CoreResult<Vec<PortDockPoint>>;
+    async fn ais_vms_port_call_timer(
+        &self,
+        vessel_id: FiskeridirVesselId,
+    ) -> CoreResult<Option<AisVmsPortCallTimer>>;
 }
 
 #[async_trait]
@@ -203,6 +237,14 @@ pub trait TripBenchmarkOutbound: Send + Sync {
 pub trait MatrixCacheOutbound: Send + Sync {
     async fn hauls_matrix(&self, query: &HaulsMatrixQuery) -> CoreResult<HaulsMatrix>;
     async fn landing_matrix(&self, query: &LandingMatrixQuery) -> CoreResult<LandingMatrix>;
+    async fn hauls_time_series(
+        &self,
+        query: &HaulsTimeSeriesQuery,
+    ) -> CoreResult<Vec<TimeSeriesPoint>>;
+    async fn landing_time_series(
+        &self,
+        query: &LandingTimeSeriesQuery,
+    ) -> CoreResult<Vec<TimeSeriesPoint>>;
 }
 
 #[async_trait]
@@ -257,6 +299,47 @@ pub trait FuelEstimation: Send + Sync {
         range: &DateRange,
     ) -> CoreResult<Vec<DailyFuelEstimationPosition>>;
     async fn vessel_max_cargo_weight(&self, vessel_id: FiskeridirVesselId) -> CoreResult<f64>;
+    async fn fuel_measurement_ranges(
+        &self,
+        vessel_id: FiskeridirVesselId,
+    ) -> CoreResult<Vec<FuelMeasurementRange>>;
+    async fn empirical_fuel_model(
+        &self,
+        vessel_id: FiskeridirVesselId,
+    ) -> CoreResult<Option<EmpiricalFuelModel>>;
+    async fn fuel_estimation_weather(
+        &self,
+        range: &DateRange,
+        weather_location_ids: &[WeatherLocationId],
+    ) -> CoreResult<Vec<FuelWeather>>;
+    /// Returns the checksum of the vessel's current fuel measurement ranges if they have changed
+    /// since its empirical model was last fitted, `None` if the fitted model is up to date.
+    async fn changed_fuel_measurements_checksum(
+        &self,
+        vessel_id: FiskeridirVesselId,
+    ) -> CoreResult<Option<String>>;
+    /// Stores the fitted model of the vessel together with the checksum of the fuel measurements
+    /// it was fitted from, `None` removes any previously fitted model.
+    /// If `reset_estimates` is set, the vessel's fuel estimates and trip fuel distributions are
+    /// reset to unprocessed such that they are re-estimated with the new model.
+    async fn set_empirical_fuel_model(
+        &self,
+        vessel_id: FiskeridirVesselId,
+        model: Option<EmpiricalFuelModel>,
+        measurements_checksum: &str,
+        reset_estimates: bool,
+    ) -> CoreResult<()>;
+}
+
+#[async_trait]
+pub trait FuelBacktestOutbound: Send + Sync {
+    async fn vessels_with_fuel_measurements(&self) -> CoreResult<Vec<Vessel>>;
+}
+
+#[async_trait]
+pub trait PriceForecastOutbound: Send + Sync {
+    /// Returns the weekly prices of all price groups ordered by week.
+    async fn weekly_prices(&self) -> CoreResult<Vec<WeeklyPrice>>;
 }
 
 #[async_trait]
@@ -270,6 +353,20 @@ pub trait CurrentPositionOutbound: Send + Sync {
     ) -> CoreResult<Vec<AisVmsPosition>>;
 }
 
+#[async_trait]
+pub trait GeofenceOutbound: Send + Sync {
+    async fn all_geofences(&self) -> CoreResult<Vec<Geofence>>;
+    /// Returns all vessels followed by the owner of each geofence together with their
+    /// processing state.
+    async fn geofence_vessels(&self) -> CoreResult<Vec<GeofenceVessel>>;
+    async fn ais_vms_positions(
+        &self,
+        mmsi: Option<Mmsi>,
+        call_sign: Option<&CallSign>,
+        range: &DateRange,
+    ) -> CoreResult<Vec<AisVmsPosition>>;
+}
+
 #[async_trait]
 pub trait VerificationOutbound: Send + Sync {
     /// Runs a set of verification queries to check if certain constraints, which we cannot express
@@ -305,6 +402,7 @@ pub trait ScraperOutboundPort {
         source: Option<FishingFacilityApiSource>,
     ) -> CoreResult<Option<DateTime<Utc>>>;
     async fn latest_weather_timestamp(&self) -> CoreResult<Option<DateTime<Utc>>>;
+    async fn latest_weather_forecast_reference_time(&self) -> CoreResult<Option<DateTime<Utc>>>;
     async fn latest_ocean_climate_timestamp(&self) -> CoreResult<Option<DateTime<Utc>>>;
     async fn latest_buyer_location_update(&self) -> CoreResult<Option<NaiveDateTime>>;
     async fn latest_weekly_sale(&self) -> CoreResult<Option<NaiveDate>>;
@@ -313,6 +411,9 @@ pub trait ScraperOutboundPort {
 #[async_trait]
 pub trait ScraperFileHashOutboundPort {
     async fn get_hashes(&self, ids: &[DataFileId]) -> CoreResult<Vec<(DataFileId, String)>>;
+    async fn checkpoint(&self, id: &DataFileId) -> CoreResult<Option<FileCheckpoint>>;
+    /// Returns the rows that failed to parse in the latest version of the file.
+    async fn parse_errors(&self, id: &DataFileId) -> CoreResult<Vec<FileParseError>>;
 }
 
 #[async_trait]
@@ -354,6 +455,7 @@ pub trait TestHelperOutbound: Send + Sync {
     async fn all_vms(&self) -> Vec<VmsPosition>;
     async fn all_ais_vms(&self) -> Vec<AisVmsPosition>;
     async fn all_fuel_estimates(&self) -> Vec<f64>;
+    async fn weather_forecast_reference_times(&self) -> Vec<DateTime<Utc>>;
     async fn all_fuel_measurement_ranges(&self) -> Vec<FuelMeasurementRange>;
     async fn sum_fuel_estimates(
         &self,
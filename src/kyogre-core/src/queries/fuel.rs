@@ -25,3 +25,9 @@ pub struct LiveFuelQuery {
     pub threshold: DateTime<Utc>,
     pub call_sign: CallSign,
 }
+
+#[derive(Debug, Clone)]
+pub struct EmissionReportQuery {
+    pub call_sign: CallSign,
+    pub year: i32,
+}
@@ -9,6 +9,7 @@ mod landing;
 mod ocean_climate;
 mod pagination;
 mod price;
+mod time_series;
 mod trip;
 mod trip_benchmark;
 mod vessel_benchmark;
@@ -23,6 +24,7 @@ pub use ocean_climate::*;
 pub use pagination::*;
 pub use price::*;
 use strum::{AsRefStr, EnumString};
+pub use time_series::*;
 pub use trip::*;
 pub use trip_benchmark::*;
 pub use vessel_benchmark::*;
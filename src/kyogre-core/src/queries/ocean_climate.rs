@@ -1,6 +1,9 @@
 use chrono::{DateTime, Utc};
 
-use crate::WeatherLocationId;
+use crate::{CatchLocationId, WeatherLocationId};
+
+/// The maximum number of days between the start and end of a catch location ocean climate query.
+pub static MAX_OCEAN_CLIMATE_RANGE_DAYS: i64 = 31;
 
 #[derive(Default, Debug, Clone)]
 pub struct OceanClimateQuery {
@@ -9,3 +12,11 @@ pub struct OceanClimateQuery {
     pub depths: Option<Vec<i32>>,
     pub weather_location_ids: Option<Vec<WeatherLocationId>>,
 }
+
+#[derive(Default, Debug, Clone)]
+pub struct CatchLocationOceanClimateQuery {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub depths: Option<Vec<i32>>,
+    pub catch_location_ids: Option<Vec<CatchLocationId>>,
+}
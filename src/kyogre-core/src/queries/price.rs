@@ -1,3 +1,4 @@
+use crate::PriceGroup;
 use fiskeridir_rs::{Condition, GearGroup, Quality, SpeciesFiskeridirId, VesselLengthGroup};
 use serde::{Deserialize, Serialize};
 use serde_with::{DisplayFromStr, serde_as};
@@ -17,3 +18,36 @@ pub struct PriceQuery {
     #[serde_as(as = "DisplayFromStr")]
     pub quality: Quality,
 }
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+pub struct PriceSeriesQuery {
+    #[serde_as(as = "DisplayFromStr")]
+    pub length_group: VesselLengthGroup,
+    #[serde_as(as = "DisplayFromStr")]
+    pub gear_group: GearGroup,
+    pub species: SpeciesFiskeridirId,
+    #[serde_as(as = "DisplayFromStr")]
+    pub condition: Condition,
+    #[serde_as(as = "DisplayFromStr")]
+    pub quality: Quality,
+    /// How many of the most recent weeks with sales to include, defaults to
+    /// [`PRICE_SERIES_DEFAULT_WEEKS`].
+    pub weeks: Option<u32>,
+}
+
+pub static PRICE_SERIES_DEFAULT_WEEKS: u32 = 52;
+
+impl PriceSeriesQuery {
+    pub fn group(&self) -> PriceGroup {
+        PriceGroup {
+            vessel_length_group: self.length_group,
+            gear_group: self.gear_group,
+            species: self.species,
+            condition: self.condition,
+            quality: self.quality,
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::*;
+use chrono::{DateTime, Utc};
+use fiskeridir_rs::{GearGroup, SpeciesGroup, VesselLengthGroup};
+use num_derive::FromPrimitive;
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, EnumString};
+
+/// The size of the time buckets a time series is aggregated into.
+/// Weeks start on mondays and all buckets are in UTC.
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    Deserialize,
+    Serialize,
+    PartialEq,
+    Eq,
+    FromPrimitive,
+    strum::Display,
+    AsRefStr,
+    EnumString,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum TimeBucket {
+    Day = 1,
+    Week = 2,
+    #[default]
+    Month = 3,
+    Year = 4,
+}
+
+/// A dimension a time series can be grouped by in addition to its time buckets.
+#[cfg_attr(feature = "oasgen", derive(oasgen::OaSchema))]
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    Deserialize,
+    Serialize,
+    PartialEq,
+    Eq,
+    FromPrimitive,
+    strum::Display,
+    AsRefStr,
+    EnumString,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum TimeSeriesGroup {
+    GearGroup = 1,
+    SpeciesGroup = 2,
+    VesselLength = 3,
+    CatchLocation = 4,
+    Vessel = 5,
+}
+
+impl TimeSeriesGroup {
+    pub fn column_name(&self) -> &'static str {
+        match self {
+            TimeSeriesGroup::GearGroup => "gear_group_id",
+            TimeSeriesGroup::SpeciesGroup => "species_group_id",
+            TimeSeriesGroup::VesselLength => "vessel_length_group",
+            TimeSeriesGroup::CatchLocation => "catch_location_id",
+            TimeSeriesGroup::Vessel => "fiskeridir_vessel_id",
+        }
+    }
+}
+
+/// All filters of `filter` are applied, its active filter is ignored.
+#[derive(Debug, Clone)]
+pub struct HaulsTimeSeriesQuery {
+    pub filter: HaulsMatrixQuery,
+    pub bucket: TimeBucket,
+    pub group_by: Vec<TimeSeriesGroup>,
+}
+
+/// All filters of `filter` are applied, its active filter is ignored.
+#[derive(Debug, Clone)]
+pub struct LandingTimeSeriesQuery {
+    pub filter: LandingMatrixQuery,
+    pub bucket: TimeBucket,
+    pub group_by: Vec<TimeSeriesGroup>,
+}
+
+/// The summed living weight of a single time bucket, the group fields are only set if the time
+/// series is grouped by them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeSeriesPoint {
+    pub bucket: DateTime<Utc>,
+    pub gear_group_id: Option<GearGroup>,
+    pub species_group_id: Option<SpeciesGroup>,
+    pub vessel_length_group: Option<VesselLengthGroup>,
+    pub catch_location_id: Option<CatchLocationId>,
+    pub fiskeridir_vessel_id: Option<FiskeridirVesselId>,
+    pub living_weight: f64,
+}
@@ -46,3 +46,18 @@ pub struct AverageFuiQuery {
     pub vessel_ids: Vec<FiskeridirVesselId>,
     pub species_group_id: Option<SpeciesGroup>,
 }
+
+/// Number of peers used when none is specified.
+pub static PEER_BENCHMARKS_DEFAULT_PEERS: u32 = 10;
+/// Lower bound on the number of peers a vessel can be compared against, so that the aggregated
+/// benchmarks never single out an individual peer.
+pub static PEER_BENCHMARKS_MIN_PEERS: u32 = 5;
+/// Upper bound on the number of peers a vessel can be compared against.
+pub static PEER_BENCHMARKS_MAX_PEERS: u32 = 100;
+
+#[derive(Debug, Clone)]
+pub struct PeerBenchmarksQuery {
+    pub call_sign: CallSign,
+    pub range: OptionalDateTimeRange,
+    pub num_peers: u32,
+}
@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 
-use crate::WeatherLocationId;
+use crate::{CatchLocationId, WeatherLocationId};
 
 #[derive(Default, Debug, Clone)]
 pub struct WeatherQuery {
@@ -8,3 +8,13 @@ pub struct WeatherQuery {
     pub end: DateTime<Utc>,
     pub weather_location_ids: Option<Vec<WeatherLocationId>>,
 }
+
+/// The default and maximum number of hours after the reference time of the latest forecast run
+/// that is returned.
+pub static MAX_WEATHER_FORECAST_HORIZON_HOURS: u32 = 72;
+
+#[derive(Debug, Clone)]
+pub struct WeatherForecastQuery {
+    pub catch_location_ids: Option<Vec<CatchLocationId>>,
+    pub horizon_hours: u32,
+}
@@ -0,0 +1,28 @@
+CREATE TABLE ais_rejection_categories (
+    ais_rejection_category_id INT PRIMARY KEY,
+    description TEXT NOT NULL CHECK (description != '')
+);
+
+INSERT INTO
+    ais_rejection_categories (ais_rejection_category_id, description)
+VALUES
+    (1, 'MaxLineLengthExceeded'),
+    (2, 'InvalidJson'),
+    (3, 'InvalidNmea'),
+    (4, 'InvalidStatic');
+
+CREATE TABLE ais_rejected_messages (
+    ais_rejected_message_id BIGSERIAL PRIMARY KEY,
+    ais_rejection_category_id INT NOT NULL REFERENCES ais_rejection_categories (ais_rejection_category_id),
+    message TEXT NOT NULL,
+    error TEXT NOT NULL,
+    received_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX ON ais_rejected_messages (ais_rejection_category_id, received_at);
+
+CREATE TABLE ais_rejection_counters (
+    ais_rejection_category_id INT PRIMARY KEY REFERENCES ais_rejection_categories (ais_rejection_category_id),
+    num_rejected BIGINT NOT NULL CHECK (num_rejected >= 0),
+    last_rejected_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,78 @@
+CREATE TABLE geofence_area_types (
+    geofence_area_type_id INT PRIMARY KEY,
+    description TEXT NOT NULL CHECK (description != '')
+);
+
+INSERT INTO
+    geofence_area_types (geofence_area_type_id, description)
+VALUES
+    (1, 'Polygon'),
+    (2, 'CatchLocation'),
+    (3, 'FishingFacility');
+
+CREATE TABLE geofence_event_types (
+    geofence_event_type_id INT PRIMARY KEY,
+    description TEXT NOT NULL CHECK (description != '')
+);
+
+INSERT INTO
+    geofence_event_types (geofence_event_type_id, description)
+VALUES
+    (1, 'Enter'),
+    (2, 'Exit');
+
+CREATE TABLE geofences (
+    geofence_id BIGSERIAL PRIMARY KEY,
+    barentswatch_user_id UUID NOT NULL,
+    "name" TEXT NOT NULL CHECK ("name" != ''),
+    geofence_area_type_id INT NOT NULL REFERENCES geofence_area_types (geofence_area_type_id),
+    catch_location_id TEXT REFERENCES catch_locations (catch_location_id) ON DELETE CASCADE,
+    fishing_facility_tool_id UUID REFERENCES fishing_facilities (tool_id) ON DELETE CASCADE,
+    "polygon" GEOMETRY NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (
+        (
+            geofence_area_type_id = 1
+            AND catch_location_id IS NULL
+            AND fishing_facility_tool_id IS NULL
+        )
+        OR (
+            geofence_area_type_id = 2
+            AND catch_location_id IS NOT NULL
+            AND fishing_facility_tool_id IS NULL
+        )
+        OR (
+            geofence_area_type_id = 3
+            AND catch_location_id IS NULL
+            AND fishing_facility_tool_id IS NOT NULL
+        )
+    )
+);
+
+CREATE INDEX ON geofences (barentswatch_user_id);
+
+CREATE TABLE geofence_vessels (
+    geofence_id BIGINT NOT NULL REFERENCES geofences (geofence_id) ON DELETE CASCADE,
+    fiskeridir_vessel_id BIGINT NOT NULL REFERENCES fiskeridir_vessels (fiskeridir_vessel_id) ON DELETE CASCADE,
+    inside BOOLEAN NOT NULL,
+    processed_until TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (geofence_id, fiskeridir_vessel_id)
+);
+
+CREATE TABLE geofence_events (
+    geofence_event_id BIGSERIAL PRIMARY KEY,
+    geofence_id BIGINT NOT NULL REFERENCES geofences (geofence_id) ON DELETE CASCADE,
+    fiskeridir_vessel_id BIGINT NOT NULL REFERENCES fiskeridir_vessels (fiskeridir_vessel_id) ON DELETE CASCADE,
+    geofence_event_type_id INT NOT NULL REFERENCES geofence_event_types (geofence_event_type_id),
+    "timestamp" TIMESTAMPTZ NOT NULL,
+    latitude DOUBLE PRECISION NOT NULL,
+    longitude DOUBLE PRECISION NOT NULL,
+    UNIQUE (
+        geofence_id,
+        fiskeridir_vessel_id,
+        "timestamp",
+        geofence_event_type_id
+    )
+);
+
+CREATE INDEX ON geofence_events (fiskeridir_vessel_id, "timestamp");
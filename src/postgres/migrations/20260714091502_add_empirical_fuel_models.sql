@@ -0,0 +1,8 @@
+CREATE TABLE empirical_fuel_models (
+    fiskeridir_vessel_id BIGINT PRIMARY KEY REFERENCES fiskeridir_vessels (fiskeridir_vessel_id) ON DELETE CASCADE,
+    idle_liter_per_hour DOUBLE PRECISION NOT NULL CHECK (idle_liter_per_hour >= 0.0),
+    load_liter_per_hour DOUBLE PRECISION NOT NULL CHECK (load_liter_per_hour >= 0.0),
+    haul_liter_per_hour DOUBLE PRECISION NOT NULL CHECK (haul_liter_per_hour >= 0.0),
+    num_measurements INT NOT NULL CHECK (num_measurements > 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,21 @@
+CREATE TABLE fuel_estimation_models (
+    fuel_estimation_model_id INT PRIMARY KEY,
+    description TEXT NOT NULL CHECK (description != '')
+);
+
+INSERT INTO
+    fuel_estimation_models (fuel_estimation_model_id, description)
+VALUES
+    (1, 'Maru'),
+    (2, 'Holtrop'),
+    (3, 'Empirical');
+
+CREATE TABLE fuel_backtest_results (
+    fiskeridir_vessel_id BIGINT NOT NULL REFERENCES fiskeridir_vessels (fiskeridir_vessel_id) ON DELETE CASCADE,
+    fuel_range TSTZRANGE NOT NULL,
+    fuel_estimation_model_id INT NOT NULL REFERENCES fuel_estimation_models (fuel_estimation_model_id),
+    measured_liter DOUBLE PRECISION NOT NULL CHECK (measured_liter > 0),
+    estimated_liter DOUBLE PRECISION NOT NULL CHECK (estimated_liter >= 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (fiskeridir_vessel_id, fuel_range, fuel_estimation_model_id)
+);
@@ -0,0 +1,91 @@
+INSERT INTO
+    trip_assemblers (trip_assembler_id, "name")
+VALUES
+    (3, 'ais_vms_port_calls');
+
+INSERT INTO
+    vessel_event_types (vessel_event_type_id, description)
+VALUES
+    (7, 'ais_vms_departure'),
+    (8, 'ais_vms_arrival');
+
+CREATE TABLE ais_vms_port_calls (
+    vessel_event_id BIGINT UNIQUE,
+    vessel_event_type_id INT NOT NULL CHECK (vessel_event_type_id IN (7, 8)) REFERENCES vessel_event_types (vessel_event_type_id),
+    fiskeridir_vessel_id BIGINT NOT NULL REFERENCES fiskeridir_vessels (fiskeridir_vessel_id),
+    "timestamp" TIMESTAMPTZ NOT NULL,
+    port_id VARCHAR REFERENCES ports (port_id),
+    PRIMARY KEY (fiskeridir_vessel_id, "timestamp")
+);
+
+CREATE TABLE ais_vms_port_call_timers (
+    fiskeridir_vessel_id BIGINT PRIMARY KEY REFERENCES fiskeridir_vessels (fiskeridir_vessel_id),
+    timer TIMESTAMPTZ NOT NULL,
+    -- Set if the vessel was in port at `timer`
+    port_id VARCHAR REFERENCES ports (port_id)
+);
+
+CREATE
+OR REPLACE FUNCTION add_ais_vms_port_call_vessel_event () RETURNS TRIGGER LANGUAGE plpgsql AS $$
+    BEGIN
+        IF (TG_OP = 'INSERT') THEN
+            NEW.vessel_event_id = add_vessel_event(NEW.vessel_event_type_id, NEW.fiskeridir_vessel_id, NEW."timestamp", NEW."timestamp");
+        END IF;
+        RETURN NEW;
+   END;
+$$;
+
+CREATE TRIGGER ais_vms_port_calls_before_insert_add_vessel_event BEFORE INSERT ON ais_vms_port_calls FOR EACH ROW
+EXECUTE FUNCTION add_ais_vms_port_call_vessel_event ();
+
+CREATE TRIGGER ais_vms_port_calls_after_delete_remove_event
+AFTER DELETE ON ais_vms_port_calls FOR EACH ROW
+EXECUTE FUNCTION delete_vessel_event ();
+
+ALTER TABLE trips
+DROP CONSTRAINT check_start_vessel_event_type_is_correct,
+DROP CONSTRAINT check_end_vessel_event_type_is_correct;
+
+ALTER TABLE trips
+ADD CONSTRAINT check_start_vessel_event_type_is_correct CHECK (
+    (
+        -- Landings assembler
+        trip_assembler_id = 1
+        -- Landing type event id
+        AND start_vessel_event_type = 1
+    )
+    OR (
+        -- Ers assembler
+        trip_assembler_id = 2
+        -- Departure type event id
+        AND start_vessel_event_type = 4
+    )
+    OR (
+        -- Ais/Vms assembler
+        trip_assembler_id = 3
+        -- Ais/Vms departure type event id
+        AND start_vessel_event_type = 7
+    )
+);
+
+ALTER TABLE trips
+ADD CONSTRAINT check_end_vessel_event_type_is_correct CHECK (
+    (
+        -- Landings assembler
+        trip_assembler_id = 1
+        -- Landing type event id
+        AND end_vessel_event_type = 1
+    )
+    OR (
+        -- Ers assembler
+        trip_assembler_id = 2
+        -- Por type event id
+        AND end_vessel_event_type = 3
+    )
+    OR (
+        -- Ais/Vms assembler
+        trip_assembler_id = 3
+        -- Ais/Vms arrival type event id
+        AND end_vessel_event_type = 8
+    )
+);
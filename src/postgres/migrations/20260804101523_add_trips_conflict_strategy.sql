@@ -0,0 +1,4 @@
+-- The conflict strategy used by the trip assembler when the trip was created, NULL for trips
+-- created prior to this migration.
+ALTER TABLE trips
+ADD COLUMN conflict_strategy VARCHAR;
@@ -0,0 +1,22 @@
+CREATE TABLE rafisklaget_price_forecasts (
+    "year" INT NOT NULL,
+    week INT NOT NULL,
+    vessel_length_group INT NOT NULL REFERENCES fiskeridir_length_groups (fiskeridir_length_group_id),
+    gear_group INT NOT NULL REFERENCES gear_groups (gear_group_id),
+    species INT NOT NULL REFERENCES species_fiskeridir (species_fiskeridir_id),
+    condition INT NOT NULL REFERENCES product_conditions (product_condition_id),
+    quality INT NOT NULL REFERENCES product_qualities (product_quality_id),
+    price DOUBLE PRECISION NOT NULL CHECK (price >= 0),
+    lower_bound DOUBLE PRECISION NOT NULL CHECK (lower_bound >= 0),
+    upper_bound DOUBLE PRECISION NOT NULL CHECK (upper_bound >= lower_bound),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (
+        "year",
+        week,
+        vessel_length_group,
+        gear_group,
+        species,
+        condition,
+        quality
+    )
+);
@@ -0,0 +1,94 @@
+CREATE INDEX ON rafisklaget_weekly_sales (
+    species,
+    gear_group,
+    vessel_length_group,
+    "year",
+    week
+);
+
+-- Returns the average price per kilo living weight across all conditions and qualities of the
+-- most recent week with sales at or before the given timestamp.
+CREATE
+OR REPLACE FUNCTION weekly_sale_unit_price (
+    species_id INT,
+    gear_group_id INT,
+    length_group_id INT,
+    ts TIMESTAMPTZ
+) RETURNS DOUBLE PRECISION AS $$
+    SELECT
+        SUM(w.sum_price) / SUM(w.sum_calculated_living_weight)
+    FROM
+        rafisklaget_weekly_sales w
+    WHERE
+        w.species = species_id
+        AND w.gear_group = gear_group_id
+        AND w.vessel_length_group = length_group_id
+        AND w.sum_calculated_living_weight > 0
+        AND (w."year", w.week) = (
+            SELECT
+                l."year",
+                l.week
+            FROM
+                rafisklaget_weekly_sales l
+            WHERE
+                l.species = species_id
+                AND l.gear_group = gear_group_id
+                AND l.vessel_length_group = length_group_id
+                AND l.sum_calculated_living_weight > 0
+                AND (l."year", l.week) <= (
+                    DATE_PART('isoyear', ts AT TIME ZONE 'UTC')::INT,
+                    DATE_PART('week', ts AT TIME ZONE 'UTC')::INT
+                )
+            ORDER BY
+                l."year" DESC,
+                l.week DESC
+            LIMIT
+                1
+        );
+$$ LANGUAGE sql STABLE;
+
+-- Returns the estimated value of the given haul catches, catches without a matching weekly sale
+-- price are not included. Returns NULL if none of the catches have a matching price.
+CREATE
+OR REPLACE FUNCTION estimated_catch_value (
+    catches JSONB,
+    gear_group_id INT,
+    length_group_id INT,
+    ts TIMESTAMPTZ
+) RETURNS DOUBLE PRECISION AS $$
+    SELECT
+        SUM(
+            (c ->> 'living_weight')::DOUBLE PRECISION * weekly_sale_unit_price(
+                (c ->> 'species_fiskeridir_id')::INT,
+                gear_group_id,
+                length_group_id,
+                ts
+            )
+        )
+    FROM
+        JSONB_ARRAY_ELEMENTS(COALESCE(catches, '[]')) c;
+$$ LANGUAGE sql STABLE;
+
+-- Adds an `estimated_value` field to each haul of the given haul array.
+CREATE
+OR REPLACE FUNCTION hauls_with_estimated_value (hauls JSONB) RETURNS JSONB AS $$
+    SELECT
+        COALESCE(
+            JSONB_AGG(
+                h || JSONB_BUILD_OBJECT(
+                    'estimated_value',
+                    estimated_catch_value(
+                        h -> 'catches',
+                        (h ->> 'gear_group_id')::INT,
+                        (h ->> 'vessel_length_group')::INT,
+                        (h ->> 'stop_timestamp')::TIMESTAMPTZ
+                    )
+                )
+                ORDER BY
+                    i
+            ),
+            '[]'
+        )
+    FROM
+        JSONB_ARRAY_ELEMENTS(hauls) WITH ORDINALITY AS e (h, i);
+$$ LANGUAGE sql STABLE;
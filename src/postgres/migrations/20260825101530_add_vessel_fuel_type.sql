@@ -0,0 +1,15 @@
+CREATE TABLE fuel_types (
+    fuel_type_id INT PRIMARY KEY,
+    description TEXT NOT NULL CHECK (description != '')
+);
+
+INSERT INTO
+    fuel_types (fuel_type_id, description)
+VALUES
+    (1, 'Diesel'),
+    (2, 'LightFuelOil'),
+    (3, 'HeavyFuelOil'),
+    (4, 'Lng');
+
+ALTER TABLE fiskeridir_vessels
+ADD COLUMN fuel_type_id INT REFERENCES fuel_types (fuel_type_id);
@@ -0,0 +1,94 @@
+CREATE TABLE
+    hauls_matrix_changes (
+        haul_id BIGINT PRIMARY KEY,
+        "version" INT NOT NULL
+    );
+
+CREATE TABLE
+    landing_matrix_changes (
+        landing_id VARCHAR PRIMARY KEY,
+        "version" INT NOT NULL
+    );
+
+CREATE INDEX ON hauls_matrix_changes ("version");
+
+CREATE INDEX ON landing_matrix_changes ("version");
+
+-- Changes older than this version have been pruned, caches that are behind this version have to
+-- do a full refresh. Changes prior to this migration were never tracked.
+ALTER TABLE duckdb_data_version
+ADD COLUMN min_change_version INT NOT NULL DEFAULT 0;
+
+UPDATE duckdb_data_version
+SET
+    min_change_version = "version" + 1;
+
+CREATE
+OR REPLACE FUNCTION track_hauls_matrix_changes () RETURNS TRIGGER LANGUAGE PLPGSQL AS $$
+    DECLARE _haul_id BIGINT;
+    BEGIN
+        IF (TG_OP = 'DELETE') THEN
+            _haul_id = OLD.haul_id;
+        ELSE
+            _haul_id = NEW.haul_id;
+        END IF;
+
+        INSERT INTO
+            hauls_matrix_changes (haul_id, "version")
+        SELECT
+            _haul_id,
+            v."version"
+        FROM
+            duckdb_data_version v
+        WHERE
+            v.duckdb_data_version_id = 'hauls'
+        ON CONFLICT (haul_id) DO
+        UPDATE
+        SET
+            "version" = EXCLUDED."version";
+
+        RETURN NULL;
+   END;
+$$;
+
+CREATE
+OR REPLACE FUNCTION track_landing_matrix_changes () RETURNS TRIGGER LANGUAGE PLPGSQL AS $$
+    DECLARE _landing_id VARCHAR;
+    BEGIN
+        IF (TG_OP = 'DELETE') THEN
+            _landing_id = OLD.landing_id;
+        ELSE
+            _landing_id = NEW.landing_id;
+        END IF;
+
+        INSERT INTO
+            landing_matrix_changes (landing_id, "version")
+        SELECT
+            _landing_id,
+            v."version"
+        FROM
+            duckdb_data_version v
+        WHERE
+            v.duckdb_data_version_id = 'landings'
+        ON CONFLICT (landing_id) DO
+        UPDATE
+        SET
+            "version" = EXCLUDED."version";
+
+        RETURN NULL;
+   END;
+$$;
+
+CREATE TRIGGER hauls_matrix_after_change
+AFTER INSERT
+OR
+UPDATE
+OR DELETE ON hauls_matrix FOR EACH ROW
+EXECUTE FUNCTION track_hauls_matrix_changes ();
+
+CREATE TRIGGER landing_matrix_after_change
+AFTER INSERT
+OR
+UPDATE
+OR DELETE ON landing_matrix FOR EACH ROW
+EXECUTE FUNCTION track_landing_matrix_changes ();
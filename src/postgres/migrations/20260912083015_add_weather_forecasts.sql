@@ -0,0 +1,17 @@
+CREATE TABLE weather_forecasts (
+    reference_time TIMESTAMPTZ NOT NULL,
+    "timestamp" TIMESTAMPTZ NOT NULL CHECK ("timestamp" >= reference_time),
+    latitude DOUBLE PRECISION NOT NULL,
+    longitude DOUBLE PRECISION NOT NULL,
+    wind_speed_10m DOUBLE PRECISION,
+    wind_direction_10m DOUBLE PRECISION,
+    air_temperature_2m DOUBLE PRECISION,
+    air_pressure_at_sea_level DOUBLE PRECISION,
+    precipitation_amount DOUBLE PRECISION,
+    weather_location_id INT NOT NULL REFERENCES weather_locations (weather_location_id) GENERATED ALWAYS AS (
+        ((FLOOR(latitude / 0.1) + 1000) * 100000) + (FLOOR(longitude / 0.1) + 1000)
+    ) STORED,
+    PRIMARY KEY (reference_time, "timestamp", weather_location_id)
+);
+
+CREATE INDEX ON weather_forecasts (weather_location_id, reference_time);
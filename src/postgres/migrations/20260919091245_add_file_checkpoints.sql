@@ -0,0 +1,16 @@
+CREATE TABLE file_checkpoints (
+    file_hash_id TEXT PRIMARY KEY,
+    file_hash TEXT NOT NULL,
+    row_offset BIGINT NOT NULL CHECK (row_offset >= 0),
+    chunk_hash TEXT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE file_parse_errors (
+    file_hash_id TEXT NOT NULL,
+    file_hash TEXT NOT NULL,
+    line_number BIGINT NOT NULL,
+    error TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (file_hash_id, file_hash, line_number)
+);
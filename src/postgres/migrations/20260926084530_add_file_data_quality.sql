@@ -0,0 +1,12 @@
+CREATE TABLE file_data_quality (
+    file_hash_id TEXT PRIMARY KEY,
+    file_hash TEXT NOT NULL,
+    dataset TEXT NOT NULL,
+    "year" INT NOT NULL,
+    "rows" BIGINT NOT NULL,
+    rejected_rows BIGINT NOT NULL,
+    coerced_fields BIGINT NOT NULL,
+    defaulted_fields BIGINT NOT NULL,
+    rejected_fields BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,8 @@
+ALTER TABLE fiskeridir_vessels
+ADD COLUMN fuel_estimation_model_id INT REFERENCES fuel_estimation_models (fuel_estimation_model_id);
+
+CREATE TABLE empirical_fuel_model_fits (
+    fiskeridir_vessel_id BIGINT PRIMARY KEY REFERENCES fiskeridir_vessels (fiskeridir_vessel_id) ON DELETE CASCADE,
+    measurements_checksum TEXT NOT NULL,
+    fitted_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
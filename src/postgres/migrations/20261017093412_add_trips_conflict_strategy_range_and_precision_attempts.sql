@@ -0,0 +1,8 @@
+-- The timestamps of the 'Replace' ('conflict_strategy_start') and 'ReplaceRange' conflict
+-- strategies, trips created prior to this migration only have the name of the strategy.
+ALTER TABLE trips
+ADD COLUMN conflict_strategy_start TIMESTAMPTZ,
+ADD COLUMN conflict_strategy_end TIMESTAMPTZ,
+-- The outcome of each precision implementation run on the trip, NULL for trips processed prior
+-- to this migration or not yet processed.
+ADD COLUMN precision_attempts JSONB;
@@ -0,0 +1,78 @@
+-- The data version is read with a share lock so that a change is never tagged with a version that
+-- is concurrently being incremented, which would cause caches refreshing to the incremented
+-- version to miss the change.
+CREATE
+OR REPLACE FUNCTION track_hauls_matrix_changes () RETURNS TRIGGER LANGUAGE PLPGSQL AS $$
+    DECLARE _haul_id BIGINT;
+    DECLARE _version INT;
+    BEGIN
+        IF (TG_OP = 'DELETE') THEN
+            _haul_id = OLD.haul_id;
+        ELSE
+            _haul_id = NEW.haul_id;
+        END IF;
+
+        SELECT
+            v."version"
+        INTO
+            _version
+        FROM
+            duckdb_data_version v
+        WHERE
+            v.duckdb_data_version_id = 'hauls'
+        FOR SHARE;
+
+        IF NOT FOUND THEN
+            RETURN NULL;
+        END IF;
+
+        INSERT INTO
+            hauls_matrix_changes (haul_id, "version")
+        VALUES
+            (_haul_id, _version)
+        ON CONFLICT (haul_id) DO
+        UPDATE
+        SET
+            "version" = EXCLUDED."version";
+
+        RETURN NULL;
+   END;
+$$;
+
+CREATE
+OR REPLACE FUNCTION track_landing_matrix_changes () RETURNS TRIGGER LANGUAGE PLPGSQL AS $$
+    DECLARE _landing_id VARCHAR;
+    DECLARE _version INT;
+    BEGIN
+        IF (TG_OP = 'DELETE') THEN
+            _landing_id = OLD.landing_id;
+        ELSE
+            _landing_id = NEW.landing_id;
+        END IF;
+
+        SELECT
+            v."version"
+        INTO
+            _version
+        FROM
+            duckdb_data_version v
+        WHERE
+            v.duckdb_data_version_id = 'landings'
+        FOR SHARE;
+
+        IF NOT FOUND THEN
+            RETURN NULL;
+        END IF;
+
+        INSERT INTO
+            landing_matrix_changes (landing_id, "version")
+        VALUES
+            (_landing_id, _version)
+        ON CONFLICT (landing_id) DO
+        UPDATE
+        SET
+            "version" = EXCLUDED."version";
+
+        RETURN NULL;
+   END;
+$$;
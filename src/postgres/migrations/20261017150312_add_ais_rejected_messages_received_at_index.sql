@@ -0,0 +1 @@
+CREATE INDEX ON ais_rejected_messages (received_at);
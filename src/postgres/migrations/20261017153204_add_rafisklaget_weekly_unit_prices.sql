@@ -0,0 +1,86 @@
+-- The average price per kilo living weight across all conditions and qualities of each week with
+-- sales, maintained when weekly sales are added.
+CREATE TABLE rafisklaget_weekly_unit_prices (
+    gear_group INT NOT NULL REFERENCES gear_groups (gear_group_id),
+    vessel_length_group INT NOT NULL REFERENCES fiskeridir_length_groups (fiskeridir_length_group_id),
+    species INT NOT NULL REFERENCES species_fiskeridir (species_fiskeridir_id),
+    "year" INT NOT NULL,
+    week INT NOT NULL,
+    unit_price DOUBLE PRECISION NOT NULL,
+    PRIMARY KEY (
+        gear_group,
+        vessel_length_group,
+        species,
+        "year",
+        week
+    )
+);
+
+INSERT INTO
+    rafisklaget_weekly_unit_prices (
+        gear_group,
+        vessel_length_group,
+        species,
+        "year",
+        week,
+        unit_price
+    )
+SELECT
+    gear_group,
+    vessel_length_group,
+    species,
+    "year",
+    week,
+    SUM(sum_price) / SUM(sum_calculated_living_weight)
+FROM
+    rafisklaget_weekly_sales
+WHERE
+    sum_calculated_living_weight > 0
+GROUP BY
+    gear_group,
+    vessel_length_group,
+    species,
+    "year",
+    week;
+
+-- Returns the estimated value of the given haul catches from the most recent weekly price at or
+-- before the given timestamp, prices older than 8 weeks are not used. Catches without a matching
+-- price are not included. Returns NULL if none of the catches have a matching price.
+CREATE
+OR REPLACE FUNCTION estimated_catch_value (
+    catches JSONB,
+    gear_group_id INT,
+    length_group_id INT,
+    ts TIMESTAMPTZ
+) RETURNS DOUBLE PRECISION AS $$
+    WITH
+        prices AS (
+            SELECT DISTINCT ON (p.species)
+                p.species,
+                p.unit_price
+            FROM
+                rafisklaget_weekly_unit_prices p
+            WHERE
+                p.gear_group = gear_group_id
+                AND p.vessel_length_group = length_group_id
+                AND (p."year", p.week) <= (
+                    DATE_PART('isoyear', ts AT TIME ZONE 'UTC')::INT,
+                    DATE_PART('week', ts AT TIME ZONE 'UTC')::INT
+                )
+                AND (p."year", p.week) >= (
+                    DATE_PART('isoyear', (ts - INTERVAL '8 weeks') AT TIME ZONE 'UTC')::INT,
+                    DATE_PART('week', (ts - INTERVAL '8 weeks') AT TIME ZONE 'UTC')::INT
+                )
+            ORDER BY
+                p.species,
+                p."year" DESC,
+                p.week DESC
+        )
+    SELECT
+        SUM((c ->> 'living_weight')::DOUBLE PRECISION * p.unit_price)
+    FROM
+        JSONB_ARRAY_ELEMENTS(COALESCE(catches, '[]')) c
+        INNER JOIN prices p ON p.species = (c ->> 'species_fiskeridir_id')::INT;
+$$ LANGUAGE sql STABLE;
+
+DROP FUNCTION weekly_sale_unit_price;
@@ -0,0 +1,199 @@
+-- The matrix tables contain one row per haul or landing and catch, tracking changes per statement
+-- reads and locks the data version once per statement instead of once per changed row.
+CREATE
+OR REPLACE FUNCTION track_hauls_matrix_changes () RETURNS TRIGGER LANGUAGE PLPGSQL AS $$
+    DECLARE _version INT;
+    BEGIN
+        SELECT
+            v."version"
+        INTO
+            _version
+        FROM
+            duckdb_data_version v
+        WHERE
+            v.duckdb_data_version_id = 'hauls'
+        FOR SHARE;
+
+        IF NOT FOUND THEN
+            RETURN NULL;
+        END IF;
+
+        IF (TG_OP = 'DELETE') THEN
+            INSERT INTO
+                hauls_matrix_changes (haul_id, "version")
+            SELECT
+                c.haul_id,
+                _version
+            FROM
+                (
+                    SELECT
+                        haul_id
+                    FROM
+                        old_rows
+                    GROUP BY
+                        haul_id
+                ) c
+            ON CONFLICT (haul_id) DO
+            UPDATE
+            SET
+                "version" = EXCLUDED."version";
+        ELSIF (TG_OP = 'UPDATE') THEN
+            INSERT INTO
+                hauls_matrix_changes (haul_id, "version")
+            SELECT
+                c.haul_id,
+                _version
+            FROM
+                (
+                    SELECT
+                        haul_id
+                    FROM
+                        old_rows
+                    UNION
+                    SELECT
+                        haul_id
+                    FROM
+                        new_rows
+                ) c
+            ON CONFLICT (haul_id) DO
+            UPDATE
+            SET
+                "version" = EXCLUDED."version";
+        ELSE
+            INSERT INTO
+                hauls_matrix_changes (haul_id, "version")
+            SELECT
+                c.haul_id,
+                _version
+            FROM
+                (
+                    SELECT
+                        haul_id
+                    FROM
+                        new_rows
+                    GROUP BY
+                        haul_id
+                ) c
+            ON CONFLICT (haul_id) DO
+            UPDATE
+            SET
+                "version" = EXCLUDED."version";
+        END IF;
+
+        RETURN NULL;
+   END;
+$$;
+
+CREATE
+OR REPLACE FUNCTION track_landing_matrix_changes () RETURNS TRIGGER LANGUAGE PLPGSQL AS $$
+    DECLARE _version INT;
+    BEGIN
+        SELECT
+            v."version"
+        INTO
+            _version
+        FROM
+            duckdb_data_version v
+        WHERE
+            v.duckdb_data_version_id = 'landings'
+        FOR SHARE;
+
+        IF NOT FOUND THEN
+            RETURN NULL;
+        END IF;
+
+        IF (TG_OP = 'DELETE') THEN
+            INSERT INTO
+                landing_matrix_changes (landing_id, "version")
+            SELECT
+                c.landing_id,
+                _version
+            FROM
+                (
+                    SELECT
+                        landing_id
+                    FROM
+                        old_rows
+                    GROUP BY
+                        landing_id
+                ) c
+            ON CONFLICT (landing_id) DO
+            UPDATE
+            SET
+                "version" = EXCLUDED."version";
+        ELSIF (TG_OP = 'UPDATE') THEN
+            INSERT INTO
+                landing_matrix_changes (landing_id, "version")
+            SELECT
+                c.landing_id,
+                _version
+            FROM
+                (
+                    SELECT
+                        landing_id
+                    FROM
+                        old_rows
+                    UNION
+                    SELECT
+                        landing_id
+                    FROM
+                        new_rows
+                ) c
+            ON CONFLICT (landing_id) DO
+            UPDATE
+            SET
+                "version" = EXCLUDED."version";
+        ELSE
+            INSERT INTO
+                landing_matrix_changes (landing_id, "version")
+            SELECT
+                c.landing_id,
+                _version
+            FROM
+                (
+                    SELECT
+                        landing_id
+                    FROM
+                        new_rows
+                    GROUP BY
+                        landing_id
+                ) c
+            ON CONFLICT (landing_id) DO
+            UPDATE
+            SET
+                "version" = EXCLUDED."version";
+        END IF;
+
+        RETURN NULL;
+   END;
+$$;
+
+DROP TRIGGER hauls_matrix_after_change ON hauls_matrix;
+
+CREATE TRIGGER hauls_matrix_after_insert
+AFTER INSERT ON hauls_matrix REFERENCING NEW TABLE AS new_rows FOR EACH STATEMENT
+EXECUTE FUNCTION track_hauls_matrix_changes ();
+
+CREATE TRIGGER hauls_matrix_after_update
+AFTER
+UPDATE ON hauls_matrix REFERENCING OLD TABLE AS old_rows NEW TABLE AS new_rows FOR EACH STATEMENT
+EXECUTE FUNCTION track_hauls_matrix_changes ();
+
+CREATE TRIGGER hauls_matrix_after_delete
+AFTER DELETE ON hauls_matrix REFERENCING OLD TABLE AS old_rows FOR EACH STATEMENT
+EXECUTE FUNCTION track_hauls_matrix_changes ();
+
+DROP TRIGGER landing_matrix_after_change ON landing_matrix;
+
+CREATE TRIGGER landing_matrix_after_insert
+AFTER INSERT ON landing_matrix REFERENCING NEW TABLE AS new_rows FOR EACH STATEMENT
+EXECUTE FUNCTION track_landing_matrix_changes ();
+
+CREATE TRIGGER landing_matrix_after_update
+AFTER
+UPDATE ON landing_matrix REFERENCING OLD TABLE AS old_rows NEW TABLE AS new_rows FOR EACH STATEMENT
+EXECUTE FUNCTION track_landing_matrix_changes ();
+
+CREATE TRIGGER landing_matrix_after_delete
+AFTER DELETE ON landing_matrix REFERENCING OLD TABLE AS old_rows FOR EACH STATEMENT
+EXECUTE FUNCTION track_landing_matrix_changes ();
@@ -293,6 +293,14 @@ impl PostgresAdapter {
     ) -> AisProcessingAction {
         match incoming {
             Ok(message) => {
+                // Rejected messages are only kept for auditing purposes and are not retried
+                if let Err(e) = self
+                    .add_ais_rejected_messages(&message.rejected_messages)
+                    .await
+                {
+                    warn!("failed to add rejected ais messages, err: {e:?}");
+                }
+
                 match (
                     self.add_ais_positions(&message.positions).await,
                     self.add_ais_vessels(&message.static_messages).await,
@@ -350,6 +358,10 @@ impl TestHelperOutbound for PostgresAdapter {
         self.all_fuel_estimates_impl().await.unwrap()
     }
 
+    async fn weather_forecast_reference_times(&self) -> Vec<DateTime<Utc>> {
+        self.weather_forecast_reference_times_impl().await.unwrap()
+    }
+
     async fn sum_fuel_estimates(
         &self,
         start: NaiveDate,
@@ -596,6 +608,53 @@ impl FuelEstimation for PostgresAdapter {
     async fn vessel_max_cargo_weight(&self, vessel_id: FiskeridirVesselId) -> CoreResult<f64> {
         Ok(retry(|| self.vessel_max_cargo_weight_impl(vessel_id)).await?)
     }
+
+    async fn fuel_measurement_ranges(
+        &self,
+        vessel_id: FiskeridirVesselId,
+    ) -> CoreResult<Vec<FuelMeasurementRange>> {
+        Ok(retry(|| self.fuel_measurement_ranges_impl(vessel_id)).await?)
+    }
+
+    async fn empirical_fuel_model(
+        &self,
+        vessel_id: FiskeridirVesselId,
+    ) -> CoreResult<Option<EmpiricalFuelModel>> {
+        Ok(retry(|| self.empirical_fuel_model_impl(vessel_id)).await?)
+    }
+
+    async fn fuel_estimation_weather(
+        &self,
+        range: &DateRange,
+        weather_location_ids: &[WeatherLocationId],
+    ) -> CoreResult<Vec<FuelWeather>> {
+        Ok(retry(|| self.fuel_estimation_weather_impl(range, weather_location_ids)).await?)
+    }
+
+    async fn changed_fuel_measurements_checksum(
+        &self,
+        vessel_id: FiskeridirVesselId,
+    ) -> CoreResult<Option<String>> {
+        Ok(retry(|| self.changed_fuel_measurements_checksum_impl(vessel_id)).await?)
+    }
+
+    async fn set_empirical_fuel_model(
+        &self,
+        vessel_id: FiskeridirVesselId,
+        model: Option<EmpiricalFuelModel>,
+        measurements_checksum: &str,
+        reset_estimates: bool,
+    ) -> CoreResult<()> {
+        Ok(retry(|| {
+            self.set_empirical_fuel_model_impl(
+                vessel_id,
+                model,
+                measurements_checksum,
+                reset_estimates,
+            )
+        })
+        .await?)
+    }
 }
 
 #[async_trait]
@@ -627,6 +686,79 @@ impl CurrentPositionOutbound for PostgresAdapter {
     }
 }
 
+#[async_trait]
+impl FuelBacktestOutbound for PostgresAdapter {
+    async fn vessels_with_fuel_measurements(&self) -> CoreResult<Vec<Vessel>> {
+        Ok(retry(|| async {
+            let out: CoreResult<Vec<Vessel>> = self
+                .vessels_with_fuel_measurements_impl()
+                .try_convert_collect()
+                .await;
+            out
+        })
+        .await?)
+    }
+}
+
+#[async_trait]
+impl FuelBacktestInbound for PostgresAdapter {
+    async fn set_fuel_backtest_results(
+        &self,
+        vessel_id: FiskeridirVesselId,
+        results: &[NewFuelBacktestResult],
+    ) -> CoreResult<()> {
+        Ok(retry(|| self.set_fuel_backtest_results_impl(vessel_id, results)).await?)
+    }
+}
+
+#[async_trait]
+impl PriceForecastOutbound for PostgresAdapter {
+    async fn weekly_prices(&self) -> CoreResult<Vec<WeeklyPrice>> {
+        Ok(retry(|| async {
+            let out: CoreResult<Vec<WeeklyPrice>> =
+                self.weekly_prices_impl().try_convert_collect().await;
+            out
+        })
+        .await?)
+    }
+}
+
+#[async_trait]
+impl PriceForecastInbound for PostgresAdapter {
+    async fn set_price_forecasts(&self, forecasts: &[NewPriceForecast]) -> CoreResult<()> {
+        Ok(retry(|| self.set_price_forecasts_impl(forecasts)).await?)
+    }
+}
+
+#[async_trait]
+impl GeofenceInbound for PostgresAdapter {
+    async fn update_geofence_vessels(&self, updates: Vec<GeofenceVesselUpdate>) -> CoreResult<()> {
+        Ok(retry(|| self.update_geofence_vessels_impl(&updates)).await?)
+    }
+}
+
+#[async_trait]
+impl GeofenceOutbound for PostgresAdapter {
+    async fn all_geofences(&self) -> CoreResult<Vec<Geofence>> {
+        Ok(retry(|| self.all_geofences_impl()).await?)
+    }
+    async fn geofence_vessels(&self) -> CoreResult<Vec<GeofenceVessel>> {
+        Ok(retry(|| self.geofence_vessels_impl()).await?)
+    }
+    async fn ais_vms_positions(
+        &self,
+        mmsi: Option<Mmsi>,
+        call_sign: Option<&CallSign>,
+        range: &DateRange,
+    ) -> CoreResult<Vec<AisVmsPosition>> {
+        Ok(retry(|| {
+            self.ais_vms_positions_impl(mmsi, call_sign, range, AisPermission::All)
+                .try_collect()
+        })
+        .await?)
+    }
+}
+
 #[async_trait]
 impl LiveFuelInbound for PostgresAdapter {
     async fn delete_old_live_fuel(
@@ -656,6 +788,12 @@ impl LiveFuelInbound for PostgresAdapter {
             .ais_positions_with_inside_user_haul_impl(vessel_id, mmsi, range)
             .await?)
     }
+    async fn empirical_fuel_model(
+        &self,
+        vessel_id: FiskeridirVesselId,
+    ) -> CoreResult<Option<EmpiricalFuelModel>> {
+        Ok(retry(|| self.empirical_fuel_model_impl(vessel_id)).await?)
+    }
 }
 
 #[async_trait]
@@ -663,6 +801,12 @@ impl WebApiOutboundPort for PostgresAdapter {
     async fn price(&self, query: PriceQuery) -> WebApiResult<Option<f64>> {
         Ok(retry(|| self.price_impl(&query)).await?)
     }
+    async fn price_series(&self, query: PriceSeriesQuery) -> WebApiResult<PriceSeries> {
+        Ok(retry(|| self.price_series_impl(&query)).await?)
+    }
+    async fn trip_explanation(&self, trip_id: TripId) -> WebApiResult<Option<TripExplanation>> {
+        Ok(retry(|| self.trip_explanation_impl(trip_id)).await?)
+    }
     async fn current_user_haul(
         &self,
         call_sign: &CallSign,
@@ -718,6 +862,21 @@ impl WebApiOutboundPort for PostgresAdapter {
     ) -> WebApiResult<Option<Vec<FuelEntry>>> {
         Ok(retry(|| self.fuel_estimation_by_org_impl(query, org_id)).await?)
     }
+    async fn emission_report(&self, query: &EmissionReportQuery) -> WebApiResult<EmissionReport> {
+        Ok(EmissionReport::new(
+            query.year,
+            retry(|| self.emission_report_impl(query)).await?,
+        ))
+    }
+    async fn emission_report_by_org(
+        &self,
+        query: &EmissionReportQuery,
+        org_id: OrgId,
+    ) -> WebApiResult<Option<EmissionReport>> {
+        Ok(retry(|| self.emission_report_by_org_impl(query, org_id))
+            .await?
+            .map(|v| EmissionReport::new(query.year, v)))
+    }
     async fn update_vessel(
         &self,
         call_sign: &CallSign,
@@ -828,6 +987,11 @@ impl WebApiOutboundPort for PostgresAdapter {
     async fn eeoi(&self, query: EeoiQuery) -> WebApiResult<Option<f64>> {
         Ok(retry(|| self.eeoi_impl(&query)).await?)
     }
+    async fn peer_benchmarks(&self, query: PeerBenchmarksQuery) -> WebApiResult<PeerBenchmarks> {
+        Ok(PeerBenchmarks::new(
+            retry(|| self.peer_benchmarks_impl(&query)).await?,
+        ))
+    }
     fn detailed_trips(
         &self,
         query: TripsQuery,
@@ -886,15 +1050,52 @@ impl WebApiOutboundPort for PostgresAdapter {
         self.weather_impl(query).map_err(|e| e.into()).boxed()
     }
 
+    fn weather_forecasts(&self, query: WeatherForecastQuery) -> PinBoxStream<'_, WeatherForecast> {
+        self.weather_forecasts_impl(query)
+            .map_err(|e| e.into())
+            .boxed()
+    }
+
     fn weather_locations(&self) -> PinBoxStream<'_, WeatherLocation> {
         self.weather_locations_impl().try_convert().boxed()
     }
 
+    fn ocean_climate(
+        &self,
+        query: CatchLocationOceanClimateQuery,
+    ) -> PinBoxStream<'_, CatchLocationOceanClimate> {
+        self.catch_location_ocean_climate_impl(query)
+            .map_err(|e| e.into())
+            .boxed()
+    }
+
     fn fuel_measurements(&self, query: FuelMeasurementsQuery) -> PinBoxStream<'_, FuelMeasurement> {
         self.fuel_measurements_impl(query)
             .map_err(|e| e.into())
             .boxed()
     }
+
+    fn fuel_backtest(&self, query: FuelBacktestQuery) -> PinBoxStream<'_, FuelBacktestMetrics> {
+        self.fuel_backtest_impl(query).map_err(|e| e.into()).boxed()
+    }
+
+    fn data_quality(&self, query: DataQualityQuery) -> PinBoxStream<'_, FileDataQuality> {
+        self.data_quality_impl(query).map_err(|e| e.into()).boxed()
+    }
+
+    fn geofences(&self, user_id: BarentswatchUserId) -> PinBoxStream<'_, Geofence> {
+        self.geofences_impl(user_id).map_err(|e| e.into()).boxed()
+    }
+
+    fn geofence_events(
+        &self,
+        query: GeofenceEventsQuery,
+        user_policy: AisPermission,
+    ) -> PinBoxStream<'_, GeofenceEvent> {
+        self.geofence_events_impl(query, user_policy)
+            .map_err(|e| e.into())
+            .boxed()
+    }
 }
 
 #[async_trait]
@@ -980,6 +1181,21 @@ impl WebApiInboundPort for PostgresAdapter {
         retry(|| self.delete_fuel_measurements_impl(measurements, call_sign)).await?;
         Ok(())
     }
+    async fn add_geofence(
+        &self,
+        geofence: &NewGeofence,
+        user_id: BarentswatchUserId,
+    ) -> WebApiResult<Geofence> {
+        Ok(retry(|| self.add_geofence_impl(geofence, user_id)).await?)
+    }
+    async fn delete_geofence(
+        &self,
+        id: GeofenceId,
+        user_id: BarentswatchUserId,
+    ) -> WebApiResult<()> {
+        retry(|| self.delete_geofence_impl(id, user_id)).await?;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -1011,6 +1227,22 @@ impl ScraperInboundPort for PostgresAdapter {
         self.add_landings_impl(landings, data_year).await?;
         Ok(())
     }
+    async fn add_landings_chunk(
+        &self,
+        landings: Vec<fiskeridir_rs::Landing>,
+        data_year: u32,
+    ) -> CoreResult<()> {
+        Ok(self.add_landings_chunk_impl(landings, data_year).await?)
+    }
+    async fn delete_removed_landings(
+        &self,
+        landing_ids: Vec<fiskeridir_rs::LandingId>,
+        data_year: u32,
+    ) -> CoreResult<()> {
+        Ok(self
+            .delete_removed_landings_impl(&landing_ids, data_year)
+            .await?)
+    }
     async fn add_ers_dca(
         &self,
         ers_dca: BoxIterator<fiskeridir_rs::Result<fiskeridir_rs::ErsDca>>,
@@ -1058,6 +1290,10 @@ impl ScraperInboundPort for PostgresAdapter {
         self.add_weather_impl(weather).await?;
         Ok(())
     }
+    async fn add_weather_forecasts(&self, forecasts: Vec<NewWeatherForecast>) -> CoreResult<()> {
+        self.add_weather_forecasts_impl(forecasts).await?;
+        Ok(())
+    }
     async fn add_ocean_climate(&self, ocean_climate: Vec<NewOceanClimate>) -> CoreResult<()> {
         self.add_ocean_climate_impl(ocean_climate).await?;
         Ok(())
@@ -1075,6 +1311,9 @@ impl ScraperOutboundPort for PostgresAdapter {
     async fn latest_weather_timestamp(&self) -> CoreResult<Option<DateTime<Utc>>> {
         Ok(self.latest_weather_timestamp_impl().await?)
     }
+    async fn latest_weather_forecast_reference_time(&self) -> CoreResult<Option<DateTime<Utc>>> {
+        Ok(self.latest_weather_forecast_reference_time_impl().await?)
+    }
     async fn latest_ocean_climate_timestamp(&self) -> CoreResult<Option<DateTime<Utc>>> {
         Ok(self.latest_ocean_climate_timestamp_impl().await?)
     }
@@ -1091,6 +1330,16 @@ impl ScraperFileHashInboundPort for PostgresAdapter {
     async fn add(&self, id: &DataFileId, hash: String) -> CoreResult<()> {
         Ok(self.add_hash(id, hash).await?)
     }
+    async fn set_checkpoint(
+        &self,
+        checkpoint: &FileCheckpoint,
+        parse_errors: Vec<FileParseError>,
+    ) -> CoreResult<()> {
+        Ok(self.set_checkpoint_impl(checkpoint, parse_errors).await?)
+    }
+    async fn set_data_quality(&self, quality: &NewFileDataQuality) -> CoreResult<()> {
+        Ok(self.set_data_quality_impl(quality).await?)
+    }
 }
 
 #[async_trait]
@@ -1098,6 +1347,12 @@ impl ScraperFileHashOutboundPort for PostgresAdapter {
     async fn get_hashes(&self, ids: &[DataFileId]) -> CoreResult<Vec<(DataFileId, String)>> {
         Ok(self.get_hashes_impl(ids).await?)
     }
+    async fn checkpoint(&self, id: &DataFileId) -> CoreResult<Option<FileCheckpoint>> {
+        Ok(self.checkpoint_impl(id).await?)
+    }
+    async fn parse_errors(&self, id: &DataFileId) -> CoreResult<Vec<FileParseError>> {
+        Ok(self.parse_errors_impl(id).await?)
+    }
 }
 
 #[async_trait]
@@ -1110,6 +1365,16 @@ impl TripAssemblerOutboundPort for PostgresAdapter {
         Ok(self.dock_points_impl().await?)
     }
 
+    async fn ais_vms_port_call_timer(
+        &self,
+        vessel_id: FiskeridirVesselId,
+    ) -> CoreResult<Option<AisVmsPortCallTimer>> {
+        Ok(self
+            .ais_vms_port_call_timer_impl(vessel_id)
+            .await?
+            .map(AisVmsPortCallTimer::from))
+    }
+
     async fn all_vessels(&self) -> CoreResult<Vec<Vessel>> {
         self.fiskeridir_ais_vessel_combinations()
             .try_convert_collect()
@@ -1142,9 +1407,24 @@ impl TripAssemblerOutboundPort for PostgresAdapter {
                 .await?
                 .map(TryFrom::try_from)
                 .transpose()?,
+            TripAssemblerId::AisVms => self
+                .trip_prior_to_timestamp_ais_vms(vessel_id, search_timestamp)
+                .await?
+                .map(TryFrom::try_from)
+                .transpose()?,
         };
         Ok(trip)
     }
+    async fn assembled_trips(
+        &self,
+        vessel_id: FiskeridirVesselId,
+        trip_assembler: TripAssemblerId,
+        end_from: Option<DateTime<Utc>>,
+    ) -> CoreResult<Vec<NewTrip>> {
+        Ok(self
+            .assembled_trips_impl(vessel_id, trip_assembler, end_from)
+            .await?)
+    }
     async fn all_vessel_events(
         &self,
         vessel_id: FiskeridirVesselId,
@@ -1161,6 +1441,11 @@ impl TripAssemblerOutboundPort for PostgresAdapter {
                     .try_convert_collect()
                     .await
             }
+            TripAssemblerId::AisVms => {
+                self.all_ais_vms_port_call_events(vessel_id)
+                    .try_convert_collect()
+                    .await
+            }
         }
     }
 }
@@ -1434,6 +1719,17 @@ impl TripPipelineInbound for PostgresAdapter {
         self.nuke_trips_impl(vessel_id).await?;
         Ok(())
     }
+
+    async fn add_ais_vms_port_calls(
+        &self,
+        vessel_id: FiskeridirVesselId,
+        port_calls: Vec<NewAisVmsPortCall>,
+        timer: AisVmsPortCallTimer,
+    ) -> CoreResult<()> {
+        Ok(self
+            .add_ais_vms_port_calls_impl(vessel_id, port_calls, timer)
+            .await?)
+    }
 }
 
 #[async_trait]
@@ -1,5 +1,5 @@
 use chrono::{DateTime, Utc};
-use kyogre_core::{AisClass, Draught, Mmsi, NavigationStatus};
+use kyogre_core::{AisClass, AisRejectionCategory, Draught, Mmsi, NavigationStatus};
 use unnest_insert::UnnestInsert;
 
 use crate::queries::{opt_type_as_static_str, opt_type_to_f64, type_to_i32};
@@ -119,6 +119,27 @@ pub struct NewAisCurrentPosition {
     pub distance_to_shore: f64,
 }
 
+#[derive(Debug, Clone, UnnestInsert)]
+#[unnest_insert(table_name = "ais_rejected_messages")]
+pub struct NewAisRejectedMessage<'a> {
+    #[unnest_insert(sql_type = "INT", type_conversion = "type_to_i32")]
+    pub ais_rejection_category_id: AisRejectionCategory,
+    pub message: &'a str,
+    pub error: &'a str,
+    pub received_at: DateTime<Utc>,
+}
+
+impl<'a> From<&'a kyogre_core::NewAisRejectedMessage> for NewAisRejectedMessage<'a> {
+    fn from(v: &'a kyogre_core::NewAisRejectedMessage) -> Self {
+        Self {
+            ais_rejection_category_id: v.category,
+            message: &v.message,
+            error: &v.error,
+            received_at: v.received_at,
+        }
+    }
+}
+
 impl<'a> From<&'a kyogre_core::NewAisStatic> for NewAisVesselHistoric<'a> {
     fn from(v: &'a kyogre_core::NewAisStatic) -> Self {
         Self {
@@ -0,0 +1,24 @@
+use unnest_insert::UnnestInsert;
+
+#[derive(Debug, Clone, UnnestInsert)]
+#[unnest_insert(
+    table_name = "file_parse_errors",
+    conflict = "file_hash_id,file_hash,line_number"
+)]
+pub struct NewFileParseError<'a> {
+    pub file_hash_id: &'a str,
+    pub file_hash: &'a str,
+    pub line_number: i64,
+    pub error: &'a str,
+}
+
+impl<'a> From<&'a kyogre_core::FileParseError> for NewFileParseError<'a> {
+    fn from(v: &'a kyogre_core::FileParseError) -> Self {
+        Self {
+            file_hash_id: v.file_id.as_ref(),
+            file_hash: &v.file_hash,
+            line_number: v.line as i64,
+            error: &v.error,
+        }
+    }
+}
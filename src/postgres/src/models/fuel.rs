@@ -1,8 +1,9 @@
 use chrono::{DateTime, Utc};
-use kyogre_core::{FiskeridirVesselId, live_fuel_year_day_hour};
+use kyogre_core::{FiskeridirVesselId, FuelEstimationModel, live_fuel_year_day_hour};
+use sqlx::postgres::types::PgRange;
 use unnest_insert::UnnestInsert;
 
-use crate::queries::type_to_i64;
+use crate::queries::{type_to_i32, type_to_i64};
 
 #[derive(Debug, Clone, UnnestInsert)]
 #[unnest_insert(
@@ -42,3 +43,38 @@ impl UpsertNewLiveFuel {
         }
     }
 }
+
+#[derive(Debug, Clone, UnnestInsert)]
+#[unnest_insert(table_name = "fuel_backtest_results")]
+pub struct NewFuelBacktestResult {
+    #[unnest_insert(sql_type = "BIGINT", type_conversion = "type_to_i64")]
+    pub fiskeridir_vessel_id: FiskeridirVesselId,
+    #[unnest_insert(sql_type = "tstzrange")]
+    pub fuel_range: PgRange<DateTime<Utc>>,
+    #[unnest_insert(sql_type = "INT", type_conversion = "type_to_i32")]
+    pub fuel_estimation_model_id: FuelEstimationModel,
+    pub measured_liter: f64,
+    pub estimated_liter: f64,
+}
+
+impl NewFuelBacktestResult {
+    pub fn from_core(
+        fiskeridir_vessel_id: FiskeridirVesselId,
+        core: &kyogre_core::NewFuelBacktestResult,
+    ) -> Self {
+        let kyogre_core::NewFuelBacktestResult {
+            fuel_range,
+            model,
+            measured_liter,
+            estimated_liter,
+        } = core;
+
+        Self {
+            fiskeridir_vessel_id,
+            fuel_range: fuel_range.into(),
+            fuel_estimation_model_id: *model,
+            measured_liter: *measured_liter,
+            estimated_liter: *estimated_liter,
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use chrono::{DateTime, Utc};
+use geo_types::geometry::Geometry;
+use geozero::wkb;
+use kyogre_core::{
+    CatchLocationId, FiskeridirVesselId, GeofenceAreaType, GeofenceEventType, GeofenceId,
+};
+use unnest_insert::UnnestInsert;
+use uuid::Uuid;
+
+use crate::{
+    error::{Error, MissingValueSnafu},
+    queries::{type_to_i32, type_to_i64},
+};
+
+pub struct Geofence {
+    pub id: GeofenceId,
+    pub name: String,
+    pub area_type: GeofenceAreaType,
+    pub catch_location_id: Option<CatchLocationId>,
+    pub fishing_facility_tool_id: Option<Uuid>,
+    pub polygon: wkb::Decode<Geometry<f64>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, UnnestInsert)]
+#[unnest_insert(
+    table_name = "geofence_vessels",
+    conflict = "geofence_id,fiskeridir_vessel_id",
+    update_all
+)]
+pub struct GeofenceVessel {
+    #[unnest_insert(sql_type = "BIGINT", type_conversion = "type_to_i64")]
+    pub geofence_id: GeofenceId,
+    #[unnest_insert(sql_type = "BIGINT", type_conversion = "type_to_i64")]
+    pub fiskeridir_vessel_id: FiskeridirVesselId,
+    pub inside: bool,
+    pub processed_until: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, UnnestInsert)]
+#[unnest_insert(
+    table_name = "geofence_events",
+    conflict = "geofence_id,fiskeridir_vessel_id,timestamp,geofence_event_type_id"
+)]
+pub struct NewGeofenceEvent {
+    #[unnest_insert(sql_type = "BIGINT", type_conversion = "type_to_i64")]
+    pub geofence_id: GeofenceId,
+    #[unnest_insert(sql_type = "BIGINT", type_conversion = "type_to_i64")]
+    pub fiskeridir_vessel_id: FiskeridirVesselId,
+    #[unnest_insert(sql_type = "INT", type_conversion = "type_to_i32")]
+    pub geofence_event_type_id: GeofenceEventType,
+    pub timestamp: DateTime<Utc>,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl TryFrom<Geofence> for kyogre_core::Geofence {
+    type Error = Error;
+
+    fn try_from(v: Geofence) -> Result<Self, Self::Error> {
+        let Geofence {
+            id,
+            name,
+            area_type,
+            catch_location_id,
+            fishing_facility_tool_id,
+            polygon,
+            created_at,
+        } = v;
+
+        let geometry = polygon.geometry.ok_or_else(|| MissingValueSnafu.build())?;
+
+        Ok(Self {
+            id,
+            name,
+            area_type,
+            catch_location_id,
+            fishing_facility_tool_id,
+            geometry,
+            created_at,
+        })
+    }
+}
+
+impl From<&kyogre_core::GeofenceVesselUpdate> for GeofenceVessel {
+    fn from(v: &kyogre_core::GeofenceVesselUpdate) -> Self {
+        Self {
+            geofence_id: v.geofence_id,
+            fiskeridir_vessel_id: v.fiskeridir_vessel_id,
+            inside: v.inside,
+            processed_until: v.processed_until,
+        }
+    }
+}
+
+impl From<&kyogre_core::NewGeofenceEvent> for NewGeofenceEvent {
+    fn from(v: &kyogre_core::NewGeofenceEvent) -> Self {
+        let kyogre_core::NewGeofenceEvent {
+            geofence_id,
+            fiskeridir_vessel_id,
+            event_type,
+            timestamp,
+            latitude,
+            longitude,
+        } = v;
+
+        Self {
+            geofence_id: *geofence_id,
+            fiskeridir_vessel_id: *fiskeridir_vessel_id,
+            geofence_event_type_id: *event_type,
+            timestamp: *timestamp,
+            latitude: *latitude,
+            longitude: *longitude,
+        }
+    }
+}
@@ -24,6 +24,8 @@ pub struct Haul {
     pub fiskeridir_vessel_id: Option<FiskeridirVesselId>,
     pub species_group_ids: Vec<SpeciesGroup>,
     pub call_sign: CallSign,
+    pub estimated_value: Option<f64>,
+    pub water_temperature: Option<f64>,
 }
 
 impl TryFrom<Haul> for kyogre_core::Haul {
@@ -49,6 +51,8 @@ impl TryFrom<Haul> for kyogre_core::Haul {
             fiskeridir_vessel_id,
             species_group_ids,
             call_sign,
+            estimated_value,
+            water_temperature,
         } = v;
 
         Ok(Self {
@@ -70,6 +74,8 @@ impl TryFrom<Haul> for kyogre_core::Haul {
             call_sign,
             stop_latitude,
             stop_longitude,
+            estimated_value,
+            water_temperature,
         })
     }
 }
@@ -9,8 +9,10 @@ mod ers_dep;
 mod ers_message;
 mod ers_por;
 mod ers_tra;
+mod file_checkpoint;
 mod fuel;
 mod gear;
+mod geofence;
 mod haul;
 mod herring;
 mod landing;
@@ -18,6 +20,8 @@ mod landing_entry;
 mod norwegian_land;
 mod ocean_climate;
 mod port;
+mod port_call;
+mod price_forecast;
 mod species;
 #[cfg(feature = "test")]
 mod test;
@@ -40,8 +44,10 @@ pub use ers_dep::*;
 pub use ers_message::*;
 pub use ers_por::*;
 pub use ers_tra::*;
+pub use file_checkpoint::*;
 pub use fuel::*;
 pub use gear::*;
+pub use geofence::*;
 pub use haul::*;
 pub use herring::*;
 pub use landing::*;
@@ -49,6 +55,8 @@ pub use landing_entry::*;
 pub use norwegian_land::*;
 pub use ocean_climate::*;
 pub use port::*;
+pub use port_call::*;
+pub use price_forecast::*;
 pub use species::*;
 #[cfg(feature = "test")]
 pub use test::*;
@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use kyogre_core::{FiskeridirVesselId, VesselEventType};
+use unnest_insert::UnnestInsert;
+
+use crate::queries::{type_to_i32, type_to_i64};
+
+#[derive(Debug, Clone, UnnestInsert)]
+#[unnest_insert(table_name = "ais_vms_port_calls")]
+pub struct NewAisVmsPortCall<'a> {
+    #[unnest_insert(sql_type = "BIGINT", type_conversion = "type_to_i64")]
+    pub fiskeridir_vessel_id: FiskeridirVesselId,
+    #[unnest_insert(sql_type = "INT", type_conversion = "type_to_i32")]
+    pub vessel_event_type_id: VesselEventType,
+    pub timestamp: DateTime<Utc>,
+    pub port_id: Option<&'a str>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AisVmsPortCallTimer {
+    pub timer: DateTime<Utc>,
+    pub port_id: Option<String>,
+}
+
+impl<'a> From<&'a kyogre_core::NewAisVmsPortCall> for NewAisVmsPortCall<'a> {
+    fn from(v: &'a kyogre_core::NewAisVmsPortCall) -> Self {
+        Self {
+            fiskeridir_vessel_id: v.fiskeridir_vessel_id,
+            vessel_event_type_id: v.port_call_type.into(),
+            timestamp: v.timestamp,
+            port_id: v.port_id.as_deref(),
+        }
+    }
+}
+
+impl From<AisVmsPortCallTimer> for kyogre_core::AisVmsPortCallTimer {
+    fn from(v: AisVmsPortCallTimer) -> Self {
+        let AisVmsPortCallTimer { timer, port_id } = v;
+        Self {
+            timestamp: timer,
+            port_id,
+        }
+    }
+}
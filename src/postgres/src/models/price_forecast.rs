@@ -0,0 +1,99 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use fiskeridir_rs::{Condition, GearGroup, Quality, SpeciesFiskeridirId, VesselLengthGroup};
+use kyogre_core::PriceGroup;
+use unnest_insert::UnnestInsert;
+
+use crate::{
+    error::{Error, InvalidIsoWeekSnafu},
+    queries::type_to_i32,
+};
+
+#[derive(Debug, Clone)]
+pub struct WeeklyPrice {
+    pub year: i32,
+    pub week: i32,
+    pub vessel_length_group: VesselLengthGroup,
+    pub gear_group: GearGroup,
+    pub species: SpeciesFiskeridirId,
+    pub condition: Condition,
+    pub quality: Quality,
+    pub price: f64,
+}
+
+impl TryFrom<WeeklyPrice> for kyogre_core::WeeklyPrice {
+    type Error = Error;
+
+    fn try_from(value: WeeklyPrice) -> Result<Self, Self::Error> {
+        let WeeklyPrice {
+            year,
+            week,
+            vessel_length_group,
+            gear_group,
+            species,
+            condition,
+            quality,
+            price,
+        } = value;
+
+        let iso_week = NaiveDate::from_isoywd_opt(year, week as _, Weekday::Mon)
+            .ok_or_else(|| InvalidIsoWeekSnafu { year, week }.build())?
+            .iso_week();
+
+        Ok(Self {
+            group: PriceGroup {
+                vessel_length_group,
+                gear_group,
+                species,
+                condition,
+                quality,
+            },
+            iso_week,
+            price,
+        })
+    }
+}
+
+#[derive(Debug, Clone, UnnestInsert)]
+#[unnest_insert(table_name = "rafisklaget_price_forecasts")]
+pub struct NewPriceForecast {
+    pub year: i32,
+    pub week: i32,
+    #[unnest_insert(sql_type = "INT", type_conversion = "type_to_i32")]
+    pub vessel_length_group: VesselLengthGroup,
+    #[unnest_insert(sql_type = "INT", type_conversion = "type_to_i32")]
+    pub gear_group: GearGroup,
+    #[unnest_insert(sql_type = "INT", type_conversion = "type_to_i32")]
+    pub species: SpeciesFiskeridirId,
+    #[unnest_insert(sql_type = "INT", type_conversion = "type_to_i32")]
+    pub condition: Condition,
+    #[unnest_insert(sql_type = "INT", type_conversion = "type_to_i32")]
+    pub quality: Quality,
+    pub price: f64,
+    pub lower_bound: f64,
+    pub upper_bound: f64,
+}
+
+impl From<&kyogre_core::NewPriceForecast> for NewPriceForecast {
+    fn from(value: &kyogre_core::NewPriceForecast) -> Self {
+        let kyogre_core::NewPriceForecast {
+            group,
+            iso_week,
+            price,
+            lower_bound,
+            upper_bound,
+        } = value;
+
+        Self {
+            year: iso_week.year(),
+            week: iso_week.week() as i32,
+            vessel_length_group: group.vessel_length_group,
+            gear_group: group.gear_group,
+            species: group.species,
+            condition: group.condition,
+            quality: group.quality,
+            price: *price,
+            lower_bound: *lower_bound,
+            upper_bound: *upper_bound,
+        }
+    }
+}
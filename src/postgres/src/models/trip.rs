@@ -1,6 +1,6 @@
 use super::{TripTra, VesselEvent};
 use crate::{
-    error::{Error, Result},
+    error::{Error, MissingValueSnafu, Result},
     models::VesselEventDetailed,
     queries::{opt_type_to_i32, type_to_i32, type_to_i64},
 };
@@ -8,9 +8,10 @@ use chrono::{DateTime, Utc};
 use fiskeridir_rs::{DeliveryPointId, Gear, GearGroup, LandingId, SpeciesGroup, VesselLengthGroup};
 use kyogre_core::{
     AisVmsPosition, Catch, DateRange, FishingFacility, FiskeridirVesselId, HasTrack,
-    MinimalVesselEvent, PositionType, PrecisionId, PrecisionOutcome, ProcessingStatus,
-    PrunedTripPosition, TripAssemblerConflict, TripAssemblerId, TripDistancerId, TripId,
-    TripPositionLayerId, TripToInsert, TripsConflictStrategy, VesselEventType,
+    MinimalVesselEvent, PositionType, PrecisionDirection, PrecisionId, PrecisionOutcome,
+    PrecisionUpdate, ProcessingStatus, PrunedTripPosition, TripAssemblerConflict, TripAssemblerId,
+    TripDistancerId, TripId, TripPositionLayerId, TripToInsert, TripsConflictStrategy,
+    VesselEventType,
 };
 use sqlx::postgres::types::PgRange;
 use std::str::FromStr;
@@ -104,6 +105,11 @@ pub struct NewTrip {
     pub trip_position_cargo_weight_distribution_status: ProcessingStatus,
     #[unnest_insert(sql_type = "INT", type_conversion = "type_to_i32")]
     pub trip_position_fuel_consumption_distribution_status: ProcessingStatus,
+    pub conflict_strategy: String,
+    pub conflict_strategy_start: Option<DateTime<Utc>>,
+    pub conflict_strategy_end: Option<DateTime<Utc>>,
+    #[unnest_insert(sql_type = "JSONB")]
+    pub precision_attempts: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, UnnestInsert)]
@@ -141,6 +147,30 @@ pub struct TripPrunedAisVmsPosition {
     pub trip_position_layer_id: TripPositionLayerId,
 }
 
+#[derive(Debug, Clone)]
+pub struct TripExplanation {
+    pub trip_id: TripId,
+    pub fiskeridir_vessel_id: FiskeridirVesselId,
+    pub trip_assembler_id: TripAssemblerId,
+    pub period: DateRange,
+    pub period_extended: DateRange,
+    pub period_precision: Option<DateRange>,
+    pub landing_coverage: DateRange,
+    pub conflict_strategy: Option<String>,
+    pub conflict_strategy_start: Option<DateTime<Utc>>,
+    pub conflict_strategy_end: Option<DateTime<Utc>>,
+    pub start_vessel_event_id: Option<i64>,
+    pub end_vessel_event_id: Option<i64>,
+    pub trip_precision_status_id: ProcessingStatus,
+    pub start_precision_id: Option<PrecisionId>,
+    pub start_precision_direction: Option<String>,
+    pub end_precision_id: Option<PrecisionId>,
+    pub end_precision_direction: Option<String>,
+    pub precision_attempts: Option<serde_json::Value>,
+    pub position_layers_status: ProcessingStatus,
+    pub track_coverage: Option<f64>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TripAndSucceedingEventsLandings {
     pub fiskeridir_vessel_id: FiskeridirVesselId,
@@ -171,6 +201,21 @@ pub struct TripAndSucceedingEventsErs {
     pub por_and_dep_events_after_trip: String,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct TripAndSucceedingEventsAisVms {
+    pub fiskeridir_vessel_id: FiskeridirVesselId,
+
+    pub arrival_vessel_event_id: i64,
+    pub arrival_port_id: Option<String>,
+    pub arrival_timestamp: DateTime<Utc>,
+
+    pub departure_vessel_event_id: i64,
+    pub departure_port_id: Option<String>,
+    pub departure_timestamp: DateTime<Utc>,
+
+    pub port_calls_after_trip: String,
+}
+
 impl TryFrom<TripAndSucceedingEventsLandings> for kyogre_core::TripAndSucceedingEvents {
     type Error = Error;
 
@@ -263,8 +308,57 @@ impl TryFrom<TripAndSucceedingEventsErs> for kyogre_core::TripAndSucceedingEvent
     }
 }
 
-impl From<&TripToInsert> for NewTrip {
-    fn from(value: &TripToInsert) -> Self {
+impl TryFrom<TripAndSucceedingEventsAisVms> for kyogre_core::TripAndSucceedingEvents {
+    type Error = Error;
+
+    fn try_from(value: TripAndSucceedingEventsAisVms) -> std::result::Result<Self, Self::Error> {
+        let TripAndSucceedingEventsAisVms {
+            fiskeridir_vessel_id,
+            arrival_vessel_event_id,
+            arrival_port_id,
+            arrival_timestamp,
+            departure_vessel_event_id,
+            departure_port_id,
+            departure_timestamp,
+            port_calls_after_trip,
+        } = value;
+
+        let start_event = kyogre_core::VesselEventDetailed {
+            event_id: departure_vessel_event_id as u64,
+            vessel_id: fiskeridir_vessel_id,
+            reported_timestamp: departure_timestamp,
+            event_type: VesselEventType::AisVmsDeparture,
+            event_data: kyogre_core::VesselEventData::AisVmsDeparture {
+                port_id: departure_port_id,
+                timestamp: departure_timestamp,
+            },
+        };
+
+        let end_event = kyogre_core::VesselEventDetailed {
+            event_id: arrival_vessel_event_id as u64,
+            vessel_id: fiskeridir_vessel_id,
+            reported_timestamp: arrival_timestamp,
+            event_type: VesselEventType::AisVmsArrival,
+            event_data: kyogre_core::VesselEventData::AisVmsArrival {
+                port_id: arrival_port_id,
+                timestamp: arrival_timestamp,
+            },
+        };
+
+        Ok(Self {
+            start_and_end_event: [start_event, end_event],
+            succeeding_events: serde_json::from_str::<Vec<VesselEventDetailed>>(
+                &port_calls_after_trip,
+            )?
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<kyogre_core::VesselEventDetailed>>>()?,
+        })
+    }
+}
+
+impl NewTrip {
+    pub fn new(value: &TripToInsert, conflict_strategy: TripsConflictStrategy) -> Result<Self> {
         let TripToInsert {
             vessel_id,
             trip:
@@ -302,6 +396,7 @@ impl From<&TripToInsert> for NewTrip {
                     new_period,
                     start_precision,
                     end_precision,
+                    attempts: _,
                 } => (
                     start_precision.as_ref().map(|v| v.id),
                     start_precision.as_ref().map(|v| v.direction),
@@ -310,19 +405,26 @@ impl From<&TripToInsert> for NewTrip {
                     Some(PgRange::from(new_period)),
                     ProcessingStatus::Successful,
                 ),
-                PrecisionOutcome::Failed => {
+                PrecisionOutcome::Failed { attempts: _ } => {
                     (None, None, None, None, None, ProcessingStatus::Attempted)
                 }
             },
             None => (None, None, None, None, None, ProcessingStatus::Unprocessed),
         };
 
+        let precision_attempts = precision_outcome
+            .as_ref()
+            .map(|v| serde_json::to_value(v.attempts()))
+            .transpose()?;
+
+        let (conflict_strategy_start, conflict_strategy_end) = conflict_strategy.timestamps();
+
         let (distance, distancer_id) = match distance_output {
             Some(v) => (v.distance, Some(v.distancer_id)),
             None => (None, None),
         };
 
-        NewTrip {
+        Ok(NewTrip {
             // `start_vessel_event_id` is `None` only for the first ever landing event which
             // contains an artifical landing event, for all other cases it should be `Some`.
             // `end_vessel_event_id` should always be `Some`.
@@ -349,7 +451,11 @@ impl From<&TripToInsert> for NewTrip {
             trip_position_fuel_consumption_distribution_status: ProcessingStatus::Successful,
             first_arrival: *first_arrival,
             trip_id: *trip_id,
-        }
+            conflict_strategy: conflict_strategy.to_string(),
+            conflict_strategy_start,
+            conflict_strategy_end,
+            precision_attempts,
+        })
     }
 }
 
@@ -466,11 +572,18 @@ impl TryFrom<CurrentTrip> for kyogre_core::CurrentTrip {
             fishing_facilities,
         } = v;
 
+        let hauls = serde_json::from_str::<Vec<kyogre_core::TripsDetailedHaul>>(&hauls)?;
+        let estimated_value = hauls
+            .iter()
+            .filter_map(|h| h.estimated_value)
+            .reduce(|acc, v| acc + v);
+
         Ok(Self {
             departure: departure_timestamp,
             target_species_fiskeridir_id,
-            hauls: serde_json::from_str::<Vec<kyogre_core::TripsDetailedHaul>>(&hauls)?,
+            hauls,
             fishing_facilities: serde_json::from_str::<Vec<FishingFacility>>(&fishing_facilities)?,
+            estimated_value,
         })
     }
 }
@@ -658,6 +771,120 @@ impl TripAisVmsPosition {
     }
 }
 
+impl TripExplanation {
+    pub fn into_core(
+        self,
+        events: Vec<kyogre_core::VesselEvent>,
+        pruned_positions: Vec<PrunedTripPosition>,
+    ) -> Result<kyogre_core::TripExplanation> {
+        let TripExplanation {
+            trip_id,
+            fiskeridir_vessel_id,
+            trip_assembler_id,
+            period,
+            period_extended,
+            period_precision,
+            landing_coverage,
+            conflict_strategy,
+            conflict_strategy_start,
+            conflict_strategy_end,
+            start_vessel_event_id,
+            end_vessel_event_id,
+            trip_precision_status_id,
+            start_precision_id,
+            start_precision_direction,
+            end_precision_id,
+            end_precision_direction,
+            precision_attempts,
+            position_layers_status,
+            track_coverage,
+        } = self;
+
+        Ok(kyogre_core::TripExplanation {
+            trip_id,
+            vessel_id: fiskeridir_vessel_id,
+            assembler_id: trip_assembler_id,
+            period,
+            period_extended,
+            period_precision,
+            landing_coverage,
+            conflict_strategy: conflict_strategy
+                .map(|v| {
+                    explained_conflict_strategy(&v, conflict_strategy_start, conflict_strategy_end)
+                })
+                .transpose()?
+                .flatten(),
+            start_vessel_event_id: start_vessel_event_id.map(|v| v as u64),
+            end_vessel_event_id: end_vessel_event_id.map(|v| v as u64),
+            events,
+            precision_status: trip_precision_status_id,
+            start_precision: precision_update(start_precision_id, start_precision_direction)?,
+            end_precision: precision_update(end_precision_id, end_precision_direction)?,
+            precision_attempts: precision_attempts
+                .map(serde_json::from_value)
+                .transpose()?
+                .unwrap_or_default(),
+            position_layers_status,
+            track_coverage,
+            pruned_positions,
+        })
+    }
+}
+
+/// Trips created before we recorded the timestamps of the conflict strategy only have its name,
+/// which is not enough to explain `Replace` and `ReplaceRange` and are treated as not recorded.
+fn explained_conflict_strategy(
+    name: &str,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+) -> Result<Option<TripsConflictStrategy>> {
+    Ok(match (TripsConflictStrategy::from_str(name)?, start, end) {
+        (TripsConflictStrategy::Replace { .. }, Some(conflict), _) => {
+            Some(TripsConflictStrategy::Replace { conflict })
+        }
+        (TripsConflictStrategy::ReplaceRange { .. }, Some(start), Some(end)) => {
+            Some(TripsConflictStrategy::ReplaceRange { start, end })
+        }
+        (
+            TripsConflictStrategy::Replace { .. } | TripsConflictStrategy::ReplaceRange { .. },
+            _,
+            _,
+        ) => None,
+        (v @ (TripsConflictStrategy::Error | TripsConflictStrategy::ReplaceAll), _, _) => Some(v),
+    })
+}
+
+fn precision_update(
+    id: Option<PrecisionId>,
+    direction: Option<String>,
+) -> Result<Option<PrecisionUpdate>> {
+    match (id, direction) {
+        (Some(id), Some(direction)) => Ok(Some(PrecisionUpdate {
+            id,
+            direction: PrecisionDirection::from_str(&direction)?,
+        })),
+        (None, None) => Ok(None),
+        (Some(_), None) | (None, Some(_)) => MissingValueSnafu.fail(),
+    }
+}
+
+impl From<TripPrunedAisVmsPosition> for PrunedTripPosition {
+    fn from(value: TripPrunedAisVmsPosition) -> Self {
+        let TripPrunedAisVmsPosition {
+            trip_id: _,
+            positions,
+            value,
+            trip_position_layer_id,
+        } = value;
+
+        Self {
+            positions,
+            value,
+            trip_layer: trip_position_layer_id,
+        }
+    }
+}
+
 impl TripPrunedAisVmsPosition {
     pub fn new(id: TripId, p: PrunedTripPosition) -> Self {
         Self {
@@ -23,6 +23,7 @@ pub struct UserHaul {
     pub end_fuel_liter: i32,
     pub total_living_weight_kg: Option<f64>,
     pub config: serde_json::Value,
+    pub estimated_value: Option<f64>,
 }
 
 impl From<UserHaul> for kyogre_core::UserHaul {
@@ -37,6 +38,7 @@ impl From<UserHaul> for kyogre_core::UserHaul {
             end_fuel_liter,
             config,
             total_living_weight_kg,
+            estimated_value,
         } = value;
 
         Self {
@@ -48,6 +50,7 @@ impl From<UserHaul> for kyogre_core::UserHaul {
             end_fuel_liter: end_fuel_liter as u32,
             config,
             total_living_weight_kg,
+            estimated_value,
         }
     }
 }
@@ -8,8 +8,8 @@ use fiskeridir_rs::{
     VesselType,
 };
 use kyogre_core::{
-    AisVessel, Draught, EngineType, FisheryId, FiskeridirVessel, FiskeridirVesselId, Mmsi, Month,
-    TripAssemblerId, VesselCurrentTrip, VesselSource,
+    AisVessel, Draught, EngineType, FisheryId, FiskeridirVessel, FiskeridirVesselId,
+    FuelEstimationModel, FuelType, Mmsi, Month, TripAssemblerId, VesselCurrentTrip, VesselSource,
 };
 use serde::Deserialize;
 use unnest_insert::UnnestInsert;
@@ -324,6 +324,8 @@ pub struct FiskeridirAisVesselCombination {
     pub fiskeridir_engine_version: i32,
     pub fiskeridir_degree_of_electrification: Option<f64>,
     pub fiskeridir_service_speed: Option<f64>,
+    pub fiskeridir_fuel_type: Option<FuelType>,
+    pub fiskeridir_fuel_estimation_model: Option<FuelEstimationModel>,
     pub fiskeridir_fishery_id: Option<FisheryId>,
     pub preferred_trip_assembler: TripAssemblerId,
     pub gear_group_ids: Vec<GearGroup>,
@@ -362,6 +364,8 @@ impl TryFrom<FiskeridirAisVesselCombination> for kyogre_core::Vessel {
             fiskeridir_engine_version,
             fiskeridir_degree_of_electrification,
             fiskeridir_service_speed,
+            fiskeridir_fuel_type,
+            fiskeridir_fuel_estimation_model,
             preferred_trip_assembler,
             gear_group_ids,
             species_group_ids,
@@ -404,6 +408,8 @@ impl TryFrom<FiskeridirAisVesselCombination> for kyogre_core::Vessel {
             engine_version: fiskeridir_engine_version as u32,
             degree_of_electrification: fiskeridir_degree_of_electrification,
             service_speed: fiskeridir_service_speed,
+            fuel_type: fiskeridir_fuel_type,
+            fuel_estimation_model: fiskeridir_fuel_estimation_model,
         };
 
         Ok(Self {
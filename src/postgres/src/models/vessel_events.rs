@@ -75,6 +75,14 @@ impl TryFrom<VesselEventDetailed> for kyogre_core::VesselEventDetailed {
                 estimated_timestamp: estimated_timestamp
                     .ok_or_else(|| MissingValueSnafu.build())?,
             },
+            VesselEventType::AisVmsDeparture => VesselEventData::AisVmsDeparture {
+                port_id,
+                timestamp: estimated_timestamp.ok_or_else(|| MissingValueSnafu.build())?,
+            },
+            VesselEventType::AisVmsArrival => VesselEventData::AisVmsArrival {
+                port_id,
+                timestamp: estimated_timestamp.ok_or_else(|| MissingValueSnafu.build())?,
+            },
         };
 
         Ok(kyogre_core::VesselEventDetailed {
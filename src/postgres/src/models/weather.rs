@@ -29,6 +29,23 @@ pub struct NewWeather {
     pub cloud_area_fraction: Option<f64>,
 }
 
+#[derive(UnnestInsert)]
+#[unnest_insert(
+    table_name = "weather_forecasts",
+    conflict = "reference_time,timestamp,weather_location_id"
+)]
+pub struct NewWeatherForecast {
+    pub reference_time: DateTime<Utc>,
+    pub timestamp: DateTime<Utc>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub wind_speed_10m: Option<f64>,
+    pub wind_direction_10m: Option<f64>,
+    pub air_temperature_2m: Option<f64>,
+    pub air_pressure_at_sea_level: Option<f64>,
+    pub precipitation_amount: Option<f64>,
+}
+
 #[derive(Debug)]
 pub struct WeatherLocation {
     pub weather_location_id: WeatherLocationId,
@@ -54,6 +71,22 @@ impl From<kyogre_core::NewWeather> for NewWeather {
     }
 }
 
+impl From<kyogre_core::NewWeatherForecast> for NewWeatherForecast {
+    fn from(v: kyogre_core::NewWeatherForecast) -> Self {
+        Self {
+            reference_time: v.reference_time,
+            timestamp: v.timestamp,
+            latitude: v.latitude,
+            longitude: v.longitude,
+            wind_speed_10m: v.wind_speed_10m.into_inner(),
+            wind_direction_10m: v.wind_direction_10m,
+            air_temperature_2m: v.air_temperature_2m.into_inner(),
+            air_pressure_at_sea_level: v.air_pressure_at_sea_level.into_inner(),
+            precipitation_amount: v.precipitation_amount.into_inner(),
+        }
+    }
+}
+
 impl TryFrom<WeatherLocation> for kyogre_core::WeatherLocation {
     type Error = Error;
 
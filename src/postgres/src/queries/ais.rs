@@ -1,7 +1,10 @@
 use crate::{
     PostgresAdapter,
     error::Result,
-    models::{self, NewAisCurrentPosition, NewAisVessel, NewAisVesselHistoric, NewAisVesselMmsi},
+    models::{
+        self, NewAisCurrentPosition, NewAisRejectedMessage, NewAisVessel, NewAisVesselHistoric,
+        NewAisVesselMmsi,
+    },
 };
 use chrono::{DateTime, Utc};
 use fiskeridir_rs::{Gear, GearGroup};
@@ -13,6 +16,10 @@ use kyogre_core::{
 };
 use std::collections::HashMap;
 
+/// The number of days rejected messages are kept for, older messages are deleted as new ones are
+/// added. The per-category counters are kept indefinitely.
+const REJECTED_MESSAGE_RETENTION_DAYS: i32 = 30;
+
 impl PostgresAdapter {
     pub(crate) fn ais_positions_impl(
         &self,
@@ -258,6 +265,74 @@ FROM
         Ok(())
     }
 
+    pub(crate) async fn add_ais_rejected_messages(
+        &self,
+        messages: &[kyogre_core::NewAisRejectedMessage],
+    ) -> Result<()> {
+        if messages.is_empty() {
+            return Ok(());
+        }
+
+        let len = messages.len();
+        let mut category_ids = Vec::with_capacity(len);
+        let mut received_at = Vec::with_capacity(len);
+
+        for m in messages {
+            category_ids.push(m.category as i32);
+            received_at.push(m.received_at);
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        self.unnest_insert_from::<_, _, NewAisRejectedMessage<'_>>(messages, &mut *tx)
+            .await?;
+
+        sqlx::query!(
+            r#"
+INSERT INTO
+    ais_rejection_counters (
+        ais_rejection_category_id,
+        num_rejected,
+        last_rejected_at
+    )
+SELECT
+    u.ais_rejection_category_id,
+    COUNT(*),
+    MAX(u.received_at)
+FROM
+    UNNEST($1::INT[], $2::TIMESTAMPTZ[]) u (ais_rejection_category_id, received_at)
+GROUP BY
+    u.ais_rejection_category_id
+ON CONFLICT (ais_rejection_category_id) DO UPDATE
+SET
+    num_rejected = ais_rejection_counters.num_rejected + EXCLUDED.num_rejected,
+    last_rejected_at = GREATEST(
+        ais_rejection_counters.last_rejected_at,
+        EXCLUDED.last_rejected_at
+    )
+            "#,
+            &category_ids,
+            &received_at,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+DELETE FROM ais_rejected_messages
+WHERE
+    received_at < NOW() - MAKE_INTERVAL(days => $1::INT)
+            "#,
+            REJECTED_MESSAGE_RETENTION_DAYS,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
     pub(crate) async fn ais_vessel_migration_progress(
         &self,
         migration_end_threshold: &DateTime<Utc>,
@@ -0,0 +1,103 @@
+use futures::{Stream, TryStreamExt};
+use kyogre_core::{DataQualityQuery, EmptyVecToNone, FileDataQuality, NewFileDataQuality};
+
+use crate::{PostgresAdapter, error::Result};
+
+impl PostgresAdapter {
+    pub(crate) async fn set_data_quality_impl(&self, quality: &NewFileDataQuality) -> Result<()> {
+        sqlx::query!(
+            r#"
+INSERT INTO
+    file_data_quality (
+        file_hash_id,
+        file_hash,
+        dataset,
+        "year",
+        "rows",
+        rejected_rows,
+        coerced_fields,
+        defaulted_fields,
+        rejected_fields
+    )
+VALUES
+    ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+ON CONFLICT (file_hash_id) DO UPDATE
+SET
+    file_hash = excluded.file_hash,
+    dataset = excluded.dataset,
+    "year" = excluded."year",
+    "rows" = excluded."rows",
+    rejected_rows = excluded.rejected_rows,
+    coerced_fields = excluded.coerced_fields,
+    defaulted_fields = excluded.defaulted_fields,
+    rejected_fields = excluded.rejected_fields,
+    updated_at = NOW()
+            "#,
+            quality.file_id.as_ref(),
+            quality.file_hash,
+            quality.dataset,
+            quality.year as i32,
+            quality.rows as i64,
+            quality.rejected_rows as i64,
+            quality.fields.coerced as i64,
+            quality.fields.defaulted as i64,
+            quality.fields.rejected as i64,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub(crate) fn data_quality_impl(
+        &self,
+        query: DataQualityQuery,
+    ) -> impl Stream<Item = Result<FileDataQuality>> + '_ {
+        let years = query
+            .years
+            .and_then(|v| v.empty_to_none())
+            .map(|v| v.into_iter().map(|y| y as i32).collect::<Vec<_>>());
+
+        sqlx::query!(
+            r#"
+SELECT
+    dataset,
+    "year",
+    "rows",
+    rejected_rows,
+    coerced_fields,
+    defaulted_fields,
+    rejected_fields,
+    updated_at
+FROM
+    file_data_quality
+WHERE
+    (
+        $1::TEXT[] IS NULL
+        OR dataset = ANY ($1)
+    )
+    AND (
+        $2::INT[] IS NULL
+        OR "year" = ANY ($2)
+    )
+ORDER BY
+    dataset,
+    "year"
+            "#,
+            query.datasets.and_then(|v| v.empty_to_none()) as Option<Vec<String>>,
+            years as Option<Vec<i32>>,
+        )
+        .fetch(&self.pool)
+        .map_ok(|r| FileDataQuality {
+            dataset: r.dataset,
+            year: r.year as u32,
+            rows: r.rows as u64,
+            rejected_rows: r.rejected_rows as u64,
+            coerced_fields: r.coerced_fields as u64,
+            defaulted_fields: r.defaulted_fields as u64,
+            rejected_fields: r.rejected_fields as u64,
+            updated_at: r.updated_at,
+        })
+        .map_err(|e| e.into())
+    }
+}
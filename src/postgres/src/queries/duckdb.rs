@@ -1,17 +1,53 @@
 use crate::{PostgresAdapter, error::Result};
 
+/// The number of versions that matrix changes are kept for, caches that are further behind than
+/// this have to do a full refresh.
+const RETAINED_CHANGE_VERSIONS: i32 = 100;
+
 impl PostgresAdapter {
     pub(crate) async fn increment_duckdb_version(&self) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
         sqlx::query!(
             r#"
 UPDATE duckdb_data_version
 SET
-    "version" = "version" + 1
+    "version" = "version" + 1,
+    min_change_version = GREATEST(min_change_version, "version" + 1 - $1::INT)
+            "#,
+            RETAINED_CHANGE_VERSIONS,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+DELETE FROM hauls_matrix_changes c
+USING
+    duckdb_data_version v
+WHERE
+    v.duckdb_data_version_id = 'hauls'
+    AND c."version" < v.min_change_version
             "#,
         )
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
 
+        sqlx::query!(
+            r#"
+DELETE FROM landing_matrix_changes c
+USING
+    duckdb_data_version v
+WHERE
+    v.duckdb_data_version_id = 'landings'
+    AND c."version" < v.min_change_version
+            "#,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
         Ok(())
     }
 }
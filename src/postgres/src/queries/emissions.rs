@@ -0,0 +1,132 @@
+use fiskeridir_rs::{CallSign, OrgId};
+use kyogre_core::{EmissionReportQuery, FiskeridirVesselId, FuelType, VesselYearlyFuel};
+
+use crate::{PostgresAdapter, error::Result};
+
+impl PostgresAdapter {
+    pub(crate) async fn emission_report_impl(
+        &self,
+        query: &EmissionReportQuery,
+    ) -> Result<Vec<VesselYearlyFuel>> {
+        let vessel_ids = self.vessel_ids_of_call_sign(&query.call_sign).await?;
+        self.vessels_yearly_fuel(&vessel_ids, query.year).await
+    }
+
+    pub(crate) async fn emission_report_by_org_impl(
+        &self,
+        query: &EmissionReportQuery,
+        org_id: OrgId,
+    ) -> Result<Option<Vec<VesselYearlyFuel>>> {
+        let Some(org_vessels) = self
+            .assert_call_sign_is_in_org(&query.call_sign, org_id)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(
+            self.vessels_yearly_fuel(&org_vessels, query.year).await?,
+        ))
+    }
+
+    async fn vessel_ids_of_call_sign(
+        &self,
+        call_sign: &CallSign,
+    ) -> Result<Vec<FiskeridirVesselId>> {
+        Ok(sqlx::query!(
+            r#"
+SELECT
+    fiskeridir_vessel_id AS "fiskeridir_vessel_id!: FiskeridirVesselId"
+FROM
+    active_vessels
+WHERE
+    call_sign = $1
+            "#,
+            call_sign.as_ref(),
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|r| r.fiskeridir_vessel_id)
+        .collect())
+    }
+
+    /// Splits the fuel consumption of the given vessels within the given year into measured and
+    /// estimated fuel. Fuel measurement ranges crossing the start or end of the year are
+    /// prorated, and fuel estimates are only counted for the parts of each day that are not
+    /// covered by a fuel measurement range.
+    async fn vessels_yearly_fuel(
+        &self,
+        vessel_ids: &[FiskeridirVesselId],
+        year: i32,
+    ) -> Result<Vec<VesselYearlyFuel>> {
+        Ok(sqlx::query_as!(
+            VesselYearlyFuel,
+            r#"
+WITH
+    year_range AS (
+        SELECT
+            TSTZRANGE(
+                MAKE_TIMESTAMPTZ($2, 1, 1, 0, 0, 0, 'UTC'),
+                MAKE_TIMESTAMPTZ($2 + 1, 1, 1, 0, 0, 0, 'UTC'),
+                '[)'
+            ) AS "range"
+    ),
+    measurements AS (
+        SELECT
+            r.fiskeridir_vessel_id,
+            SUM(
+                COMPUTE_TS_RANGE_PERCENT_OVERLAP (r.fuel_range, y.range) * r.fuel_used_liter
+            ) AS fuel_used_liter,
+            RANGE_AGG(r.fuel_range) AS fuel_ranges
+        FROM
+            year_range y
+            INNER JOIN fuel_measurement_ranges r ON r.fuel_range && y.range
+        WHERE
+            r.fiskeridir_vessel_id = ANY ($1::BIGINT[])
+        GROUP BY
+            r.fiskeridir_vessel_id
+    ),
+    estimates AS (
+        SELECT
+            f.fiskeridir_vessel_id,
+            SUM(
+                CASE
+                    WHEN m.fuel_ranges IS NULL THEN f.estimate_liter
+                    ELSE (
+                        1.0 - COMPUTE_TS_RANGE_MUTLIRANGE_PERCENT_OVERLAP (f.day_range, m.fuel_ranges)
+                    ) * f.estimate_liter
+                END
+            ) AS fuel_liter
+        FROM
+            year_range y
+            INNER JOIN fuel_estimates f ON f.day_range <@ y.range
+            LEFT JOIN measurements m ON m.fiskeridir_vessel_id = f.fiskeridir_vessel_id
+            AND m.fuel_ranges && f.day_range
+        WHERE
+            f.fiskeridir_vessel_id = ANY ($1::BIGINT[])
+        GROUP BY
+            f.fiskeridir_vessel_id
+    )
+SELECT
+    v.fiskeridir_vessel_id AS "fiskeridir_vessel_id!: FiskeridirVesselId",
+    COALESCE(v.fuel_type_id, $3) AS "fuel_type!: FuelType",
+    COALESCE(m.fuel_used_liter, 0.0) AS "measured_fuel_liter!",
+    COALESCE(e.fuel_liter, 0.0) AS "estimated_fuel_liter!"
+FROM
+    fiskeridir_vessels v
+    LEFT JOIN measurements m ON v.fiskeridir_vessel_id = m.fiskeridir_vessel_id
+    LEFT JOIN estimates e ON v.fiskeridir_vessel_id = e.fiskeridir_vessel_id
+WHERE
+    v.fiskeridir_vessel_id = ANY ($1::BIGINT[])
+ORDER BY
+    v.fiskeridir_vessel_id
+            "#,
+            vessel_ids as &[FiskeridirVesselId],
+            year,
+            FuelType::default() as i32,
+        )
+        .fetch_all(&self.pool)
+        .await?)
+    }
+}
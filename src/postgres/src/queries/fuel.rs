@@ -2,13 +2,18 @@ use chrono::{DateTime, NaiveDate, Utc};
 use fiskeridir_rs::CallSign;
 use futures::{Stream, TryStreamExt};
 use kyogre_core::{
-    DateRange, Draught, EngineType, FiskeridirVesselId, FuelQuery, LiveFuelQuery, LiveFuelVessel,
-    Mmsi, NewFuelDayEstimate, NewLiveFuel, ProcessingStatus,
+    DateRange, Draught, EmpiricalFuelModel, EngineType, FiskeridirVesselId, FuelBacktestMetrics,
+    FuelBacktestQuery, FuelEstimationModel, FuelMeasurementRange, FuelQuery, LiveFuelQuery,
+    LiveFuelVessel, Mmsi, NewFuelBacktestResult, NewFuelDayEstimate, NewLiveFuel, ProcessingStatus,
 };
 use sqlx::postgres::types::PgRange;
 use unnest_insert::UnnestInsert;
 
-use crate::{PostgresAdapter, error::Result, models::UpsertNewLiveFuel};
+use crate::{
+    PostgresAdapter,
+    error::Result,
+    models::{self, UpsertNewLiveFuel},
+};
 
 impl PostgresAdapter {
     pub(crate) fn live_fuel_impl(
@@ -81,6 +86,7 @@ SELECT
     a.ship_width::DOUBLE PRECISION AS breadth,
     COALESCE(f.length, a.ship_length::DOUBLE PRECISION) AS length,
     a.draught AS "current_draught: Draught",
+    f.fuel_estimation_model_id AS "fuel_estimation_model: FuelEstimationModel",
     (
         SELECT
             MAX(latest_position_timestamp)
@@ -362,4 +368,262 @@ WHERE
         .map(|_| ())
         .map_err(|e| e.into())
     }
+
+    pub(crate) async fn fuel_measurement_ranges_impl(
+        &self,
+        vessel_id: FiskeridirVesselId,
+    ) -> Result<Vec<FuelMeasurementRange>> {
+        Ok(sqlx::query_as!(
+            FuelMeasurementRange,
+            r#"
+SELECT
+    fiskeridir_vessel_id AS "fiskeridir_vessel_id: FiskeridirVesselId",
+    fuel_range AS "fuel_range: DateRange",
+    fuel_used_liter
+FROM
+    fuel_measurement_ranges
+WHERE
+    fiskeridir_vessel_id = $1
+ORDER BY
+    fuel_range
+            "#,
+            vessel_id.into_inner(),
+        )
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    pub(crate) async fn empirical_fuel_model_impl(
+        &self,
+        vessel_id: FiskeridirVesselId,
+    ) -> Result<Option<EmpiricalFuelModel>> {
+        Ok(sqlx::query!(
+            r#"
+SELECT
+    idle_liter_per_hour,
+    load_liter_per_hour,
+    haul_liter_per_hour,
+    num_measurements
+FROM
+    empirical_fuel_models
+WHERE
+    fiskeridir_vessel_id = $1
+            "#,
+            vessel_id.into_inner(),
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .map(|r| EmpiricalFuelModel {
+            idle_liter_per_hour: r.idle_liter_per_hour,
+            load_liter_per_hour: r.load_liter_per_hour,
+            haul_liter_per_hour: r.haul_liter_per_hour,
+            num_measurements: r.num_measurements as u32,
+        }))
+    }
+
+    pub(crate) async fn changed_fuel_measurements_checksum_impl(
+        &self,
+        vessel_id: FiskeridirVesselId,
+    ) -> Result<Option<String>> {
+        let row = sqlx::query!(
+            r#"
+SELECT
+    c.checksum AS "checksum!",
+    c.checksum IS DISTINCT FROM f.measurements_checksum AS "changed!"
+FROM
+    (
+        SELECT
+            COALESCE(
+                MD5(
+                    STRING_AGG(
+                        r.fuel_range::TEXT || ':' || r.fuel_used_liter::TEXT,
+                        ','
+                        ORDER BY
+                            r.fuel_range
+                    )
+                ),
+                ''
+            ) AS checksum
+        FROM
+            fuel_measurement_ranges r
+        WHERE
+            r.fiskeridir_vessel_id = $1
+    ) c
+    LEFT JOIN empirical_fuel_model_fits f ON f.fiskeridir_vessel_id = $1
+            "#,
+            vessel_id.into_inner(),
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.changed.then_some(row.checksum))
+    }
+
+    pub(crate) async fn set_empirical_fuel_model_impl(
+        &self,
+        vessel_id: FiskeridirVesselId,
+        model: Option<EmpiricalFuelModel>,
+        measurements_checksum: &str,
+        reset_estimates: bool,
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        match model {
+            Some(model) => {
+                sqlx::query!(
+                    r#"
+INSERT INTO
+    empirical_fuel_models (
+        fiskeridir_vessel_id,
+        idle_liter_per_hour,
+        load_liter_per_hour,
+        haul_liter_per_hour,
+        num_measurements
+    )
+VALUES
+    ($1, $2, $3, $4, $5)
+ON CONFLICT (fiskeridir_vessel_id) DO UPDATE
+SET
+    idle_liter_per_hour = EXCLUDED.idle_liter_per_hour,
+    load_liter_per_hour = EXCLUDED.load_liter_per_hour,
+    haul_liter_per_hour = EXCLUDED.haul_liter_per_hour,
+    num_measurements = EXCLUDED.num_measurements,
+    updated_at = NOW()
+                    "#,
+                    vessel_id.into_inner(),
+                    model.idle_liter_per_hour,
+                    model.load_liter_per_hour,
+                    model.haul_liter_per_hour,
+                    model.num_measurements as i32,
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            None => {
+                sqlx::query!(
+                    r#"
+DELETE FROM empirical_fuel_models
+WHERE
+    fiskeridir_vessel_id = $1
+                    "#,
+                    vessel_id.into_inner(),
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        sqlx::query!(
+            r#"
+INSERT INTO
+    empirical_fuel_model_fits (fiskeridir_vessel_id, measurements_checksum)
+VALUES
+    ($1, $2)
+ON CONFLICT (fiskeridir_vessel_id) DO UPDATE
+SET
+    measurements_checksum = EXCLUDED.measurements_checksum,
+    fitted_at = NOW()
+            "#,
+            vessel_id.into_inner(),
+            measurements_checksum,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if reset_estimates {
+            self.reset_fuel_estimation(vessel_id, &mut tx).await?;
+
+            sqlx::query!(
+                r#"
+UPDATE trips
+SET
+    trip_position_fuel_consumption_distribution_status = $1
+WHERE
+    fiskeridir_vessel_id = $2
+                "#,
+                ProcessingStatus::Unprocessed as i32,
+                vessel_id.into_inner(),
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn set_fuel_backtest_results_impl(
+        &self,
+        vessel_id: FiskeridirVesselId,
+        results: &[NewFuelBacktestResult],
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+DELETE FROM fuel_backtest_results
+WHERE
+    fiskeridir_vessel_id = $1
+            "#,
+            vessel_id.into_inner(),
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        self.unnest_insert(
+            results
+                .iter()
+                .map(|r| models::NewFuelBacktestResult::from_core(vessel_id, r)),
+            &mut *tx,
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub(crate) fn fuel_backtest_impl(
+        &self,
+        query: FuelBacktestQuery,
+    ) -> impl Stream<Item = Result<FuelBacktestMetrics>> + '_ {
+        sqlx::query_as!(
+            FuelBacktestMetrics,
+            r#"
+SELECT
+    fiskeridir_vessel_id AS "fiskeridir_vessel_id?: FiskeridirVesselId",
+    fuel_estimation_model_id AS "model!: FuelEstimationModel",
+    COUNT(*) AS "num_ranges!",
+    SUM(measured_liter) AS "measured_liter!",
+    SUM(estimated_liter) AS "estimated_liter!",
+    AVG(ABS(estimated_liter - measured_liter)) AS "mae_liter!",
+    AVG(estimated_liter - measured_liter) AS "bias_liter!",
+    AVG(ABS(estimated_liter - measured_liter) / measured_liter) * 100 AS "mape!"
+FROM
+    fuel_backtest_results
+WHERE
+    (
+        $1::BIGINT IS NULL
+        OR fiskeridir_vessel_id = $1
+    )
+GROUP BY
+    GROUPING SETS (
+        (fuel_estimation_model_id),
+        (fiskeridir_vessel_id, fuel_estimation_model_id)
+    )
+HAVING
+    -- The metrics across all vessels are identical to the vessel's own metrics when filtering
+    -- on a single vessel
+    $1::BIGINT IS NULL
+    OR GROUPING(fiskeridir_vessel_id) = 0
+ORDER BY
+    fiskeridir_vessel_id NULLS FIRST,
+    fuel_estimation_model_id
+            "#,
+            query.fiskeridir_vessel_id.map(|v| v.into_inner()),
+        )
+        .fetch(&self.pool)
+        .map_err(|e| e.into())
+    }
 }
@@ -0,0 +1,423 @@
+use fiskeridir_rs::CallSign;
+use futures::{Stream, TryStreamExt};
+use geo_types::geometry::Geometry;
+use geozero::wkb;
+use kyogre_core::{
+    AisPermission, BarentswatchUserId, CatchLocationId, EarliestVmsUsedBy,
+    FISHING_FACILITY_GEOFENCE_BUFFER_METERS, FiskeridirVesselId, GeofenceArea, GeofenceAreaType,
+    GeofenceEvent, GeofenceEventType, GeofenceEventsQuery, GeofenceId, GeofenceVessel,
+    GeofenceVesselUpdate, LEISURE_VESSEL_LENGTH_AIS_BOUNDARY, LEISURE_VESSEL_SHIP_TYPES, Mmsi,
+    NewGeofence, Object, PRIVATE_AIS_DATA_VESSEL_LENGTH_BOUNDARY,
+};
+
+use crate::{
+    PostgresAdapter,
+    error::{ObjectNotFoundSnafu, Result},
+    models,
+};
+
+impl PostgresAdapter {
+    pub(crate) async fn add_geofence_impl(
+        &self,
+        geofence: &NewGeofence,
+        user_id: BarentswatchUserId,
+    ) -> Result<kyogre_core::Geofence> {
+        let area_type = geofence.area.area_type();
+
+        let inserted = match &geofence.area {
+            GeofenceArea::Polygon(polygon) => {
+                sqlx::query_as!(
+                    models::Geofence,
+                    r#"
+INSERT INTO
+    geofences (
+        barentswatch_user_id,
+        "name",
+        geofence_area_type_id,
+        "polygon"
+    )
+VALUES
+    ($1, $2, $3, $4::GEOMETRY)
+RETURNING
+    geofence_id AS "id!: GeofenceId",
+    "name",
+    geofence_area_type_id AS "area_type!: GeofenceAreaType",
+    catch_location_id AS "catch_location_id: CatchLocationId",
+    fishing_facility_tool_id,
+    "polygon" AS "polygon!: _",
+    created_at
+                    "#,
+                    user_id.as_ref(),
+                    geofence.name,
+                    area_type as i32,
+                    wkb::Encode(Geometry::Polygon(polygon.clone())) as _,
+                )
+                .fetch_one(&self.pool)
+                .await?
+            }
+            GeofenceArea::CatchLocation(id) => sqlx::query_as!(
+                models::Geofence,
+                r#"
+INSERT INTO
+    geofences (
+        barentswatch_user_id,
+        "name",
+        geofence_area_type_id,
+        catch_location_id,
+        "polygon"
+    )
+SELECT
+    $1,
+    $2,
+    $3,
+    catch_location_id,
+    "polygon"
+FROM
+    catch_locations
+WHERE
+    catch_location_id = $4
+RETURNING
+    geofence_id AS "id!: GeofenceId",
+    "name",
+    geofence_area_type_id AS "area_type!: GeofenceAreaType",
+    catch_location_id AS "catch_location_id: CatchLocationId",
+    fishing_facility_tool_id,
+    "polygon" AS "polygon!: _",
+    created_at
+                "#,
+                user_id.as_ref(),
+                geofence.name,
+                area_type as i32,
+                id.as_ref(),
+            )
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| {
+                ObjectNotFoundSnafu {
+                    object: Object::CatchLocation(id.clone()),
+                }
+                .build()
+            })?,
+            GeofenceArea::FishingFacility(tool_id) => sqlx::query_as!(
+                models::Geofence,
+                r#"
+INSERT INTO
+    geofences (
+        barentswatch_user_id,
+        "name",
+        geofence_area_type_id,
+        fishing_facility_tool_id,
+        "polygon"
+    )
+SELECT
+    $1,
+    $2,
+    $3,
+    tool_id,
+    ST_BUFFER (geometry_wkt::geography, $5)::geometry
+FROM
+    fishing_facilities
+WHERE
+    tool_id = $4
+    AND geometry_wkt IS NOT NULL
+RETURNING
+    geofence_id AS "id!: GeofenceId",
+    "name",
+    geofence_area_type_id AS "area_type!: GeofenceAreaType",
+    catch_location_id AS "catch_location_id: CatchLocationId",
+    fishing_facility_tool_id,
+    "polygon" AS "polygon!: _",
+    created_at
+                "#,
+                user_id.as_ref(),
+                geofence.name,
+                area_type as i32,
+                tool_id,
+                FISHING_FACILITY_GEOFENCE_BUFFER_METERS,
+            )
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| {
+                ObjectNotFoundSnafu {
+                    object: Object::FishingFacility(*tool_id),
+                }
+                .build()
+            })?,
+        };
+
+        inserted.try_into()
+    }
+
+    pub(crate) async fn delete_geofence_impl(
+        &self,
+        id: GeofenceId,
+        user_id: BarentswatchUserId,
+    ) -> Result<()> {
+        let result = sqlx::query!(
+            r#"
+DELETE FROM geofences
+WHERE
+    geofence_id = $1
+    AND barentswatch_user_id = $2
+            "#,
+            i64::from(id),
+            user_id.as_ref(),
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return ObjectNotFoundSnafu {
+                object: Object::Geofence(id),
+            }
+            .fail();
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn geofences_impl(
+        &self,
+        user_id: BarentswatchUserId,
+    ) -> impl Stream<Item = Result<kyogre_core::Geofence>> + '_ {
+        sqlx::query_as!(
+            models::Geofence,
+            r#"
+SELECT
+    geofence_id AS "id!: GeofenceId",
+    "name",
+    geofence_area_type_id AS "area_type!: GeofenceAreaType",
+    catch_location_id AS "catch_location_id: CatchLocationId",
+    fishing_facility_tool_id,
+    "polygon" AS "polygon!: _",
+    created_at
+FROM
+    geofences
+WHERE
+    barentswatch_user_id = $1
+ORDER BY
+    geofence_id
+            "#,
+            user_id.as_ref(),
+        )
+        .fetch(&self.pool)
+        .map_err(|e| e.into())
+        .and_then(|v| async move { v.try_into() })
+    }
+
+    pub(crate) async fn all_geofences_impl(&self) -> Result<Vec<kyogre_core::Geofence>> {
+        sqlx::query_as!(
+            models::Geofence,
+            r#"
+SELECT
+    geofence_id AS "id!: GeofenceId",
+    "name",
+    geofence_area_type_id AS "area_type!: GeofenceAreaType",
+    catch_location_id AS "catch_location_id: CatchLocationId",
+    fishing_facility_tool_id,
+    "polygon" AS "polygon!: _",
+    created_at
+FROM
+    geofences
+            "#,
+        )
+        .fetch(&self.pool)
+        .map_err(|e| e.into())
+        .and_then(|v| async move { v.try_into() })
+        .try_collect()
+        .await
+    }
+
+    pub(crate) async fn geofence_vessels_impl(&self) -> Result<Vec<GeofenceVessel>> {
+        let vessels = sqlx::query_as!(
+            GeofenceVessel,
+            r#"
+SELECT
+    g.geofence_id AS "geofence_id!: GeofenceId",
+    v.fiskeridir_vessel_id AS "fiskeridir_vessel_id!: FiskeridirVesselId",
+    v.mmsi AS "mmsi: Mmsi",
+    v.call_sign AS "call_sign: CallSign",
+    s.inside AS "inside?",
+    s.processed_until AS "processed_until?",
+    e.timestamp AS "earliest_vms_insertion?"
+FROM
+    geofences g
+    INNER JOIN user_follows u ON g.barentswatch_user_id = u.barentswatch_user_id
+    INNER JOIN active_vessels v ON u.fiskeridir_vessel_id = v.fiskeridir_vessel_id
+    LEFT JOIN geofence_vessels s ON g.geofence_id = s.geofence_id
+    AND v.fiskeridir_vessel_id = s.fiskeridir_vessel_id
+    LEFT JOIN earliest_vms_insertion e ON v.call_sign = e.call_sign
+    AND e.used_by = $1
+WHERE
+    v.mmsi IS NOT NULL
+    OR v.call_sign IS NOT NULL
+            "#,
+            EarliestVmsUsedBy::GeofenceProcessor as i32,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(vessels)
+    }
+
+    pub(crate) async fn update_geofence_vessels_impl(
+        &self,
+        updates: &[GeofenceVesselUpdate],
+    ) -> Result<()> {
+        let len = updates.len();
+        let mut geofence_ids = Vec::with_capacity(len);
+        let mut vessel_ids = Vec::with_capacity(len);
+        let mut replace_events_from = Vec::with_capacity(len);
+        let mut call_signs = Vec::with_capacity(len);
+        let mut earliest_vms_insertions = Vec::with_capacity(len);
+
+        for u in updates {
+            if let Some(timestamp) = u.replace_events_from {
+                geofence_ids.push(i64::from(u.geofence_id));
+                vessel_ids.push(u.fiskeridir_vessel_id);
+                replace_events_from.push(timestamp);
+            }
+            if let Some(call_sign) = &u.call_sign
+                && let Some(timestamp) = u.earliest_vms_insertion
+            {
+                call_signs.push(call_sign.as_ref());
+                earliest_vms_insertions.push(timestamp);
+            }
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+DELETE FROM geofence_events e USING UNNEST($1::BIGINT[], $2::BIGINT[], $3::TIMESTAMPTZ[]) u (
+    geofence_id,
+    fiskeridir_vessel_id,
+    "timestamp"
+)
+WHERE
+    e.geofence_id = u.geofence_id
+    AND e.fiskeridir_vessel_id = u.fiskeridir_vessel_id
+    AND e.timestamp >= u.timestamp
+            "#,
+            &geofence_ids as &[i64],
+            &vessel_ids as &[FiskeridirVesselId],
+            &replace_events_from,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        self.unnest_insert_from::<_, _, models::GeofenceVessel>(updates, &mut *tx)
+            .await?;
+        self.unnest_insert_from::<_, _, models::NewGeofenceEvent>(
+            updates.iter().flat_map(|v| &v.events),
+            &mut *tx,
+        )
+        .await?;
+
+        // VMS positions added while processing lower the timestamp, which must then be kept
+        sqlx::query!(
+            r#"
+DELETE FROM earliest_vms_insertion e USING UNNEST($1::TEXT[], $2::TIMESTAMPTZ[]) u (call_sign, "timestamp")
+WHERE
+    e.call_sign = u.call_sign
+    AND e.used_by = $3
+    AND e.timestamp >= u.timestamp
+            "#,
+            &call_signs as &[&str],
+            &earliest_vms_insertions,
+            EarliestVmsUsedBy::GeofenceProcessor as i32,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub(crate) fn geofence_events_impl(
+        &self,
+        query: GeofenceEventsQuery,
+        permission: AisPermission,
+    ) -> impl Stream<Item = Result<GeofenceEvent>> + '_ {
+        let GeofenceEventsQuery {
+            user_id,
+            geofence_id,
+            fiskeridir_vessel_id,
+            range,
+            limit,
+            offset,
+        } = query;
+
+        sqlx::query_as!(
+            GeofenceEvent,
+            r#"
+SELECT
+    e.geofence_id AS "geofence_id!: GeofenceId",
+    e.fiskeridir_vessel_id AS "fiskeridir_vessel_id!: FiskeridirVesselId",
+    e.geofence_event_type_id AS "event_type!: GeofenceEventType",
+    e.timestamp,
+    e.latitude,
+    e.longitude
+FROM
+    geofence_events e
+    INNER JOIN geofences g ON e.geofence_id = g.geofence_id
+    INNER JOIN active_vessels m ON e.fiskeridir_vessel_id = m.fiskeridir_vessel_id
+WHERE
+    g.barentswatch_user_id = $1
+    AND (
+        $2::BIGINT IS NULL
+        OR e.geofence_id = $2
+    )
+    AND (
+        $3::BIGINT IS NULL
+        OR e.fiskeridir_vessel_id = $3
+    )
+    AND (
+        $4::TIMESTAMPTZ IS NULL
+        OR e.timestamp >= $4
+    )
+    AND (
+        $5::TIMESTAMPTZ IS NULL
+        OR e.timestamp <= $5
+    )
+    AND (
+        m.mmsi IS NULL
+        OR (
+            CASE
+                WHEN $6 = 0 THEN TRUE
+                WHEN $6 = 1 THEN (
+                    length >= $7
+                    AND (
+                        ship_type IS NOT NULL
+                        AND NOT (ship_type = ANY ($8::INT[]))
+                        OR length > $9
+                    )
+                )
+            END
+        )
+    )
+ORDER BY
+    e.timestamp DESC,
+    e.geofence_event_id DESC
+LIMIT
+    $10
+OFFSET
+    $11
+            "#,
+            user_id.as_ref(),
+            geofence_id.map(i64::from),
+            fiskeridir_vessel_id.map(|v| v.into_inner()),
+            range.start(),
+            range.end(),
+            permission as i32,
+            PRIVATE_AIS_DATA_VESSEL_LENGTH_BOUNDARY as i32,
+            LEISURE_VESSEL_SHIP_TYPES.as_slice(),
+            LEISURE_VESSEL_LENGTH_AIS_BOUNDARY as i32,
+            limit.map(|v| v as i64),
+            offset.map(|v| v as i64),
+        )
+        .fetch(&self.pool)
+        .map_err(|e| e.into())
+    }
+}
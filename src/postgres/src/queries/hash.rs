@@ -1,9 +1,12 @@
-use crate::{PostgresAdapter, error::Result};
+use crate::{PostgresAdapter, error::Result, models::NewFileParseError};
 use fiskeridir_rs::DataFileId;
 use futures::TryStreamExt;
+use kyogre_core::{FileCheckpoint, FileParseError};
 
 impl PostgresAdapter {
     pub(crate) async fn add_hash(&self, id: &DataFileId, hash: String) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
         sqlx::query!(
             r#"
 INSERT INTO
@@ -18,9 +21,22 @@ SET
             hash,
             id.as_ref(),
         )
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
 
+        sqlx::query!(
+            r#"
+DELETE FROM file_checkpoints
+WHERE
+    file_hash_id = $1
+            "#,
+            id.as_ref(),
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
         Ok(())
     }
 
@@ -45,4 +61,119 @@ WHERE
         .try_collect()
         .await?)
     }
+
+    pub(crate) async fn set_checkpoint_impl(
+        &self,
+        checkpoint: &FileCheckpoint,
+        parse_errors: Vec<FileParseError>,
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        // Parse errors of previous versions of the file are no longer relevant
+        sqlx::query!(
+            r#"
+DELETE FROM file_parse_errors
+WHERE
+    file_hash_id = $1
+    AND file_hash <> $2
+            "#,
+            checkpoint.file_id.as_ref(),
+            checkpoint.file_hash,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        self.unnest_insert_from::<_, _, NewFileParseError<'_>>(&parse_errors, &mut *tx)
+            .await?;
+
+        sqlx::query!(
+            r#"
+INSERT INTO
+    file_checkpoints (file_hash_id, file_hash, row_offset, chunk_hash)
+VALUES
+    ($1, $2, $3, $4)
+ON CONFLICT (file_hash_id) DO UPDATE
+SET
+    file_hash = excluded.file_hash,
+    row_offset = excluded.row_offset,
+    chunk_hash = excluded.chunk_hash,
+    updated_at = NOW()
+            "#,
+            checkpoint.file_id.as_ref(),
+            checkpoint.file_hash,
+            checkpoint.row_offset as i64,
+            checkpoint.chunk_hash,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn checkpoint_impl(&self, id: &DataFileId) -> Result<Option<FileCheckpoint>> {
+        Ok(sqlx::query!(
+            r#"
+SELECT
+    file_hash_id AS "file_id!: DataFileId",
+    file_hash,
+    row_offset,
+    chunk_hash
+FROM
+    file_checkpoints
+WHERE
+    file_hash_id = $1
+            "#,
+            id.as_ref(),
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .map(|r| FileCheckpoint {
+            file_id: r.file_id,
+            file_hash: r.file_hash,
+            row_offset: r.row_offset as u64,
+            chunk_hash: r.chunk_hash,
+        }))
+    }
+
+    pub(crate) async fn parse_errors_impl(&self, id: &DataFileId) -> Result<Vec<FileParseError>> {
+        Ok(sqlx::query!(
+            r#"
+SELECT
+    e.file_hash_id AS "file_id!: DataFileId",
+    e.file_hash,
+    e.line_number,
+    e.error
+FROM
+    file_parse_errors e
+WHERE
+    e.file_hash_id = $1
+    AND e.file_hash = (
+        SELECT
+            f.file_hash
+        FROM
+            file_parse_errors f
+        WHERE
+            f.file_hash_id = $1
+        ORDER BY
+            f.created_at DESC
+        LIMIT
+            1
+    )
+ORDER BY
+    e.line_number
+            "#,
+            id.as_ref(),
+        )
+        .fetch(&self.pool)
+        .map_ok(|r| FileParseError {
+            file_id: r.file_id,
+            file_hash: r.file_hash,
+            line: r.line_number as u64,
+            error: r.error,
+        })
+        .try_collect()
+        .await?)
+    }
 }
@@ -207,7 +207,14 @@ SELECT
     h.vessel_length_group AS "vessel_length_group!: VesselLengthGroup",
     COALESCE(h.vessel_name, h.vessel_name_ers) AS vessel_name,
     COALESCE(h.vessel_call_sign, h.vessel_call_sign_ers) AS "call_sign!: CallSign",
-    h.catches::TEXT AS "catches!"
+    h.catches::TEXT AS "catches!",
+    estimated_catch_value(
+        h.catches,
+        h.gear_group_id,
+        h.vessel_length_group,
+        h.stop_timestamp
+    ) AS estimated_value,
+    h.water_temperature
 FROM
     hauls h
     LEFT JOIN vessel_events e ON h.vessel_event_id = e.vessel_event_id
@@ -170,9 +170,83 @@ LIMIT
         data_year: u32,
     ) -> Result<()> {
         let mut tx = self.pool.begin().await?;
-
         let existing_landings = self.existing_landings(data_year, &mut tx).await?;
+        self.add_landings_inner(landings, data_year, existing_landings, true, &mut tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn add_landings_chunk_impl(
+        &self,
+        landings: Vec<fiskeridir_rs::Landing>,
+        data_year: u32,
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        let landing_ids = landings.iter().map(|l| l.id.clone()).collect::<Vec<_>>();
+        let existing_landings = self.existing_landings_of_ids(&landing_ids, &mut tx).await?;
+        self.add_landings_inner(
+            Box::new(landings.into_iter().map(Ok)),
+            data_year,
+            existing_landings,
+            false,
+            &mut tx,
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn delete_removed_landings_impl(
+        &self,
+        landing_ids: &[LandingId],
+        data_year: u32,
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let mut trip_assembler_conflicts = HashMap::new();
 
+        self.delete_removed_landings(
+            landing_ids,
+            &mut trip_assembler_conflicts,
+            data_year,
+            &mut tx,
+        )
+        .await?;
+        self.add_trip_assembler_conflicts(
+            trip_assembler_conflicts.into_values().collect(),
+            TripAssemblerId::Landings,
+            &mut tx,
+        )
+        .await?;
+
+        let vessel_ids = self.landing_vessel_ids(data_year, &mut tx).await?;
+        self.add_vessel_gear_and_species_groups(vessel_ids, &mut tx)
+            .await?;
+
+        self.set_landing_vessels_call_signs(&mut tx).await?;
+        self.refresh_vessel_mappings(&mut tx).await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Adds the given landings, skipping those in `existing_landings` with the same or a newer
+    /// version.
+    /// If `delete_removed` is set the landings of the data year that are not among them are
+    /// deleted and the vessels are updated, otherwise both are left to
+    /// `delete_removed_landings_impl` once the whole file has been added.
+    async fn add_landings_inner<'a>(
+        &'a self,
+        landings: BoxIterator<fiskeridir_rs::Result<fiskeridir_rs::Landing>>,
+        data_year: u32,
+        existing_landings: HashMap<LandingId, i32>,
+        delete_removed: bool,
+        tx: &mut sqlx::Transaction<'a, sqlx::Postgres>,
+    ) -> Result<()> {
         let mut all_vessel_ids = HashSet::new();
         let mut existing_landing_ids = HashSet::new();
         let mut inserted_landing_ids = HashSet::new();
@@ -220,7 +294,7 @@ LIMIT
                 &mut inserted_landing_ids,
                 &mut vessel_event_ids,
                 &mut trip_assembler_conflicts,
-                &mut tx,
+                tx,
             )
             .await?;
 
@@ -230,32 +304,34 @@ LIMIT
         let existing_landing_ids = existing_landing_ids.into_iter().collect::<Vec<_>>();
         let inserted_landing_ids = inserted_landing_ids.into_iter().collect::<Vec<_>>();
 
-        self.delete_removed_landings(
-            &existing_landing_ids,
-            &mut trip_assembler_conflicts,
-            data_year,
-            &mut tx,
-        )
-        .await?;
-
-        self.add_landing_matrix(&inserted_landing_ids, &mut tx)
+        if delete_removed {
+            self.delete_removed_landings(
+                &existing_landing_ids,
+                &mut trip_assembler_conflicts,
+                data_year,
+                tx,
+            )
             .await?;
+        }
+
+        self.add_landing_matrix(&inserted_landing_ids, tx).await?;
 
         self.add_trip_assembler_conflicts(
             trip_assembler_conflicts.into_values().collect(),
             TripAssemblerId::Landings,
-            &mut tx,
+            tx,
         )
         .await?;
-        self.connect_trip_to_events(&vessel_event_ids, VesselEventType::Landing, &mut tx)
-            .await?;
-        self.add_vessel_gear_and_species_groups(all_vessel_ids, &mut tx)
+        self.connect_trip_to_events(&vessel_event_ids, VesselEventType::Landing, tx)
             .await?;
 
-        self.set_landing_vessels_call_signs(&mut tx).await?;
-        self.refresh_vessel_mappings(&mut tx).await?;
+        if delete_removed {
+            self.add_vessel_gear_and_species_groups(all_vessel_ids, tx)
+                .await?;
 
-        tx.commit().await?;
+            self.set_landing_vessels_call_signs(tx).await?;
+            self.refresh_vessel_mappings(tx).await?;
+        }
 
         Ok(())
     }
@@ -431,6 +507,56 @@ WHERE
         Ok(landings)
     }
 
+    async fn existing_landings_of_ids<'a>(
+        &'a self,
+        landing_ids: &[LandingId],
+        tx: &mut sqlx::Transaction<'a, sqlx::Postgres>,
+    ) -> Result<HashMap<LandingId, i32>> {
+        let landings = sqlx::query!(
+            r#"
+SELECT
+    landing_id AS "id!: LandingId",
+    "version"
+FROM
+    landings
+WHERE
+    landing_id = ANY ($1::TEXT[])
+            "#,
+            landing_ids as &[LandingId],
+        )
+        .fetch(&mut **tx)
+        .map_ok(|r| (r.id, r.version))
+        .try_collect::<HashMap<_, _>>()
+        .await?;
+
+        Ok(landings)
+    }
+
+    async fn landing_vessel_ids<'a>(
+        &'a self,
+        data_year: u32,
+        tx: &mut sqlx::Transaction<'a, sqlx::Postgres>,
+    ) -> Result<HashSet<FiskeridirVesselId>> {
+        let vessel_ids = sqlx::query!(
+            r#"
+SELECT DISTINCT
+    fiskeridir_vessel_id AS "fiskeridir_vessel_id!: FiskeridirVesselId"
+FROM
+    landings
+WHERE
+    data_year = $1
+    AND fiskeridir_vessel_id IS NOT NULL
+            "#,
+            data_year as i32,
+        )
+        .fetch(&mut **tx)
+        .map_ok(|r| r.fiskeridir_vessel_id)
+        .try_collect::<HashSet<_>>()
+        .await?;
+
+        Ok(vessel_ids)
+    }
+
     pub(crate) async fn delete_removed_landings<'a>(
         &'a self,
         existing_landing_ids: &[LandingId],
@@ -3,8 +3,10 @@ pub mod ais_vms;
 pub mod assert;
 pub mod catch_location;
 pub mod current_position;
+pub mod data_quality;
 pub mod delivery_point;
 pub mod duckdb;
+pub mod emissions;
 pub mod ers_dca;
 pub mod ers_dep;
 pub mod ers_por;
@@ -12,6 +14,7 @@ pub mod ers_tra;
 pub mod fishing_facility;
 pub mod fuel;
 pub mod fuel_measurement;
+pub mod geofence;
 pub mod hash;
 pub mod haul;
 pub mod landing;
@@ -19,6 +22,7 @@ pub mod landing_matrix;
 pub mod ocean_climate;
 pub mod org;
 pub mod port;
+pub mod port_call;
 pub mod price;
 pub mod processor;
 pub mod rafisklaget;
@@ -27,6 +31,7 @@ pub mod species;
 pub mod test;
 pub mod trip;
 pub mod trip_benchmarks;
+pub mod trip_explanation;
 pub mod unnest_insert;
 pub mod unnest_update;
 pub mod user;
@@ -1,6 +1,9 @@
 use chrono::{DateTime, Utc};
 use futures::{Stream, TryStreamExt};
-use kyogre_core::{HaulOceanClimate, OceanClimate, OceanClimateQuery, WeatherLocationId};
+use kyogre_core::{
+    CatchLocationId, CatchLocationOceanClimate, CatchLocationOceanClimateQuery, EmptyVecToNone,
+    HaulOceanClimate, OceanClimate, OceanClimateQuery, WeatherLocationId,
+};
 
 use crate::{PostgresAdapter, error::Result, models::NewOceanClimate};
 
@@ -58,6 +61,53 @@ GROUP BY
         .map_err(|e| e.into())
     }
 
+    pub(crate) fn catch_location_ocean_climate_impl(
+        &self,
+        query: CatchLocationOceanClimateQuery,
+    ) -> impl Stream<Item = Result<CatchLocationOceanClimate>> + '_ {
+        sqlx::query_as!(
+            CatchLocationOceanClimate,
+            r#"
+SELECT
+    c.catch_location_id AS "catch_location_id!: CatchLocationId",
+    o."depth" AS "depth!",
+    AVG(o.water_speed) AS "water_speed",
+    AVG(o.water_direction) AS "water_direction",
+    AVG(o.upward_sea_velocity) AS "upward_sea_velocity",
+    AVG(o.wind_speed) AS "wind_speed",
+    AVG(o.wind_direction) AS "wind_direction",
+    AVG(o.salinity) AS "salinity",
+    AVG(o.temperature) AS "temperature",
+    AVG(o.sea_floor_depth) AS "sea_floor_depth!"
+FROM
+    catch_locations c
+    INNER JOIN ocean_climate o ON o.weather_location_id = ANY (c.weather_location_ids)
+WHERE
+    o."timestamp" BETWEEN $1::TIMESTAMPTZ AND $2::TIMESTAMPTZ
+    AND (
+        $3::INT[] IS NULL
+        OR o."depth" = ANY ($3)
+    )
+    AND (
+        $4::VARCHAR[] IS NULL
+        OR c.catch_location_id = ANY ($4)
+    )
+GROUP BY
+    c.catch_location_id,
+    o."depth"
+ORDER BY
+    c.catch_location_id,
+    o."depth"
+            "#,
+            query.start,
+            query.end,
+            query.depths.as_deref(),
+            query.catch_location_ids.empty_to_none() as Option<Vec<CatchLocationId>>,
+        )
+        .fetch(&self.pool)
+        .map_err(|e| e.into())
+    }
+
     pub(crate) async fn haul_ocean_climate_impl(
         &self,
         query: OceanClimateQuery,
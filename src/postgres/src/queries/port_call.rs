@@ -0,0 +1,66 @@
+use kyogre_core::FiskeridirVesselId;
+
+use crate::{
+    PostgresAdapter,
+    error::Result,
+    models::{AisVmsPortCallTimer, NewAisVmsPortCall},
+};
+
+impl PostgresAdapter {
+    pub(crate) async fn add_ais_vms_port_calls_impl(
+        &self,
+        vessel_id: FiskeridirVesselId,
+        port_calls: Vec<kyogre_core::NewAisVmsPortCall>,
+        timer: kyogre_core::AisVmsPortCallTimer,
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        self.unnest_insert_from::<_, _, NewAisVmsPortCall<'_>>(&port_calls, &mut *tx)
+            .await?;
+
+        sqlx::query!(
+            r#"
+INSERT INTO
+    ais_vms_port_call_timers (fiskeridir_vessel_id, timer, port_id)
+VALUES
+    ($1, $2, $3)
+ON CONFLICT (fiskeridir_vessel_id) DO UPDATE
+SET
+    timer = EXCLUDED.timer,
+    port_id = EXCLUDED.port_id
+            "#,
+            vessel_id.into_inner(),
+            timer.timestamp,
+            timer.port_id,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn ais_vms_port_call_timer_impl(
+        &self,
+        vessel_id: FiskeridirVesselId,
+    ) -> Result<Option<AisVmsPortCallTimer>> {
+        let timer = sqlx::query_as!(
+            AisVmsPortCallTimer,
+            r#"
+SELECT
+    timer,
+    port_id
+FROM
+    ais_vms_port_call_timers
+WHERE
+    fiskeridir_vessel_id = $1
+            "#,
+            vessel_id.into_inner(),
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(timer)
+    }
+}
@@ -1 +1,2 @@
+pub mod price_forecast;
 pub mod weekly_sales;
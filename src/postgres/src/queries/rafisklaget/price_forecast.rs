@@ -0,0 +1,147 @@
+use fiskeridir_rs::{Condition, GearGroup, Quality, SpeciesFiskeridirId, VesselLengthGroup};
+use futures::{Stream, TryStreamExt};
+use kyogre_core::{
+    NewPriceForecast, PRICE_SERIES_DEFAULT_WEEKS, PriceForecast, PricePoint, PriceSeries,
+    PriceSeriesQuery,
+};
+
+use crate::{
+    PostgresAdapter,
+    error::Result,
+    models::{self, WeeklyPrice},
+};
+
+impl PostgresAdapter {
+    pub(crate) fn weekly_prices_impl(&self) -> impl Stream<Item = Result<WeeklyPrice>> + '_ {
+        sqlx::query_as!(
+            WeeklyPrice,
+            r#"
+SELECT
+    "year",
+    week,
+    vessel_length_group AS "vessel_length_group!: VesselLengthGroup",
+    gear_group AS "gear_group!: GearGroup",
+    species AS "species!: SpeciesFiskeridirId",
+    condition AS "condition!: Condition",
+    quality AS "quality!: Quality",
+    sum_price / sum_calculated_living_weight AS "price!"
+FROM
+    rafisklaget_weekly_sales
+WHERE
+    sum_calculated_living_weight > 0
+ORDER BY
+    "year",
+    week
+            "#
+        )
+        .fetch(&self.pool)
+        .map_err(|e| e.into())
+    }
+
+    pub(crate) async fn set_price_forecasts_impl(
+        &self,
+        forecasts: &[NewPriceForecast],
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+DELETE FROM rafisklaget_price_forecasts
+            "#
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        self.unnest_insert_from::<_, _, models::NewPriceForecast>(forecasts, &mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn price_series_impl(&self, query: &PriceSeriesQuery) -> Result<PriceSeries> {
+        let PriceSeriesQuery {
+            length_group,
+            gear_group,
+            species,
+            condition,
+            quality,
+            weeks,
+        } = query;
+
+        let prices = sqlx::query_as!(
+            PricePoint,
+            r#"
+SELECT
+    q."year" AS "year!",
+    q.week AS "week!",
+    q.price AS "price!"
+FROM
+    (
+        SELECT
+            "year",
+            week,
+            sum_price / sum_calculated_living_weight AS price
+        FROM
+            rafisklaget_weekly_sales
+        WHERE
+            vessel_length_group = $1
+            AND gear_group = $2
+            AND species = $3
+            AND condition = $4
+            AND quality = $5
+            AND sum_calculated_living_weight > 0
+        ORDER BY
+            "year" DESC,
+            week DESC
+        LIMIT
+            $6
+    ) q
+ORDER BY
+    q."year",
+    q.week
+            "#,
+            length_group as &VesselLengthGroup,
+            gear_group as &GearGroup,
+            species as &SpeciesFiskeridirId,
+            condition as &Condition,
+            quality as &Quality,
+            weeks.unwrap_or(PRICE_SERIES_DEFAULT_WEEKS) as i64,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let forecast = sqlx::query_as!(
+            PriceForecast,
+            r#"
+SELECT
+    "year",
+    week,
+    price,
+    lower_bound,
+    upper_bound
+FROM
+    rafisklaget_price_forecasts
+WHERE
+    vessel_length_group = $1
+    AND gear_group = $2
+    AND species = $3
+    AND condition = $4
+    AND quality = $5
+ORDER BY
+    "year",
+    week
+            "#,
+            length_group as &VesselLengthGroup,
+            gear_group as &GearGroup,
+            species as &SpeciesFiskeridirId,
+            condition as &Condition,
+            quality as &Quality,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(PriceSeries { prices, forecast })
+    }
+}
@@ -163,6 +163,75 @@ WHERE
         .execute(&mut *tx)
         .await?;
 
+        sqlx::query!(
+            r#"
+INSERT INTO
+    rafisklaget_weekly_unit_prices (
+        gear_group,
+        vessel_length_group,
+        species,
+        "year",
+        week,
+        unit_price
+    )
+SELECT
+    w.gear_group,
+    w.vessel_length_group,
+    w.species,
+    w."year",
+    w.week,
+    SUM(w.sum_price) / SUM(w.sum_calculated_living_weight)
+FROM
+    rafisklaget_weekly_sales w
+    INNER JOIN (
+        SELECT DISTINCT
+            *
+        FROM
+            UNNEST(
+                $1::INT[],
+                $2::INT[],
+                $3::INT[],
+                $4::INT[],
+                $5::INT[]
+            ) u (
+                species,
+                "year",
+                week,
+                vessel_length_group,
+                gear_group
+            )
+    ) u ON w.species = u.species
+    AND w."year" = u."year"
+    AND w.week = u.week
+    AND w.vessel_length_group = u.vessel_length_group
+    AND w.gear_group = u.gear_group
+WHERE
+    w.sum_calculated_living_weight > 0
+GROUP BY
+    w.gear_group,
+    w.vessel_length_group,
+    w.species,
+    w."year",
+    w.week
+ON CONFLICT (
+    gear_group,
+    vessel_length_group,
+    species,
+    "year",
+    week
+) DO UPDATE
+SET
+    unit_price = EXCLUDED.unit_price
+            "#,
+            &species as &Vec<SpeciesFiskeridirId>,
+            &year,
+            &week,
+            &length_group as &Vec<VesselLengthGroup>,
+            &gear_group as &Vec<GearGroup>,
+        )
+        .execute(&mut *tx)
+        .await?;
+
         tx.commit().await?;
 
         Ok(())
@@ -1,4 +1,4 @@
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, Utc};
 use fiskeridir_rs::{CallSign, DeliveryPointId, Gear};
 use futures::{Stream, StreamExt, TryStreamExt};
 use kyogre_core::{
@@ -171,6 +171,23 @@ ORDER BY
         .await?)
     }
 
+    pub(crate) async fn weather_forecast_reference_times_impl(&self) -> Result<Vec<DateTime<Utc>>> {
+        Ok(sqlx::query!(
+            r#"
+SELECT DISTINCT
+    reference_time
+FROM
+    weather_forecasts
+ORDER BY
+    reference_time
+            "#,
+        )
+        .fetch(&self.pool)
+        .map_ok(|v| v.reference_time)
+        .try_collect()
+        .await?)
+    }
+
     pub(crate) async fn fuel_estimates_with_status_impl(
         &self,
         status: ProcessingStatus,
@@ -3,8 +3,9 @@ use crate::{
     error::Result,
     models::{
         CurrentTrip, NewTrip, NewTripAssemblerConflict, NewTripAssemblerLogEntry, NewTripReturning,
-        Trip, TripAisVmsPosition, TripAndSucceedingEventsErs, TripAndSucceedingEventsLandings,
-        TripCalculationTimer, TripDetailed, TripPrunedAisVmsPosition,
+        Trip, TripAisVmsPosition, TripAndSucceedingEventsAisVms, TripAndSucceedingEventsErs,
+        TripAndSucceedingEventsLandings, TripCalculationTimer, TripDetailed,
+        TripPrunedAisVmsPosition,
     },
 };
 use chrono::{DateTime, Utc};
@@ -742,6 +743,7 @@ WHERE
             .await?;
         }
         if let Some(precision) = update.precision {
+            let precision_attempts = serde_json::to_value(precision.attempts())?;
             let (
                 start_precision_id,
                 start_precision_direction,
@@ -754,6 +756,7 @@ WHERE
                     new_period,
                     start_precision,
                     end_precision,
+                    attempts: _,
                 } => (
                     start_precision.as_ref().map(|v| v.id as i32),
                     start_precision
@@ -766,7 +769,7 @@ WHERE
                     Some(PgRange::from(&new_period)),
                     ProcessingStatus::Successful as i32,
                 ),
-                PrecisionOutcome::Failed => (
+                PrecisionOutcome::Failed { attempts: _ } => (
                     None,
                     None,
                     None,
@@ -785,9 +788,10 @@ SET
     end_precision_id = $3,
     end_precision_direction = $4,
     period_precision = $5,
-    trip_precision_status_id = $6
+    trip_precision_status_id = $6,
+    precision_attempts = $7
 WHERE
-    trip_id = $7
+    trip_id = $8
                 "#,
                 start_precision_id,
                 start_precision_direction,
@@ -795,6 +799,7 @@ WHERE
                 end_precision_direction,
                 period_precision,
                 trip_precision_status_id,
+                precision_attempts,
                 update.trip_id.into_inner(),
             )
             .execute(&mut *tx)
@@ -1696,10 +1701,12 @@ WITH
     )
 SELECT
     departure_timestamp,
-    CASE
-        WHEN l.fiskeridir_vessel_id IS NOT NULL THEN t.ers_and_user_hauls
-        ELSE t.ers_hauls
-    END::TEXT AS "hauls!",
+    hauls_with_estimated_value(
+        CASE
+            WHEN l.fiskeridir_vessel_id IS NOT NULL THEN t.ers_and_user_hauls
+            ELSE t.ers_hauls
+        END
+    )::TEXT AS "hauls!",
     CASE
         WHEN $2 THEN fishing_facilities::TEXT
         ELSE '[]'
@@ -1806,15 +1813,29 @@ VALUES
             .iter()
             .map(|t| &t.trip)
             .min_by_key(|t| t.period.start())
-            .unwrap()
-            .clone();
-
-        let calculation_timer = value
-            .values
-            .iter()
-            .map(|t| t.trip.period.end())
-            .max()
-            .unwrap();
+            .cloned();
+
+        let calculation_timer = match (
+            value.values.iter().map(|t| t.trip.period.end()).max(),
+            value.prior_trip_calculation_time,
+        ) {
+            (Some(timer), _) => timer,
+            // Incremental trip assembly produces an empty set when the re-assembled trips are
+            // identical to the existing ones except for removed trips, which still have to be
+            // deleted by the conflict strategy.
+            (None, Some(timer)) if value.conflict_strategy != TripsConflictStrategy::Error => timer,
+            // Nothing changed, but the conflict still has to be resolved.
+            _ => {
+                return self
+                    .clear_trip_assembler_conflict(
+                        value.fiskeridir_vessel_id,
+                        value.trip_assembler_id,
+                        id,
+                        value.processed_event_ids,
+                    )
+                    .await;
+            }
+        };
 
         let log = NewTripAssemblerLogEntry {
             fiskeridir_vessel_id: value.fiskeridir_vessel_id,
@@ -1834,14 +1855,14 @@ VALUES
 
         let new_trips = value
             .values
-            .into_iter()
-            .map(|v| crate::models::NewTrip::from(&v))
-            .collect();
+            .iter()
+            .map(|v| crate::models::NewTrip::new(v, value.conflict_strategy))
+            .collect::<Result<_>>()?;
 
         if let Err(e) = self
             .add_trips_inner(
                 new_trips,
-                &earliest_trip,
+                earliest_trip.as_ref(),
                 value.fiskeridir_vessel_id,
                 value.trip_assembler_id,
                 value.conflict_strategy,
@@ -1863,7 +1884,7 @@ VALUES
     pub(crate) async fn add_trips_inner(
         &self,
         new_trips: Vec<NewTrip>,
-        earliest_trip: &kyogre_core::NewTrip,
+        earliest_trip: Option<&kyogre_core::NewTrip>,
         vessel_id: FiskeridirVesselId,
         trip_assembler_id: TripAssemblerId,
         conflict_strategy: TripsConflictStrategy,
@@ -1872,8 +1893,15 @@ VALUES
         id: TripAssemblerProcessingId,
         processed_event_ids: Vec<i64>,
     ) -> Result<()> {
-        let earliest_trip_start = earliest_trip.period.start();
-        let earliest_trip_period = PgRange::from(&earliest_trip.period);
+        // Without new trips the earliest change is the start of the deleted trips.
+        let earliest_trip_start = match (earliest_trip, conflict_strategy) {
+            (Some(t), _) => t.period.start(),
+            (None, TripsConflictStrategy::Replace { conflict }) => conflict,
+            (None, TripsConflictStrategy::ReplaceRange { start, .. }) => start,
+            (None, TripsConflictStrategy::ReplaceAll | TripsConflictStrategy::Error) => {
+                new_trip_calculation_time
+            }
+        };
 
         let mut tx = self.pool.begin().await?;
         // We assume that trip assemblers process all events and conflicts on each run and can
@@ -1888,7 +1916,10 @@ VALUES
     ($1, $2, $3)
 ON CONFLICT (fiskeridir_vessel_id) DO UPDATE
 SET
-    timer = EXCLUDED.timer,
+    timer = CASE
+        WHEN $5 THEN GREATEST(trip_calculation_timers.timer, EXCLUDED.timer)
+        ELSE EXCLUDED.timer
+    END,
     queued_reset = COALESCE($4, EXCLUDED.queued_reset),
     "conflict" = NULL,
     conflict_vessel_event_type_id = NULL,
@@ -1902,6 +1933,12 @@ SET
             // information. This can occur if we manually queue a reset while the trip assembler is
             // running.
             queued_reset.then_some(false),
+            // Trips after the replaced range are kept and the timer should therefore not move
+            // backwards.
+            matches!(
+                conflict_strategy,
+                TripsConflictStrategy::ReplaceRange { .. }
+            ),
         )
         .execute(&mut *tx)
         .await?;
@@ -1923,6 +1960,24 @@ WHERE
                 .execute(&mut *tx)
                 .await?;
             }
+            TripsConflictStrategy::ReplaceRange { start, end } => {
+                sqlx::query!(
+                    r#"
+DELETE FROM trips
+WHERE
+    LOWER(period) >= $1::TIMESTAMPTZ
+    AND LOWER(period) <= $2::TIMESTAMPTZ
+    AND fiskeridir_vessel_id = $3
+    AND trip_assembler_id = $4
+                    "#,
+                    start,
+                    end,
+                    vessel_id.into_inner(),
+                    trip_assembler_id as i32,
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
             TripsConflictStrategy::ReplaceAll => {
                 sqlx::query!(
                     r#"
@@ -1940,9 +1995,9 @@ WHERE
             TripsConflictStrategy::Error => {}
         };
 
-        let start_of_prior_trip: Option<DateTime<Utc>> = match trip_assembler_id {
-            TripAssemblerId::Landings => None,
-            TripAssemblerId::Ers => sqlx::query!(
+        let start_of_prior_trip: Option<DateTime<Utc>> = match (trip_assembler_id, earliest_trip) {
+            (TripAssemblerId::Landings, _) | (_, None) => None,
+            (TripAssemblerId::Ers | TripAssemblerId::AisVms, Some(earliest_trip)) => sqlx::query!(
                 r#"
 UPDATE trips
 SET
@@ -1966,12 +2021,17 @@ RETURNING
                 "#,
                 // The start of our earliest trip's landing_coverage is the end of the prior trips
                 // landing_coverage.
-                // 'first_arrival' should always be set by this assembler.
-                earliest_trip
-                    .period
-                    .ers_landing_coverage_start(earliest_trip.first_arrival.unwrap()),
+                match trip_assembler_id {
+                    // 'first_arrival' should always be set by this assembler.
+                    TripAssemblerId::Ers => earliest_trip
+                        .period
+                        .ers_landing_coverage_start(earliest_trip.first_arrival.unwrap()),
+                    TripAssemblerId::Landings | TripAssemblerId::AisVms => {
+                        earliest_trip.landing_coverage.start()
+                    }
+                },
                 vessel_id.into_inner(),
-                earliest_trip_period,
+                PgRange::from(&earliest_trip.period),
             )
             .fetch_optional(&mut *tx)
             .await?
@@ -2002,14 +2062,23 @@ RETURNING
 
         let boundary = self.trips_refresh_boundary(vessel_id, &mut tx).await?;
 
-        let boundary = if let Some(boundary) = boundary {
+        // Trips after a replaced range are unchanged and only need a refresh if there is a
+        // refresh boundary prior to them.
+        let (boundary, refresh_end) = if let Some(boundary) = boundary {
             if boundary < earliest {
-                boundary
+                (boundary, None)
             } else {
-                earliest
+                (earliest, None)
             }
         } else {
-            earliest
+            match conflict_strategy {
+                TripsConflictStrategy::ReplaceRange { .. } => {
+                    (earliest, Some(new_trip_calculation_time))
+                }
+                TripsConflictStrategy::Error
+                | TripsConflictStrategy::Replace { .. }
+                | TripsConflictStrategy::ReplaceAll => (earliest, None),
+            }
         };
 
         let refresh_trip_ids = sqlx::query!(
@@ -2021,9 +2090,14 @@ FROM
 WHERE
     t.fiskeridir_vessel_id = $1
     AND UPPER(t.period) >= $2
+    AND (
+        $3::TIMESTAMPTZ IS NULL
+        OR LOWER(t.period) <= $3
+    )
             "#,
             vessel_id.into_inner(),
             boundary,
+            refresh_end,
         )
         .fetch(&mut *tx)
         .map_ok(|r| r.trip_id)
@@ -2050,12 +2124,68 @@ WHERE
         .await?;
 
         match trip_assembler_id {
-            TripAssemblerId::Landings => (),
+            TripAssemblerId::Landings | TripAssemblerId::AisVms => (),
             TripAssemblerId::Ers => {
                 self.set_current_trip_impl(vessel_id, &mut tx).await?;
             }
         };
 
+        self.add_trip_assembler_processing_id(id, &processed_event_ids, &mut tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn clear_trip_assembler_conflict(
+        &self,
+        vessel_id: FiskeridirVesselId,
+        trip_assembler_id: TripAssemblerId,
+        id: TripAssemblerProcessingId,
+        processed_event_ids: Vec<i64>,
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+UPDATE trip_calculation_timers
+SET
+    "conflict" = NULL,
+    conflict_vessel_event_type_id = NULL,
+    conflict_vessel_event_id = NULL,
+    conflict_vessel_event_timestamp = NULL
+WHERE
+    fiskeridir_vessel_id = $1
+    AND trip_assembler_id = $2
+            "#,
+            vessel_id.into_inner(),
+            trip_assembler_id as i32,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        match trip_assembler_id {
+            TripAssemblerId::Landings | TripAssemblerId::AisVms => (),
+            TripAssemblerId::Ers => {
+                self.set_current_trip_impl(vessel_id, &mut tx).await?;
+            }
+        };
+
+        self.add_trip_assembler_processing_id(id, &processed_event_ids, &mut tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn add_trip_assembler_processing_id<'a>(
+        &'a self,
+        id: TripAssemblerProcessingId,
+        vessel_event_ids: &[i64],
+        tx: &mut sqlx::Transaction<'a, sqlx::Postgres>,
+    ) -> Result<()> {
         sqlx::query!(
             r#"
 UPDATE vessel_events
@@ -2065,13 +2195,11 @@ WHERE
     vessel_event_id = ANY ($2)
             "#,
             id as TripAssemblerProcessingId,
-            &processed_event_ids
+            vessel_event_ids
         )
-        .execute(&mut *tx)
+        .execute(&mut **tx)
         .await?;
 
-        tx.commit().await?;
-
         Ok(())
     }
 
@@ -2237,7 +2365,21 @@ WHERE
         AND v.fiskeridir_vessel_id = u.fiskeridir_vessel_id
     )
     OR (
-        $5 = 2
+        $5 = 3
+        AND v.vessel_event_type_id = 8
+        AND v.occurence_timestamp > LOWER(u.period)
+        AND v.occurence_timestamp <= UPPER(u.period)
+        AND v.fiskeridir_vessel_id = u.fiskeridir_vessel_id
+    )
+    OR (
+        $5 = 3
+        AND v.vessel_event_type_id = 7
+        AND v.occurence_timestamp >= LOWER(u.period)
+        AND v.occurence_timestamp < UPPER(u.period)
+        AND v.fiskeridir_vessel_id = u.fiskeridir_vessel_id
+    )
+    OR (
+        $5 != 1
         AND v.vessel_event_type_id = 1
         AND v.occurence_timestamp >= LOWER(u.landing_coverage)
         AND v.occurence_timestamp < UPPER(u.landing_coverage)
@@ -2541,6 +2683,136 @@ GROUP BY
         Ok(trip)
     }
 
+    pub(crate) async fn trip_prior_to_timestamp_ais_vms(
+        &self,
+        vessel_id: FiskeridirVesselId,
+        search_timestamp: TripSearchTimestamp,
+    ) -> Result<Option<TripAndSucceedingEventsAisVms>> {
+        let trip = sqlx::query_as!(
+            TripAndSucceedingEventsAisVms,
+            r#"
+WITH
+    trip AS (
+        SELECT
+            trip_id,
+            start_vessel_event_id,
+            end_vessel_event_id,
+            fiskeridir_vessel_id
+        FROM
+            trips
+        WHERE
+            fiskeridir_vessel_id = $1
+            AND (
+                (
+                    $2 = 1
+                    AND UPPER(period) <= $3
+                )
+                OR (
+                    $2 = 2
+                    AND UPPER(period) < $3
+                )
+            )
+        ORDER BY
+            period DESC
+        LIMIT
+            1
+    )
+SELECT
+    MAX(t.fiskeridir_vessel_id) AS "fiskeridir_vessel_id!: FiskeridirVesselId",
+    MAX(t.end_vessel_event_id) AS "arrival_vessel_event_id!",
+    MAX(a.port_id) AS "arrival_port_id",
+    MAX(a."timestamp") AS "arrival_timestamp!",
+    MAX(t.start_vessel_event_id) AS "departure_vessel_event_id!",
+    MAX(d.port_id) AS "departure_port_id",
+    MAX(d."timestamp") AS "departure_timestamp!",
+    COALESCE(
+        JSONB_AGG(
+            JSONB_BUILD_OBJECT(
+                'vessel_event_id',
+                e.vessel_event_id,
+                'fiskeridir_vessel_id',
+                e.fiskeridir_vessel_id,
+                'report_timestamp',
+                e."timestamp",
+                'estimated_timestamp',
+                e."timestamp",
+                'vessel_event_type_id',
+                e.vessel_event_type_id,
+                'departure_port_id',
+                NULL,
+                'arrival_port_id',
+                NULL,
+                'port_id',
+                e.port_id
+            )
+            ORDER BY
+                e."timestamp",
+                e.vessel_event_id
+        ) FILTER (
+            WHERE
+                e.vessel_event_id IS NOT NULL
+        ),
+        '[]'
+    )::TEXT AS "port_calls_after_trip!"
+FROM
+    trip t
+    INNER JOIN ais_vms_port_calls d ON d.vessel_event_id = t.start_vessel_event_id
+    INNER JOIN ais_vms_port_calls a ON a.vessel_event_id = t.end_vessel_event_id
+    LEFT JOIN ais_vms_port_calls e ON e.fiskeridir_vessel_id = t.fiskeridir_vessel_id
+    AND e."timestamp" > a."timestamp"
+GROUP BY
+    t.trip_id
+            "#,
+            vessel_id.into_inner(),
+            search_timestamp.bound() as i32,
+            search_timestamp.timestamp(),
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(trip)
+    }
+
+    pub(crate) async fn assembled_trips_impl(
+        &self,
+        vessel_id: FiskeridirVesselId,
+        trip_assembler_id: TripAssemblerId,
+        end_from: Option<DateTime<Utc>>,
+    ) -> Result<Vec<kyogre_core::NewTrip>> {
+        let trips = sqlx::query_as!(
+            kyogre_core::NewTrip,
+            r#"
+SELECT
+    first_arrival,
+    period AS "period!: DateRange",
+    period_extended AS "period_extended!: DateRange",
+    landing_coverage AS "landing_coverage!: DateRange",
+    start_port_id AS start_port_code,
+    end_port_id AS end_port_code,
+    start_vessel_event_id,
+    end_vessel_event_id
+FROM
+    trips
+WHERE
+    fiskeridir_vessel_id = $1
+    AND trip_assembler_id = $2
+    AND (
+        $3::TIMESTAMPTZ IS NULL
+        OR UPPER(period) >= $3
+    )
+ORDER BY
+    period
+            "#,
+            vessel_id.into_inner(),
+            trip_assembler_id as i32,
+            end_from,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(trips)
+    }
+
     pub(crate) fn trips_without_precision_impl(
         &self,
         vessel_id: FiskeridirVesselId,
@@ -2747,7 +3019,10 @@ LIMIT
                 self.connect_trip_to_ers_dca_tra_haul_events(event_ids, tx)
                     .await
             }
-            VesselEventType::ErsDep | VesselEventType::ErsPor => Ok(()),
+            VesselEventType::ErsDep
+            | VesselEventType::ErsPor
+            | VesselEventType::AisVmsDeparture
+            | VesselEventType::AisVmsArrival => Ok(()),
         }
     }
 
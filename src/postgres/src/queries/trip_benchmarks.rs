@@ -4,7 +4,8 @@ use kyogre_core::{
     AverageEeoiQuery, AverageFuiQuery, AverageTripBenchmarks, AverageTripBenchmarksQuery,
     DIESEL_LITER_CARBON_FACTOR, DateRange, EeoiQuery, EmptyVecToNone, EngineType,
     FiskeridirVesselId, FuiQuery, METERS_TO_NAUTICAL_MILES, MIN_EEOI_DISTANCE, Mmsi,
-    ProcessingStatus, TripBenchmarksQuery, TripId, TripWithBenchmark,
+    PeerBenchmarkVessel, PeerBenchmarksQuery, ProcessingStatus, TripBenchmarksQuery, TripId,
+    TripWithBenchmark,
 };
 
 use crate::{PostgresAdapter, error::Result, models::TripBenchmarkOutput};
@@ -73,7 +74,7 @@ SELECT
     td.period AS "period: DateRange",
     td.period_precision AS "period_precision?: DateRange",
     CASE
-        WHEN td.trip_assembler_id = 1 THEN td.landing_total_living_weight
+        WHEN td.trip_assembler_id IN (1, 3) THEN td.landing_total_living_weight
         WHEN td.trip_assembler_id = 2 THEN td.haul_total_weight::DOUBLE PRECISION
         ELSE NULL
     END AS "total_catch_weight!",
@@ -165,6 +166,107 @@ ORDER BY
         Ok(trips)
     }
 
+    pub(crate) async fn peer_benchmarks_impl(
+        &self,
+        query: &PeerBenchmarksQuery,
+    ) -> Result<Vec<PeerBenchmarkVessel>> {
+        let vessels = sqlx::query_as!(
+            PeerBenchmarkVessel,
+            r#"
+WITH
+    vessel AS (
+        SELECT
+            f.fiskeridir_vessel_id,
+            f.fiskeridir_length_group_id,
+            f.gear_group_ids,
+            f.engine_power_final,
+            f.fishery_id
+        FROM
+            active_vessels a
+            INNER JOIN fiskeridir_vessels f ON a.fiskeridir_vessel_id = f.fiskeridir_vessel_id
+        WHERE
+            a.call_sign = $1
+    ),
+    candidates AS (
+        SELECT
+            f.fiskeridir_vessel_id,
+            f.engine_power_final
+        FROM
+            vessel v
+            INNER JOIN fiskeridir_vessels f ON f.fiskeridir_vessel_id = v.fiskeridir_vessel_id
+            OR (
+                f.fiskeridir_length_group_id = v.fiskeridir_length_group_id
+                AND f.fishery_id IS NOT DISTINCT FROM v.fishery_id
+                AND (
+                    CARDINALITY(v.gear_group_ids) = 0
+                    OR f.gear_group_ids && v.gear_group_ids
+                )
+            )
+    ),
+    benchmarks AS (
+        SELECT
+            t.fiskeridir_vessel_id,
+            AVG(t.benchmark_weight_per_hour) AS weight_per_hour,
+            AVG(t.benchmark_weight_per_distance) AS weight_per_distance,
+            AVG(t.benchmark_weight_per_fuel_liter) AS weight_per_fuel_liter,
+            AVG(t.benchmark_catch_value_per_fuel_liter) AS catch_value_per_fuel_liter,
+            AVG(t.benchmark_eeoi) AS eeoi
+        FROM
+            candidates c
+            INNER JOIN trips_detailed t ON t.fiskeridir_vessel_id = c.fiskeridir_vessel_id
+        WHERE
+            (
+                $2::TIMESTAMPTZ IS NULL
+                OR LOWER(t.period) >= $2
+            )
+            AND (
+                $3::TIMESTAMPTZ IS NULL
+                OR UPPER(t.period) <= $3
+            )
+        GROUP BY
+            t.fiskeridir_vessel_id
+    ),
+    peers AS (
+        SELECT
+            c.fiskeridir_vessel_id
+        FROM
+            vessel v
+            INNER JOIN candidates c ON c.fiskeridir_vessel_id != v.fiskeridir_vessel_id
+            INNER JOIN benchmarks b ON c.fiskeridir_vessel_id = b.fiskeridir_vessel_id
+        ORDER BY
+            ABS(c.engine_power_final - v.engine_power_final) ASC NULLS LAST,
+            c.fiskeridir_vessel_id
+        LIMIT
+            $4
+    )
+SELECT
+    (b.fiskeridir_vessel_id = v.fiskeridir_vessel_id) AS "is_self!",
+    b.weight_per_hour,
+    b.weight_per_distance,
+    b.weight_per_fuel_liter,
+    b.catch_value_per_fuel_liter,
+    b.eeoi
+FROM
+    vessel v
+    INNER JOIN benchmarks b ON b.fiskeridir_vessel_id = v.fiskeridir_vessel_id
+    OR b.fiskeridir_vessel_id IN (
+        SELECT
+            fiskeridir_vessel_id
+        FROM
+            peers
+    )
+            "#,
+            query.call_sign.as_ref(),
+            query.range.start(),
+            query.range.end(),
+            query.num_peers as i64,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(vessels)
+    }
+
     pub(crate) async fn fui_impl(&self, query: &FuiQuery) -> Result<Option<f64>> {
         let result = sqlx::query!(
             r#"
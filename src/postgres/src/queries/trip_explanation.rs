@@ -0,0 +1,103 @@
+use futures::TryStreamExt;
+use kyogre_core::{
+    DateRange, FiskeridirVesselId, PrecisionId, ProcessingStatus, TripAssemblerId, TripId,
+    TripPositionLayerId, VesselEventType,
+};
+
+use crate::{
+    PostgresAdapter,
+    error::Result,
+    models::{TripExplanation, TripPrunedAisVmsPosition, VesselEvent},
+};
+
+impl PostgresAdapter {
+    pub(crate) async fn trip_explanation_impl(
+        &self,
+        trip_id: TripId,
+    ) -> Result<Option<kyogre_core::TripExplanation>> {
+        let Some(trip) = sqlx::query_as!(
+            TripExplanation,
+            r#"
+SELECT
+    trip_id AS "trip_id!: TripId",
+    fiskeridir_vessel_id AS "fiskeridir_vessel_id!: FiskeridirVesselId",
+    trip_assembler_id AS "trip_assembler_id!: TripAssemblerId",
+    period AS "period!: DateRange",
+    period_extended AS "period_extended!: DateRange",
+    period_precision AS "period_precision: DateRange",
+    landing_coverage AS "landing_coverage!: DateRange",
+    conflict_strategy,
+    conflict_strategy_start,
+    conflict_strategy_end,
+    start_vessel_event_id,
+    end_vessel_event_id,
+    trip_precision_status_id AS "trip_precision_status_id!: ProcessingStatus",
+    start_precision_id AS "start_precision_id: PrecisionId",
+    start_precision_direction,
+    end_precision_id AS "end_precision_id: PrecisionId",
+    end_precision_direction,
+    precision_attempts,
+    position_layers_status AS "position_layers_status!: ProcessingStatus",
+    track_coverage
+FROM
+    trips
+WHERE
+    trip_id = $1
+            "#,
+            trip_id.into_inner(),
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let events = sqlx::query_as!(
+            VesselEvent,
+            r#"
+SELECT
+    vessel_event_id,
+    fiskeridir_vessel_id AS "fiskeridir_vessel_id!: FiskeridirVesselId",
+    vessel_event_type_id AS "vessel_event_type_id!: VesselEventType",
+    report_timestamp,
+    occurence_timestamp
+FROM
+    vessel_events
+WHERE
+    trip_id = $1
+ORDER BY
+    report_timestamp,
+    vessel_event_id
+            "#,
+            trip_id.into_inner(),
+        )
+        .fetch(&self.pool)
+        .map_ok(kyogre_core::VesselEvent::from)
+        .try_collect()
+        .await?;
+
+        let pruned_positions = sqlx::query_as!(
+            TripPrunedAisVmsPosition,
+            r#"
+SELECT
+    trip_id AS "trip_id!: TripId",
+    positions,
+    "value",
+    trip_position_layer_id AS "trip_position_layer_id!: TripPositionLayerId"
+FROM
+    trip_positions_pruned
+WHERE
+    trip_id = $1
+ORDER BY
+    trip_position_layer_id
+            "#,
+            trip_id.into_inner(),
+        )
+        .fetch(&self.pool)
+        .map_ok(kyogre_core::PrunedTripPosition::from)
+        .try_collect()
+        .await?;
+
+        Ok(Some(trip.into_core(events, pruned_positions)?))
+    }
+}
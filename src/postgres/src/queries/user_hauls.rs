@@ -301,7 +301,20 @@ RETURNING
     start_fuel_liter,
     end_fuel_liter AS "end_fuel_liter!",
     config,
-    total_living_weight_kg
+    total_living_weight_kg,
+    (
+        SELECT
+            estimated_catch_value(
+                h.catches,
+                h.gear_group_id,
+                h.vessel_length_group,
+                h.stop_timestamp
+            )
+        FROM
+            hauls h
+        WHERE
+            h.haul_id = user_hauls.haul_id
+    ) AS estimated_value
             "#,
             config,
             start_ts,
@@ -524,7 +537,20 @@ SELECT
     start_fuel_liter,
     end_fuel_liter AS "end_fuel_liter!",
     config,
-    total_living_weight_kg
+    total_living_weight_kg,
+    (
+        SELECT
+            estimated_catch_value(
+                h.catches,
+                h.gear_group_id,
+                h.vessel_length_group,
+                h.stop_timestamp
+            )
+        FROM
+            hauls h
+        WHERE
+            h.haul_id = user_hauls.haul_id
+    ) AS estimated_value
 FROM
     user_hauls
 WHERE
@@ -706,7 +732,20 @@ RETURNING
     start_fuel_liter,
     end_fuel_liter AS "end_fuel_liter!",
     config,
-    total_living_weight_kg
+    total_living_weight_kg,
+    (
+        SELECT
+            estimated_catch_value(
+                h.catches,
+                h.gear_group_id,
+                h.vessel_length_group,
+                h.stop_timestamp
+            )
+        FROM
+            hauls h
+        WHERE
+            h.haul_id = user_hauls.haul_id
+    ) AS estimated_value
             "#,
             Utc::now(),
             *fuel_liter_end as i32,
@@ -10,8 +10,8 @@ use fiskeridir_rs::{CallSign, GearGroup, OrgId, SpeciesGroup, VesselLengthGroup}
 use futures::{Stream, TryStreamExt};
 use kyogre_core::Draught;
 use kyogre_core::{
-    ActiveVesselConflict, EngineType, FisheryId, FiskeridirVesselId, HasTrack, Mmsi,
-    TripAssemblerId, Vessel, VesselSource,
+    ActiveVesselConflict, EngineType, FisheryId, FiskeridirVesselId, FuelEstimationModel, FuelType,
+    HasTrack, Mmsi, TripAssemblerId, Vessel, VesselSource,
 };
 use std::collections::{HashMap, HashSet};
 
@@ -66,6 +66,8 @@ SELECT
     f.engine_rpm_manual AS "fiskeridir_engine_rpm",
     f.service_speed AS fiskeridir_service_speed,
     f.degree_of_electrification AS fiskeridir_degree_of_electrification,
+    f.fuel_type_id AS "fiskeridir_fuel_type: FuelType",
+    f.fuel_estimation_model_id AS "fiskeridir_fuel_estimation_model: FuelEstimationModel",
     f.gear_group_ids AS "gear_group_ids!: Vec<GearGroup>",
     f.species_group_ids AS "species_group_ids!: Vec<SpeciesGroup>",
     a.mmsi AS "ais_mmsi?: Mmsi",
@@ -107,6 +109,68 @@ WHERE
         .map_err(|e| e.into())
     }
 
+    pub(crate) fn vessels_with_fuel_measurements_impl(
+        &self,
+    ) -> impl Stream<Item = Result<FiskeridirAisVesselCombination>> + '_ {
+        sqlx::query_as!(
+            FiskeridirAisVesselCombination,
+            r#"
+SELECT
+    f.preferred_trip_assembler AS "preferred_trip_assembler!: TripAssemblerId",
+    f.fiskeridir_vessel_id AS "fiskeridir_vessel_id!: FiskeridirVesselId",
+    f.fiskeridir_length_group_id AS "fiskeridir_length_group_id!: VesselLengthGroup",
+    f.fishery_id AS "fiskeridir_fishery_id?: FisheryId",
+    v.call_sign AS "fiskeridir_call_sign: CallSign",
+    f."name" AS fiskeridir_name,
+    f.registration_id AS fiskeridir_registration_id,
+    f."length" AS fiskeridir_length,
+    f."width" AS fiskeridir_width,
+    f.owners::TEXT AS "fiskeridir_owners!",
+    f.engine_building_year_final AS fiskeridir_engine_building_year,
+    f.engine_power_final AS fiskeridir_engine_power,
+    f.building_year AS fiskeridir_building_year,
+    f.auxiliary_engine_power AS fiskeridir_auxiliary_engine_power,
+    f.auxiliary_engine_building_year AS fiskeridir_auxiliary_engine_building_year,
+    f.engine_version AS fiskeridir_engine_version,
+    f.boiler_engine_power AS fiskeridir_boiler_engine_power,
+    f.boiler_engine_building_year AS fiskeridir_boiler_engine_building_year,
+    f.engine_type_manual AS "fiskeridir_engine_type: EngineType",
+    f.engine_rpm_manual AS "fiskeridir_engine_rpm",
+    f.service_speed AS fiskeridir_service_speed,
+    f.degree_of_electrification AS fiskeridir_degree_of_electrification,
+    f.fuel_type_id AS "fiskeridir_fuel_type: FuelType",
+    f.fuel_estimation_model_id AS "fiskeridir_fuel_estimation_model: FuelEstimationModel",
+    f.gear_group_ids AS "gear_group_ids!: Vec<GearGroup>",
+    f.species_group_ids AS "species_group_ids!: Vec<SpeciesGroup>",
+    a.mmsi AS "ais_mmsi?: Mmsi",
+    a.call_sign AS "ais_call_sign: CallSign",
+    a.name AS ais_name,
+    a.draught AS "ais_draught: Draught",
+    a.ship_length AS ais_length,
+    a.ship_width AS ais_width,
+    c.departure_timestamp AS "current_trip_departure_timestamp?",
+    c.target_species_fiskeridir_id AS "current_trip_target_species_fiskeridir_id?",
+    v.is_active AS "is_active!"
+FROM
+    all_vessels AS v
+    INNER JOIN fiskeridir_vessels AS f ON v.fiskeridir_vessel_id = f.fiskeridir_vessel_id
+    LEFT JOIN ais_vessels AS a ON v.mmsi = a.mmsi
+    LEFT JOIN current_trips AS c ON v.fiskeridir_vessel_id = c.fiskeridir_vessel_id
+WHERE
+    EXISTS (
+        SELECT
+            1
+        FROM
+            fuel_measurement_ranges r
+        WHERE
+            r.fiskeridir_vessel_id = v.fiskeridir_vessel_id
+    )
+            "#,
+        )
+        .fetch(&self.pool)
+        .map_err(|e| e.into())
+    }
+
     pub(crate) async fn update_vessel_impl(
         &self,
         call_sign: &CallSign,
@@ -128,9 +192,11 @@ SET
     degree_of_electrification = $8,
     engine_type_manual = $9,
     engine_rpm_manual = $10,
+    fuel_type_id = $11,
+    fuel_estimation_model_id = $12,
     engine_version = engine_version + 1
 WHERE
-    call_sign = $11
+    call_sign = $13
 RETURNING
     fiskeridir_vessel_id AS "fiskeridir_vessel_id: FiskeridirVesselId"
             "#,
@@ -144,6 +210,8 @@ RETURNING
             update.degree_of_electrification,
             update.engine_type.map(|v| v as i32),
             update.engine_rpm.map(|v| v as i32),
+            update.fuel_type.map(|v| v as i32),
+            update.fuel_estimation_model.map(|v| v as i32),
             call_sign
         )
         .fetch_optional(&mut *tx)
@@ -670,6 +738,8 @@ SELECT
     f.engine_rpm_manual AS "fiskeridir_engine_rpm",
     f.service_speed AS fiskeridir_service_speed,
     f.degree_of_electrification AS fiskeridir_degree_of_electrification,
+    f.fuel_type_id AS "fiskeridir_fuel_type: FuelType",
+    f.fuel_estimation_model_id AS "fiskeridir_fuel_estimation_model: FuelEstimationModel",
     f.gear_group_ids AS "gear_group_ids!: Vec<GearGroup>",
     f.species_group_ids AS "species_group_ids!: Vec<SpeciesGroup>",
     a.mmsi AS "ais_mmsi?: Mmsi",
@@ -737,6 +807,8 @@ WHERE
         Ok(())
     }
 
+    /// Vessels without departures fall back to the landings trip assembler as soon as they have
+    /// a single landing, the AIS/VMS trip assembler is only used for vessels without landings.
     pub(crate) async fn update_preferred_trip_assemblers_impl(&self) -> Result<()> {
         let mut tx = self.pool.begin().await?;
 
@@ -773,6 +845,55 @@ RETURNING
         sqlx::query!(
             r#"
 UPDATE fiskeridir_vessels f
+SET
+    preferred_trip_assembler = CASE
+        WHEN EXISTS (
+            SELECT
+                1
+            FROM
+                landings l
+            WHERE
+                l.fiskeridir_vessel_id = f.fiskeridir_vessel_id
+        ) THEN $1
+        ELSE $2
+    END
+FROM
+    active_vessels a
+WHERE
+    a.fiskeridir_vessel_id = f.fiskeridir_vessel_id
+    AND (
+        a.mmsi IS NOT NULL
+        OR a.call_sign IS NOT NULL
+    )
+    AND NOT EXISTS (
+        SELECT
+            1
+        FROM
+            ers_departures e
+        WHERE
+            e.fiskeridir_vessel_id = f.fiskeridir_vessel_id
+    )
+    AND (
+        f.preferred_trip_assembler = $2
+        OR NOT EXISTS (
+            SELECT
+                1
+            FROM
+                landings l
+            WHERE
+                l.fiskeridir_vessel_id = f.fiskeridir_vessel_id
+        )
+    )
+            "#,
+            TripAssemblerId::Landings as i32,
+            TripAssemblerId::AisVms as i32,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+UPDATE fiskeridir_vessels f
 SET
     preferred_trip_assembler = $1
 FROM
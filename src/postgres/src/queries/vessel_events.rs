@@ -154,6 +154,37 @@ ORDER BY
         .map_err(|e| e.into())
     }
 
+    pub(crate) fn all_ais_vms_port_call_events(
+        &self,
+        vessel_id: FiskeridirVesselId,
+    ) -> impl Stream<Item = Result<VesselEventDetailed>> + '_ {
+        sqlx::query_as!(
+            VesselEventDetailed,
+            r#"
+SELECT
+    v.vessel_event_id,
+    v.fiskeridir_vessel_id AS "fiskeridir_vessel_id!: FiskeridirVesselId",
+    v.report_timestamp,
+    v.vessel_event_type_id AS "vessel_event_type_id!: VesselEventType",
+    p.port_id,
+    NULL AS "arrival_port_id",
+    NULL AS "departure_port_id",
+    p."timestamp" AS "estimated_timestamp?"
+FROM
+    vessel_events v
+    INNER JOIN ais_vms_port_calls p ON p.vessel_event_id = v.vessel_event_id
+WHERE
+    v.fiskeridir_vessel_id = $1::BIGINT
+ORDER BY
+    p."timestamp",
+    v.vessel_event_id
+           "#,
+            vessel_id.into_inner(),
+        )
+        .fetch(&self.pool)
+        .map_err(|e| e.into())
+    }
+
     pub(crate) async fn dangling_vessel_events(&self) -> Result<i64> {
         let mut count = 0;
         for e in VesselEventType::iter() {
@@ -240,6 +271,24 @@ FROM
     LEFT JOIN ers_tra t ON t.vessel_event_id = v.vessel_event_id
 WHERE
     t.message_id IS NULL
+    AND v.vessel_event_type_id = $1
+            "#,
+                        e as i32
+                    )
+                    .fetch_one(&self.pool)
+                    .await?
+                    .count;
+                }
+                VesselEventType::AisVmsDeparture | VesselEventType::AisVmsArrival => {
+                    count += sqlx::query!(
+                        r#"
+SELECT
+    COUNT(*) AS "count!"
+FROM
+    vessel_events v
+    LEFT JOIN ais_vms_port_calls p ON p.vessel_event_id = v.vessel_event_id
+WHERE
+    p.vessel_event_id IS NULL
     AND v.vessel_event_type_id = $1
             "#,
                         e as i32
@@ -3,14 +3,15 @@ use std::collections::HashSet;
 use chrono::{DateTime, NaiveDate, TimeZone, Utc};
 use futures::{Stream, TryStreamExt};
 use kyogre_core::{
-    CatchLocationId, HaulId, HaulWeather, HaulWeatherOutput, Weather, WeatherLocationId,
+    CatchLocationId, DateRange, EmptyVecToNone, FuelWeather, HaulId, HaulWeather,
+    HaulWeatherOutput, Weather, WeatherForecast, WeatherForecastQuery, WeatherLocationId,
     WeatherQuery,
 };
 
 use crate::{
     PostgresAdapter,
     error::Result,
-    models::{NewWeather, NewWeatherDailyDirty, WeatherLocation},
+    models::{NewWeather, NewWeatherDailyDirty, NewWeatherForecast, WeatherLocation},
 };
 
 impl PostgresAdapter {
@@ -310,6 +311,62 @@ GROUP BY
         .map_err(|e| e.into())
     }
 
+    pub(crate) fn weather_forecasts_impl(
+        &self,
+        query: WeatherForecastQuery,
+    ) -> impl Stream<Item = Result<WeatherForecast>> + '_ {
+        sqlx::query_as!(
+            WeatherForecast,
+            r#"
+SELECT
+    c.catch_location_id AS "catch_location_id!: CatchLocationId",
+    w.reference_time AS "reference_time!",
+    w."timestamp" AS "timestamp!",
+    (
+        EXTRACT(
+            epoch
+            FROM
+                w."timestamp" - w.reference_time
+        ) / 3600
+    )::INT AS "horizon_hours!",
+    AVG(w.wind_speed_10m) AS "wind_speed_10m",
+    AVG(w.wind_direction_10m) AS "wind_direction_10m",
+    AVG(w.air_temperature_2m) AS "air_temperature_2m",
+    AVG(w.air_pressure_at_sea_level) AS "air_pressure_at_sea_level",
+    AVG(w.precipitation_amount) AS "precipitation_amount"
+FROM
+    catch_locations c
+    CROSS JOIN LATERAL (
+        SELECT
+            MAX(f.reference_time) AS reference_time
+        FROM
+            weather_forecasts f
+        WHERE
+            f.weather_location_id = ANY (c.weather_location_ids)
+    ) l
+    INNER JOIN weather_forecasts w ON w.weather_location_id = ANY (c.weather_location_ids)
+    AND w.reference_time = l.reference_time
+WHERE
+    (
+        $1::VARCHAR[] IS NULL
+        OR c.catch_location_id = ANY ($1)
+    )
+    AND w."timestamp" <= w.reference_time + MAKE_INTERVAL(hours => $2)
+GROUP BY
+    c.catch_location_id,
+    w.reference_time,
+    w."timestamp"
+ORDER BY
+    c.catch_location_id,
+    w."timestamp"
+            "#,
+            query.catch_location_ids.empty_to_none() as Option<Vec<CatchLocationId>>,
+            query.horizon_hours as i32,
+        )
+        .fetch(&self.pool)
+        .map_err(|e| e.into())
+    }
+
     pub(crate) async fn haul_weather_impl(
         &self,
         query: WeatherQuery,
@@ -498,6 +555,53 @@ WHERE
         Ok(())
     }
 
+    pub(crate) async fn add_weather_forecasts_impl(
+        &self,
+        forecasts: Vec<kyogre_core::NewWeatherForecast>,
+    ) -> Result<()> {
+        let Some(reference_time) = forecasts.iter().map(|f| f.reference_time).max() else {
+            return Ok(());
+        };
+
+        let mut tx = self.pool.begin().await?;
+
+        self.unnest_insert_from::<_, _, NewWeatherForecast>(forecasts, &mut *tx)
+            .await?;
+
+        // Older runs are superseded by the new run, so there is no point in keeping them around.
+        sqlx::query!(
+            r#"
+DELETE FROM weather_forecasts
+WHERE
+    reference_time < $1
+            "#,
+            reference_time,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn latest_weather_forecast_reference_time_impl(
+        &self,
+    ) -> Result<Option<DateTime<Utc>>> {
+        let row = sqlx::query!(
+            r#"
+SELECT
+    MAX(reference_time) AS ts
+FROM
+    weather_forecasts
+            "#
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.ts)
+    }
+
     pub(crate) async fn latest_weather_timestamp_impl(&self) -> Result<Option<DateTime<Utc>>> {
         let row = sqlx::query!(
             r#"
@@ -529,4 +633,35 @@ FROM
         .fetch(&self.pool)
         .map_err(|e| e.into())
     }
+
+    pub(crate) async fn fuel_estimation_weather_impl(
+        &self,
+        range: &DateRange,
+        weather_location_ids: &[WeatherLocationId],
+    ) -> Result<Vec<FuelWeather>> {
+        Ok(sqlx::query_as!(
+            FuelWeather,
+            r#"
+SELECT
+    "timestamp",
+    weather_location_id AS "weather_location_id!: WeatherLocationId",
+    latitude,
+    longitude,
+    wind_speed_10m AS "wind_speed_10m!",
+    wind_direction_10m AS "wind_direction_10m!"
+FROM
+    weather
+WHERE
+    "timestamp" BETWEEN $1 AND $2
+    AND weather_location_id = ANY ($3)
+    AND wind_speed_10m IS NOT NULL
+    AND wind_direction_10m IS NOT NULL
+            "#,
+            range.start(),
+            range.end(),
+            weather_location_ids as &[WeatherLocationId],
+        )
+        .fetch_all(&self.pool)
+        .await?)
+    }
 }
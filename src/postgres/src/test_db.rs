@@ -47,7 +47,14 @@ SELECT
     h.vessel_length_group AS "vessel_length_group!: VesselLengthGroup",
     COALESCE(h.vessel_name, h.vessel_name_ers) AS vessel_name,
     COALESCE(h.vessel_call_sign, h.vessel_call_sign_ers) AS "call_sign!: CallSign",
-    h.catches::TEXT AS "catches!"
+    h.catches::TEXT AS "catches!",
+    estimated_catch_value(
+        h.catches,
+        h.gear_group_id,
+        h.vessel_length_group,
+        h.stop_timestamp
+    ) AS estimated_value,
+    h.water_temperature
 FROM
     hauls h
     LEFT JOIN vessel_events e ON h.vessel_event_id = e.vessel_event_id
@@ -236,6 +243,49 @@ FROM
         .map(|v| v.unwrap())
     }
 
+    pub async fn all_ais_rejected_messages(&self) -> Vec<AisRejectedMessage> {
+        sqlx::query_as!(
+            AisRejectedMessage,
+            r#"
+SELECT
+    ais_rejection_category_id AS "category!: AisRejectionCategory",
+    message,
+    error,
+    received_at
+FROM
+    ais_rejected_messages
+ORDER BY
+    ais_rejected_message_id
+            "#
+        )
+        .fetch_all(&self.db.pool)
+        .await
+        .unwrap()
+    }
+
+    pub async fn add_ais_rejected_messages(&self, messages: &[NewAisRejectedMessage]) {
+        self.db.add_ais_rejected_messages(messages).await.unwrap();
+    }
+
+    pub async fn ais_rejection_counts(&self) -> Vec<AisRejectionCount> {
+        sqlx::query_as!(
+            AisRejectionCount,
+            r#"
+SELECT
+    ais_rejection_category_id AS "category!: AisRejectionCategory",
+    num_rejected,
+    last_rejected_at
+FROM
+    ais_rejection_counters
+ORDER BY
+    ais_rejection_category_id
+            "#
+        )
+        .fetch_all(&self.db.pool)
+        .await
+        .unwrap()
+    }
+
     pub async fn all_ais_vessels_with_eta(&self) -> Vec<(AisVessel, Option<DateTime<Utc>>)> {
         sqlx::query!(
             r#"
@@ -0,0 +1,155 @@
+use std::{sync::Arc, time::Duration};
+
+use kyogre_core::{
+    FuelBacktestInbound, FuelBacktestOutbound, FuelEstimation, FuelEstimationModel,
+    NewFuelBacktestResult, Vessel,
+};
+use tracing::{error, instrument};
+
+use crate::{
+    FuelComputation, FuelImpl, FuelImplDiscriminants, FuelWeatherLookup, Result, VesselFuelInfo,
+    estimate_fuel_for_positions, load_fuel_weather,
+};
+
+static RUN_INTERVAL: Duration = Duration::from_secs(60 * 60 * 24);
+
+pub trait FuelBacktesting: FuelEstimation + FuelBacktestOutbound + FuelBacktestInbound {}
+
+impl<T> FuelBacktesting for T where T: FuelEstimation + FuelBacktestOutbound + FuelBacktestInbound {}
+
+/// Estimates the fuel consumption of each fuel measurement range with all estimation models the
+/// vessel has the necessary information for, and stores the estimates alongside the measured fuel
+/// so the accuracy of the models can be compared.
+#[derive(Clone)]
+pub struct FuelBacktester {
+    adapter: Arc<dyn FuelBacktesting>,
+}
+
+impl FuelBacktester {
+    pub fn new(adapter: Arc<dyn FuelBacktesting>) -> Self {
+        Self { adapter }
+    }
+
+    pub async fn run_continuous(self) -> ! {
+        loop {
+            self.run_cycle().await;
+            tokio::time::sleep(RUN_INTERVAL).await;
+        }
+    }
+
+    #[instrument(skip_all)]
+    async fn run_cycle(&self) {
+        if let Err(e) = self.run_single().await {
+            error!("fuel backtest failed: {e:?}");
+        }
+    }
+
+    pub async fn run_single(&self) -> Result<()> {
+        let vessels = self.adapter.vessels_with_fuel_measurements().await?;
+
+        for v in vessels {
+            if let Err(e) = self.process_vessel(&v).await {
+                error!(
+                    "failed to backtest fuel for vessel {}: {e:?}",
+                    v.fiskeridir.id
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn process_vessel(&self, vessel: &Vessel) -> Result<()> {
+        let vessel_id = vessel.fiskeridir.id;
+
+        if vessel.engines().is_empty() {
+            return Ok(());
+        }
+
+        let vessels = self.model_vessels(vessel).await?;
+        let ranges = self.adapter.fuel_measurement_ranges(vessel_id).await?;
+
+        let mut results = Vec::with_capacity(ranges.len() * vessels.len());
+
+        for range in ranges {
+            if range.fuel_used_liter <= 0. {
+                continue;
+            }
+
+            let positions = self
+                .adapter
+                .fuel_estimation_positions(
+                    vessel_id,
+                    vessel.mmsi(),
+                    vessel.fiskeridir_call_sign(),
+                    &range.fuel_range,
+                )
+                .await?;
+
+            let weather = if vessels
+                .iter()
+                .any(|(m, _)| *m == FuelEstimationModel::Holtrop)
+            {
+                load_fuel_weather(self.adapter.as_ref(), &positions).await?
+            } else {
+                FuelWeatherLookup::default()
+            };
+
+            for (model, info) in &vessels {
+                let mut fuel_impl = FuelImpl::new(info);
+                fuel_impl.set_weather(&weather);
+                let estimate = estimate_fuel_for_positions(&mut fuel_impl, &positions, info);
+
+                results.push(NewFuelBacktestResult {
+                    fuel_range: range.fuel_range.clone(),
+                    model: *model,
+                    measured_liter: range.fuel_used_liter,
+                    estimated_liter: estimate.fuel_liter,
+                });
+            }
+        }
+
+        self.adapter
+            .set_fuel_backtest_results(vessel_id, &results)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns the vessel information for each model that can be used for the vessel.
+    ///
+    /// Note that the empirical model is fitted from the same measurements it is tested against,
+    /// its metrics are therefore an in-sample error and not directly comparable to the others.
+    async fn model_vessels(
+        &self,
+        vessel: &Vessel,
+    ) -> Result<Vec<(FuelEstimationModel, VesselFuelInfo)>> {
+        let vessel_id = vessel.fiskeridir.id;
+        let max_cargo_weight = self.adapter.vessel_max_cargo_weight(vessel_id).await?;
+
+        let mut vessels = vec![(
+            FuelEstimationModel::Maru,
+            VesselFuelInfo::from_core(vessel, Some(max_cargo_weight), FuelImplDiscriminants::Maru),
+        )];
+
+        let holtrop = VesselFuelInfo::from_core(vessel, None, FuelImplDiscriminants::Holtrop);
+        // `FuelImpl` falls back to Maru for vessels lacking the information needed by Holtrop
+        if FuelImpl::new(&holtrop).mode() == FuelImplDiscriminants::Holtrop {
+            vessels.push((FuelEstimationModel::Holtrop, holtrop));
+        }
+
+        if let Some(model) = self.adapter.empirical_fuel_model(vessel_id).await? {
+            vessels.push((
+                FuelEstimationModel::Empirical,
+                VesselFuelInfo::from_core(
+                    vessel,
+                    Some(max_cargo_weight),
+                    FuelImplDiscriminants::Empirical,
+                )
+                .with_empirical_model(Some(model)),
+            ));
+        }
+
+        Ok(vessels)
+    }
+}
@@ -0,0 +1,281 @@
+use super::{
+    FuelComputation, FuelImplDiscriminants, FuelItem, VesselFuelInfo, prune_unrealistic_speed,
+};
+use crate::Result;
+use kyogre_core::{EmpiricalFuelModel, FuelEstimation};
+use std::ops::AddAssign;
+
+/// The minimum number of fuel measurement ranges required before a vessel specific model is
+/// fitted and used instead of the physical models.
+pub static MIN_EMPIRICAL_FUEL_MEASUREMENTS: usize = 10;
+
+#[derive(Debug, Default)]
+pub struct Empirical {
+    model: EmpiricalFuelModel,
+}
+
+/// The hours spent in each term of an [`EmpiricalFuelModel`], weighted by the load of the term.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct EmpiricalFeatures {
+    pub hours: f64,
+    pub load_hours: f64,
+    pub haul_hours: f64,
+}
+
+impl FuelComputation for Empirical {
+    fn fuel_liter(
+        &mut self,
+        first: &FuelItem,
+        second: &FuelItem,
+        vessel: &VesselFuelInfo,
+        time_ms: u64,
+    ) -> Option<f64> {
+        let features = self.features(first, second, vessel, time_ms)?;
+        let EmpiricalFuelModel {
+            idle_liter_per_hour,
+            load_liter_per_hour,
+            haul_liter_per_hour,
+            num_measurements: _,
+        } = self.model;
+
+        Some(
+            idle_liter_per_hour * features.hours
+                + load_liter_per_hour * features.load_hours
+                + haul_liter_per_hour * features.haul_hours,
+        )
+    }
+
+    fn mode(&self) -> FuelImplDiscriminants {
+        FuelImplDiscriminants::Empirical
+    }
+}
+
+impl Empirical {
+    pub fn new(model: EmpiricalFuelModel) -> Self {
+        Self { model }
+    }
+
+    pub fn features(
+        &self,
+        first: &FuelItem,
+        second: &FuelItem,
+        vessel: &VesselFuelInfo,
+        time_ms: u64,
+    ) -> Option<EmpiricalFeatures> {
+        let speed_knots = self.speed_knots(first, second, time_ms)?;
+        let hours = time_ms as f64 / 3_600_000.;
+        // Same cubic relation between speed and engine load as the Maru model
+        let load = (speed_knots / vessel.service_speed).powf(3.).clamp(0., 1.);
+        let haul_factor = self.haul_factor(first, second);
+
+        Some(EmpiricalFeatures {
+            hours,
+            load_hours: load * hours,
+            haul_hours: (haul_factor - 1.).max(0.) * load * hours,
+        })
+    }
+}
+
+impl EmpiricalFeatures {
+    fn as_array(&self) -> [f64; 3] {
+        [self.hours, self.load_hours, self.haul_hours]
+    }
+}
+
+impl AddAssign for EmpiricalFeatures {
+    fn add_assign(&mut self, rhs: Self) {
+        self.hours += rhs.hours;
+        self.load_hours += rhs.load_hours;
+        self.haul_hours += rhs.haul_hours;
+    }
+}
+
+/// Fits an [`EmpiricalFuelModel`] to the vessel's fuel measurement ranges, returns `None` if the
+/// vessel does not have enough measurements or if the measurements does not yield a plausible
+/// model.
+pub async fn fit_empirical_fuel_model(
+    adapter: &dyn FuelEstimation,
+    vessel: &VesselFuelInfo,
+) -> Result<Option<EmpiricalFuelModel>> {
+    let ranges = adapter.fuel_measurement_ranges(vessel.vessel_id).await?;
+    if ranges.len() < MIN_EMPIRICAL_FUEL_MEASUREMENTS {
+        return Ok(None);
+    }
+
+    let empirical = Empirical::default();
+    let mut samples = Vec::with_capacity(ranges.len());
+
+    for range in ranges {
+        let positions = adapter
+            .fuel_estimation_positions(
+                vessel.vessel_id,
+                vessel.mmsi,
+                vessel.call_sign.as_ref(),
+                &range.fuel_range,
+            )
+            .await?;
+
+        let positions = prune_unrealistic_speed(&positions);
+        if positions.len() < 2 {
+            continue;
+        }
+
+        let mut features = EmpiricalFeatures::default();
+        for w in positions.windows(2) {
+            let first = FuelItem::from(&w[0]);
+            let second = FuelItem::from(&w[1]);
+
+            let time_ms = empirical.time_ms(&first, &second);
+            if time_ms <= 0 {
+                continue;
+            }
+
+            if let Some(f) = empirical.features(&first, &second, vessel, time_ms as u64) {
+                features += f;
+            }
+        }
+
+        samples.push((features, range.fuel_used_liter));
+    }
+
+    Ok(fit(&samples))
+}
+
+/// Least squares fit of the model terms to the measured fuel, terms the samples have no data for
+/// (such as hauling for vessels without active gear) are left at zero.
+fn fit(samples: &[(EmpiricalFeatures, f64)]) -> Option<EmpiricalFuelModel> {
+    if samples.len() < MIN_EMPIRICAL_FUEL_MEASUREMENTS {
+        return None;
+    }
+
+    let terms = (0..3)
+        .filter(|i| samples.iter().any(|(f, _)| f.as_array()[*i] > 0.))
+        .collect::<Vec<_>>();
+
+    // The idle term is always required as every sample spans some time
+    if !terms.contains(&0) {
+        return None;
+    }
+
+    let n = terms.len();
+    let mut matrix = vec![vec![0.; n + 1]; n];
+
+    for (features, fuel_liter) in samples {
+        let x = features.as_array();
+        for (row, i) in terms.iter().enumerate() {
+            for (col, j) in terms.iter().enumerate() {
+                matrix[row][col] += x[*i] * x[*j];
+            }
+            matrix[row][n] += x[*i] * fuel_liter;
+        }
+    }
+
+    let solution = solve(matrix)?;
+
+    let mut coefficients = [0.; 3];
+    for (i, v) in terms.into_iter().zip(solution) {
+        // Negative consumption is not physically possible and indicates that the measurements
+        // are too noisy to separate the terms.
+        if !v.is_finite() || v < 0. {
+            return None;
+        }
+        coefficients[i] = v;
+    }
+
+    Some(EmpiricalFuelModel {
+        idle_liter_per_hour: coefficients[0],
+        load_liter_per_hour: coefficients[1],
+        haul_liter_per_hour: coefficients[2],
+        num_measurements: samples.len() as u32,
+    })
+}
+
+/// Solves the augmented matrix with Gaussian elimination using partial pivoting.
+fn solve(mut matrix: Vec<Vec<f64>>) -> Option<Vec<f64>> {
+    let n = matrix.len();
+
+    for col in 0..n {
+        let pivot =
+            (col..n).max_by(|a, b| matrix[*a][col].abs().total_cmp(&matrix[*b][col].abs()))?;
+        if matrix[pivot][col].abs() < f64::EPSILON {
+            return None;
+        }
+        matrix.swap(col, pivot);
+
+        for row in col + 1..n {
+            let factor = matrix[row][col] / matrix[col][col];
+            for k in col..=n {
+                matrix[row][k] -= factor * matrix[col][k];
+            }
+        }
+    }
+
+    let mut solution = vec![0.; n];
+    for row in (0..n).rev() {
+        let sum = (row + 1..n)
+            .map(|k| matrix[row][k] * solution[k])
+            .sum::<f64>();
+        solution[row] = (matrix[row][n] - sum) / matrix[row][row];
+    }
+
+    Some(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(hours: f64, load_hours: f64, haul_hours: f64) -> (EmpiricalFeatures, f64) {
+        let features = EmpiricalFeatures {
+            hours,
+            load_hours,
+            haul_hours,
+        };
+        (features, 10. * hours + 100. * load_hours + 5. * haul_hours)
+    }
+
+    #[test]
+    fn test_fit_recovers_model_coefficients() {
+        let samples = (0..MIN_EMPIRICAL_FUEL_MEASUREMENTS)
+            .map(|i| {
+                let i = i as f64;
+                sample(10. + i, 2. + (i * 0.7) % 3., (i * 1.3) % 4.)
+            })
+            .collect::<Vec<_>>();
+
+        let model = fit(&samples).unwrap();
+
+        assert!((model.idle_liter_per_hour - 10.).abs() < 1e-6);
+        assert!((model.load_liter_per_hour - 100.).abs() < 1e-6);
+        assert!((model.haul_liter_per_hour - 5.).abs() < 1e-6);
+        assert_eq!(
+            model.num_measurements,
+            MIN_EMPIRICAL_FUEL_MEASUREMENTS as u32
+        );
+    }
+
+    #[test]
+    fn test_fit_ignores_haul_term_without_hauling() {
+        let samples = (0..MIN_EMPIRICAL_FUEL_MEASUREMENTS)
+            .map(|i| {
+                let i = i as f64;
+                sample(10. + i, 2. + (i * 0.7) % 3., 0.)
+            })
+            .collect::<Vec<_>>();
+
+        let model = fit(&samples).unwrap();
+
+        assert!((model.idle_liter_per_hour - 10.).abs() < 1e-6);
+        assert!((model.load_liter_per_hour - 100.).abs() < 1e-6);
+        assert_eq!(model.haul_liter_per_hour, 0.);
+    }
+
+    #[test]
+    fn test_fit_requires_enough_measurements() {
+        let samples = (0..MIN_EMPIRICAL_FUEL_MEASUREMENTS - 1)
+            .map(|i| sample(10. + i as f64, 2., 0.))
+            .collect::<Vec<_>>();
+
+        assert!(fit(&samples).is_none());
+    }
+}
@@ -1,4 +1,4 @@
-use super::{FuelComputation, FuelImplDiscriminants, FuelItem, VesselFuelInfo};
+use super::{FuelComputation, FuelImplDiscriminants, FuelItem, FuelWeatherLookup, VesselFuelInfo};
 use kyogre_core::{DIESEL_KG_TO_LITER, Draught};
 
 // Source: https://www.boatdesign.net/attachments/resistance-characteristics-of-fishing-boats-series-of-itu-1-pdf.179126/
@@ -27,6 +27,19 @@ static NREFF: f64 = 1.0;
 static KINVISCOSITY: f64 = 0.00000118831;
 static SHAFT_EFFICIENCY: f64 = 0.95;
 
+// Source: ISO 15016:2015, wind resistance using the longitudinal force coefficient at head wind
+static RHO_AIR: f64 = 1.225;
+static WIND_FORCE_COEFFICIENT: f64 = 0.8;
+// The transverse projected area above the waterline relative to the breadth squared, a rough
+// estimate of the wheelhouse and deck structures of fishing vessels.
+static WINDAGE_AREA_FACTOR: f64 = 0.7;
+// Source: ITTC 7.5-04-01-01.1 (STAWAVE-1), the length of the bow on the waterline to 95% of the
+// maximum breadth relative to the waterline length.
+static BOW_LENGTH_FACTOR: f64 = 0.25;
+// Waves are assumed to be a fully developed sea from the local wind (Pierson-Moskowitz),
+// H_s = 0.21 * U^2 / g.
+static WAVE_HEIGHT_FACTOR: f64 = 0.21;
+
 #[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
 pub enum ScrewType {
     Twin,
@@ -54,6 +67,7 @@ pub struct Holtrop {
     midship_section_coefficient: f64,
     waterplane_area_coefficent: f64,
     stern_parameter: f64,
+    weather: Option<FuelWeatherLookup>,
 }
 
 #[allow(dead_code)]
@@ -83,7 +97,13 @@ impl FuelComputation for Holtrop {
     ) -> Option<f64> {
         let speed_knots = self.speed_knots(first, second, time_ms)?;
 
-        self.fuel_liter_impl(speed_knots, time_ms, self.haul_factor(first, second))
+        let added_resistance = self.weather_resistance(first, second, speed_knots * 0.5144);
+        self.fuel_liter_impl(
+            speed_knots,
+            time_ms,
+            self.haul_factor(first, second),
+            added_resistance,
+        )
     }
 
     fn mode(&self) -> FuelImplDiscriminants {
@@ -177,20 +197,86 @@ impl HoltropBuilder {
             waterplane_area_coefficent: 0.55 + 0.45 * block_coefficient,
             screw_type: self.screw_type,
             stern_parameter: self.stern_parameter.unwrap_or(STERN_PARAMETER),
+            weather: None,
         }
     }
 }
 
 impl Holtrop {
+    /// Includes the added resistance from wind and waves in the estimates, positions without
+    /// weather are estimated in calm water.
+    pub fn set_weather(&mut self, weather: FuelWeatherLookup) {
+        self.weather = (!weather.is_empty()).then_some(weather);
+    }
+
+    /// Returns the added resistance in kN from wind and waves at the midpoint of the segment.
+    fn weather_resistance(&self, first: &FuelItem, second: &FuelItem, speed: f64) -> f64 {
+        let Some(weather) = &self.weather else {
+            return 0.;
+        };
+
+        // The heading cannot be determined for a stationary vessel, and the propulsion power is
+        // negligible regardless.
+        if speed < 0.5 {
+            return 0.;
+        }
+
+        let latitude = (first.latitude + second.latitude) / 2.;
+        let longitude = (first.longitude + second.longitude) / 2.;
+        let timestamp = first.timestamp + (second.timestamp - first.timestamp) / 2;
+
+        let Some(w) = weather.weather(latitude, longitude, timestamp) else {
+            return 0.;
+        };
+
+        let heading = ((second.longitude - first.longitude) * latitude.to_radians().cos())
+            .atan2(second.latitude - first.latitude);
+
+        // Wind direction is where the wind is coming from, zero is a head wind
+        let relative_direction = w.wind_direction_10m.to_radians() - heading;
+
+        self.wind_resistance(w.wind_speed_10m, relative_direction, speed)
+            + self.wave_resistance(w.wind_speed_10m, relative_direction)
+    }
+
+    fn wind_resistance(&self, wind_speed: f64, relative_direction: f64, speed: f64) -> f64 {
+        let head = wind_speed * relative_direction.cos() + speed;
+        let cross = wind_speed * relative_direction.sin();
+        let relative_speed = head.hypot(cross);
+
+        let area = self.breadth.powi(2) * WINDAGE_AREA_FACTOR;
+
+        // C_X(psi) * V^2 with C_X(psi) = C_X(0) * cos(psi) simplifies to C_X(0) * head * V,
+        // following winds are not credited as reduced resistance.
+        (0.5 * RHO_AIR * WIND_FORCE_COEFFICIENT * area * head * relative_speed / 1000.).max(0.)
+    }
+
+    fn wave_resistance(&self, wind_speed: f64, relative_direction: f64) -> f64 {
+        // STAWAVE-1 is only valid for waves within 45 degrees of the bow
+        if relative_direction.cos() < std::f64::consts::FRAC_PI_4.cos() {
+            return 0.;
+        }
+
+        let wave_height = WAVE_HEIGHT_FACTOR * wind_speed.powi(2) / GRAVITY;
+        let bow_length = self.length_at_waterline() * BOW_LENGTH_FACTOR;
+
+        (RHO * GRAVITY * wave_height.powi(2) * self.breadth * (self.breadth / bow_length).sqrt())
+            / 16.
+            / 1000.
+    }
+
+    /// `added_resistance` is the resistance in kN from wind and waves in addition to the calm
+    /// water resistance.
     pub fn fuel_liter_impl(
         &mut self,
         speed_knots: f64,
         time_diff_ms: u64,
         haul_factor: f64,
+        added_resistance: f64,
     ) -> Option<f64> {
         self.speed_meter_per_second = speed_knots * 0.5144;
 
-        let break_power = self.break_power() * haul_factor;
+        let break_power = self.break_power(added_resistance) * haul_factor;
 
         let hours_diff = time_diff_ms as f64 / 3_600_000.;
 
@@ -237,18 +323,18 @@ impl Holtrop {
         0.08 * self.midship_section_coefficient * self.breadth * self.draught
     }
 
-    fn break_power(&self) -> f64 {
-        self.sea_margin() * self.shaft_efficency
+    fn break_power(&self, added_resistance: f64) -> f64 {
+        self.sea_margin(added_resistance) * self.shaft_efficency
     }
 
-    fn sea_margin(&self) -> f64 {
+    fn sea_margin(&self, added_resistance: f64) -> f64 {
         let sea_margin = 0.85;
 
-        self.pd() / sea_margin
+        self.pd(added_resistance) / sea_margin
     }
 
-    fn pd(&self) -> f64 {
-        self.cpe() / self.cnd()
+    fn pd(&self, added_resistance: f64) -> f64 {
+        self.cpe(added_resistance) / self.cnd()
     }
 
     fn cnd(&self) -> f64 {
@@ -332,8 +418,8 @@ impl Holtrop {
         (1.0 - self.t()) / (1.0 - self.w())
     }
 
-    fn cpe(&self) -> f64 {
-        self.crt() * self.speed_meter_per_second
+    fn cpe(&self, added_resistance: f64) -> f64 {
+        self.crt(added_resistance) * self.speed_meter_per_second
     }
 
     fn rapp(&self) -> f64 {
@@ -390,13 +476,14 @@ impl Holtrop {
             / 1000.0
     }
 
-    fn crt(&self) -> f64 {
+    fn crt(&self, added_resistance: f64) -> f64 {
         (self.crf() * self.form_factor())
             + self.rapp()
             + self.rw()
             + self.rb()
             + self.rtr()
             + self.ra()
+            + added_resistance
     }
 
     fn cm1(&self) -> f64 {
@@ -621,3 +708,70 @@ impl Holtrop {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Duration, Utc};
+    use kyogre_core::{FuelWeather, PositionType, WeatherLocationId};
+
+    fn item(latitude: f64, timestamp: DateTime<Utc>) -> FuelItem {
+        FuelItem {
+            speed: None,
+            latitude,
+            longitude: 20.05,
+            timestamp,
+            position_type_id: PositionType::Ais,
+            active_gear: None,
+            cumulative_cargo_weight: 0.,
+        }
+    }
+
+    fn holtrop(wind_direction_10m: f64, timestamp: DateTime<Utc>) -> Holtrop {
+        let mut holtrop =
+            HoltropBuilder::new(Draught::default(), 40., 10., 200., ScrewType::default()).build();
+        holtrop.set_weather(FuelWeatherLookup::new(vec![FuelWeather {
+            timestamp,
+            weather_location_id: WeatherLocationId::from_lat_lon(70.05, 20.05),
+            latitude: 70.05,
+            longitude: 20.05,
+            wind_speed_10m: 15.,
+            wind_direction_10m,
+        }]));
+        holtrop
+    }
+
+    #[test]
+    fn test_head_wind_adds_resistance() {
+        let timestamp = "2024-01-01T12:00:00Z".parse().unwrap();
+        let first = item(70.04, timestamp - Duration::minutes(5));
+        let second = item(70.06, timestamp + Duration::minutes(5));
+
+        // Heading north into a northerly wind
+        let resistance = holtrop(0., timestamp).weather_resistance(&first, &second, 4.);
+
+        assert!(resistance > 0.);
+    }
+
+    #[test]
+    fn test_following_wind_adds_no_resistance() {
+        let timestamp = "2024-01-01T12:00:00Z".parse().unwrap();
+        let first = item(70.04, timestamp - Duration::minutes(5));
+        let second = item(70.06, timestamp + Duration::minutes(5));
+
+        let resistance = holtrop(180., timestamp).weather_resistance(&first, &second, 4.);
+
+        assert_eq!(resistance, 0.);
+    }
+
+    #[test]
+    fn test_no_resistance_without_weather_for_position() {
+        let timestamp: DateTime<Utc> = "2024-01-01T12:00:00Z".parse().unwrap();
+        let first = item(72.04, timestamp - Duration::minutes(5));
+        let second = item(72.06, timestamp + Duration::minutes(5));
+
+        let resistance = holtrop(0., timestamp).weather_resistance(&first, &second, 4.);
+
+        assert_eq!(resistance, 0.);
+    }
+}
@@ -7,8 +7,8 @@ use kyogre_core::LiveFuelVessel;
 use kyogre_core::Mmsi;
 use kyogre_core::{
     AisPosition, AisVmsPosition, ComputedFuelEstimation, DailyFuelEstimationPosition, DateRange,
-    Draught, FiskeridirVesselId, FuelEstimation, NewFuelDayEstimate, PositionType, Vessel,
-    VesselEngine,
+    Draught, EmpiricalFuelModel, FiskeridirVesselId, FuelEstimation, FuelEstimationModel,
+    NewFuelDayEstimate, PositionType, Vessel, VesselEngine,
 };
 use serde::Deserialize;
 use std::sync::Arc;
@@ -17,17 +17,21 @@ use tokio::{
     io::{AsyncBufReadExt, BufReader, stdin},
     task::JoinSet,
 };
-use tracing::{error, info, instrument, warn};
+use tracing::{debug, error, info, instrument, warn};
 
 static RUN_INTERVAL: Duration = Duration::hours(5);
 static FUEL_ESTIMATE_COMMIT_SIZE: usize = 50;
 static METER_PER_SECONDS_TO_KNOTS: f64 = 1.943844;
 
+mod empirical;
 mod holtrop;
 mod normal;
+mod weather;
 
+pub use empirical::*;
 pub use holtrop::*;
 pub use normal::*;
+pub use weather::*;
 
 #[cfg(not(feature = "test"))]
 static REQUIRED_TRIPS_TO_ESTIMATE_FUEL: u32 = 5;
@@ -37,6 +41,7 @@ static REQUIRED_TRIPS_TO_ESTIMATE_FUEL: u32 = 5;
 pub enum FuelImpl {
     Holtrop(Holtrop),
     Maru(Maru),
+    Empirical(Empirical),
 }
 
 impl FuelImpl {
@@ -65,6 +70,40 @@ impl FuelImpl {
                     FuelImpl::Maru(Maru)
                 }
             },
+            FuelImplDiscriminants::Empirical => match vessel.empirical_model {
+                Some(model) => FuelImpl::Empirical(Empirical::new(model)),
+                None => {
+                    debug!(
+                        "missing empirical fuel model for fuel estimation, vessel_id: {}",
+                        vessel.vessel_id
+                    );
+                    FuelImpl::Maru(Maru)
+                }
+            },
+        }
+    }
+
+    /// Loads the weather along the given positions for implementations that account for wind
+    /// and waves, which is currently only Holtrop.
+    pub async fn load_weather<T>(
+        &mut self,
+        adapter: &dyn FuelEstimation,
+        positions: &[T],
+    ) -> Result<()>
+    where
+        FuelItem: for<'a> From<&'a T>,
+    {
+        if let FuelImpl::Holtrop(holtrop) = self {
+            holtrop.set_weather(load_fuel_weather(adapter, positions).await?);
+        }
+        Ok(())
+    }
+
+    /// Sets already loaded weather for implementations that account for wind and waves, used
+    /// when the same positions are estimated with several implementations.
+    pub fn set_weather(&mut self, weather: &FuelWeatherLookup) {
+        if let FuelImpl::Holtrop(holtrop) = self {
+            holtrop.set_weather(weather.clone());
         }
     }
 }
@@ -80,6 +119,7 @@ impl FuelComputation for FuelImpl {
         match self {
             FuelImpl::Holtrop(holtrop) => holtrop.fuel_liter(first, second, vessel, time_ms),
             FuelImpl::Maru(normal_fuel) => normal_fuel.fuel_liter(first, second, vessel, time_ms),
+            FuelImpl::Empirical(empirical) => empirical.fuel_liter(first, second, vessel, time_ms),
         }
     }
 
@@ -87,6 +127,7 @@ impl FuelComputation for FuelImpl {
         match self {
             FuelImpl::Holtrop(holtrop) => holtrop.mode(),
             FuelImpl::Maru(normal_fuel) => normal_fuel.mode(),
+            FuelImpl::Empirical(empirical) => empirical.mode(),
         }
     }
 }
@@ -145,6 +186,7 @@ pub struct VesselFuelInfo {
     pub mode: FuelImplDiscriminants,
     pub main_sfc: Option<f64>,
     pub max_cargo_weight: Option<f64>,
+    pub empirical_model: Option<EmpiricalFuelModel>,
 }
 
 impl VesselFuelInfo {
@@ -179,6 +221,7 @@ impl VesselFuelInfo {
             mode,
             main_sfc: vessel.main_sfc(),
             max_cargo_weight,
+            empirical_model: None,
         }
     }
     pub fn from_live(
@@ -203,8 +246,15 @@ impl VesselFuelInfo {
             mode,
             main_sfc: Some(vessel.main_sfc()),
             max_cargo_weight,
+            empirical_model: None,
         }
     }
+    /// Sets the fitted empirical model of the vessel, which is only used if the vessel is in
+    /// [`FuelImplDiscriminants::Empirical`] mode.
+    pub fn with_empirical_model(mut self, model: Option<EmpiricalFuelModel>) -> Self {
+        self.empirical_model = model;
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -248,12 +298,20 @@ impl FuelEstimator {
             .await
             .unwrap();
 
-        let max_cargo_weight = max_cargo_weight(self.mode, self.adapter.as_ref(), vessel)
+        let empirical_model = self
+            .adapter
+            .empirical_fuel_model(vessel.fiskeridir.id)
             .await
             .unwrap();
 
-        let vessel = VesselFuelInfo::from_core(vessel, max_cargo_weight, self.mode);
+        let vessel = vessel_fuel_info(self.adapter.as_ref(), self.mode, vessel, empirical_model)
+            .await
+            .unwrap();
         let mut fuel_impl = FuelImpl::new(&vessel);
+        fuel_impl
+            .load_weather(self.adapter.as_ref(), &ais_vms)
+            .await
+            .unwrap();
 
         estimate_fuel_for_positions(&mut fuel_impl, &ais_vms, &vessel).fuel_liter
     }
@@ -349,10 +407,23 @@ impl FuelEstimator {
                 continue;
             }
 
-            let max_cargo_weight =
-                max_cargo_weight(self.mode, self.adapter.as_ref(), &vessel).await?;
+            // Vessels are skipped if fitting fails as estimating them with another model than
+            // the one they would otherwise use would yield inconsistent estimates.
+            let empirical_model = match self.update_empirical_model(&vessel).await {
+                Ok(v) => v,
+                Err(e) => {
+                    error!(
+                        "failed to fit empirical fuel model for vessel_id: '{}', err: {e:?}",
+                        vessel.id()
+                    );
+                    continue;
+                }
+            };
+
+            let vessel =
+                vessel_fuel_info(self.adapter.as_ref(), self.mode, &vessel, empirical_model)
+                    .await?;
 
-            let vessel = VesselFuelInfo::from_core(&vessel, max_cargo_weight, self.mode);
             // Only errors on all receivers being dropped which cannot be at this step as we have
             // the receiver in scope
             sender.send(vessel).await.unwrap();
@@ -373,6 +444,47 @@ impl FuelEstimator {
         Ok(())
     }
 
+    /// Refits the empirical model of the vessel if its fuel measurements have changed since the
+    /// last fit, otherwise the stored model is returned.
+    /// The existing fuel estimates of the vessel are reset if the refit changes the model it is
+    /// estimated with, such that they are re-estimated with the new model.
+    async fn update_empirical_model(&self, vessel: &Vessel) -> Result<Option<EmpiricalFuelModel>> {
+        let vessel_id = vessel.fiskeridir.id;
+
+        // The checksum is read before the fit so that measurements added during the fit are not
+        // marked as fitted.
+        let Some(checksum) = self
+            .adapter
+            .changed_fuel_measurements_checksum(vessel_id)
+            .await?
+        else {
+            return Ok(self.adapter.empirical_fuel_model(vessel_id).await?);
+        };
+
+        let previous = self.adapter.empirical_fuel_model(vessel_id).await?;
+
+        let max_cargo_weight = max_cargo_weight(
+            FuelImplDiscriminants::Empirical,
+            self.adapter.as_ref(),
+            vessel,
+        )
+        .await?;
+        let fit_vessel =
+            VesselFuelInfo::from_core(vessel, max_cargo_weight, FuelImplDiscriminants::Empirical);
+        let model = fit_empirical_fuel_model(self.adapter.as_ref(), &fit_vessel).await?;
+
+        let reset_estimates = model != previous
+            && matches!(
+                vessel.fiskeridir.fuel_estimation_model,
+                None | Some(FuelEstimationModel::Empirical)
+            );
+
+        self.adapter
+            .set_empirical_fuel_model(vessel_id, model, &checksum, reset_estimates)
+            .await?;
+        Ok(model)
+    }
+
     #[instrument(skip_all)]
     async fn run_local_fuel_estimation(&self, vessel_ids: &[FiskeridirVesselId]) -> Result<()> {
         let vessels = self.adapter.vessels_with_trips(1).await?;
@@ -402,14 +514,12 @@ impl FuelEstimator {
             let len = dates_to_estimate.len();
             info!("dates to process: {len}");
 
-            let max_cargo_weight =
-                max_cargo_weight(self.mode, self.adapter.as_ref(), &vessel).await?;
+            let empirical_model = self.adapter.empirical_fuel_model(vessel.id()).await?;
 
-            let vessel = Arc::new(VesselFuelInfo::from_core(
-                &vessel,
-                max_cargo_weight,
-                self.mode,
-            ));
+            let vessel = Arc::new(
+                vessel_fuel_info(self.adapter.as_ref(), self.mode, &vessel, empirical_model)
+                    .await?,
+            );
 
             let (worker_tx, worker_rx) = async_channel::bounded(len);
             let (master_tx, master_rx) = async_channel::bounded(len);
@@ -572,6 +682,7 @@ async fn process_day(
     let mut start_idx = 0;
 
     let mut fuel_impl = FuelImpl::new(vessel);
+    fuel_impl.load_weather(adapter, &positions).await?;
     while start_idx < len - 1 {
         let start = &positions[start_idx];
 
@@ -818,13 +929,57 @@ where
     }
 }
 
+/// Returns the fuel estimation mode of a vessel, vessels with a selected model use it, vessels
+/// with a fitted empirical model (i.e. with enough fuel measurements) use the empirical model and
+/// all others use the given default mode.
+pub fn vessel_fuel_mode(
+    default: FuelImplDiscriminants,
+    selected: Option<FuelEstimationModel>,
+    empirical_model: Option<&EmpiricalFuelModel>,
+) -> FuelImplDiscriminants {
+    match (selected, empirical_model) {
+        (Some(selected), _) => selected.into(),
+        (None, Some(_)) => FuelImplDiscriminants::Empirical,
+        (None, None) => default,
+    }
+}
+
+/// Builds the fuel info of the vessel in the mode it is estimated with, see [`vessel_fuel_mode`].
+pub async fn vessel_fuel_info(
+    adapter: &dyn FuelEstimation,
+    default: FuelImplDiscriminants,
+    vessel: &Vessel,
+    empirical_model: Option<EmpiricalFuelModel>,
+) -> Result<VesselFuelInfo> {
+    let mode = vessel_fuel_mode(
+        default,
+        vessel.fiskeridir.fuel_estimation_model,
+        empirical_model.as_ref(),
+    );
+    let max_cargo_weight = max_cargo_weight(mode, adapter, vessel).await?;
+
+    Ok(VesselFuelInfo::from_core(vessel, max_cargo_weight, mode)
+        .with_empirical_model(empirical_model))
+}
+
+impl From<FuelEstimationModel> for FuelImplDiscriminants {
+    fn from(value: FuelEstimationModel) -> Self {
+        match value {
+            FuelEstimationModel::Maru => FuelImplDiscriminants::Maru,
+            FuelEstimationModel::Holtrop => FuelImplDiscriminants::Holtrop,
+            FuelEstimationModel::Empirical => FuelImplDiscriminants::Empirical,
+        }
+    }
+}
+
 pub async fn max_cargo_weight(
     mode: FuelImplDiscriminants,
     adapter: &dyn FuelEstimation,
     vessel: &Vessel,
 ) -> Result<Option<f64>> {
     match mode {
-        FuelImplDiscriminants::Maru => Ok(Some(
+        // The empirical mode falls back to Maru for vessels without a fitted model
+        FuelImplDiscriminants::Maru | FuelImplDiscriminants::Empirical => Ok(Some(
             adapter
                 .vessel_max_cargo_weight(vessel.fiskeridir.id)
                 .await?,
@@ -832,3 +987,65 @@ pub async fn max_cargo_weight(
         FuelImplDiscriminants::Holtrop => Ok(None),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model() -> EmpiricalFuelModel {
+        EmpiricalFuelModel {
+            idle_liter_per_hour: 10.,
+            load_liter_per_hour: 100.,
+            haul_liter_per_hour: 20.,
+            num_measurements: 10,
+        }
+    }
+
+    #[test]
+    fn test_vessel_fuel_mode_uses_selected_model_over_default() {
+        assert_eq!(
+            vessel_fuel_mode(
+                FuelImplDiscriminants::Empirical,
+                Some(FuelEstimationModel::Holtrop),
+                None,
+            ),
+            FuelImplDiscriminants::Holtrop
+        );
+        assert_eq!(
+            vessel_fuel_mode(
+                FuelImplDiscriminants::Maru,
+                Some(FuelEstimationModel::Empirical),
+                None,
+            ),
+            FuelImplDiscriminants::Empirical
+        );
+    }
+
+    #[test]
+    fn test_vessel_fuel_mode_uses_selected_model_over_fitted_model() {
+        assert_eq!(
+            vessel_fuel_mode(
+                FuelImplDiscriminants::Maru,
+                Some(FuelEstimationModel::Holtrop),
+                Some(&model()),
+            ),
+            FuelImplDiscriminants::Holtrop
+        );
+    }
+
+    #[test]
+    fn test_vessel_fuel_mode_uses_fitted_model_without_selected_model() {
+        assert_eq!(
+            vessel_fuel_mode(FuelImplDiscriminants::Holtrop, None, Some(&model())),
+            FuelImplDiscriminants::Empirical
+        );
+    }
+
+    #[test]
+    fn test_vessel_fuel_mode_uses_default_without_selected_or_fitted_model() {
+        assert_eq!(
+            vessel_fuel_mode(FuelImplDiscriminants::Holtrop, None, None),
+            FuelImplDiscriminants::Holtrop
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use super::FuelItem;
+use crate::Result;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use kyogre_core::{DateRange, FuelEstimation, FuelWeather, WeatherLocationId};
+use std::collections::{HashMap, HashSet};
+
+/// The size of a weather location cell in degrees, see [`WeatherLocationId::from_lat_lon`].
+static WEATHER_CELL_DEGREES: f64 = 0.1;
+/// Weather is stored hourly, positions are matched with the closest hour.
+static WEATHER_INTERVAL: Duration = Duration::hours(1);
+
+/// Hourly wind along a set of positions, looked up by the weather location cell containing a
+/// position or the nearest of its neighbouring cells.
+#[derive(Debug, Default, Clone)]
+pub struct FuelWeatherLookup {
+    weather: HashMap<(DateTime<Utc>, WeatherLocationId), FuelWeather>,
+}
+
+impl FuelWeatherLookup {
+    pub fn new(weather: Vec<FuelWeather>) -> Self {
+        Self {
+            weather: weather
+                .into_iter()
+                .map(|w| ((w.timestamp, w.weather_location_id), w))
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weather.is_empty()
+    }
+
+    pub fn weather(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        timestamp: DateTime<Utc>,
+    ) -> Option<&FuelWeather> {
+        let hour = timestamp.duration_round(WEATHER_INTERVAL).ok()?;
+        let lon_scale = latitude.to_radians().cos();
+
+        neighbouring_cells(latitude, longitude)
+            .filter_map(|id| self.weather.get(&(hour, id)))
+            .min_by(|a, b| {
+                let distance = |w: &FuelWeather| {
+                    (w.latitude - latitude).powi(2)
+                        + ((w.longitude - longitude) * lon_scale).powi(2)
+                };
+                distance(a).total_cmp(&distance(b))
+            })
+    }
+}
+
+/// Loads the weather of all cells the given positions pass through, returns an empty lookup if
+/// there are no positions.
+pub async fn load_fuel_weather<T>(
+    adapter: &dyn FuelEstimation,
+    positions: &[T],
+) -> Result<FuelWeatherLookup>
+where
+    FuelItem: for<'a> From<&'a T>,
+{
+    let mut ids = HashSet::new();
+    let mut start: Option<DateTime<Utc>> = None;
+    let mut end: Option<DateTime<Utc>> = None;
+
+    for p in positions {
+        let item = FuelItem::from(p);
+        ids.extend(neighbouring_cells(item.latitude, item.longitude));
+        start = Some(start.map_or(item.timestamp, |v| v.min(item.timestamp)));
+        end = Some(end.map_or(item.timestamp, |v| v.max(item.timestamp)));
+    }
+
+    let (Some(start), Some(end)) = (start, end) else {
+        return Ok(FuelWeatherLookup::default());
+    };
+
+    let range = DateRange::new(start - WEATHER_INTERVAL, end + WEATHER_INTERVAL)?;
+    let ids = ids.into_iter().collect::<Vec<_>>();
+
+    let weather = adapter.fuel_estimation_weather(&range, &ids).await?;
+
+    Ok(FuelWeatherLookup::new(weather))
+}
+
+fn neighbouring_cells(latitude: f64, longitude: f64) -> impl Iterator<Item = WeatherLocationId> {
+    [-1., 0., 1.].into_iter().flat_map(move |lat| {
+        [-1., 0., 1.].into_iter().map(move |lon| {
+            WeatherLocationId::from_lat_lon(
+                latitude + lat * WEATHER_CELL_DEGREES,
+                longitude + lon * WEATHER_CELL_DEGREES,
+            )
+        })
+    })
+}
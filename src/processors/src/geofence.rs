@@ -0,0 +1,177 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use chrono::Utc;
+use kyogre_core::{
+    DateRange, FiskeridirVesselId, Geofence, GeofenceEventType, GeofenceId, GeofenceInbound,
+    GeofenceOutbound, GeofenceVessel, GeofenceVesselUpdate, NewGeofenceEvent,
+};
+use tracing::{error, instrument};
+
+use crate::Result;
+
+static RUN_INTERVAL: Duration = Duration::from_secs(60);
+/// How far back in time we look for positions of vessels that have not yet been processed for a
+/// geofence, the first position found only establishes whether the vessel is inside or outside.
+static DEFAULT_GEOFENCE_LOOKBACK: chrono::Duration = chrono::Duration::hours(24);
+
+pub trait GeofenceProcessing: GeofenceOutbound + GeofenceInbound {}
+
+impl<T> GeofenceProcessing for T where T: GeofenceOutbound + GeofenceInbound {}
+
+/// Evaluates the positions of all vessels followed by the owner of a geofence and records an
+/// event each time a vessel enters or exits the geofence.
+#[derive(Clone)]
+pub struct GeofenceProcessor {
+    adapter: Arc<dyn GeofenceProcessing>,
+}
+
+impl GeofenceProcessor {
+    pub fn new(adapter: Arc<dyn GeofenceProcessing>) -> Self {
+        Self { adapter }
+    }
+
+    pub async fn run_continuous(self) -> ! {
+        loop {
+            self.run_cycle().await;
+            tokio::time::sleep(RUN_INTERVAL).await;
+        }
+    }
+
+    #[instrument(skip_all)]
+    async fn run_cycle(&self) {
+        if let Err(e) = self.run_single().await {
+            error!("geofence processor failed: {e:?}");
+        }
+    }
+
+    pub async fn run_single(&self) -> Result<()> {
+        let geofences = self
+            .adapter
+            .all_geofences()
+            .await?
+            .into_iter()
+            .map(|g| (g.id, g))
+            .collect::<HashMap<_, _>>();
+
+        if geofences.is_empty() {
+            return Ok(());
+        }
+
+        let mut vessels: HashMap<FiskeridirVesselId, Vec<GeofenceVessel>> = HashMap::new();
+        for v in self.adapter.geofence_vessels().await? {
+            vessels.entry(v.fiskeridir_vessel_id).or_default().push(v);
+        }
+
+        for (vessel_id, states) in vessels {
+            if let Err(e) = self.process_vessel(&geofences, states).await {
+                error!("failed to process geofences for vessel {vessel_id}: {e:?}");
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn process_vessel(
+        &self,
+        geofences: &HashMap<GeofenceId, Geofence>,
+        states: Vec<GeofenceVessel>,
+    ) -> Result<()> {
+        let Some(vessel) = states.first() else {
+            return Ok(());
+        };
+
+        let now = Utc::now();
+        let lookback = now - DEFAULT_GEOFENCE_LOOKBACK;
+
+        // Late VMS positions can be added before positions that have already been processed, the
+        // events from the earliest of them are then replaced. The positions before it are only
+        // used to establish whether the vessel was inside or outside.
+        let reprocess_from = |state: &GeofenceVessel| {
+            vessel
+                .earliest_vms_insertion
+                .filter(|t| state.processed_until.is_some_and(|p| *t <= p))
+        };
+
+        let start = states
+            .iter()
+            .map(|s| match reprocess_from(s) {
+                Some(t) => t - DEFAULT_GEOFENCE_LOOKBACK,
+                None => s.processed_until.unwrap_or(lookback),
+            })
+            .min()
+            .unwrap_or(lookback);
+
+        let range = DateRange::new(start, now)?;
+
+        let positions = self
+            .adapter
+            .ais_vms_positions(vessel.mmsi, vessel.call_sign.as_ref(), &range)
+            .await?;
+
+        let mut updates = Vec::with_capacity(states.len());
+
+        for state in &states {
+            let Some(geofence) = geofences.get(&state.geofence_id) else {
+                continue;
+            };
+
+            let replace_events_from = reprocess_from(state);
+
+            let (mut inside, mut processed_until) = match replace_events_from {
+                Some(t) => (
+                    positions
+                        .iter()
+                        .rev()
+                        .find(|p| p.timestamp < t)
+                        .map(|p| geofence.contains(p.latitude, p.longitude)),
+                    None,
+                ),
+                None => (state.inside, state.processed_until),
+            };
+            let mut events = Vec::new();
+
+            for pos in positions.iter().filter(|p| match replace_events_from {
+                Some(t) => p.timestamp >= t,
+                None => state.processed_until.is_none_or(|t| p.timestamp > t),
+            }) {
+                let is_inside = geofence.contains(pos.latitude, pos.longitude);
+
+                if inside.is_some_and(|v| v != is_inside) {
+                    events.push(NewGeofenceEvent {
+                        geofence_id: state.geofence_id,
+                        fiskeridir_vessel_id: state.fiskeridir_vessel_id,
+                        event_type: GeofenceEventType::from_transition(is_inside),
+                        timestamp: pos.timestamp,
+                        latitude: pos.latitude,
+                        longitude: pos.longitude,
+                    });
+                }
+
+                inside = Some(is_inside);
+                processed_until = Some(pos.timestamp);
+            }
+
+            if let Some(inside) = inside
+                && let Some(processed_until) = processed_until
+                && (replace_events_from.is_some() || Some(processed_until) != state.processed_until)
+            {
+                updates.push(GeofenceVesselUpdate {
+                    geofence_id: state.geofence_id,
+                    fiskeridir_vessel_id: state.fiskeridir_vessel_id,
+                    inside,
+                    processed_until,
+                    events,
+                    replace_events_from,
+                    call_sign: vessel.call_sign.clone(),
+                    earliest_vms_insertion: vessel.earliest_vms_insertion,
+                });
+            }
+        }
+
+        if !updates.is_empty() {
+            self.adapter.update_geofence_vessels(updates).await?;
+        }
+
+        Ok(())
+    }
+}
@@ -5,8 +5,11 @@ pub mod ais_vms_conflict;
 pub mod benchmarks;
 pub mod current_position;
 pub mod error;
+pub mod fuel_backtest;
 pub mod fuel_estimation;
+pub mod geofence;
 pub mod live_fuel;
+pub mod price_forecast;
 pub mod settings;
 pub mod startup;
 pub mod unrealistic_speed;
@@ -15,8 +18,11 @@ pub mod user_haul_refresher;
 pub use ais_vms_conflict::*;
 pub use benchmarks::*;
 pub use error::*;
+pub use fuel_backtest::*;
 pub use fuel_estimation::*;
+pub use geofence::*;
 pub use live_fuel::*;
+pub use price_forecast::*;
 pub use settings::*;
 pub use startup::*;
 pub use unrealistic_speed::*;
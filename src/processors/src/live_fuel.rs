@@ -1,4 +1,7 @@
-use crate::{FuelImpl, FuelImplDiscriminants, Result, VesselFuelInfo, estimate_fuel_for_positions};
+use crate::{
+    FuelImpl, FuelImplDiscriminants, Result, VesselFuelInfo, estimate_fuel_for_positions,
+    vessel_fuel_mode,
+};
 use chrono::Utc;
 use kyogre_core::{
     AisPosition, Bound, DateRange, LiveFuelInbound, LiveFuelVessel, NewLiveFuel,
@@ -104,7 +107,15 @@ impl LiveFuel {
             }
         }
 
-        let vessel = VesselFuelInfo::from_live(vessel, None, self.mode);
+        let empirical_model = self.adapter.empirical_fuel_model(vessel.vessel_id).await?;
+
+        let mode = vessel_fuel_mode(
+            self.mode,
+            vessel.fuel_estimation_model,
+            empirical_model.as_ref(),
+        );
+        let vessel =
+            VesselFuelInfo::from_live(vessel, None, mode).with_empirical_model(empirical_model);
         let mut fuel_impl = FuelImpl::new(&vessel);
         Ok(hour_split
             .into_values()
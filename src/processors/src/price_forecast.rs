@@ -0,0 +1,270 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use chrono::{Datelike, IsoWeek, NaiveDate, Utc, Weekday};
+use kyogre_core::{NewPriceForecast, PriceForecastInbound, PriceForecastOutbound, WeeklyPrice};
+use tracing::{error, instrument};
+
+use crate::Result;
+
+static RUN_INTERVAL: Duration = Duration::from_secs(60 * 60 * 24);
+
+/// Number of weeks to forecast after the current week.
+pub static PRICE_FORECAST_HORIZON_WEEKS: u32 = 4;
+/// Price groups with fewer weeks with sales than this are not forecasted.
+pub static PRICE_FORECAST_MIN_WEEKS: usize = 8;
+/// Price groups without sales within this many weeks before the current week are not forecasted.
+pub static PRICE_FORECAST_MAX_WEEKS_SINCE_LAST_SALE: i64 = 12;
+
+/// Smoothing factor of the level.
+static ALPHA: f64 = 0.5;
+/// Smoothing factor of the trend.
+static BETA: f64 = 0.1;
+/// Z-score of the 95% prediction interval.
+static Z_95: f64 = 1.96;
+
+pub trait PriceForecasting: PriceForecastOutbound + PriceForecastInbound {}
+
+impl<T> PriceForecasting for T where T: PriceForecastOutbound + PriceForecastInbound {}
+
+/// Forecasts the weekly price of each Råfisklaget price group for the next
+/// [`PRICE_FORECAST_HORIZON_WEEKS`] weeks using Holt's linear exponential smoothing.
+#[derive(Clone)]
+pub struct PriceForecaster {
+    adapter: Arc<dyn PriceForecasting>,
+}
+
+impl PriceForecaster {
+    pub fn new(adapter: Arc<dyn PriceForecasting>) -> Self {
+        Self { adapter }
+    }
+
+    pub async fn run_continuous(self) -> ! {
+        loop {
+            self.run_cycle().await;
+            tokio::time::sleep(RUN_INTERVAL).await;
+        }
+    }
+
+    #[instrument(skip_all)]
+    async fn run_cycle(&self) {
+        if let Err(e) = self.run_single().await {
+            error!("price forecast failed: {e:?}");
+        }
+    }
+
+    pub async fn run_single(&self) -> Result<()> {
+        let prices = self.adapter.weekly_prices().await?;
+        let current_week = Utc::now().iso_week();
+
+        let mut groups: HashMap<_, Vec<WeeklyPrice>> = HashMap::new();
+        for p in prices {
+            groups.entry(p.group).or_default().push(p);
+        }
+
+        let forecasts: Vec<_> = groups
+            .into_values()
+            .flat_map(|prices| forecast(&prices, current_week, PRICE_FORECAST_HORIZON_WEEKS))
+            .collect();
+
+        self.adapter.set_price_forecasts(&forecasts).await?;
+
+        Ok(())
+    }
+}
+
+/// Forecasts the `horizon` weeks succeeding `current_week` from the given prices, which must
+/// belong to the same price group and be ordered ascendingly by week.
+///
+/// Weeks without sales are skipped rather than interpolated, as Råfisklaget only reports weeks
+/// with sales. The number of steps ahead is counted from the last week with sales, so groups
+/// without recent sales extrapolate their trend further and get wider intervals, groups without
+/// sales in the last [`PRICE_FORECAST_MAX_WEEKS_SINCE_LAST_SALE`] weeks are skipped. The prediction
+/// interval is derived from the one-step-ahead residuals and widens with the square root of the
+/// number of steps ahead.
+pub fn forecast(
+    prices: &[WeeklyPrice],
+    current_week: IsoWeek,
+    horizon: u32,
+) -> Vec<NewPriceForecast> {
+    if prices.len() < PRICE_FORECAST_MIN_WEEKS {
+        return vec![];
+    }
+
+    let last = &prices[prices.len() - 1];
+    let last_monday = monday(last.iso_week);
+    let current_monday = monday(current_week);
+
+    if (current_monday - last_monday).num_weeks() > PRICE_FORECAST_MAX_WEEKS_SINCE_LAST_SALE {
+        return vec![];
+    }
+
+    let mut level = prices[0].price;
+    let mut trend = prices[1].price - prices[0].price;
+    let mut sum_squared_residuals = 0.;
+
+    for p in &prices[1..] {
+        let residual = p.price - (level + trend);
+        sum_squared_residuals += residual.powi(2);
+
+        let prev_level = level;
+        level = ALPHA * p.price + (1. - ALPHA) * (level + trend);
+        trend = BETA * (level - prev_level) + (1. - BETA) * trend;
+    }
+
+    let std_dev = (sum_squared_residuals / (prices.len() - 1) as f64).sqrt();
+
+    (1..=horizon)
+        .filter_map(|h| {
+            let target = current_monday + chrono::Duration::weeks(h as i64);
+            let steps = (target - last_monday).num_weeks();
+            if steps < 1 {
+                return None;
+            }
+
+            let steps = steps as f64;
+            let price = (level + steps * trend).max(0.);
+            let margin = Z_95 * std_dev * steps.sqrt();
+
+            Some(NewPriceForecast {
+                group: last.group,
+                iso_week: target.iso_week(),
+                price,
+                lower_bound: (price - margin).max(0.),
+                upper_bound: price + margin,
+            })
+        })
+        .collect()
+}
+
+fn monday(iso_week: IsoWeek) -> NaiveDate {
+    // Unwrap is safe as the week is constructed from a valid `IsoWeek`
+    NaiveDate::from_isoywd_opt(iso_week.year(), iso_week.week(), Weekday::Mon).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use fiskeridir_rs::{Condition, GearGroup, Quality, SpeciesFiskeridirId, VesselLengthGroup};
+    use kyogre_core::PriceGroup;
+
+    use super::*;
+
+    fn week(year: i32, week: u32) -> IsoWeek {
+        NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)
+            .unwrap()
+            .iso_week()
+    }
+
+    fn group() -> PriceGroup {
+        PriceGroup {
+            vessel_length_group: VesselLengthGroup::TwentyEightAndAbove,
+            gear_group: GearGroup::Trawl,
+            species: "1022".parse::<SpeciesFiskeridirId>().unwrap(),
+            condition: Condition::SloydMedHode,
+            quality: Quality::Extra,
+        }
+    }
+
+    fn prices(start: IsoWeek, values: impl IntoIterator<Item = f64>) -> Vec<WeeklyPrice> {
+        let start = monday(start);
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(i, price)| WeeklyPrice {
+                group: group(),
+                iso_week: (start + chrono::Duration::weeks(i as i64)).iso_week(),
+                price,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_forecast_requires_min_weeks() {
+        let prices = prices(
+            week(2026, 1),
+            (0..PRICE_FORECAST_MIN_WEEKS - 1).map(|_| 10.),
+        );
+        assert!(forecast(&prices, week(2026, 10), 4).is_empty());
+    }
+
+    #[test]
+    fn test_forecast_skips_groups_without_recent_sales() {
+        let prices = prices(week(2026, 1), (0..10).map(|_| 10.));
+        let last = monday(prices.last().unwrap().iso_week);
+
+        let current =
+            (last + chrono::Duration::weeks(PRICE_FORECAST_MAX_WEEKS_SINCE_LAST_SALE)).iso_week();
+        assert_eq!(forecast(&prices, current, 4).len(), 4);
+
+        let current = (last
+            + chrono::Duration::weeks(PRICE_FORECAST_MAX_WEEKS_SINCE_LAST_SALE + 1))
+        .iso_week();
+        assert!(forecast(&prices, current, 4).is_empty());
+    }
+
+    #[test]
+    fn test_forecast_extrapolates_linear_trend() {
+        let prices = prices(week(2026, 1), (0..10).map(|i| 10. + i as f64));
+        let current = prices.last().unwrap().iso_week;
+
+        let forecasts = forecast(&prices, current, 3);
+
+        assert_eq!(forecasts.len(), 3);
+        for (i, f) in forecasts.iter().enumerate() {
+            let expected = 19. + (i + 1) as f64;
+            assert!((f.price - expected).abs() < 1e-9, "{f:?}");
+            assert!((f.lower_bound - f.price).abs() < 1e-9);
+            assert!((f.upper_bound - f.price).abs() < 1e-9);
+            assert_eq!(f.group, group());
+        }
+    }
+
+    #[test]
+    fn test_forecast_bounds_are_non_negative_and_widen() {
+        let prices = prices(week(2026, 1), [10., 2., 12., 1., 9., 3., 11., 1., 8., 0.5]);
+        let current = prices.last().unwrap().iso_week;
+
+        let forecasts = forecast(&prices, current, 4);
+
+        assert_eq!(forecasts.len(), 4);
+        for f in &forecasts {
+            assert!(f.price >= 0.);
+            assert!(f.lower_bound >= 0.);
+            assert!(f.lower_bound <= f.price && f.price <= f.upper_bound);
+        }
+        for w in forecasts.windows(2) {
+            assert!(w[1].upper_bound - w[1].price > w[0].upper_bound - w[0].price);
+        }
+    }
+
+    #[test]
+    fn test_forecast_is_anchored_to_current_week() {
+        let prices = prices(week(2026, 1), (0..10).map(|i| 10. + i as f64));
+        let current = week(2026, 20);
+
+        let forecasts = forecast(&prices, current, 4);
+
+        let weeks: Vec<_> = forecasts.iter().map(|f| f.iso_week).collect();
+        assert_eq!(
+            weeks,
+            vec![
+                week(2026, 21),
+                week(2026, 22),
+                week(2026, 23),
+                week(2026, 24)
+            ]
+        );
+        // Last sale is in week 10, so the first forecasted week is 11 steps ahead
+        assert!((forecasts[0].price - (19. + 11.)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_forecast_crosses_year_boundary() {
+        let prices = prices(week(2026, 40), (0..10).map(|_| 10.));
+        let current = week(2026, 52);
+
+        let forecasts = forecast(&prices, current, 2);
+
+        let weeks: Vec<_> = forecasts.iter().map(|f| f.iso_week).collect();
+        assert_eq!(weeks, vec![week(2026, 53), week(2027, 1)]);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    FuelEstimator, LiveFuel, Result, Settings, TripBenchmarkRunner, UserHaulRefresher,
-    current_position::CurrentPositionProcessor,
+    FuelBacktester, FuelEstimator, GeofenceProcessor, LiveFuel, PriceForecaster, Result, Settings,
+    TripBenchmarkRunner, UserHaulRefresher, current_position::CurrentPositionProcessor,
 };
 use orca_core::Environment;
 use postgres::PostgresAdapter;
@@ -13,6 +13,9 @@ pub struct App {
     trip_benchmark_runner: TripBenchmarkRunner,
     live_fuel: LiveFuel,
     current_position: CurrentPositionProcessor,
+    geofence: GeofenceProcessor,
+    fuel_backtester: FuelBacktester,
+    price_forecaster: PriceForecaster,
     user_haul_refresher: UserHaulRefresher,
     environment: Environment,
 }
@@ -38,6 +41,9 @@ impl App {
                 settings.fuel_estimation_vessels.clone(),
             ),
             user_haul_refresher: UserHaulRefresher::new(postgres.clone()),
+            geofence: GeofenceProcessor::new(postgres.clone()),
+            fuel_backtester: FuelBacktester::new(postgres.clone()),
+            price_forecaster: PriceForecaster::new(postgres.clone()),
             current_position: CurrentPositionProcessor::new(
                 postgres,
                 settings.current_positions_batch_size,
@@ -58,6 +64,9 @@ impl App {
                     estimator,
                     live_fuel,
                     current_position,
+                    geofence,
+                    fuel_backtester,
+                    price_forecaster,
                     environment: _,
                     trip_benchmark_runner,
                     user_haul_refresher,
@@ -66,6 +75,9 @@ impl App {
                 set.spawn(estimator.run_continuous());
                 set.spawn(live_fuel.run_continuous());
                 set.spawn(current_position.run_continuous());
+                set.spawn(geofence.run_continuous());
+                set.spawn(fuel_backtester.run_continuous());
+                set.spawn(price_forecaster.run_continuous());
                 set.spawn(trip_benchmark_runner.run_continuous());
                 set.spawn(user_haul_refresher.run_continuous());
 
@@ -76,6 +88,9 @@ impl App {
                     estimator,
                     live_fuel,
                     current_position,
+                    geofence,
+                    fuel_backtester,
+                    price_forecaster,
                     environment: _,
                     mut trip_benchmark_runner,
                     user_haul_refresher,
@@ -84,6 +99,9 @@ impl App {
                 estimator.run_single(None).await?;
                 live_fuel.run_single().await?;
                 current_position.run_single().await?;
+                geofence.run_single().await?;
+                fuel_backtester.run_single().await?;
+                price_forecaster.run_single().await?;
                 trip_benchmark_runner.run_single().await?;
                 user_haul_refresher.run_single().await?;
 
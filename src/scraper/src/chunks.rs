@@ -1,36 +1,514 @@
-use crate::Result;
-use kyogre_core::CoreResult;
-use std::future::Future;
-use tracing::error;
+use crate::{FiskeridirSource, Result};
+use fiskeridir_rs::{DataFile, DataFileId, FieldQuality, FiskeridirRecord};
+use kyogre_core::{CoreResult, FileCheckpoint, FileParseError, NewFileDataQuality};
+use std::{future::Future, sync::Arc};
+use tracing::{error, info, warn};
 
-pub(crate) async fn add_in_chunks<A, B, D>(
+/// The file that is being added, used to store checkpoints such that a failed attempt resumes
+/// after the last added chunk instead of starting over.
+pub(crate) struct FileIngestion {
+    source: Arc<FiskeridirSource>,
+    file: DataFile,
+    file_id: DataFileId,
+    file_hash: String,
+}
+
+impl FileIngestion {
+    pub fn new(source: Arc<FiskeridirSource>, file: DataFile, file_hash: String) -> Self {
+        Self {
+            source,
+            file_id: file.id(),
+            file,
+            file_hash,
+        }
+    }
+
+    async fn set_checkpoint(
+        &self,
+        row_offset: u64,
+        chunk_hash: u64,
+        parse_errors: Vec<FileParseError>,
+    ) -> Result<()> {
+        let checkpoint = FileCheckpoint {
+            file_id: self.file_id.clone(),
+            file_hash: self.file_hash.clone(),
+            row_offset,
+            chunk_hash: chunk_hash.to_string(),
+        };
+
+        Ok(self
+            .source
+            .hash_store
+            .set_checkpoint(&checkpoint, parse_errors)
+            .await?)
+    }
+
+    async fn set_data_quality(
+        &self,
+        rows: u64,
+        rejected_rows: u64,
+        fields: FieldQuality,
+    ) -> Result<()> {
+        let quality = NewFileDataQuality {
+            file_id: self.file_id.clone(),
+            file_hash: self.file_hash.clone(),
+            dataset: self.file.to_string(),
+            year: self.file.year(),
+            rows,
+            rejected_rows,
+            fields,
+        };
+
+        Ok(self.source.hash_store.set_data_quality(&quality).await?)
+    }
+}
+
+pub(crate) async fn add_in_chunks<A, B, D, R, I>(
     insert_closure: A,
-    data: Box<dyn Iterator<Item = fiskeridir_rs::Result<D>> + Send>,
+    records: R,
     chunk_size: usize,
+    ingestion: &FileIngestion,
 ) -> Result<()>
 where
     A: Fn(Vec<D>) -> B,
     B: Future<Output = CoreResult<()>>,
+    R: Fn() -> Result<I>,
+    I: Iterator<Item = FiskeridirRecord<D>>,
 {
-    let modulo = chunk_size - 1;
+    add_in_grouped_chunks(insert_closure, records, chunk_size, ingestion, |_, _| false).await
+}
+
+/// Adds the records in chunks of at least `chunk_size`, a chunk is only ended between two records
+/// that are not in the same group according to `same_group`.
+///
+/// A checkpoint is stored after each chunk together with the rows that failed to parse, and if a
+/// checkpoint exists for the same version of the file the rows before it are skipped.
+/// If the skipped rows do not match the checkpoint the file is added from the first row again
+/// with the records returned by a new call to `records`.
+/// The data quality of the whole file, including skipped rows, is stored once all chunks are added.
+/// As chunks are added and checkpointed separately a chunk might be added again if storing its
+/// checkpoint fails, so `insert_closure` must be idempotent.
+pub(crate) async fn add_in_grouped_chunks<A, B, D, G, R, I>(
+    insert_closure: A,
+    records: R,
+    chunk_size: usize,
+    ingestion: &FileIngestion,
+    same_group: G,
+) -> Result<()>
+where
+    A: Fn(Vec<D>) -> B,
+    B: Future<Output = CoreResult<()>>,
+    G: Fn(&D, &D) -> bool,
+    R: Fn() -> Result<I>,
+    I: Iterator<Item = FiskeridirRecord<D>>,
+{
+    let checkpoint = ingestion
+        .source
+        .hash_store
+        .checkpoint(&ingestion.file_id)
+        .await?
+        .filter(|c| c.file_hash == ingestion.file_hash);
+
+    let mut rows = records()?;
+    let mut row_offset = 0;
+    let mut chunk_hash = 0;
+    let mut rejected_rows = 0;
+    let mut fields = FieldQuality::default();
+
+    if let Some(checkpoint) = checkpoint {
+        for record in rows.by_ref().take(checkpoint.row_offset as usize) {
+            row_offset += 1;
+            chunk_hash = combine_hash(chunk_hash, record.hash);
+            rejected_rows += record.value.is_err() as u64;
+            fields += record.quality;
+        }
+
+        if row_offset == checkpoint.row_offset && chunk_hash.to_string() == checkpoint.chunk_hash {
+            info!(
+                "resuming {} from row {row_offset}",
+                ingestion.file_id.as_ref()
+            );
+        } else {
+            warn!(
+                "checkpoint of {} does not match the file, adding it from the start",
+                ingestion.file_id.as_ref()
+            );
+            ingestion.set_checkpoint(0, 0, vec![]).await?;
+
+            rows = records()?;
+            row_offset = 0;
+            chunk_hash = 0;
+            rejected_rows = 0;
+            fields = FieldQuality::default();
+        }
+    }
+
     let mut chunk: Vec<D> = Vec::with_capacity(chunk_size);
+    let mut parse_errors = Vec::new();
+
+    for FiskeridirRecord {
+        line,
+        hash,
+        quality,
+        value,
+    } in rows
+    {
+        fields += quality;
 
-    for (i, item) in data.enumerate() {
-        match item {
-            Err(e) => error!("failed to read data: {e:?}"),
+        match value {
+            Err(e) => {
+                rejected_rows += 1;
+                error!("failed to read data on line {line}: {e:?}");
+                parse_errors.push(FileParseError {
+                    file_id: ingestion.file_id.clone(),
+                    file_hash: ingestion.file_hash.clone(),
+                    line,
+                    error: format!("{e:?}"),
+                });
+            }
             Ok(item) => {
-                chunk.push(item);
-                if i % chunk_size == modulo {
-                    insert_closure(chunk).await?;
-                    chunk = Vec::with_capacity(chunk_size);
+                if chunk.len() >= chunk_size
+                    && !chunk.last().is_some_and(|last| same_group(last, &item))
+                {
+                    let full = std::mem::replace(&mut chunk, Vec::with_capacity(chunk_size));
+                    insert_closure(full).await?;
+                    ingestion
+                        .set_checkpoint(row_offset, chunk_hash, std::mem::take(&mut parse_errors))
+                        .await?;
                 }
+                chunk.push(item);
             }
         }
+
+        row_offset += 1;
+        chunk_hash = combine_hash(chunk_hash, hash);
     }
 
     if !chunk.is_empty() {
         insert_closure(chunk).await?;
     }
 
-    Ok(())
+    ingestion
+        .set_checkpoint(row_offset, chunk_hash, parse_errors)
+        .await?;
+
+    ingestion
+        .set_data_quality(row_offset, rejected_rows, fields)
+        .await
+}
+
+fn combine_hash(hash: u64, row_hash: u64) -> u64 {
+    // FNV-1a step over whole row hashes
+    (hash ^ row_hash).wrapping_mul(0x100000001b3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use fiskeridir_rs::{ApiDownloader, DataDownloader};
+    use kyogre_core::{CatchLocationId, ScraperFileHashInboundPort, ScraperFileHashOutboundPort};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct State {
+        checkpoint: Option<FileCheckpoint>,
+        parse_errors: Vec<FileParseError>,
+        data_quality: Option<NewFileDataQuality>,
+    }
+
+    #[derive(Default, Clone)]
+    struct TestStore(Arc<Mutex<State>>);
+
+    #[async_trait]
+    impl ScraperFileHashInboundPort for TestStore {
+        async fn add(&self, _id: &DataFileId, _hash: String) -> CoreResult<()> {
+            self.0.lock().unwrap().checkpoint = None;
+            Ok(())
+        }
+        async fn set_checkpoint(
+            &self,
+            checkpoint: &FileCheckpoint,
+            parse_errors: Vec<FileParseError>,
+        ) -> CoreResult<()> {
+            let mut state = self.0.lock().unwrap();
+            state.checkpoint = Some(checkpoint.clone());
+            state.parse_errors.extend(parse_errors);
+            Ok(())
+        }
+        async fn set_data_quality(&self, quality: &NewFileDataQuality) -> CoreResult<()> {
+            self.0.lock().unwrap().data_quality = Some(quality.clone());
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl ScraperFileHashOutboundPort for TestStore {
+        async fn get_hashes(&self, _ids: &[DataFileId]) -> CoreResult<Vec<(DataFileId, String)>> {
+            Ok(vec![])
+        }
+        async fn checkpoint(&self, _id: &DataFileId) -> CoreResult<Option<FileCheckpoint>> {
+            Ok(self.0.lock().unwrap().checkpoint.clone())
+        }
+        async fn parse_errors(&self, _id: &DataFileId) -> CoreResult<Vec<FileParseError>> {
+            Ok(self.0.lock().unwrap().parse_errors.clone())
+        }
+    }
+
+    fn ingestion(store: &TestStore, file_hash: &str) -> FileIngestion {
+        let source = FiskeridirSource::new(
+            Box::new(store.clone()),
+            DataDownloader::new(std::env::temp_dir()),
+            ApiDownloader::new(),
+        );
+        FileIngestion::new(
+            Arc::new(source),
+            DataFile::ErsDca { year: 2024 },
+            file_hash.into(),
+        )
+    }
+
+    fn records(values: &[u32]) -> impl Iterator<Item = FiskeridirRecord<u32>> + use<> {
+        values
+            .to_vec()
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| FiskeridirRecord {
+                line: i as u64 + 2,
+                hash: v as u64,
+                quality: FieldQuality::default(),
+                value: Ok(v),
+            })
+    }
+
+    fn insert_error() -> kyogre_core::Error {
+        "invalid".parse::<CatchLocationId>().unwrap_err().into()
+    }
+
+    #[tokio::test]
+    async fn test_add_in_chunks_resumes_after_last_added_chunk() {
+        let store = TestStore::default();
+        let added = Mutex::new(Vec::new());
+
+        let result = add_in_chunks(
+            |chunk: Vec<u32>| {
+                let added = &added;
+                async move {
+                    if chunk.contains(&7) {
+                        return Err(insert_error());
+                    }
+                    added.lock().unwrap().extend(chunk);
+                    Ok(())
+                }
+            },
+            || Ok(records(&[1, 2, 3, 4, 5, 6, 7, 8, 9])),
+            3,
+            &ingestion(&store, "a"),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            store
+                .0
+                .lock()
+                .unwrap()
+                .checkpoint
+                .as_ref()
+                .unwrap()
+                .row_offset,
+            6
+        );
+
+        add_in_chunks(
+            |chunk: Vec<u32>| {
+                let added = &added;
+                async move {
+                    added.lock().unwrap().extend(chunk);
+                    Ok(())
+                }
+            },
+            || Ok(records(&[1, 2, 3, 4, 5, 6, 7, 8, 9])),
+            3,
+            &ingestion(&store, "a"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(*added.lock().unwrap(), vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[tokio::test]
+    async fn test_add_in_chunks_ignores_checkpoint_of_other_file_version() {
+        let store = TestStore::default();
+        store.0.lock().unwrap().checkpoint = Some(FileCheckpoint {
+            file_id: DataFile::ErsDca { year: 2024 }.id(),
+            file_hash: "a".into(),
+            row_offset: 2,
+            chunk_hash: "0".into(),
+        });
+        let added = Mutex::new(Vec::new());
+
+        add_in_chunks(
+            |chunk: Vec<u32>| {
+                let added = &added;
+                async move {
+                    added.lock().unwrap().extend(chunk);
+                    Ok(())
+                }
+            },
+            || Ok(records(&[1, 2, 3])),
+            2,
+            &ingestion(&store, "b"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(*added.lock().unwrap(), vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_add_in_chunks_restarts_from_first_row_on_chunk_hash_mismatch() {
+        let store = TestStore::default();
+        store.0.lock().unwrap().checkpoint = Some(FileCheckpoint {
+            file_id: DataFile::ErsDca { year: 2024 }.id(),
+            file_hash: "a".into(),
+            row_offset: 2,
+            chunk_hash: "1".into(),
+        });
+        let added = Mutex::new(Vec::new());
+
+        add_in_chunks(
+            |chunk: Vec<u32>| {
+                let added = &added;
+                async move {
+                    added.lock().unwrap().extend(chunk);
+                    Ok(())
+                }
+            },
+            || Ok(records(&[1, 2, 3])),
+            2,
+            &ingestion(&store, "a"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(*added.lock().unwrap(), vec![1, 2, 3]);
+
+        let state = store.0.lock().unwrap();
+        assert_eq!(state.checkpoint.as_ref().unwrap().row_offset, 3);
+        assert_eq!(state.data_quality.as_ref().unwrap().rows, 3);
+    }
+
+    #[tokio::test]
+    async fn test_add_in_grouped_chunks_does_not_split_groups() {
+        let store = TestStore::default();
+        let chunks = Mutex::new(Vec::new());
+
+        add_in_grouped_chunks(
+            |chunk: Vec<u32>| {
+                let chunks = &chunks;
+                async move {
+                    chunks.lock().unwrap().push(chunk);
+                    Ok(())
+                }
+            },
+            || Ok(records(&[1, 1, 1, 2, 3, 3])),
+            2,
+            &ingestion(&store, "a"),
+            |a, b| a == b,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(*chunks.lock().unwrap(), vec![vec![1, 1, 1], vec![2, 3, 3]]);
+    }
+
+    #[tokio::test]
+    async fn test_add_in_chunks_stores_parse_errors_with_line_numbers() {
+        let store = TestStore::default();
+
+        let records = || {
+            records(&[1, 2, 3]).map(|mut r| {
+                if r.value.as_ref().is_ok_and(|v| *v == 2) {
+                    r.value = Err(std::io::Error::other("invalid row").into());
+                }
+                r
+            })
+        };
+
+        add_in_chunks(
+            |_: Vec<u32>| async { Ok(()) },
+            || Ok(records()),
+            2,
+            &ingestion(&store, "a"),
+        )
+        .await
+        .unwrap();
+
+        let state = store.0.lock().unwrap();
+        assert_eq!(state.parse_errors.len(), 1);
+        assert_eq!(state.parse_errors[0].line, 3);
+        assert_eq!(state.checkpoint.as_ref().unwrap().row_offset, 3);
+    }
+
+    #[tokio::test]
+    async fn test_add_in_chunks_stores_data_quality_of_whole_file_on_resume() {
+        let store = TestStore::default();
+        let quality = FieldQuality {
+            coerced: 1,
+            defaulted: 2,
+            rejected: 0,
+        };
+
+        let records = || {
+            records(&[1, 2, 3, 4]).map(move |mut r| {
+                r.quality = quality;
+                if r.value.as_ref().is_ok_and(|v| *v == 1) {
+                    r.value = Err(std::io::Error::other("invalid row").into());
+                }
+                r
+            })
+        };
+
+        let result = add_in_chunks(
+            |chunk: Vec<u32>| async move {
+                if chunk.contains(&4) {
+                    return Err(insert_error());
+                }
+                Ok(())
+            },
+            || Ok(records()),
+            2,
+            &ingestion(&store, "a"),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(store.0.lock().unwrap().data_quality.is_none());
+
+        add_in_chunks(
+            |_: Vec<u32>| async { Ok(()) },
+            || Ok(records()),
+            2,
+            &ingestion(&store, "a"),
+        )
+        .await
+        .unwrap();
+
+        let state = store.0.lock().unwrap();
+        let data_quality = state.data_quality.as_ref().unwrap();
+        assert_eq!(data_quality.dataset, "ers_dca");
+        assert_eq!(data_quality.year, 2024);
+        assert_eq!(data_quality.rows, 4);
+        assert_eq!(data_quality.rejected_rows, 1);
+        assert_eq!(
+            data_quality.fields,
+            FieldQuality {
+                coerced: 4,
+                defaulted: 8,
+                rejected: 0,
+            }
+        );
+    }
 }
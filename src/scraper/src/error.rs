@@ -36,12 +36,38 @@ pub enum Error {
         #[snafu(source)]
         error: csv::Error,
     },
-    #[snafu(display("Python error"))]
-    Python {
+    #[snafu(display("IO error"))]
+    Io {
         #[snafu(implicit)]
         location: Location,
         #[snafu(source)]
-        error: pyo3::PyErr,
+        error: std::io::Error,
+    },
+    #[snafu(display("NetCDF error"))]
+    Netcdf {
+        #[snafu(implicit)]
+        location: Location,
+        #[snafu(source)]
+        error: netcdf3::error::ReadError,
+    },
+    #[snafu(display("NetCDF variable '{name}' is missing or has an unexpected shape"))]
+    NetcdfVariable {
+        #[snafu(implicit)]
+        location: Location,
+        name: String,
+    },
+    #[snafu(display("Unsupported NetCDF time units '{units}'"))]
+    NetcdfTimeUnits {
+        #[snafu(implicit)]
+        location: Location,
+        units: String,
+    },
+    #[snafu(display("Failed to read THREDDS catalog '{url}': {message}"))]
+    ThreddsCatalog {
+        #[snafu(implicit)]
+        location: Location,
+        url: String,
+        message: String,
     },
     #[snafu(display("Oauth error"))]
     Oauth {
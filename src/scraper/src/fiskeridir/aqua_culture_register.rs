@@ -39,12 +39,12 @@ impl DataSource for AquaCultureRegisterScraper {
             self.fiskeridir_source.clone(),
             self.source.clone().map(|s| vec![s]).unwrap_or_default(),
             Some(2020),
-            |dir, file| async move {
-                let data = dir.into_deserialize(&file)?;
+            |dir, file, ingestion| async move {
                 add_in_chunks(
                     |data| processor.add_aqua_culture_register(data),
-                    Box::new(data),
+                    || Ok(dir.clone().into_records(&file)?),
                     10000,
+                    &ingestion,
                 )
                 .await
             },
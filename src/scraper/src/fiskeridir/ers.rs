@@ -1,11 +1,17 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use fiskeridir_rs::{DataFile, FileSource};
+use fiskeridir_rs::{DataFile, ErsDca, ErsDep, ErsPor, ErsTra, FileSource};
 use orca_core::Environment;
 
 use super::FiskeridirSource;
-use crate::{DataSource, Processor, Result, ScraperId, utils::prefetch_and_scrape};
+use crate::{
+    DataSource, Processor, Result, ScraperId, chunks::add_in_grouped_chunks,
+    utils::prefetch_and_scrape,
+};
+
+/// Number of rows added and checkpointed at a time, chunks are extended to not split messages.
+const CHUNK_SIZE: usize = 100_000;
 
 pub struct ErsScraper {
     sources: Vec<FileSource>,
@@ -39,23 +45,47 @@ impl DataSource for ErsScraper {
             self.fiskeridir_source.clone(),
             self.sources.clone(),
             Some(2020),
-            |dir, file| async move {
+            |dir, file, ingestion| async move {
                 match file {
                     DataFile::ErsDca { .. } => {
-                        let data = dir.into_deserialize(&file)?;
-                        Ok(processor.add_ers_dca(Box::new(data)).await?)
+                        add_in_grouped_chunks(
+                            |v: Vec<ErsDca>| processor.add_ers_dca(Box::new(v.into_iter().map(Ok))),
+                            || Ok(dir.clone().into_records(&file)?),
+                            CHUNK_SIZE,
+                            &ingestion,
+                            |a, b| a.message_info.message_id == b.message_info.message_id,
+                        )
+                        .await
                     }
                     DataFile::ErsPor { .. } => {
-                        let data = dir.into_deserialize(&file)?;
-                        Ok(processor.add_ers_por(Box::new(data)).await?)
+                        add_in_grouped_chunks(
+                            |v: Vec<ErsPor>| processor.add_ers_por(Box::new(v.into_iter().map(Ok))),
+                            || Ok(dir.clone().into_records(&file)?),
+                            CHUNK_SIZE,
+                            &ingestion,
+                            |a, b| a.message_info.message_id == b.message_info.message_id,
+                        )
+                        .await
                     }
                     DataFile::ErsDep { .. } => {
-                        let data = dir.into_deserialize(&file)?;
-                        Ok(processor.add_ers_dep(Box::new(data)).await?)
+                        add_in_grouped_chunks(
+                            |v: Vec<ErsDep>| processor.add_ers_dep(Box::new(v.into_iter().map(Ok))),
+                            || Ok(dir.clone().into_records(&file)?),
+                            CHUNK_SIZE,
+                            &ingestion,
+                            |a, b| a.message_info.message_id == b.message_info.message_id,
+                        )
+                        .await
                     }
                     DataFile::ErsTra { .. } => {
-                        let data = dir.into_deserialize(&file)?;
-                        Ok(processor.add_ers_tra(Box::new(data)).await?)
+                        add_in_grouped_chunks(
+                            |v: Vec<ErsTra>| processor.add_ers_tra(Box::new(v.into_iter().map(Ok))),
+                            || Ok(dir.clone().into_records(&file)?),
+                            CHUNK_SIZE,
+                            &ingestion,
+                            |a, b| a.message_info.message_id == b.message_info.message_id,
+                        )
+                        .await
                     }
                     DataFile::Landings { .. }
                     | DataFile::Vms { .. }
@@ -1,9 +1,18 @@
 use super::FiskeridirSource;
-use crate::{DataSource, Processor, Result, ScraperId, utils::prefetch_and_scrape};
+use crate::{
+    DataSource, Processor, Result, ScraperId, chunks::add_in_grouped_chunks,
+    utils::prefetch_and_scrape,
+};
 use async_trait::async_trait;
 use fiskeridir_rs::{FileSource, Landing, LandingRaw};
 use orca_core::Environment;
-use std::sync::Arc;
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+
+/// Number of rows added and checkpointed at a time, chunks are extended to not split landings.
+const CHUNK_SIZE: usize = 100_000;
 
 pub struct LandingScraper {
     sources: Vec<FileSource>,
@@ -37,13 +46,37 @@ impl DataSource for LandingScraper {
             self.fiskeridir_source.clone(),
             self.sources.clone(),
             Some(2020),
-            |dir, file| async move {
+            |dir, file, ingestion| async move {
                 let year = file.year();
-                let data = dir
-                    .into_deserialize::<LandingRaw>(&file)?
-                    .map(move |v| v.map(|v| Landing::from_raw(v, year)));
 
-                Ok(processor.add_landings(Box::new(data), year).await?)
+                // Landings missing from the file are deleted once all of it has been added, so
+                // the ids of rows skipped when resuming are collected as well.
+                let landing_ids = Mutex::new(HashSet::new());
+                add_in_grouped_chunks(
+                    |v| processor.add_landings_chunk(v, year),
+                    || {
+                        Ok(dir
+                            .clone()
+                            .into_records::<LandingRaw>(&file)?
+                            .map(move |r| r.map(|v| Landing::from_raw(v, year)))
+                            .inspect(|r| {
+                                if let Ok(v) = &r.value {
+                                    landing_ids.lock().unwrap().insert(v.id.clone());
+                                }
+                            }))
+                    },
+                    CHUNK_SIZE,
+                    &ingestion,
+                    |a, b| a.id == b.id,
+                )
+                .await?;
+
+                Ok(processor
+                    .delete_removed_landings(
+                        landing_ids.into_inner().unwrap().into_iter().collect(),
+                        year,
+                    )
+                    .await?)
             },
         )
         .await
@@ -38,9 +38,14 @@ impl DataSource for VmsScraper {
             self.fiskeridir_source.clone(),
             self.sources.clone(),
             Some(2023),
-            |dir, file| async move {
-                let data = dir.into_deserialize(&file)?;
-                add_in_chunks(|vms| processor.add_vms(vms), Box::new(data), 10000).await
+            |dir, file, ingestion| async move {
+                add_in_chunks(
+                    |vms| processor.add_vms(vms),
+                    || Ok(dir.clone().into_records(&file)?),
+                    10000,
+                    &ingestion,
+                )
+                .await
             },
         )
         .await
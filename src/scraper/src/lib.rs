@@ -17,13 +17,14 @@ use serde::Deserialize;
 use std::sync::Arc;
 use std::{fmt::Debug, path::PathBuf};
 use tracing::{error, instrument};
-use weather::WeatherScraper;
+use weather::{WeatherForecastScraper, WeatherScraper};
 
 mod barentswatch;
 mod chunks;
 mod error;
 mod fiskeridir;
 mod mattilsynet;
+mod netcdf;
 mod ocean_climate;
 mod rafisklaget;
 mod utils;
@@ -161,12 +162,22 @@ impl Scraper {
             config.fishing_facility_historic,
         );
 
+        let _weather_scraper = WeatherScraper::new(
+            http_client.clone(),
+            config.file_download_dir.join("weather"),
+        );
+        let weather_forecast_scraper = WeatherForecastScraper::new(
+            http_client.clone(),
+            config.file_download_dir.join("weather_forecast"),
+        );
+        let _ocean_climate_scraper = OceanClimateScraper::new(
+            http_client.clone(),
+            config.file_download_dir.join("ocean_climate"),
+        );
+
         let weekly_sales_scraper =
             WeeklySalesScraper::new(config.rafisklaget_weekly_sales, http_client);
 
-        let _weather_scraper = WeatherScraper::new();
-        let _ocean_climate_scraper = OceanClimateScraper::new();
-
         Scraper {
             environment,
             scrapers: vec![
@@ -182,6 +193,7 @@ impl Scraper {
                 ],
                 vec![Arc::new(vms_scraper)],
                 vec![Arc::new(mattilsynet_scraper)],
+                vec![Arc::new(weather_forecast_scraper)],
                 // vec![Arc::new(weather_scraper)],
                 // vec![Box::new(ocean_climate_scraper)],
             ],
@@ -248,6 +260,7 @@ pub enum ScraperId {
     AquaCultureRegister,
     Mattilsynet,
     Weather,
+    WeatherForecast,
     OceanClimate,
     RafisklagetWeeklySales,
 }
@@ -265,6 +278,7 @@ impl std::fmt::Display for ScraperId {
             ScraperId::AquaCultureRegister => write!(f, "aqua_culture_register"),
             ScraperId::Mattilsynet => write!(f, "mattilsynet"),
             ScraperId::Weather => write!(f, "weather"),
+            ScraperId::WeatherForecast => write!(f, "weather_forecast"),
             ScraperId::OceanClimate => write!(f, "ocean_climate"),
             ScraperId::RafisklagetWeeklySales => write!(f, "rafisklaget_weekly_sales"),
         }
@@ -0,0 +1,5 @@
+mod reader;
+mod thredds;
+
+pub(crate) use reader::{BinnedGrid, NetcdfReader};
+pub(crate) use thredds::{ThreddsClient, ThreddsDataset};
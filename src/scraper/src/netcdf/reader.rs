@@ -0,0 +1,468 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::Path,
+};
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+use netcdf3::{DataSet, DataVector, FileReader};
+use snafu::OptionExt;
+
+use crate::{
+    Result,
+    error::error::{NetcdfTimeUnitsSnafu, NetcdfVariableSnafu},
+};
+
+/// The size of the latitude/longitude bins grid cells are downscaled into, in degrees.
+const BIN_RESOLUTION: f64 = 0.1;
+/// Values at or above this magnitude are the NetCDF default fill values of floating point
+/// variables, which are used when a variable has no explicit `_FillValue`.
+const DEFAULT_FLOAT_FILL: f64 = 9.9e36;
+
+/// Reads gridded NetCDF-3 files, decoding packed values and fill values the same way as the
+/// CF conventions describe.
+pub(crate) struct NetcdfReader {
+    reader: FileReader,
+}
+
+struct Variable {
+    shape: Vec<usize>,
+    values: Vec<Option<f64>>,
+}
+
+/// The metadata needed to read and decode the values of a variable.
+struct VariableInfo {
+    shape: Vec<usize>,
+    is_record: bool,
+    scale_factor: f64,
+    add_offset: f64,
+    fill_value: Option<f64>,
+    missing_value: Option<f64>,
+}
+
+/// The mean of each variable over every latitude/longitude bin of the grid, per time step.
+/// Bins are ordered by latitude and then longitude.
+pub(crate) struct BinnedGrid {
+    times: Vec<DateTime<Utc>>,
+    num_bins: usize,
+    /// Indexed by time step and then bin.
+    values: HashMap<String, Vec<Option<f64>>>,
+}
+
+impl NetcdfReader {
+    pub fn open(path: impl AsRef<Path>) -> Result<NetcdfReader> {
+        Ok(NetcdfReader {
+            reader: FileReader::open(path)?,
+        })
+    }
+
+    /// Reads the `time` variable, which has to have units on the form `<unit> since <timestamp>`.
+    pub fn times(&mut self) -> Result<Vec<DateTime<Utc>>> {
+        let units = self
+            .reader
+            .data_set()
+            .get_var_attr_as_string("time", "units")
+            .context(NetcdfVariableSnafu { name: "time" })?;
+
+        let (unit_seconds, reference) =
+            parse_time_units(&units).context(NetcdfTimeUnitsSnafu { units: &units })?;
+
+        let time = self
+            .read_variable("time")?
+            .context(NetcdfVariableSnafu { name: "time" })?;
+
+        time.values
+            .into_iter()
+            .map(|v| {
+                let v = v.context(NetcdfVariableSnafu { name: "time" })?;
+                Ok(reference + Duration::milliseconds((v * unit_seconds * 1000.).round() as i64))
+            })
+            .collect()
+    }
+
+    /// Computes the mean of each of the given variables over 0.1 degree latitude/longitude bins.
+    ///
+    /// The latitude and longitude variables are either 2D grids or 1D axes, and all other
+    /// variables must have the grid as their last two dimensions. Variables without a time
+    /// dimension apply to every time step and missing variables are treated as having no values.
+    pub fn binned(
+        &mut self,
+        latitude: &str,
+        longitude: &str,
+        variables: &[&str],
+    ) -> Result<BinnedGrid> {
+        let times = self.times()?;
+
+        let lat = self
+            .read_variable(latitude)?
+            .context(NetcdfVariableSnafu { name: latitude })?;
+        let lon = self
+            .read_variable(longitude)?
+            .context(NetcdfVariableSnafu { name: longitude })?;
+
+        let (ny, nx, coordinates) = match (lat.shape.as_slice(), lon.shape.as_slice()) {
+            ([ny, nx], [ly, lx]) if ny == ly && nx == lx => (
+                *ny,
+                *nx,
+                lat.values
+                    .into_iter()
+                    .zip(lon.values)
+                    .map(|(lat, lon)| lat.zip(lon))
+                    .collect::<Vec<_>>(),
+            ),
+            ([ny], [nx]) => (
+                *ny,
+                *nx,
+                lat.values
+                    .iter()
+                    .flat_map(|lat| lon.values.iter().map(|lon| lat.zip(*lon)))
+                    .collect(),
+            ),
+            _ => {
+                return NetcdfVariableSnafu { name: latitude }.fail();
+            }
+        };
+
+        let cell_keys = coordinates
+            .iter()
+            .map(|c| c.map(|(lat, lon)| (to_bin(lat), to_bin(lon))))
+            .collect::<Vec<_>>();
+
+        let bins = cell_keys.iter().flatten().collect::<BTreeSet<_>>();
+        let bin_indices = bins
+            .into_iter()
+            .enumerate()
+            .map(|(i, key)| (*key, i))
+            .collect::<HashMap<_, _>>();
+
+        let cell_bins = cell_keys
+            .iter()
+            .map(|key| key.map(|k| bin_indices[&k]))
+            .collect::<Vec<_>>();
+
+        let num_bins = bin_indices.len();
+        let num_cells = ny * nx;
+
+        let mut grid = BinnedGrid {
+            times,
+            num_bins,
+            values: HashMap::with_capacity(variables.len() + 2),
+        };
+
+        // The coordinates are averaged as well to place each bin at the center of its cells.
+        let lat_values = coordinates
+            .iter()
+            .map(|c| c.map(|c| c.0))
+            .collect::<Vec<_>>();
+        let lon_values = coordinates
+            .iter()
+            .map(|c| c.map(|c| c.1))
+            .collect::<Vec<_>>();
+        for (name, values) in [(latitude, lat_values), (longitude, lon_values)] {
+            let means = bin_means(&values, &cell_bins, num_bins);
+            grid.values
+                .insert(name.to_owned(), means.repeat(grid.times.len()));
+        }
+
+        for name in variables {
+            let Some(info) = self.variable_info(name)? else {
+                continue;
+            };
+
+            if info.shape.len() < 2 || info.shape[info.shape.len() - 2..] != [ny, nx] {
+                return NetcdfVariableSnafu { name: *name }.fail();
+            }
+
+            let num_times = grid.times.len();
+            let num_slices = info.shape.iter().product::<usize>() / num_cells;
+            if num_slices != 1 && num_slices != num_times {
+                return NetcdfVariableSnafu { name: *name }.fail();
+            }
+
+            let mut means = Vec::with_capacity(num_times * num_bins);
+
+            if info.is_record && num_slices == num_times {
+                // Each record holds a single time step, so only one time step is kept in memory
+                // at a time.
+                for time in 0..num_times {
+                    let values = self.read_values(name, &info, Some(time))?;
+                    if values.len() != num_cells {
+                        return NetcdfVariableSnafu { name: *name }.fail();
+                    }
+                    means.extend(bin_means(&values, &cell_bins, num_bins));
+                }
+            } else {
+                // Variables without a record dimension can only be read in full.
+                let values = self.read_values(name, &info, None)?;
+                for time in 0..num_times {
+                    let slice = if num_slices == 1 { 0 } else { time };
+                    means.extend(bin_means(
+                        &values[slice * num_cells..(slice + 1) * num_cells],
+                        &cell_bins,
+                        num_bins,
+                    ));
+                }
+            }
+
+            grid.values.insert((*name).to_owned(), means);
+        }
+
+        Ok(grid)
+    }
+
+    /// Reads and decodes all values of a variable, which should only be used for small variables
+    /// such as coordinates.
+    fn read_variable(&mut self, name: &str) -> Result<Option<Variable>> {
+        let Some(info) = self.variable_info(name)? else {
+            return Ok(None);
+        };
+
+        let values = self.read_values(name, &info, None)?;
+        Ok(Some(Variable {
+            shape: info.shape,
+            values,
+        }))
+    }
+
+    fn variable_info(&self, name: &str) -> Result<Option<VariableInfo>> {
+        let data_set = self.reader.data_set();
+
+        let Some(variable) = data_set.get_var(name) else {
+            return Ok(None);
+        };
+
+        let shape = variable
+            .dim_names()
+            .iter()
+            .map(|d| data_set.dim_size(d).context(NetcdfVariableSnafu { name }))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Some(VariableInfo {
+            shape,
+            is_record: variable.is_record_var(),
+            scale_factor: attribute(data_set, name, "scale_factor").unwrap_or(1.),
+            add_offset: attribute(data_set, name, "add_offset").unwrap_or(0.),
+            fill_value: attribute(data_set, name, "_FillValue"),
+            missing_value: attribute(data_set, name, "missing_value"),
+        }))
+    }
+
+    /// Reads and decodes a single record of a record variable if `record` is given, or all values
+    /// of the variable otherwise.
+    fn read_values(
+        &mut self,
+        name: &str,
+        info: &VariableInfo,
+        record: Option<usize>,
+    ) -> Result<Vec<Option<f64>>> {
+        let data = match record {
+            Some(record) => self.reader.read_record(name, record)?,
+            None => self.reader.read_var(name)?,
+        };
+
+        let raw: Vec<f64> = match data {
+            DataVector::I8(v) => v.into_iter().map(f64::from).collect(),
+            DataVector::U8(v) => v.into_iter().map(f64::from).collect(),
+            DataVector::I16(v) => v.into_iter().map(f64::from).collect(),
+            DataVector::I32(v) => v.into_iter().map(f64::from).collect(),
+            DataVector::F32(v) => v.into_iter().map(f64::from).collect(),
+            DataVector::F64(v) => v,
+        };
+
+        if record.is_none() && raw.len() != info.shape.iter().product::<usize>() {
+            return NetcdfVariableSnafu { name }.fail();
+        }
+
+        Ok(raw
+            .into_iter()
+            .map(|v| {
+                if v.is_nan()
+                    || v.abs() >= DEFAULT_FLOAT_FILL
+                    || Some(v) == info.fill_value
+                    || Some(v) == info.missing_value
+                {
+                    None
+                } else {
+                    Some(v * info.scale_factor + info.add_offset)
+                }
+            })
+            .collect())
+    }
+}
+
+impl BinnedGrid {
+    pub fn times(&self) -> &[DateTime<Utc>] {
+        &self.times
+    }
+
+    pub fn num_bins(&self) -> usize {
+        self.num_bins
+    }
+
+    /// Returns the mean value of the variable for the given time step and bin, `None` if the
+    /// variable is missing or had no values within the bin.
+    pub fn value(&self, variable: &str, time: usize, bin: usize) -> Option<f64> {
+        self.values
+            .get(variable)
+            .and_then(|v| v[time * self.num_bins + bin])
+    }
+}
+
+/// Returns the mean of the values of a single time step within each bin, `cell_bins` holds the bin
+/// of each grid cell.
+fn bin_means(
+    values: &[Option<f64>],
+    cell_bins: &[Option<usize>],
+    num_bins: usize,
+) -> Vec<Option<f64>> {
+    let mut sums = vec![(0., 0_u32); num_bins];
+
+    for (bin, value) in cell_bins.iter().zip(values) {
+        if let (Some(bin), Some(value)) = (bin, value) {
+            let sum = &mut sums[*bin];
+            sum.0 += value;
+            sum.1 += 1;
+        }
+    }
+
+    sums.into_iter()
+        .map(|(sum, count)| {
+            if count > 0 {
+                Some(sum / count as f64)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn to_bin(v: f64) -> i32 {
+    (v / BIN_RESOLUTION).floor() as i32
+}
+
+fn attribute(data_set: &DataSet, variable: &str, name: &str) -> Option<f64> {
+    data_set
+        .get_var_attr_f64(variable, name)
+        .and_then(|v| v.first().copied())
+        .or_else(|| {
+            data_set
+                .get_var_attr_f32(variable, name)
+                .and_then(|v| v.first().map(|v| f64::from(*v)))
+        })
+        .or_else(|| {
+            data_set
+                .get_var_attr_i32(variable, name)
+                .and_then(|v| v.first().map(|v| f64::from(*v)))
+        })
+        .or_else(|| {
+            data_set
+                .get_var_attr_i16(variable, name)
+                .and_then(|v| v.first().map(|v| f64::from(*v)))
+        })
+        .or_else(|| {
+            data_set
+                .get_var_attr_i8(variable, name)
+                .and_then(|v| v.first().map(|v| f64::from(*v)))
+        })
+        .or_else(|| {
+            data_set
+                .get_var_attr_u8(variable, name)
+                .and_then(|v| v.first().map(|v| f64::from(*v)))
+        })
+}
+
+/// Parses CF time units such as `seconds since 1970-01-01 00:00:00 +00:00` into the number of
+/// seconds per unit and the reference timestamp, offsets other than UTC are not supported.
+fn parse_time_units(units: &str) -> Option<(f64, DateTime<Utc>)> {
+    let (unit, reference) = units.split_once(" since ")?;
+
+    let unit_seconds = match unit.trim() {
+        "seconds" | "second" | "secs" | "s" => 1.,
+        "minutes" | "minute" | "mins" => 60.,
+        "hours" | "hour" | "h" => 3600.,
+        "days" | "day" | "d" => 86400.,
+        _ => return None,
+    };
+
+    let reference = reference.trim().replace('T', " ");
+    let reference = reference.trim_end_matches('Z');
+
+    let timestamp = reference
+        .get(..19)
+        .and_then(|v| NaiveDateTime::parse_from_str(v, "%Y-%m-%d %H:%M:%S").ok())
+        .or_else(|| {
+            reference
+                .get(..10)
+                .and_then(|v| NaiveDate::parse_from_str(v, "%Y-%m-%d").ok())
+                .and_then(|v| v.and_hms_opt(0, 0, 0))
+        })?;
+
+    Some((unit_seconds, timestamp.and_utc()))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn test_data(name: &str) -> std::path::PathBuf {
+        [env!("CARGO_MANIFEST_DIR"), "test_data", name]
+            .iter()
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_time_units() {
+        let (seconds, reference) =
+            parse_time_units("seconds since 1970-01-01 00:00:00 +00:00").unwrap();
+        assert_eq!(seconds, 1.);
+        assert_eq!(reference, DateTime::UNIX_EPOCH);
+
+        let (seconds, reference) = parse_time_units("hours since 2023-07-12T00:00:00Z").unwrap();
+        assert_eq!(seconds, 3600.);
+        assert_eq!(
+            reference,
+            Utc.with_ymd_and_hms(2023, 7, 12, 0, 0, 0).unwrap()
+        );
+
+        let (seconds, reference) = parse_time_units("days since 2000-01-01").unwrap();
+        assert_eq!(seconds, 86400.);
+        assert_eq!(
+            reference,
+            Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap()
+        );
+
+        assert!(parse_time_units("fortnights since 2000-01-01").is_none());
+    }
+
+    #[test]
+    fn test_binned_grid_decodes_packed_values_and_skips_fill_values() {
+        let mut reader = NetcdfReader::open(test_data("weather.nc")).unwrap();
+        let grid = reader
+            .binned(
+                "latitude",
+                "longitude",
+                &[
+                    "air_temperature_2m",
+                    "precipitation_amount",
+                    "does_not_exist",
+                ],
+            )
+            .unwrap();
+
+        assert_eq!(
+            grid.times(),
+            [Utc.with_ymd_and_hms(2023, 6, 16, 15, 0, 0).unwrap()]
+        );
+        assert_eq!(grid.num_bins(), 3);
+
+        let temperature = grid.value("air_temperature_2m", 0, 0).unwrap();
+        assert!((temperature - (273.15 + 2.)).abs() < 1e-4);
+
+        let latitude = grid.value("latitude", 0, 0).unwrap();
+        assert!((latitude - 70.04).abs() < 1e-9);
+
+        assert_eq!(grid.value("precipitation_amount", 0, 0), None);
+        assert_eq!(grid.value("does_not_exist", 0, 0), None);
+    }
+}
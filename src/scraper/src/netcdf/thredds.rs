@@ -0,0 +1,233 @@
+use std::path::Path;
+
+use futures::StreamExt;
+use http_client::HttpClient;
+use serde::Deserialize;
+use tokio::io::AsyncWriteExt;
+
+use crate::{Result, error::error::ThreddsCatalogSnafu};
+
+const NETCDF_SUBSET_SERVICE: &str = "NetcdfSubset";
+
+/// A THREDDS data server catalog, only the parts needed to find datasets and their subset urls
+/// are deserialized.
+#[derive(Debug, Deserialize)]
+struct Catalog {
+    #[serde(rename = "service", default)]
+    services: Vec<Service>,
+    #[serde(rename = "dataset", default)]
+    datasets: Vec<Dataset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Service {
+    #[serde(rename = "@serviceType")]
+    service_type: String,
+    #[serde(rename = "@base")]
+    base: String,
+    #[serde(rename = "service", default)]
+    services: Vec<Service>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Dataset {
+    #[serde(rename = "@name")]
+    name: String,
+    #[serde(rename = "@urlPath")]
+    url_path: Option<String>,
+    #[serde(rename = "dataset", default)]
+    datasets: Vec<Dataset>,
+    #[serde(rename = "catalogRef", default)]
+    catalog_refs: Vec<CatalogRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CatalogRef {
+    #[serde(rename = "@xlink:href")]
+    href: String,
+}
+
+/// A dataset that can be downloaded as NetCDF-3 through the NetCDF subset service.
+#[derive(Debug, Clone)]
+pub(crate) struct ThreddsDataset {
+    pub name: String,
+    subset_url: String,
+}
+
+pub(crate) struct ThreddsClient<'a> {
+    client: &'a HttpClient,
+}
+
+impl<'a> ThreddsClient<'a> {
+    pub fn new(client: &'a HttpClient) -> Self {
+        Self { client }
+    }
+
+    /// Walks the catalog at `url` and all catalogs it references depth first, in the order they
+    /// are listed, until `stop` returns true for a dataset. The datasets preceding it are
+    /// returned.
+    pub async fn datasets_until(
+        &self,
+        url: &str,
+        stop: impl Fn(&ThreddsDataset) -> Result<bool>,
+    ) -> Result<Vec<ThreddsDataset>> {
+        let mut datasets = Vec::new();
+        let mut pending = vec![url.to_owned()];
+
+        while let Some(url) = pending.pop() {
+            let catalog = self.catalog(&url).await?;
+
+            let subset_base = catalog
+                .services
+                .iter()
+                .find_map(|s| s.find_base(NETCDF_SUBSET_SERVICE));
+
+            let mut references = Vec::new();
+
+            let mut stack = catalog.datasets.iter().rev().collect::<Vec<_>>();
+            while let Some(dataset) = stack.pop() {
+                if let (Some(path), Some(base)) = (&dataset.url_path, subset_base) {
+                    let dataset = ThreddsDataset {
+                        name: dataset.name.clone(),
+                        subset_url: resolve(&url, &format!("{base}{path}")),
+                    };
+                    if stop(&dataset)? {
+                        return Ok(datasets);
+                    }
+                    datasets.push(dataset);
+                }
+
+                references.extend(dataset.catalog_refs.iter().map(|r| r.href.as_str()));
+                stack.extend(dataset.datasets.iter().rev());
+            }
+
+            for href in references.into_iter().rev() {
+                pending.push(resolve(&url, href));
+            }
+        }
+
+        Ok(datasets)
+    }
+
+    /// Downloads the given variables of the dataset as NetCDF-3 to `path`, additional subset
+    /// parameters such as the vertical level can be given through `query`.
+    pub async fn download(
+        &self,
+        dataset: &ThreddsDataset,
+        variables: &[&str],
+        query: &[(&str, String)],
+        path: &Path,
+    ) -> Result<()> {
+        let mut params = vec![
+            ("var", variables.join(",")),
+            ("accept", "netcdf3".to_owned()),
+        ];
+        params.extend(query.iter().map(|(k, v)| (*k, v.clone())));
+
+        let response = self
+            .client
+            .get(&dataset.subset_url)
+            .query(&params)
+            .send()
+            .await?;
+
+        let mut file = tokio::fs::File::create(path).await?;
+        let mut stream = response.bytes_stream();
+
+        while let Some(item) = stream.next().await {
+            file.write_all(&item?).await?;
+        }
+
+        file.flush().await?;
+
+        Ok(())
+    }
+
+    async fn catalog(&self, url: &str) -> Result<Catalog> {
+        let xml = self.client.get(url).send().await?.text().await?;
+        quick_xml::de::from_str(&xml).map_err(|e| {
+            ThreddsCatalogSnafu {
+                url,
+                message: e.to_string(),
+            }
+            .build()
+        })
+    }
+}
+
+impl Service {
+    fn find_base(&self, service_type: &str) -> Option<&str> {
+        if self.service_type == service_type {
+            Some(&self.base)
+        } else {
+            self.services.iter().find_map(|s| s.find_base(service_type))
+        }
+    }
+}
+
+/// Resolves a catalog reference or service path relative to the catalog url it was found in.
+fn resolve(catalog_url: &str, reference: &str) -> String {
+    if reference.starts_with("http://") || reference.starts_with("https://") {
+        reference.to_owned()
+    } else if reference.starts_with('/') {
+        let host_start = catalog_url.find("://").map(|i| i + 3).unwrap_or(0);
+        let origin_end = catalog_url[host_start..]
+            .find('/')
+            .map(|i| host_start + i)
+            .unwrap_or(catalog_url.len());
+        format!("{}{reference}", &catalog_url[..origin_end])
+    } else {
+        let directory_end = catalog_url.rfind('/').map(|i| i + 1).unwrap_or(0);
+        format!("{}{reference}", &catalog_url[..directory_end])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_deserializes_services_datasets_and_references() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<catalog xmlns="http://www.unidata.ucar.edu/namespaces/thredds/InvCatalog/v1.0" xmlns:xlink="http://www.w3.org/1999/xlink" version="1.0.1">
+  <service name="all" serviceType="Compound" base="">
+    <service name="odap" serviceType="OpenDAP" base="/thredds/dodsC/" />
+    <service name="ncss" serviceType="NetcdfSubset" base="/thredds/ncss/grid/" />
+  </service>
+  <dataset name="2023" ID="metpparchive/2023">
+    <catalogRef xlink:href="12/catalog.xml" xlink:title="12" name="" />
+    <catalogRef xlink:href="11/catalog.xml" xlink:title="11" name="" />
+    <dataset name="met_analysis_1_0km_nordic_20231231T23Z.nc" urlPath="metpparchive/2023/12/31/met_analysis_1_0km_nordic_20231231T23Z.nc" />
+  </dataset>
+</catalog>"#;
+
+        let catalog: Catalog = quick_xml::de::from_str(xml).unwrap();
+
+        assert_eq!(
+            catalog.services[0].find_base(NETCDF_SUBSET_SERVICE),
+            Some("/thredds/ncss/grid/")
+        );
+
+        let dataset = &catalog.datasets[0];
+        assert_eq!(dataset.catalog_refs.len(), 2);
+        assert_eq!(dataset.catalog_refs[0].href, "12/catalog.xml");
+        assert_eq!(
+            dataset.datasets[0].url_path.as_deref(),
+            Some("metpparchive/2023/12/31/met_analysis_1_0km_nordic_20231231T23Z.nc")
+        );
+    }
+
+    #[test]
+    fn test_resolve_relative_and_absolute_references() {
+        let catalog = "https://thredds.met.no/thredds/catalog/metpparchive/catalog.xml";
+
+        assert_eq!(
+            resolve(catalog, "2023/catalog.xml"),
+            "https://thredds.met.no/thredds/catalog/metpparchive/2023/catalog.xml"
+        );
+        assert_eq!(
+            resolve(catalog, "/thredds/ncss/grid/metpparchive/a.nc"),
+            "https://thredds.met.no/thredds/ncss/grid/metpparchive/a.nc"
+        );
+    }
+}
@@ -8,7 +8,7 @@ use snafu::ResultExt;
 
 use crate::error::{
     TimestampError,
-    timestamp_error::{InvalidFilenameSnafu, InvalidHourSnafu, InvalidYMDSnafu},
+    timestamp_error::{InvalidFilenameSnafu, InvalidHourSnafu, InvalidYMDSnafu, MalformedSnafu},
 };
 
 pub(crate) fn angle_between_vectors(v1: (f64, f64), v2: (f64, f64)) -> f64 {
@@ -22,16 +22,22 @@ pub(crate) fn length_of_vector(vec: (f64, f64)) -> f64 {
     ((vec.0.powf(2.)) + (vec.1.powf(2.))).sqrt()
 }
 
-pub(crate) fn timestamp_and_depth_from_filename(
-    name: &str,
-) -> Result<(DateTime<Utc>, i32), TimestampError> {
-    // Example name: `ocean_data/2023071218_100.nc.csv`
+pub(crate) fn timestamp_from_dataset_name(name: &str) -> Result<DateTime<Utc>, TimestampError> {
+    // Example name: `NorKyst-800m_ZDEPTHS_his.an.2023071200.nc`
 
-    let folder_len = "ocean_data/".len();
-    let suffix_len = ".nc.csv".len();
+    let date_part = name.rsplit('.').nth(1).ok_or_else(|| {
+        MalformedSnafu {
+            file_name: name.to_owned(),
+        }
+        .build()
+    })?;
 
-    let date_part = &name[folder_len..folder_len + 10];
-    let depth_part = &name[folder_len + 11..name.len() - suffix_len];
+    if date_part.len() != 10 {
+        return MalformedSnafu {
+            file_name: name.to_owned(),
+        }
+        .fail();
+    }
 
     let year = date_part[0..4]
         .parse::<i32>()
@@ -62,9 +68,5 @@ pub(crate) fn timestamp_and_depth_from_filename(
         .ok_or_else(|| InvalidHourSnafu { hour }.build())?
         .and_utc();
 
-    let depth = depth_part.parse::<i32>().context(InvalidFilenameSnafu {
-        file_name: name.to_owned(),
-    })?;
-
-    Ok((ts, depth))
+    Ok(ts)
 }
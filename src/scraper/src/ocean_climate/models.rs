@@ -1,24 +1,41 @@
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
 
 use super::{angle_between_vectors, length_of_vector};
+use crate::netcdf::BinnedGrid;
 
-#[derive(Debug, Clone, Deserialize)]
-#[allow(non_snake_case)]
+#[derive(Debug, Clone)]
 pub struct OceanClimate {
     pub lat: f64,
     pub lon: f64,
     pub u: Option<f64>,
     pub v: Option<f64>,
     pub w: Option<f64>,
-    pub Uwind: Option<f64>,
-    pub Vwind: Option<f64>,
+    pub u_wind: Option<f64>,
+    pub v_wind: Option<f64>,
     pub salinity: Option<f64>,
     pub temperature: Option<f64>,
     pub h: f64,
 }
 
 impl OceanClimate {
+    /// Returns `None` if any of the non-optional values are missing for the bin.
+    pub fn from_grid(grid: &BinnedGrid, time: usize, bin: usize) -> Option<OceanClimate> {
+        let value = |name: &str| grid.value(name, time, bin);
+
+        Some(OceanClimate {
+            lat: value("lat")?,
+            lon: value("lon")?,
+            u: value("u"),
+            v: value("v"),
+            w: value("w"),
+            u_wind: value("Uwind"),
+            v_wind: value("Vwind"),
+            salinity: value("salinity"),
+            temperature: value("temperature"),
+            h: value("h")?,
+        })
+    }
+
     pub fn to_core_ocean_climate(
         v: OceanClimate,
         timestamp: DateTime<Utc>,
@@ -33,7 +50,7 @@ impl OceanClimate {
             (None, None)
         };
 
-        let (wind_speed, wind_direction) = if let (Some(x), Some(y)) = (v.Uwind, v.Vwind) {
+        let (wind_speed, wind_direction) = if let (Some(x), Some(y)) = (v.u_wind, v.v_wind) {
             (
                 Some(length_of_vector((x, y))),
                 Some(angle_between_vectors((1., 0.), (x, y))),
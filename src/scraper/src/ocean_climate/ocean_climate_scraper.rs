@@ -1,18 +1,38 @@
-use async_trait::async_trait;
-use chrono::{DateTime, Duration, Utc};
-use csv::Reader;
-use pyo3::{
-    BoundObject, Python,
-    ffi::c_str,
-    types::{PyAnyMethods, PyDateTime, PyModule, PyTzInfo},
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
 };
-use tracing::{error, info};
 
-use crate::{DataSource, Processor, Result, ScraperId};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use http_client::HttpClient;
+use tracing::{error, info};
 
-use super::{models::OceanClimate, timestamp_and_depth_from_filename};
+use crate::{
+    DataSource, Processor, Result, ScraperId,
+    netcdf::{NetcdfReader, ThreddsClient, ThreddsDataset},
+};
 
-pub struct OceanClimateScraper {}
+use super::{models::OceanClimate, timestamp_from_dataset_name};
+
+const ARCHIVE_URL: &str =
+    "https://thredds.met.no/thredds/catalog/fou-hi/norkyst800m-1h/catalog.xml";
+const DEPTHS: [i32; 3] = [0, 25, 500];
+const VARIABLES: &[&str] = &[
+    "u",
+    "v",
+    "w",
+    "Uwind",
+    "Vwind",
+    "salinity",
+    "temperature",
+    "h",
+];
+
+pub struct OceanClimateScraper {
+    http_client: Arc<HttpClient>,
+    download_dir: PathBuf,
+}
 
 #[async_trait]
 impl DataSource for OceanClimateScraper {
@@ -28,40 +48,71 @@ impl DataSource for OceanClimateScraper {
             // In those cases we don't want to scrape all ocean_climate data, so just take the last day.
             .unwrap_or_else(|| Utc::now() - Duration::days(1));
 
-        let mut files: Vec<String> = download_ocean_climate_data(latest)?;
-        files.sort();
-
-        for file in files {
-            let (timestamp, depth) = timestamp_and_depth_from_filename(&file)?;
-
-            let reader = Reader::from_path(&file)?;
-
-            let ocean_climate = reader
-                .into_deserialize::<OceanClimate>()
-                .map(|o| match o {
-                    Ok(o) => Ok(OceanClimate::to_core_ocean_climate(o, timestamp, depth)),
-                    Err(e) => Err(e),
-                })
-                .collect::<std::result::Result<Vec<_>, _>>()?;
-
-            match processor.add_ocean_climate(ocean_climate).await {
-                Ok(()) => info!(
-                    "successfully scraped ocean_climate timestamp / depth: {} / {}",
-                    timestamp, depth,
-                ),
-                Err(e) => {
-                    error!(
-                        "failed to scrape ocean_climate timestamp / depth: {timestamp} / {depth}, error: {e:?}"
-                    );
-                    // Since we srape ocean_climate data from the latest value in the database, we don't
-                    // want to continue here and potentially get holes in the dataset that would
-                    // have to be patched manually.
-                    return Err(e.into());
+        let thredds = ThreddsClient::new(&self.http_client);
+
+        let mut datasets = thredds
+            .datasets_until(ARCHIVE_URL, |d| {
+                if is_forecast(d) {
+                    Ok(false)
+                } else {
+                    Ok(timestamp_from_dataset_name(&d.name)? <= latest)
+                }
+            })
+            .await?;
+
+        datasets.retain(|d| !is_forecast(d));
+        datasets.sort_by_key(|d| timestamp_from_dataset_name(&d.name).ok());
+
+        tokio::fs::create_dir_all(&self.download_dir).await?;
+
+        for dataset in datasets {
+            let start = timestamp_from_dataset_name(&dataset.name)?;
+
+            let mut ocean_climate = Vec::new();
+            for depth in DEPTHS {
+                let file = self
+                    .download_dir
+                    .join(format!("{}_{depth}.nc", start.format("%Y%m%d%H")));
+
+                thredds
+                    .download(
+                        &dataset,
+                        VARIABLES,
+                        &[
+                            ("vertCoord", depth.to_string()),
+                            ("temporal", "all".to_owned()),
+                        ],
+                        &file,
+                    )
+                    .await?;
+
+                let values = read_ocean_climate(&file, depth);
+
+                if let Err(e) = tokio::fs::remove_file(&file).await {
+                    error!("failed to delete ocean_climate file: {e:?}");
                 }
+
+                ocean_climate.extend(values?);
             }
 
-            if let Err(e) = tokio::fs::remove_file(file).await {
-                error!("failed to delete ocean_climate file: {e:?}");
+            // Add all depths of a timestamp together such that a failure can't leave some of the
+            // depths of the latest timestamp missing.
+            ocean_climate.sort_by_key(|o| o.timestamp);
+            for chunk in ocean_climate.chunk_by(|a, b| a.timestamp == b.timestamp) {
+                let timestamp = chunk[0].timestamp;
+
+                match processor.add_ocean_climate(chunk.to_vec()).await {
+                    Ok(()) => info!("successfully scraped ocean_climate timestamp: {timestamp}"),
+                    Err(e) => {
+                        error!(
+                            "failed to scrape ocean_climate timestamp: {timestamp}, error: {e:?}"
+                        );
+                        // Since we scrape ocean_climate data from the latest value in the database, we don't
+                        // want to continue here and potentially get holes in the dataset that would
+                        // have to be patched manually.
+                        return Err(e.into());
+                    }
+                }
             }
         }
 
@@ -70,26 +121,80 @@ impl DataSource for OceanClimateScraper {
 }
 
 impl OceanClimateScraper {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(http_client: Arc<HttpClient>, download_dir: PathBuf) -> Self {
+        Self {
+            http_client,
+            download_dir,
+        }
+    }
+}
+
+/// Reads all time steps of an ocean climate file subset to a single depth, downscaled to 0.1
+/// degree bins.
+pub(crate) fn read_ocean_climate(
+    path: &Path,
+    depth: i32,
+) -> Result<Vec<kyogre_core::NewOceanClimate>> {
+    let mut reader = NetcdfReader::open(path)?;
+    let grid = reader.binned("lat", "lon", VARIABLES)?;
+
+    let mut ocean_climate = Vec::with_capacity(grid.times().len() * grid.num_bins());
+    for (time, timestamp) in grid.times().iter().enumerate() {
+        for bin in 0..grid.num_bins() {
+            if let Some(o) = OceanClimate::from_grid(&grid, time, bin) {
+                ocean_climate.push(OceanClimate::to_core_ocean_climate(o, *timestamp, depth));
+            }
+        }
     }
+
+    Ok(ocean_climate)
+}
+
+fn is_forecast(dataset: &ThreddsDataset) -> bool {
+    dataset.name.contains(".fc.")
 }
 
-fn download_ocean_climate_data(latest: DateTime<Utc>) -> Result<Vec<String>> {
-    let py_code = c_str!(include_str!(
-        "../../../../scripts/python/ocean_climate/main.py"
-    ));
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_read_ocean_climate_reads_all_time_steps() {
+        let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "test_data", "ocean_climate.nc"]
+            .iter()
+            .collect();
 
-    Ok(Python::attach(|py| {
-        let tz = PyTzInfo::utc(py)?.into_bound();
-        let py_datetime = PyDateTime::from_timestamp(py, latest.timestamp() as f64, Some(&tz))?;
+        let ocean_climate = read_ocean_climate(&path, 25).unwrap();
 
-        let py_module = PyModule::from_code(py, py_code, c_str!(""), c_str!(""))?;
+        assert_eq!(ocean_climate.len(), 4);
+        assert!(ocean_climate.iter().all(|o| o.depth == 25));
 
-        let py_main = py_module.getattr("main")?;
+        let start = Utc.with_ymd_and_hms(2023, 7, 12, 0, 0, 0).unwrap();
+        assert_eq!(ocean_climate[0].timestamp, start);
+        assert_eq!(ocean_climate[2].timestamp, start + Duration::hours(1));
 
-        let result = py_main.call1((py_datetime,))?;
+        // The first bin of the first time step averages 1.0 and 1.2 degrees.
+        let first = &ocean_climate[0];
+        assert!((first.temperature.unwrap() - 1.1).abs() < 1e-6);
+        assert_eq!(first.sea_floor_depth, 200.);
+        assert_eq!(first.salinity, Some(34.5));
+        assert_eq!(first.water_speed, Some(1.));
+        assert_eq!(first.wind_speed, Some(5.));
+        assert_eq!(first.upward_sea_velocity, None);
 
-        result.extract()
-    })?)
+        // The second bin of the second time step only has a single non-fill temperature.
+        let last = &ocean_climate[3];
+        assert!((last.temperature.unwrap() - 2.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_timestamp_from_dataset_name() {
+        assert_eq!(
+            timestamp_from_dataset_name("NorKyst-800m_ZDEPTHS_his.an.2023071218.nc").unwrap(),
+            Utc.with_ymd_and_hms(2023, 7, 12, 18, 0, 0).unwrap()
+        );
+        assert!(timestamp_from_dataset_name("NorKyst-800m_ZDEPTHS_his.nc").is_err());
+    }
 }
@@ -1,6 +1,6 @@
 use std::{future::Future, sync::Arc};
 
-use crate::{Error, FiskeridirSource, Result};
+use crate::{Error, FiskeridirSource, Result, chunks::FileIngestion};
 use fiskeridir_rs::{DataDir, DataFile, FileSource};
 use orca_core::Environment;
 use tokio::sync::mpsc::channel;
@@ -31,7 +31,7 @@ pub async fn prefetch_and_scrape<F, Fut>(
     closure: F,
 ) -> Result<()>
 where
-    F: Fn(DataDir, DataFile) -> Fut,
+    F: Fn(DataDir, DataFile, FileIngestion) -> Fut,
     Fut: Future<Output = Result<()>>,
 {
     if sources.is_empty() {
@@ -130,7 +130,12 @@ where
                             file_hash,
                         } => {
                             let year = file.year();
-                            match closure(dir, file).await {
+                            let ingestion = FileIngestion::new(
+                                fiskeridir_source.clone(),
+                                file,
+                                file_hash.clone(),
+                            );
+                            match closure(dir, file, ingestion).await {
                                 Ok(()) => match fiskeridir_source
                                     .hash_store
                                     .add(&file.id(), file_hash)
@@ -1,14 +1,21 @@
 use chrono::{DateTime, NaiveDate, Utc};
 
 mod models;
+mod weather_forecast_scraper;
 mod weather_scraper;
 
 use snafu::ResultExt;
+pub use weather_forecast_scraper::WeatherForecastScraper;
 pub use weather_scraper::WeatherScraper;
 
-use crate::error::{
-    TimestampError,
-    timestamp_error::{InvalidFilenameSnafu, InvalidHourSnafu, InvalidYMDSnafu, MalformedSnafu},
+use crate::{
+    error::{
+        TimestampError,
+        timestamp_error::{
+            InvalidFilenameSnafu, InvalidHourSnafu, InvalidYMDSnafu, MalformedSnafu,
+        },
+    },
+    netcdf::ThreddsDataset,
 };
 
 pub(crate) fn angle_between_vectors(v1: (f64, f64), v2: (f64, f64)) -> f64 {
@@ -22,8 +29,13 @@ pub(crate) fn length_of_vector(vec: (f64, f64)) -> f64 {
     ((vec.0.powf(2.)) + (vec.1.powf(2.))).sqrt()
 }
 
+/// Example name: `met_analysis_1_0km_nordic_20220618T15Z.nc`
+pub(crate) fn file_name(dataset: &ThreddsDataset) -> &str {
+    dataset.name.rsplit('_').next().unwrap_or(&dataset.name)
+}
+
 pub(crate) fn timestamp_from_filename(name: &str) -> Result<DateTime<Utc>, TimestampError> {
-    // Example name: `weather_data/20220618T15Z.nc.csv`
+    // Example name: `20220618T15Z.nc`
 
     let date_part = name
         .rsplit('/')
@@ -43,6 +55,13 @@ pub(crate) fn timestamp_from_filename(name: &str) -> Result<DateTime<Utc>, Times
             .build()
         })?;
 
+    if date_part.len() != 12 {
+        return MalformedSnafu {
+            file_name: name.to_owned(),
+        }
+        .fail();
+    }
+
     let year = date_part[0..4]
         .parse::<i32>()
         .with_context(|_| InvalidFilenameSnafu {
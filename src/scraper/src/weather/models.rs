@@ -1,9 +1,9 @@
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
 
 use super::{angle_between_vectors, length_of_vector};
+use crate::netcdf::BinnedGrid;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone)]
 pub struct Weather {
     pub latitude: f64,
     pub longitude: f64,
@@ -21,6 +21,27 @@ pub struct Weather {
 }
 
 impl Weather {
+    /// Returns `None` if any of the non-optional values are missing for the bin.
+    pub fn from_grid(grid: &BinnedGrid, time: usize, bin: usize) -> Option<Weather> {
+        let value = |name: &str| grid.value(name, time, bin);
+
+        Some(Weather {
+            latitude: value("latitude")?,
+            longitude: value("longitude")?,
+            altitude: value("altitude")?,
+            wind_speed_10m: value("wind_speed_10m"),
+            wind_direction_10m: value("wind_direction_10m"),
+            x_wind_10m: value("x_wind_10m"),
+            y_wind_10m: value("y_wind_10m"),
+            air_temperature_2m: value("air_temperature_2m"),
+            relative_humidity_2m: value("relative_humidity_2m"),
+            air_pressure_at_sea_level: value("air_pressure_at_sea_level"),
+            precipitation_amount: value("precipitation_amount"),
+            land_area_fraction: value("land_area_fraction")?,
+            cloud_area_fraction: value("cloud_area_fraction"),
+        })
+    }
+
     pub fn to_core_weather(v: Weather, timestamp: DateTime<Utc>) -> kyogre_core::NewWeather {
         let (wind_speed_10m, wind_direction_10m) =
             if v.wind_speed_10m.is_some() && v.wind_direction_10m.is_some() {
@@ -50,3 +71,51 @@ impl Weather {
         }
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct WeatherForecast {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub wind_speed_10m: Option<f64>,
+    pub wind_direction_10m: Option<f64>,
+    pub air_temperature_2m: Option<f64>,
+    pub air_pressure_at_sea_level: Option<f64>,
+    pub precipitation_amount: Option<f64>,
+    pub land_area_fraction: f64,
+}
+
+impl WeatherForecast {
+    /// Returns `None` if any of the non-optional values are missing for the bin.
+    pub fn from_grid(grid: &BinnedGrid, time: usize, bin: usize) -> Option<WeatherForecast> {
+        let value = |name: &str| grid.value(name, time, bin);
+
+        Some(WeatherForecast {
+            latitude: value("latitude")?,
+            longitude: value("longitude")?,
+            wind_speed_10m: value("wind_speed_10m"),
+            wind_direction_10m: value("wind_direction_10m"),
+            air_temperature_2m: value("air_temperature_2m"),
+            air_pressure_at_sea_level: value("air_pressure_at_sea_level"),
+            precipitation_amount: value("precipitation_amount"),
+            land_area_fraction: value("land_area_fraction")?,
+        })
+    }
+
+    pub fn to_core_weather_forecast(
+        v: WeatherForecast,
+        reference_time: DateTime<Utc>,
+        timestamp: DateTime<Utc>,
+    ) -> kyogre_core::NewWeatherForecast {
+        kyogre_core::NewWeatherForecast {
+            reference_time,
+            timestamp,
+            latitude: v.latitude,
+            longitude: v.longitude,
+            wind_speed_10m: v.wind_speed_10m.into(),
+            wind_direction_10m: v.wind_direction_10m,
+            air_temperature_2m: v.air_temperature_2m.into(),
+            air_pressure_at_sea_level: v.air_pressure_at_sea_level.into(),
+            precipitation_amount: v.precipitation_amount.into(),
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use super::{file_name, models::WeatherForecast, timestamp_from_filename};
+use crate::{
+    DataSource, Processor, Result, ScraperId,
+    netcdf::{NetcdfReader, ThreddsClient, ThreddsDataset},
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use http_client::HttpClient;
+use kyogre_core::MAX_WEATHER_FORECAST_HORIZON_HOURS;
+use tracing::{error, info};
+
+const LATEST_FORECASTS_URL: &str = "https://thredds.met.no/thredds/catalog/metpplatest/catalog.xml";
+const FORECAST_PREFIX: &str = "met_forecast_1_0km_nordic_";
+
+/// The variables downloaded from each forecast run.
+const VARIABLES: &[&str] = &[
+    "land_area_fraction",
+    "wind_speed_10m",
+    "wind_direction_10m",
+    "air_temperature_2m",
+    "air_pressure_at_sea_level",
+    "precipitation_amount",
+];
+
+pub struct WeatherForecastScraper {
+    http_client: Arc<HttpClient>,
+    download_dir: PathBuf,
+}
+
+#[async_trait]
+impl DataSource for WeatherForecastScraper {
+    fn id(&self) -> ScraperId {
+        ScraperId::WeatherForecast
+    }
+
+    async fn scrape(&self, processor: &dyn Processor) -> Result<()> {
+        let thredds = ThreddsClient::new(&self.http_client);
+
+        // Only the latest run is relevant as it supersedes all earlier runs.
+        let Some(dataset) = thredds
+            .datasets_until(LATEST_FORECASTS_URL, |_| Ok(false))
+            .await?
+            .into_iter()
+            .filter(is_forecast_run)
+            .max_by(|a, b| file_name(a).cmp(file_name(b)))
+        else {
+            return Ok(());
+        };
+
+        let reference_time = timestamp_from_filename(file_name(&dataset))?;
+
+        if processor
+            .latest_weather_forecast_reference_time()
+            .await?
+            .is_some_and(|latest| latest >= reference_time)
+        {
+            return Ok(());
+        }
+
+        tokio::fs::create_dir_all(&self.download_dir).await?;
+        let file = self.download_dir.join(file_name(&dataset));
+
+        let end = reference_time + Duration::hours(MAX_WEATHER_FORECAST_HORIZON_HOURS as i64);
+        thredds
+            .download(
+                &dataset,
+                VARIABLES,
+                &[
+                    (
+                        "time_start",
+                        reference_time.to_rfc3339_opts(SecondsFormat::Secs, true),
+                    ),
+                    ("time_end", end.to_rfc3339_opts(SecondsFormat::Secs, true)),
+                ],
+                &file,
+            )
+            .await?;
+
+        let forecasts = read_weather_forecast(&file, reference_time);
+
+        if let Err(e) = tokio::fs::remove_file(&file).await {
+            error!("failed to delete weather forecast file: {e:?}");
+        }
+
+        processor.add_weather_forecasts(forecasts?).await?;
+        info!("successfully scraped weather forecast run: {reference_time}");
+
+        Ok(())
+    }
+}
+
+impl WeatherForecastScraper {
+    pub fn new(http_client: Arc<HttpClient>, download_dir: PathBuf) -> Self {
+        Self {
+            http_client,
+            download_dir,
+        }
+    }
+}
+
+/// Reads all time steps of a forecast run downscaled to 0.1 degree bins, bins that are entirely
+/// on land are skipped.
+pub(crate) fn read_weather_forecast(
+    path: &Path,
+    reference_time: DateTime<Utc>,
+) -> Result<Vec<kyogre_core::NewWeatherForecast>> {
+    let mut reader = NetcdfReader::open(path)?;
+    let grid = reader.binned("latitude", "longitude", VARIABLES)?;
+
+    let mut forecasts = Vec::with_capacity(grid.times().len() * grid.num_bins());
+    for (time, timestamp) in grid.times().iter().enumerate() {
+        if *timestamp < reference_time {
+            continue;
+        }
+        for bin in 0..grid.num_bins() {
+            if let Some(w) = WeatherForecast::from_grid(&grid, time, bin)
+                && w.land_area_fraction < 1.0
+            {
+                forecasts.push(WeatherForecast::to_core_weather_forecast(
+                    w,
+                    reference_time,
+                    *timestamp,
+                ));
+            }
+        }
+    }
+
+    Ok(forecasts)
+}
+
+/// Example name: `met_forecast_1_0km_nordic_20220618T06Z.nc`, the catalog also lists the latest
+/// run as `met_forecast_1_0km_nordic_latest.nc` which is skipped as it has no reference time.
+fn is_forecast_run(dataset: &ThreddsDataset) -> bool {
+    dataset.name.starts_with(FORECAST_PREFIX) && !dataset.name.contains("latest")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_read_weather_forecast_skips_bins_on_land() {
+        let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "test_data", "weather.nc"]
+            .iter()
+            .collect();
+
+        let reference_time = Utc.with_ymd_and_hms(2023, 6, 16, 12, 0, 0).unwrap();
+        let forecasts = read_weather_forecast(&path, reference_time).unwrap();
+
+        assert_eq!(forecasts.len(), 2);
+        assert!(forecasts.iter().all(|f| f.reference_time == reference_time));
+        assert!(
+            forecasts
+                .iter()
+                .all(|f| f.timestamp == Utc.with_ymd_and_hms(2023, 6, 16, 15, 0, 0).unwrap())
+        );
+        assert_eq!(forecasts[0].wind_speed_10m.into_inner(), Some(3.));
+        assert_eq!(forecasts[0].precipitation_amount.into_inner(), None);
+    }
+
+    #[test]
+    fn test_read_weather_forecast_skips_time_steps_before_the_reference_time() {
+        let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "test_data", "weather.nc"]
+            .iter()
+            .collect();
+
+        let reference_time = Utc.with_ymd_and_hms(2023, 6, 16, 18, 0, 0).unwrap();
+        assert!(
+            read_weather_forecast(&path, reference_time)
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
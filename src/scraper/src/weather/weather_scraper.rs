@@ -1,16 +1,42 @@
-use super::{models::Weather, timestamp_from_filename};
-use crate::{DataSource, Processor, Result, ScraperId};
-use async_trait::async_trait;
-use chrono::{DateTime, Duration, Utc};
-use csv::Reader;
-use pyo3::{
-    BoundObject, Python,
-    ffi::c_str,
-    types::{PyAnyMethods, PyDateTime, PyModule, PyTzInfo},
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use super::{file_name, models::Weather, timestamp_from_filename};
+use crate::{
+    DataSource, Processor, Result, ScraperId,
+    netcdf::{NetcdfReader, ThreddsClient, ThreddsDataset},
 };
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use http_client::HttpClient;
 use tracing::{error, info};
 
-pub struct WeatherScraper {}
+const OPERATIONAL_ARCHIVE_URL: &str =
+    "https://thredds.met.no/thredds/catalog/metpparchive/catalog.xml";
+
+/// The variables downloaded from the archive.
+const VARIABLES: &[&str] = &[
+    "altitude",
+    "land_area_fraction",
+    "wind_speed_10m",
+    "wind_direction_10m",
+    "air_temperature_2m",
+    "relative_humidity_2m",
+    "air_pressure_at_sea_level",
+    "precipitation_amount",
+    "cloud_area_fraction",
+];
+
+/// Wind vectors are only used if the wind speed and direction are missing, they are not part of
+/// the operational archive but are read if present.
+const WIND_VECTOR_VARIABLES: &[&str] = &["x_wind_10m", "y_wind_10m"];
+
+pub struct WeatherScraper {
+    http_client: Arc<HttpClient>,
+    download_dir: PathBuf,
+}
 
 #[async_trait]
 impl DataSource for WeatherScraper {
@@ -26,29 +52,36 @@ impl DataSource for WeatherScraper {
             // In those cases we don't want to scrape all weather data, so just take the last day.
             .unwrap_or_else(|| Utc::now() - Duration::days(1));
 
-        let mut files: Vec<String> = download_weather_data(latest)?;
-        files.sort();
-
-        for file in files {
-            let timestamp = timestamp_from_filename(&file)?;
-
-            let reader = Reader::from_path(&file)?;
-
-            let weather = reader
-                .into_deserialize::<Weather>()
-                .filter_map(|w| match w {
-                    Ok(w) => {
-                        if w.land_area_fraction < 1.0 {
-                            Some(Ok(Weather::to_core_weather(w, timestamp)))
-                        } else {
-                            None
-                        }
-                    }
-                    Err(e) => Some(Err(e)),
-                })
-                .collect::<std::result::Result<Vec<_>, _>>()?;
-
-            match processor.add_weather(weather).await {
+        let thredds = ThreddsClient::new(&self.http_client);
+
+        let mut datasets = thredds
+            .datasets_until(OPERATIONAL_ARCHIVE_URL, |d| {
+                if is_forecast(d) {
+                    Ok(false)
+                } else {
+                    Ok(timestamp_from_filename(file_name(d))? <= latest)
+                }
+            })
+            .await?;
+
+        datasets.retain(|d| !is_forecast(d));
+        datasets.sort_by(|a, b| file_name(a).cmp(file_name(b)));
+
+        tokio::fs::create_dir_all(&self.download_dir).await?;
+
+        for dataset in datasets {
+            let timestamp = timestamp_from_filename(file_name(&dataset))?;
+            let file = self.download_dir.join(file_name(&dataset));
+
+            thredds.download(&dataset, VARIABLES, &[], &file).await?;
+
+            let weather = read_weather(&file);
+
+            if let Err(e) = tokio::fs::remove_file(&file).await {
+                error!("failed to delete weather file: {e:?}");
+            }
+
+            match processor.add_weather(weather?).await {
                 Ok(()) => info!("successfully scraped weather timestamp: {}", timestamp,),
                 Err(e) => {
                     error!("failed to scrape weather timestamp: {timestamp}, error: {e}");
@@ -58,10 +91,6 @@ impl DataSource for WeatherScraper {
                     return Err(e.into());
                 }
             }
-
-            if let Err(e) = tokio::fs::remove_file(file).await {
-                error!("failed to delete weather file: {e:?}");
-            }
         }
 
         Ok(())
@@ -69,24 +98,76 @@ impl DataSource for WeatherScraper {
 }
 
 impl WeatherScraper {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(http_client: Arc<HttpClient>, download_dir: PathBuf) -> Self {
+        Self {
+            http_client,
+            download_dir,
+        }
     }
 }
 
-fn download_weather_data(latest: DateTime<Utc>) -> Result<Vec<String>> {
-    let py_code = c_str!(include_str!("../../../../scripts/python/weather/main.py"));
-
-    Ok(Python::attach(|py| {
-        let tz = PyTzInfo::utc(py)?.into_bound();
-        let py_datetime = PyDateTime::from_timestamp(py, latest.timestamp() as f64, Some(&tz))?;
-
-        let py_module = PyModule::from_code(py, py_code, c_str!(""), c_str!(""))?;
+/// Reads a weather file downscaled to 0.1 degree bins, bins that are entirely on land are
+/// skipped.
+pub(crate) fn read_weather(path: &Path) -> Result<Vec<kyogre_core::NewWeather>> {
+    let mut reader = NetcdfReader::open(path)?;
+
+    let variables = VARIABLES
+        .iter()
+        .chain(WIND_VECTOR_VARIABLES)
+        .copied()
+        .collect::<Vec<_>>();
+
+    let grid = reader.binned("latitude", "longitude", &variables)?;
+
+    let mut weather = Vec::with_capacity(grid.num_bins());
+    for (time, timestamp) in grid.times().iter().enumerate() {
+        for bin in 0..grid.num_bins() {
+            if let Some(w) = Weather::from_grid(&grid, time, bin)
+                && w.land_area_fraction < 1.0
+            {
+                weather.push(Weather::to_core_weather(w, *timestamp));
+            }
+        }
+    }
 
-        let py_main = py_module.getattr("main")?;
+    Ok(weather)
+}
 
-        let result = py_main.call1((py_datetime,))?;
+fn is_forecast(dataset: &ThreddsDataset) -> bool {
+    dataset.name.contains("forecast") || dataset.name.contains("latest")
+}
 
-        result.extract()
-    })?)
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_read_weather_downscales_and_skips_bins_on_land() {
+        let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "test_data", "weather.nc"]
+            .iter()
+            .collect();
+
+        let weather = read_weather(&path).unwrap();
+
+        assert_eq!(weather.len(), 2);
+        assert!(weather.iter().all(|w| w.land_area_fraction < 1.0));
+        assert!(
+            weather
+                .iter()
+                .all(|w| w.timestamp == Utc.with_ymd_and_hms(2023, 6, 16, 15, 0, 0).unwrap())
+        );
+
+        let first = &weather[0];
+        assert!((first.latitude - 70.04).abs() < 1e-9);
+        assert!((first.longitude - 20.025).abs() < 1e-9);
+        assert_eq!(first.wind_speed_10m.into_inner(), Some(3.));
+        assert_eq!(first.wind_direction_10m, Some(90.));
+        assert_eq!(first.precipitation_amount.into_inner(), None);
+
+        let second = &weather[1];
+        assert_eq!(second.land_area_fraction, 0.5);
+        assert_eq!(second.wind_direction_10m, Some(180.));
+    }
 }
@@ -72,6 +72,33 @@ pub enum Error {
         fuel_after_liter: f64,
         fuel_liter: f64,
     },
+    #[snafu(display(
+        "Exactly one of polygon, catch location or fishing facility must be given for a geofence"
+    ))]
+    InvalidGeofenceArea {
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("A geofence polygon requires at least 3 points, got '{num_points}' points"))]
+    InvalidGeofencePolygon {
+        #[snafu(implicit)]
+        location: Location,
+        num_points: usize,
+    },
+    #[snafu(display(
+        "Geofence point with latitude '{latitude}' and longitude '{longitude}' is out of range"
+    ))]
+    InvalidGeofenceCoordinate {
+        #[snafu(implicit)]
+        location: Location,
+        latitude: f64,
+        longitude: f64,
+    },
+    #[snafu(display("A geofence name cannot be empty"))]
+    InvalidGeofenceName {
+        #[snafu(implicit)]
+        location: Location,
+    },
     #[snafu(display("Start date: '{start}' cannot be after end date: '{end}'"))]
     StartAfterEnd {
         #[snafu(implicit)]
@@ -79,6 +106,16 @@ pub enum Error {
         start: DateTime<Utc>,
         end: DateTime<Utc>,
     },
+    #[snafu(display(
+        "The range between start: '{start}' and end: '{end}' cannot exceed '{max_days}' days"
+    ))]
+    DateRangeTooLong {
+        #[snafu(implicit)]
+        location: Location,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        max_days: i64,
+    },
     #[snafu(display("The given call sign '{call_sign}' was invalid"))]
     InvalidCallSign {
         #[snafu(implicit)]
@@ -182,6 +219,11 @@ pub enum Error {
         opaque: OpaqueError,
         call_sign: CallSign,
     },
+    #[snafu(display("The matrix cache is not available"))]
+    MatrixCacheUnavailable {
+        #[snafu(implicit)]
+        location: Location,
+    },
     #[snafu(display("An unexpected error occured"))]
     #[stack_error(
         opaque_stack_from = [kyogre_core::Error, http_client::Error, ParseStringError],
@@ -204,6 +246,7 @@ impl ResponseError for Error {
         use ErrorDiscriminants::*;
         match ErrorDiscriminants::from(self) {
             StartAfterEnd
+            | DateRangeTooLong
             | InvalidCallSign
             | MissingBwFiskInfoProfile
             | InvalidDateRange
@@ -214,6 +257,10 @@ impl ResponseError for Error {
             | InvalidExcel
             | CallSignDoesNotExist
             | CannotModifyActiveUserHaul
+            | InvalidGeofenceArea
+            | InvalidGeofencePolygon
+            | InvalidGeofenceCoordinate
+            | InvalidGeofenceName
             | MissingMmsiOrCallSignOrTripId => StatusCode::BAD_REQUEST,
             InsufficientPermissions => StatusCode::FORBIDDEN,
             NoActiveUserHaul => StatusCode::CONFLICT,
@@ -221,6 +268,7 @@ impl ResponseError for Error {
                 StatusCode::UNAUTHORIZED
             }
             InvalidVesselSelection | ObjectNotFound => StatusCode::NOT_FOUND,
+            MatrixCacheUnavailable => StatusCode::SERVICE_UNAVAILABLE,
             Unexpected => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
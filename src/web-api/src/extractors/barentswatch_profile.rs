@@ -23,7 +23,7 @@ use crate::{
     Database,
     error::{
         Error, Result,
-        error::{MissingBwFiskInfoProfileSnafu, MissingJWTSnafu},
+        error::{InsufficientPermissionsSnafu, MissingBwFiskInfoProfileSnafu, MissingJWTSnafu},
     },
     extractors::AcceptedIssuer,
     states::BwState,
@@ -117,6 +117,11 @@ pub struct BwJwtClaims {
 #[serde(rename_all = "camelCase")]
 pub struct OptionBwProfile(Option<BwProfile>);
 
+/// A profile with the `BwFiskinfoAdmin` role, extraction fails with insufficient permissions for
+/// all other profiles.
+#[derive(Debug, Clone)]
+pub struct AdminBwProfile(BwProfile);
+
 impl BwProfile {
     pub fn tracing_id(&self) -> String {
         self.user.id.to_string()
@@ -186,6 +191,12 @@ impl OaParameter for OptionBwProfile {
     }
 }
 
+impl OaParameter for AdminBwProfile {
+    fn parameters() -> Vec<RefOr<Parameter>> {
+        BwProfile::parameters()
+    }
+}
+
 impl From<&BwProfile> for AisPermission {
     fn from(value: &BwProfile) -> Self {
         let ais_policy = value.policies.contains(&BwPolicy::BwAisFiskinfo);
@@ -254,6 +265,27 @@ impl FromRequest for OptionBwProfile {
     }
 }
 
+impl FromRequest for AdminBwProfile {
+    type Error = Error;
+
+    type Future = Pin<Box<dyn Future<Output = Result<Self>>>>;
+
+    fn from_request(
+        req: &actix_web::HttpRequest,
+        payload: &mut actix_web::dev::Payload,
+    ) -> Self::Future {
+        let fut = BwProfile::from_request(req, payload);
+        Box::pin(async move {
+            let profile = fut.await?;
+            if profile.roles.contains(&BwRole::BwFiskinfoAdmin) {
+                Ok(Self(profile))
+            } else {
+                InsufficientPermissionsSnafu.fail()
+            }
+        })
+    }
+}
+
 impl BwProfile {
     pub async fn extract_impl(
         state: Data<BwState>,
@@ -333,3 +365,11 @@ impl Deref for OptionBwProfile {
         &self.0
     }
 }
+
+impl Deref for AdminBwProfile {
+    type Target = BwProfile;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
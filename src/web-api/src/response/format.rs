@@ -0,0 +1,40 @@
+use actix_web::{
+    HttpRequest, HttpResponse, HttpResponseBuilder,
+    http::header::{Accept, ContentDisposition, DispositionParam, DispositionType, Header},
+};
+
+/// A response format that is negotiated through the `Accept` header of the request, where the
+/// default variant is used if the header is missing or contains no supported formats.
+pub trait NegotiatedFormat: Default + Sized {
+    /// Returns the format matching the given MIME type essence, if supported.
+    fn from_mime(essence: &str) -> Option<Self>;
+    /// File extension used when the response is downloaded as an attachment.
+    fn extension(&self) -> &'static str;
+
+    /// Returns the first supported format in the ranked `Accept` header, falling back to the
+    /// default format if the header is missing or contains no supported formats.
+    fn negotiate(req: &HttpRequest) -> Self {
+        let Ok(accept) = Accept::parse(req) else {
+            return Self::default();
+        };
+
+        accept
+            .ranked()
+            .into_iter()
+            .find_map(|mime| Self::from_mime(mime.essence_str()))
+            .unwrap_or_default()
+    }
+}
+
+/// Returns a successful response that is downloaded as `<name>.<extension>`.
+pub fn attachment(name: &str, format: impl NegotiatedFormat) -> HttpResponseBuilder {
+    let mut response = HttpResponse::Ok();
+    response.insert_header(ContentDisposition {
+        disposition: DispositionType::Attachment,
+        parameters: vec![DispositionParam::Filename(format!(
+            "{name}.{}",
+            format.extension()
+        ))],
+    });
+    response
+}
@@ -4,9 +4,15 @@ use actix_web::{HttpRequest, HttpResponse, Responder, body::BoxBody};
 use oasgen::{OaSchema, ObjectType, RefOr, Schema, SchemaData, SchemaKind, Type};
 use serde::{Deserialize, Serialize};
 
+mod format;
 mod stream;
+mod tabular;
+mod track;
 
+pub use format::*;
 pub use stream::*;
+pub use tabular::*;
+pub use track::*;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Response<T> {
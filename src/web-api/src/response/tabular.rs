@@ -0,0 +1,508 @@
+use std::{fmt::Display, pin::Pin};
+
+use actix_web::{HttpRequest, HttpResponse, Responder, body::BoxBody, web::Bytes};
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures::{Stream, StreamExt, stream};
+use kyogre_core::{FuelEntry, FuelMeasurement, WebApiResult};
+use oasgen::{OaSchema, Schema};
+use rust_xlsxwriter::{Workbook, Worksheet, XlsxError};
+use serde::Serialize;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::error;
+
+use super::{NegotiatedFormat, Response, ResponseOrStream, StreamResponse, attachment};
+use crate::routes::v1::{haul::Haul, landing::Landing, trip::Trip};
+
+pub const XLSX_MIME: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+const CSV_CONTENT_TYPE: &str = "text/csv; charset=utf-8";
+/// Maximum number of rows of XLSX exports, which are buffered in memory in their entirety.
+pub const XLSX_MAX_ROWS: u32 = 100_000;
+
+/// Separator used when a list of values is written to a single cell.
+const LIST_SEPARATOR: &str = ";";
+
+/// The format of a `TabularResponse`, negotiated through the `Accept` header of the request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+    Xlsx,
+}
+
+impl NegotiatedFormat for ExportFormat {
+    fn from_mime(essence: &str) -> Option<Self> {
+        match essence {
+            "application/json" => Some(Self::Json),
+            "text/csv" => Some(Self::Csv),
+            XLSX_MIME => Some(Self::Xlsx),
+            _ => None,
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Csv => "csv",
+            Self::Xlsx => "xlsx",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell {
+    Empty,
+    Text(String),
+    Number(f64),
+    Bool(bool),
+}
+
+impl Cell {
+    pub fn text(value: impl Display) -> Self {
+        Self::Text(value.to_string())
+    }
+
+    pub fn opt_text(value: Option<impl Display>) -> Self {
+        value.map(Self::text).unwrap_or(Self::Empty)
+    }
+
+    pub fn number(value: impl Into<f64>) -> Self {
+        Self::Number(value.into())
+    }
+
+    pub fn opt_number(value: Option<impl Into<f64>>) -> Self {
+        value.map(Self::number).unwrap_or(Self::Empty)
+    }
+
+    pub fn timestamp(value: DateTime<Utc>) -> Self {
+        Self::Text(value.to_rfc3339_opts(SecondsFormat::Millis, true))
+    }
+
+    pub fn opt_timestamp(value: Option<DateTime<Utc>>) -> Self {
+        value.map(Self::timestamp).unwrap_or(Self::Empty)
+    }
+
+    pub fn list<T: Display>(values: impl IntoIterator<Item = T>) -> Self {
+        let values = values
+            .into_iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>();
+
+        if values.is_empty() {
+            Self::Empty
+        } else {
+            Self::Text(values.join(LIST_SEPARATOR))
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        match self {
+            Self::Empty => String::new(),
+            Self::Text(v) => v.clone(),
+            Self::Number(v) => v.to_string(),
+            Self::Bool(v) => v.to_string(),
+        }
+    }
+
+    fn write(&self, worksheet: &mut Worksheet, row: u32, col: u16) -> Result<(), XlsxError> {
+        match self {
+            Self::Empty => {}
+            Self::Text(v) => {
+                worksheet.write_string(row, col, v)?;
+            }
+            Self::Number(v) => {
+                worksheet.write_number(row, col, *v)?;
+            }
+            Self::Bool(v) => {
+                worksheet.write_boolean(row, col, *v)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A type that can be exported as a row in a CSV or XLSX file.
+/// `HEADERS` is part of the public API of the export and columns should only ever be appended,
+/// and `cells` must return exactly one cell per header.
+pub trait Tabular {
+    /// Used as the file name of exports and the worksheet name of XLSX exports.
+    const NAME: &'static str;
+    const HEADERS: &'static [&'static str];
+
+    fn cells(&self) -> Vec<Cell>;
+}
+
+/// A response that is serialized as JSON by default, or exported as CSV or XLSX if requested
+/// through the `Accept` header.
+pub struct TabularResponse<T>(ResponseOrStream<T>);
+
+type RowStream<T> = Pin<Box<dyn Stream<Item = WebApiResult<T>>>>;
+
+impl<T: 'static> TabularResponse<T> {
+    fn rows(self) -> RowStream<T> {
+        match self.0 {
+            ResponseOrStream::Response(v) => Box::pin(stream::iter(v.body.into_iter().map(Ok))),
+            ResponseOrStream::Stream(v) => Box::pin(ReceiverStream::new(v.rx)),
+        }
+    }
+}
+
+impl<T> Responder for TabularResponse<T>
+where
+    T: Serialize + Tabular + 'static,
+{
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
+        match ExportFormat::negotiate(req) {
+            ExportFormat::Json => self.0.respond_to(req),
+            ExportFormat::Csv => attachment(T::NAME, ExportFormat::Csv)
+                .content_type(CSV_CONTENT_TYPE)
+                .streaming(csv_stream(self.rows())),
+            ExportFormat::Xlsx => attachment(T::NAME, ExportFormat::Xlsx)
+                .content_type(XLSX_MIME)
+                .streaming(buffered_xlsx(self.rows(), XLSX_MAX_ROWS)),
+        }
+    }
+}
+
+impl<T: OaSchema + 'static> OaSchema for TabularResponse<T> {
+    fn schema_ref() -> oasgen::ReferenceOr<Schema> {
+        ResponseOrStream::<T>::schema_ref()
+    }
+
+    fn schema() -> Schema {
+        ResponseOrStream::<T>::schema()
+    }
+}
+
+impl<T> From<Response<Vec<T>>> for TabularResponse<T> {
+    fn from(value: Response<Vec<T>>) -> Self {
+        Self(value.into())
+    }
+}
+
+impl<T> From<StreamResponse<T>> for TabularResponse<T> {
+    fn from(value: StreamResponse<T>) -> Self {
+        Self(value.into())
+    }
+}
+
+fn csv_record(values: Vec<String>) -> Result<Bytes, csv::Error> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    writer.write_record(values)?;
+    writer
+        .into_inner()
+        .map(Bytes::from)
+        .map_err(|e| e.into_error().into())
+}
+
+/// Streams a header row followed by one row per item, items that fail to be retrieved or
+/// serialized are logged and skipped in the same way as for JSON streams.
+fn csv_stream<T: Tabular + 'static>(
+    rows: RowStream<T>,
+) -> impl Stream<Item = Result<Bytes, String>> {
+    let header: Vec<String> = T::HEADERS.iter().map(|h| h.to_string()).collect();
+
+    stream::iter([Ok(header)])
+        .chain(rows.map(|v| v.map(|v| v.cells().iter().map(Cell::to_csv).collect::<Vec<_>>())))
+        .filter_map(|v| async move {
+            match v {
+                Ok(v) => match csv_record(v) {
+                    Ok(v) => Some(Ok(v)),
+                    Err(e) => {
+                        error!(error = true, "failed to serialize streaming item: {e:?}");
+                        None
+                    }
+                },
+                Err(e) => {
+                    error!(error = true, "failed to retrieve streaming item: {e:?}");
+                    None
+                }
+            }
+        })
+}
+
+/// XLSX files are zip archives and can only be written once all rows are known, so unlike CSV
+/// exports the whole workbook is buffered before it is sent. Rows are written to the worksheet as
+/// they are received using the constant memory mode of `rust_xlsxwriter` to avoid keeping both the
+/// items and the worksheet in memory, and exports with more than `max_rows` rows fail instead of
+/// buffering an unbounded workbook.
+fn buffered_xlsx<T: Tabular + 'static>(
+    mut rows: RowStream<T>,
+    max_rows: u32,
+) -> impl Stream<Item = Result<Bytes, String>> {
+    stream::once(async move { write_xlsx(&mut rows, max_rows).await }).map(|v| {
+        v.map_err(|e| {
+            error!(error = true, "failed to write xlsx export: {e}");
+            e
+        })
+    })
+}
+
+async fn write_xlsx<T: Tabular>(rows: &mut RowStream<T>, max_rows: u32) -> Result<Bytes, String> {
+    let xlsx_error = |e: XlsxError| e.to_string();
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet_with_constant_memory();
+    worksheet.set_name(T::NAME).map_err(xlsx_error)?;
+
+    for (col, header) in T::HEADERS.iter().enumerate() {
+        worksheet
+            .write_string(0, col as u16, *header)
+            .map_err(xlsx_error)?;
+    }
+
+    let mut num_rows = 0;
+    while let Some(v) = rows.next().await {
+        match v {
+            Ok(v) => {
+                if num_rows == max_rows {
+                    return Err(format!(
+                        "xlsx exports are limited to {max_rows} rows, use csv for larger exports"
+                    ));
+                }
+                num_rows += 1;
+                for (col, cell) in v.cells().iter().enumerate() {
+                    cell.write(worksheet, num_rows, col as u16)
+                        .map_err(xlsx_error)?;
+                }
+            }
+            Err(e) => {
+                error!(error = true, "failed to retrieve streaming item: {e:?}");
+            }
+        }
+    }
+
+    workbook
+        .save_to_buffer()
+        .map(Bytes::from)
+        .map_err(xlsx_error)
+}
+
+impl Tabular for Trip {
+    const NAME: &'static str = "trips";
+    const HEADERS: &'static [&'static str] = &[
+        "tripId",
+        "fiskeridirVesselId",
+        "start",
+        "end",
+        "landingCoverageStart",
+        "landingCoverageEnd",
+        "tripAssemblerId",
+        "startPortId",
+        "endPortId",
+        "gearIds",
+        "deliveryPointIds",
+        "landingIds",
+        "numDeliveries",
+        "mostRecentDeliveryDate",
+        "numHauls",
+        "totalLivingWeight",
+        "totalProductWeight",
+        "totalGrossWeight",
+        "totalPriceForFisher",
+        "priceForFisherIsEstimated",
+        "targetSpeciesFiskeridirId",
+        "targetSpeciesFaoId",
+        "fuelConsumption",
+        "fuelConsumptionEstimatedOnly",
+        "percentageOfTripCoveredByMeasurements",
+        "distance",
+        "trackCoverage",
+    ];
+
+    fn cells(&self) -> Vec<Cell> {
+        vec![
+            Cell::text(self.trip_id),
+            Cell::text(self.fiskeridir_vessel_id),
+            Cell::timestamp(self.start),
+            Cell::timestamp(self.end),
+            Cell::timestamp(self.landing_coverage_start),
+            Cell::timestamp(self.landing_coverage_end),
+            Cell::text(self.trip_assembler_id),
+            Cell::opt_text(self.start_port_id.as_ref()),
+            Cell::opt_text(self.end_port_id.as_ref()),
+            Cell::list(&self.gear_ids),
+            Cell::list(self.delivery_point_ids.iter().map(AsRef::<str>::as_ref)),
+            Cell::list(&self.landing_ids),
+            Cell::number(self.num_deliveries),
+            Cell::opt_timestamp(self.most_recent_delivery_date),
+            Cell::number(self.hauls.len() as f64),
+            Cell::number(self.delivery.total_living_weight),
+            Cell::number(self.delivery.total_product_weight),
+            Cell::number(self.delivery.total_gross_weight),
+            Cell::number(self.delivery.total_price_for_fisher),
+            Cell::Bool(self.delivery.price_for_fisher_is_estimated),
+            Cell::opt_number(self.target_species_fiskeridir_id),
+            Cell::opt_text(self.target_species_fao_id.as_ref()),
+            Cell::opt_number(self.fuel_consumption),
+            Cell::opt_number(self.fuel_consumption_estimated_only),
+            Cell::opt_number(self.percentage_of_trip_covered_by_measurements),
+            Cell::opt_number(self.distance),
+            Cell::number(self.track_coverage),
+        ]
+    }
+}
+
+impl Tabular for Haul {
+    const NAME: &'static str = "hauls";
+    const HEADERS: &'static [&'static str] = &[
+        "id",
+        "tripId",
+        "fiskeridirVesselId",
+        "callSign",
+        "vesselName",
+        "startTimestamp",
+        "stopTimestamp",
+        "startLatitude",
+        "startLongitude",
+        "stopLatitude",
+        "stopLongitude",
+        "haulDistance",
+        "gear",
+        "gearGroupId",
+        "catchLocations",
+        "totalLivingWeight",
+        "catches",
+        "estimatedValue",
+        "waterTemperature",
+    ];
+
+    fn cells(&self) -> Vec<Cell> {
+        vec![
+            Cell::text(self.id),
+            Cell::opt_text(self.trip_id),
+            Cell::opt_text(self.fiskeridir_vessel_id),
+            Cell::text(&self.call_sign),
+            Cell::opt_text(self.vessel_name.as_ref()),
+            Cell::timestamp(self.start_timestamp),
+            Cell::timestamp(self.stop_timestamp),
+            Cell::number(self.start_latitude),
+            Cell::number(self.start_longitude),
+            Cell::number(self.stop_latitude),
+            Cell::number(self.stop_longitude),
+            Cell::opt_number(self.haul_distance),
+            Cell::text(self.gear),
+            Cell::text(self.gear_group_id),
+            Cell::list(self.catch_locations.iter().flatten()),
+            Cell::number(self.catches.iter().map(|c| c.living_weight).sum::<i32>()),
+            Cell::list(
+                self.catches
+                    .iter()
+                    .map(|c| format!("{}={}", c.species_fiskeridir_id, c.living_weight)),
+            ),
+            Cell::opt_number(self.estimated_value),
+            Cell::opt_number(self.water_temperature),
+        ]
+    }
+}
+
+impl Tabular for Landing {
+    const NAME: &'static str = "landings";
+    const HEADERS: &'static [&'static str] = &[
+        "id",
+        "tripId",
+        "landingTimestamp",
+        "fiskeridirVesselId",
+        "vesselCallSign",
+        "vesselName",
+        "vesselLength",
+        "vesselLengthGroup",
+        "gearId",
+        "gearGroupId",
+        "catchLocation",
+        "deliveryPointId",
+        "totalLivingWeight",
+        "totalProductWeight",
+        "totalGrossWeight",
+        "catches",
+    ];
+
+    fn cells(&self) -> Vec<Cell> {
+        vec![
+            Cell::text(&self.id),
+            Cell::opt_text(self.trip_id),
+            Cell::timestamp(self.landing_timestamp),
+            Cell::opt_text(self.fiskeridir_vessel_id),
+            Cell::opt_text(self.vessel_call_sign.as_ref()),
+            Cell::opt_text(self.vessel_name.as_ref()),
+            Cell::opt_number(self.vessel_length),
+            Cell::text(self.vessel_length_group),
+            Cell::text(self.gear_id),
+            Cell::text(self.gear_group_id),
+            Cell::opt_text(self.catch_location.as_ref()),
+            Cell::opt_text(self.delivery_point_id.as_ref().map(AsRef::<str>::as_ref)),
+            Cell::number(self.total_living_weight),
+            Cell::number(self.total_product_weight),
+            Cell::number(self.total_gross_weight),
+            Cell::list(
+                self.catches
+                    .iter()
+                    .map(|c| format!("{}={}", c.species_fiskeridir_id, c.living_weight)),
+            ),
+        ]
+    }
+}
+
+impl Tabular for FuelMeasurement {
+    const NAME: &'static str = "fuel_measurements";
+    const HEADERS: &'static [&'static str] = &["id", "timestamp", "fuel", "fuelAfter"];
+
+    fn cells(&self) -> Vec<Cell> {
+        vec![
+            Cell::text(i64::from(self.id)),
+            Cell::timestamp(self.timestamp),
+            Cell::number(self.fuel_liter),
+            Cell::opt_number(self.fuel_after_liter),
+        ]
+    }
+}
+
+impl Tabular for FuelEntry {
+    const NAME: &'static str = "fuel";
+    const HEADERS: &'static [&'static str] = &["fiskeridirVesselId", "estimatedFuel"];
+
+    fn cells(&self) -> Vec<Cell> {
+        vec![
+            Cell::text(self.fiskeridir_vessel_id),
+            Cell::number(self.estimated_fuel_liter),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Row;
+
+    impl Tabular for Row {
+        const NAME: &'static str = "rows";
+        const HEADERS: &'static [&'static str] = &["value"];
+
+        fn cells(&self) -> Vec<Cell> {
+            vec![Cell::number(1)]
+        }
+    }
+
+    fn rows(amount: usize) -> RowStream<Row> {
+        Box::pin(stream::iter((0..amount).map(|_| Ok(Row))))
+    }
+
+    #[tokio::test]
+    async fn test_buffered_xlsx_writes_up_to_max_rows() {
+        let bytes: Vec<_> = buffered_xlsx(rows(3), 3).collect().await;
+
+        assert_eq!(bytes.len(), 1);
+        assert!(bytes[0].is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_buffered_xlsx_fails_with_more_than_max_rows() {
+        let bytes: Vec<_> = buffered_xlsx(rows(4), 3).collect().await;
+
+        assert_eq!(bytes.len(), 1);
+        assert!(bytes[0].is_err());
+    }
+}
@@ -0,0 +1,311 @@
+use actix_web::{HttpRequest, HttpResponse, Responder, body::BoxBody, web::Bytes};
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures::{Stream, StreamExt, stream};
+use kyogre_core::WebApiResult;
+use oasgen::{OaSchema, Schema};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::error;
+
+use super::{NegotiatedFormat, StreamResponse, attachment};
+use crate::routes::v1::{ais::AisPosition, ais_vms::AisVmsPosition, vms::VmsPosition};
+
+pub const GEOJSON_MIME: &str = "application/geo+json";
+pub const GPX_MIME: &str = "application/gpx+xml";
+
+const KNOTS_TO_METERS_PER_SECOND: f64 = 1852. / 3600.;
+
+/// The format of a `TrackResponse`, negotiated through the `Accept` header of the request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TrackFormat {
+    #[default]
+    Json,
+    GeoJson,
+    Gpx,
+}
+
+impl NegotiatedFormat for TrackFormat {
+    fn from_mime(essence: &str) -> Option<Self> {
+        match essence {
+            "application/json" => Some(Self::Json),
+            GEOJSON_MIME => Some(Self::GeoJson),
+            GPX_MIME => Some(Self::Gpx),
+            _ => None,
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::GeoJson => "geojson",
+            Self::Gpx => "gpx",
+        }
+    }
+}
+
+/// A position that can be exported as part of a GeoJSON or GPX track.
+/// The GeoJSON properties of each point are the JSON representation of the position without its
+/// coordinates, where the fields of the nested `det` object are lifted to the top level.
+pub trait TrackPoint: Serialize {
+    fn lat(&self) -> f64;
+    fn lon(&self) -> f64;
+    fn timestamp(&self) -> DateTime<Utc>;
+    /// Speed over ground in knots.
+    fn speed(&self) -> Option<f64>;
+    /// Course over ground in degrees.
+    fn course(&self) -> Option<f64>;
+}
+
+/// A streamed track that is serialized as a JSON array by default, or as a GeoJSON
+/// `FeatureCollection` or a GPX track if requested through the `Accept` header.
+pub struct TrackResponse<T> {
+    stream: StreamResponse<T>,
+    name: String,
+}
+
+impl<T> TrackResponse<T> {
+    /// `name` is used as the file name of GeoJSON and GPX downloads.
+    pub fn new(stream: StreamResponse<T>, name: impl Into<String>) -> Self {
+        Self {
+            stream,
+            name: name.into(),
+        }
+    }
+}
+
+impl<T> Responder for TrackResponse<T>
+where
+    T: TrackPoint + 'static,
+{
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
+        match TrackFormat::negotiate(req) {
+            TrackFormat::Json => self.stream.respond_to(req),
+            TrackFormat::GeoJson => attachment(&self.name, TrackFormat::GeoJson)
+                .content_type(GEOJSON_MIME)
+                .streaming(geojson_stream(ReceiverStream::new(self.stream.rx))),
+            TrackFormat::Gpx => attachment(&self.name, TrackFormat::Gpx)
+                .content_type(GPX_MIME)
+                .streaming(gpx_stream(ReceiverStream::new(self.stream.rx))),
+        }
+    }
+}
+
+impl<T: OaSchema + 'static> OaSchema for TrackResponse<T> {
+    fn schema_ref() -> oasgen::ReferenceOr<Schema> {
+        StreamResponse::<T>::schema_ref()
+    }
+
+    fn schema() -> Schema {
+        StreamResponse::<T>::schema()
+    }
+}
+
+fn geojson_point<T: TrackPoint>(point: &T) -> serde_json::Result<Bytes> {
+    let mut properties = match serde_json::to_value(point)? {
+        Value::Object(v) => v,
+        _ => Map::new(),
+    };
+    properties.remove("lat");
+    properties.remove("lon");
+    if let Some(Value::Object(details)) = properties.remove("det") {
+        properties.extend(details);
+    }
+
+    let feature = json!({
+        "type": "Feature",
+        "geometry": {
+            "type": "Point",
+            "coordinates": [point.lon(), point.lat()],
+        },
+        "properties": properties,
+    });
+
+    Ok(Bytes::from(serde_json::to_vec(&feature)?))
+}
+
+fn geojson_line(coordinates: Vec<[f64; 2]>) -> serde_json::Result<Bytes> {
+    let feature = json!({
+        "type": "Feature",
+        "geometry": {
+            "type": "LineString",
+            "coordinates": coordinates,
+        },
+        "properties": {},
+    });
+
+    Ok(Bytes::from(serde_json::to_vec(&feature)?))
+}
+
+/// Streams a `FeatureCollection` with one `Point` feature per position, followed by a single
+/// `LineString` feature of the whole track.
+/// Only the coordinates are kept in memory to be able to write the `LineString` once all positions
+/// are received.
+fn geojson_stream<T: TrackPoint + 'static>(
+    points: impl Stream<Item = WebApiResult<T>> + Unpin + 'static,
+) -> impl Stream<Item = Result<Bytes, String>> {
+    struct State<S> {
+        points: S,
+        coordinates: Vec<[f64; 2]>,
+        done: bool,
+    }
+
+    let state = State {
+        points,
+        coordinates: Vec::new(),
+        done: false,
+    };
+
+    let features = stream::unfold(state, |mut state| async move {
+        if state.done {
+            return None;
+        }
+
+        loop {
+            match state.points.next().await {
+                Some(Ok(v)) => match geojson_point(&v) {
+                    Ok(feature) => {
+                        let is_first = state.coordinates.is_empty();
+                        state.coordinates.push([v.lon(), v.lat()]);
+                        let bytes = if is_first {
+                            feature
+                        } else {
+                            Bytes::from([b",".as_slice(), &feature].concat())
+                        };
+                        return Some((bytes, state));
+                    }
+                    Err(e) => {
+                        error!(error = true, "failed to serialize streaming item: {e:?}");
+                    }
+                },
+                Some(Err(e)) => {
+                    error!(error = true, "failed to retrieve streaming item: {e:?}");
+                }
+                None => {
+                    state.done = true;
+                    // A `LineString` must consist of at least two positions
+                    if state.coordinates.len() < 2 {
+                        return None;
+                    }
+                    let coordinates = std::mem::take(&mut state.coordinates);
+                    return match geojson_line(coordinates) {
+                        Ok(line) => Some((Bytes::from([b",".as_slice(), &line].concat()), state)),
+                        Err(e) => {
+                            error!(error = true, "failed to serialize track: {e:?}");
+                            None
+                        }
+                    };
+                }
+            }
+        }
+    });
+
+    stream::once(async { Bytes::from_static(br#"{"type":"FeatureCollection","features":["#) })
+        .chain(features)
+        .chain(stream::once(async { Bytes::from_static(b"]}") }))
+        .map(Ok)
+}
+
+fn gpx_point<T: TrackPoint>(point: &T) -> Bytes {
+    let mut trkpt = format!(
+        r#"<trkpt lat="{}" lon="{}"><time>{}</time>"#,
+        point.lat(),
+        point.lon(),
+        point
+            .timestamp()
+            .to_rfc3339_opts(SecondsFormat::Millis, true),
+    );
+    if let Some(course) = point.course() {
+        trkpt.push_str(&format!("<course>{course}</course>"));
+    }
+    if let Some(speed) = point.speed() {
+        trkpt.push_str(&format!(
+            "<speed>{}</speed>",
+            speed * KNOTS_TO_METERS_PER_SECOND
+        ));
+    }
+    trkpt.push_str("</trkpt>");
+
+    Bytes::from(trkpt)
+}
+
+/// Streams a GPX 1.0 document with a single track segment, GPX 1.0 is used over GPX 1.1 as it
+/// supports `course` and `speed` (in m/s) on track points without extensions.
+fn gpx_stream<T: TrackPoint + 'static>(
+    points: impl Stream<Item = WebApiResult<T>> + 'static,
+) -> impl Stream<Item = Result<Bytes, String>> {
+    let header = Bytes::from_static(
+        br#"<?xml version="1.0" encoding="UTF-8"?><gpx version="1.0" creator="Kyogre" xmlns="http://www.topografix.com/GPX/1/0"><trk><trkseg>"#,
+    );
+
+    stream::once(async { header })
+        .chain(points.filter_map(|v| async move {
+            match v {
+                Ok(v) => Some(gpx_point(&v)),
+                Err(e) => {
+                    error!(error = true, "failed to retrieve streaming item: {e:?}");
+                    None
+                }
+            }
+        }))
+        .chain(stream::once(async {
+            Bytes::from_static(b"</trkseg></trk></gpx>")
+        }))
+        .map(Ok)
+}
+
+impl TrackPoint for AisPosition {
+    fn lat(&self) -> f64 {
+        self.lat
+    }
+    fn lon(&self) -> f64 {
+        self.lon
+    }
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+    fn speed(&self) -> Option<f64> {
+        self.det.as_ref().and_then(|d| d.speed_over_ground)
+    }
+    fn course(&self) -> Option<f64> {
+        self.cog
+    }
+}
+
+impl TrackPoint for VmsPosition {
+    fn lat(&self) -> f64 {
+        self.lat
+    }
+    fn lon(&self) -> f64 {
+        self.lon
+    }
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+    fn speed(&self) -> Option<f64> {
+        self.speed
+    }
+    fn course(&self) -> Option<f64> {
+        self.course.map(f64::from)
+    }
+}
+
+impl TrackPoint for AisVmsPosition {
+    fn lat(&self) -> f64 {
+        self.lat
+    }
+    fn lon(&self) -> f64 {
+        self.lon
+    }
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+    fn speed(&self) -> Option<f64> {
+        self.speed
+    }
+    fn course(&self) -> Option<f64> {
+        self.cog
+    }
+}
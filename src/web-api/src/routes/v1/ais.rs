@@ -2,7 +2,7 @@ use crate::{
     Database,
     error::Result,
     extractors::UserAuth,
-    response::{StreamResponse, ais_unfold},
+    response::{StreamResponse, TrackResponse, ais_unfold},
     stream_response,
 };
 use actix_web::web::{self, Path};
@@ -29,6 +29,7 @@ pub struct AisTrackPath {
 /// Returns the AIS track for the given vessel matching the given filter if any.
 /// If no time filter is provided the track of the last 24 hours are returned.
 /// AIS data for vessels under 15m are restricted to authenticated users with sufficient permissions.
+/// Supports GeoJSON and GPX output through the `Accept` header.
 #[oasgen(skip(db), tags("Ais"))]
 #[tracing::instrument(skip(db), fields(user_id = user.tracing_id()))]
 pub async fn ais_track<T: Database + Send + Sync + 'static>(
@@ -36,13 +37,17 @@ pub async fn ais_track<T: Database + Send + Sync + 'static>(
     params: Query<AisTrackParameters>,
     path: Path<AisTrackPath>,
     user: UserAuth,
-) -> Result<StreamResponse<AisPosition>> {
-    Ok(stream_response! {
+) -> Result<TrackResponse<AisPosition>> {
+    let name = format!("ais_{}", path.mmsi);
+
+    let response = stream_response! {
         ais_unfold(
             db.ais_positions(path.mmsi, &(params.into_inner().range.into()), user.ais_permission())
                 .map_ok(AisPosition::from),
         )
-    })
+    };
+
+    Ok(TrackResponse::new(response, name))
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, OaSchema)]
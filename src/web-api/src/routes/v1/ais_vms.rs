@@ -2,7 +2,7 @@ use crate::{
     Database,
     error::{Result, error::MissingMmsiOrCallSignOrTripIdSnafu},
     extractors::UserAuth,
-    response::{StreamResponse, ais_unfold},
+    response::{StreamResponse, TrackResponse, ais_unfold},
     stream_response,
 };
 use actix_web::web;
@@ -56,34 +56,38 @@ pub async fn current_positions<T: Database + Send + Sync + 'static>(
 /// Returns the combined AIS/VMS track for the given vessel matching the given filter if any.
 /// If no time filter is provided the track of the last 24 hours are returned.
 /// AIS data for vessels under 15m are restricted to authenticated users with sufficient permissions.
+/// Supports GeoJSON and GPX output through the `Accept` header.
 #[oasgen(skip(db), tags("AisVms"))]
 #[tracing::instrument(skip(db), fields(user_id = user.tracing_id()))]
 pub async fn ais_vms_positions<T: Database + Send + Sync + 'static>(
     db: web::Data<T>,
     params: Query<AisVmsParameters>,
     user: UserAuth,
-) -> Result<StreamResponse<AisVmsPosition>> {
+) -> Result<TrackResponse<AisVmsPosition>> {
     let params = params.into_inner();
     if params.mmsi.is_none() && params.call_sign.is_none() && params.trip_id.is_none() {
         return MissingMmsiOrCallSignOrTripIdSnafu.fail();
     }
 
-    let params = if let Some(trip_id) = params.trip_id {
-        AisVmsParams::Trip(trip_id)
+    let (params, name) = if let Some(trip_id) = params.trip_id {
+        (AisVmsParams::Trip(trip_id), format!("trip_{trip_id}"))
     } else {
-        AisVmsParams::Range {
+        let params = AisVmsParams::Range {
             range: params.range.into(),
             mmsi: params.mmsi,
             call_sign: params.call_sign,
-        }
+        };
+        (params, "ais_vms".into())
     };
 
-    Ok(stream_response! {
+    let response = stream_response! {
         ais_unfold(
             db.ais_vms_positions(params, user.ais_permission())
                 .map_ok(AisVmsPosition::from),
         )
-    })
+    };
+
+    Ok(TrackResponse::new(response, name))
 }
 
 #[serde_as]
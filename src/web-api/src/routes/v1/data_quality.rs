@@ -0,0 +1,42 @@
+use actix_web::web;
+use kyogre_core::{DataQualityQuery, FileDataQuality};
+use oasgen::{OaSchema, oasgen};
+use serde::{Deserialize, Serialize};
+use serde_qs::actix::QsQuery as Query;
+
+use crate::{
+    Database, error::Result, extractors::AdminBwProfile, response::StreamResponse, stream_response,
+};
+
+#[derive(Default, Debug, Clone, Deserialize, Serialize, OaSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DataQualityParams {
+    pub datasets: Option<Vec<String>>,
+    pub years: Option<Vec<u32>>,
+}
+
+/// Returns the number of rows and fields per Fiskeridir dataset and year that were coerced,
+/// defaulted or rejected during the last ingestion of the file.
+#[oasgen(skip(db), tags("DataQuality"))]
+#[tracing::instrument(skip(db), fields(user_id = profile.tracing_id()))]
+pub async fn data_quality<T: Database + Send + Sync + 'static>(
+    db: web::Data<T>,
+    profile: AdminBwProfile,
+    params: Query<DataQualityParams>,
+) -> Result<StreamResponse<FileDataQuality>> {
+    let query = params.into_inner().into();
+
+    let response = stream_response! {
+        db.data_quality(query)
+    };
+
+    Ok(response)
+}
+
+impl From<DataQualityParams> for DataQualityQuery {
+    fn from(value: DataQualityParams) -> Self {
+        let DataQualityParams { datasets, years } = value;
+
+        Self { datasets, years }
+    }
+}
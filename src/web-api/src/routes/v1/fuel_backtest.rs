@@ -0,0 +1,47 @@
+use actix_web::web;
+use fiskeridir_rs::FiskeridirVesselId;
+use kyogre_core::{FuelBacktestMetrics, FuelBacktestQuery};
+use oasgen::{OaSchema, oasgen};
+use serde::{Deserialize, Serialize};
+use serde_qs::actix::QsQuery as Query;
+
+use crate::{
+    Database, error::Result, extractors::AdminBwProfile, response::StreamResponse, stream_response,
+};
+
+#[derive(Default, Debug, Clone, Deserialize, Serialize, OaSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FuelBacktestParams {
+    pub fiskeridir_vessel_id: Option<FiskeridirVesselId>,
+}
+
+/// Returns the error of each fuel estimation model compared to the fuel measurements reported by
+/// vessels. Metrics across all vessels are returned without a `fiskeridirVesselId`, if a vessel is
+/// given only the metrics of that vessel are returned.
+#[oasgen(skip(db), tags("FuelMeasurement"))]
+#[tracing::instrument(skip(db), fields(user_id = profile.tracing_id()))]
+pub async fn fuel_backtest<T: Database + Send + Sync + 'static>(
+    db: web::Data<T>,
+    profile: AdminBwProfile,
+    params: Query<FuelBacktestParams>,
+) -> Result<StreamResponse<FuelBacktestMetrics>> {
+    let query = params.into_inner().into();
+
+    let response = stream_response! {
+        db.fuel_backtest(query)
+    };
+
+    Ok(response)
+}
+
+impl From<FuelBacktestParams> for FuelBacktestQuery {
+    fn from(value: FuelBacktestParams) -> Self {
+        let FuelBacktestParams {
+            fiskeridir_vessel_id,
+        } = value;
+
+        Self {
+            fiskeridir_vessel_id,
+        }
+    }
+}
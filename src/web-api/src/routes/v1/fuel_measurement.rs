@@ -15,7 +15,7 @@ use crate::{
     error::{Result, error::FuelAfterLowerThanFuelSnafu},
     excel::decode_excel_base64,
     extractors::BwProfile,
-    response::{Response, StreamResponse},
+    response::{Response, StreamResponse, TabularResponse},
     stream_response,
 };
 
@@ -33,13 +33,14 @@ pub struct UploadFuelMeasurement {
     pub file: String,
 }
 
+/// Supports CSV and XLSX exports through the `Accept` header.
 #[oasgen(skip(db), tags("FuelMeasurement"))]
 #[tracing::instrument(skip(db), fields(user_id = profile.tracing_id()))]
 pub async fn get_fuel_measurements<T: Database + Send + Sync + 'static>(
     db: web::Data<T>,
     profile: BwProfile,
     params: Query<FuelMeasurementsParams>,
-) -> Result<StreamResponse<FuelMeasurement>> {
+) -> Result<TabularResponse<FuelMeasurement>> {
     let call_sign = profile.call_sign(db.as_ref()).await?;
     let query = params.into_inner().to_query(call_sign.clone());
 
@@ -47,7 +48,7 @@ pub async fn get_fuel_measurements<T: Database + Send + Sync + 'static>(
         db.fuel_measurements(query)
     };
 
-    Ok(response)
+    Ok(response.into())
 }
 
 #[oasgen(skip(db), tags("FuelMeasurement"))]
@@ -0,0 +1,241 @@
+use actix_web::web::{self, Path};
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use geo::{LineString, Polygon};
+use kyogre_core::{
+    AisPermission, CatchLocationId, FiskeridirVesselId, GeofenceArea, GeofenceAreaType,
+    GeofenceEvent, GeofenceEventsQuery, GeofenceId, NewGeofence, OptionalDateTimeRange,
+};
+use oasgen::{OaSchema, oasgen};
+use serde::{Deserialize, Serialize};
+use serde_qs::actix::QsQuery as Query;
+use uuid::Uuid;
+use wkt::ToWkt;
+
+use crate::{
+    Database,
+    error::{
+        Result,
+        error::{
+            InsufficientPermissionsSnafu, InvalidGeofenceAreaSnafu, InvalidGeofenceCoordinateSnafu,
+            InvalidGeofenceNameSnafu, InvalidGeofencePolygonSnafu,
+        },
+    },
+    extractors::{BwPolicy, BwProfile},
+    response::{Response, StreamResponse},
+    stream_response,
+};
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, OaSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Geofence {
+    pub id: GeofenceId,
+    pub name: String,
+    pub area_type: GeofenceAreaType,
+    pub catch_location_id: Option<CatchLocationId>,
+    pub fishing_facility_tool_id: Option<Uuid>,
+    pub geometry_wkt: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A geofence is either a polygon drawn by the user, an existing catch location or the area
+/// around a fishing facility, exactly one of them must be provided.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, OaSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateGeofence {
+    pub name: String,
+    /// The corners of the polygon, the polygon is closed automatically if the last point differs
+    /// from the first.
+    pub polygon: Option<Vec<GeofencePoint>>,
+    pub catch_location_id: Option<CatchLocationId>,
+    pub fishing_facility_tool_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, OaSchema)]
+pub struct GeofencePoint {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+#[derive(Default, Debug, Clone, Deserialize, Serialize, OaSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GeofenceEventsParams {
+    pub geofence_id: Option<GeofenceId>,
+    pub fiskeridir_vessel_id: Option<FiskeridirVesselId>,
+    #[serde(flatten)]
+    pub range: OptionalDateTimeRange,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+/// Returns all geofences registered by the user.
+#[oasgen(skip(db), tags("Geofence"))]
+#[tracing::instrument(skip(db), fields(user_id = profile.tracing_id()))]
+pub async fn geofences<T: Database + Send + Sync + 'static>(
+    db: web::Data<T>,
+    profile: BwProfile,
+) -> Result<StreamResponse<Geofence>> {
+    let user_id = profile.user.id;
+
+    let response = stream_response! {
+        db.geofences(user_id).map_ok(Geofence::from)
+    };
+
+    Ok(response)
+}
+
+/// Registers a new geofence, enter and exit events are recorded for all vessels the user follows.
+/// Geofences based on fishing facilities require access to fishing facilities.
+#[oasgen(skip(db), tags("Geofence"))]
+#[tracing::instrument(skip(db), fields(user_id = profile.tracing_id()))]
+pub async fn create_geofence<T: Database + 'static>(
+    db: web::Data<T>,
+    profile: BwProfile,
+    body: web::Json<CreateGeofence>,
+) -> Result<Response<Geofence>> {
+    let geofence = NewGeofence::try_from(body.into_inner())?;
+
+    if matches!(geofence.area, GeofenceArea::FishingFacility(_))
+        && !profile
+            .policies
+            .contains(&BwPolicy::BwReadExtendedFishingFacility)
+    {
+        return InsufficientPermissionsSnafu.fail();
+    }
+
+    let geofence = db.add_geofence(&geofence, profile.user.id).await?;
+
+    Ok(Response::new(geofence.into()))
+}
+
+/// Deletes the geofence and all of its recorded events.
+#[oasgen(skip(db), tags("Geofence"))]
+#[tracing::instrument(skip(db), fields(user_id = profile.tracing_id()))]
+pub async fn delete_geofence<T: Database + 'static>(
+    db: web::Data<T>,
+    profile: BwProfile,
+    path: Path<GeofenceId>,
+) -> Result<Response<()>> {
+    db.delete_geofence(path.into_inner(), profile.user.id)
+        .await?;
+    Ok(Response::new(()))
+}
+
+/// Returns the enter and exit events of the user's geofences, the most recent events first.
+#[oasgen(skip(db), tags("Geofence"))]
+#[tracing::instrument(skip(db), fields(user_id = profile.tracing_id()))]
+pub async fn geofence_events<T: Database + Send + Sync + 'static>(
+    db: web::Data<T>,
+    profile: BwProfile,
+    params: Query<GeofenceEventsParams>,
+) -> Result<StreamResponse<GeofenceEvent>> {
+    let permission = AisPermission::from(&profile);
+    let query = params.into_inner().to_query(&profile);
+
+    let response = stream_response! {
+        db.geofence_events(query, permission)
+    };
+
+    Ok(response)
+}
+
+impl GeofenceEventsParams {
+    pub fn to_query(self, profile: &BwProfile) -> GeofenceEventsQuery {
+        let Self {
+            geofence_id,
+            fiskeridir_vessel_id,
+            range,
+            limit,
+            offset,
+        } = self;
+
+        GeofenceEventsQuery {
+            user_id: profile.user.id,
+            geofence_id,
+            fiskeridir_vessel_id,
+            range,
+            limit,
+            offset,
+        }
+    }
+}
+
+impl TryFrom<CreateGeofence> for NewGeofence {
+    type Error = crate::error::Error;
+
+    fn try_from(v: CreateGeofence) -> Result<Self> {
+        let CreateGeofence {
+            name,
+            polygon,
+            catch_location_id,
+            fishing_facility_tool_id,
+        } = v;
+
+        if name.trim().is_empty() {
+            return InvalidGeofenceNameSnafu.fail();
+        }
+
+        let area = match (polygon, catch_location_id, fishing_facility_tool_id) {
+            (Some(points), None, None) => GeofenceArea::Polygon(polygon_from_points(points)?),
+            (None, Some(id), None) => GeofenceArea::CatchLocation(id),
+            (None, None, Some(id)) => GeofenceArea::FishingFacility(id),
+            _ => return InvalidGeofenceAreaSnafu.fail(),
+        };
+
+        Ok(Self { name, area })
+    }
+}
+
+fn polygon_from_points(mut points: Vec<GeofencePoint>) -> Result<Polygon<f64>> {
+    if points.first().is_some_and(|f| Some(f) == points.last()) {
+        points.pop();
+    }
+
+    let num_points = points.len();
+    if num_points < 3 {
+        return InvalidGeofencePolygonSnafu { num_points }.fail();
+    }
+
+    if let Some(p) = points
+        .iter()
+        .find(|p| !(-90. ..=90.).contains(&p.latitude) || !(-180. ..=180.).contains(&p.longitude))
+    {
+        return InvalidGeofenceCoordinateSnafu {
+            latitude: p.latitude,
+            longitude: p.longitude,
+        }
+        .fail();
+    }
+
+    let exterior = points
+        .into_iter()
+        .map(|p| (p.longitude, p.latitude))
+        .collect::<LineString<f64>>();
+
+    // `Polygon::new` closes the exterior ring
+    Ok(Polygon::new(exterior, vec![]))
+}
+
+impl From<kyogre_core::Geofence> for Geofence {
+    fn from(v: kyogre_core::Geofence) -> Self {
+        let kyogre_core::Geofence {
+            id,
+            name,
+            area_type,
+            catch_location_id,
+            fishing_facility_tool_id,
+            geometry,
+            created_at,
+        } = v;
+
+        Self {
+            id,
+            name,
+            area_type,
+            catch_location_id,
+            fishing_facility_tool_id,
+            geometry_wkt: geometry.wkt_string(),
+            created_at,
+        }
+    }
+}
@@ -15,7 +15,7 @@ use tracing::error;
 use crate::{
     Cache, Database,
     error::Result,
-    response::{Response, ResponseOrStream, StreamResponse},
+    response::{Response, StreamResponse, TabularResponse},
     routes::utils::*,
     stream_response,
 };
@@ -57,12 +57,13 @@ pub struct HaulsMatrixParams {
 }
 
 /// Returns all hauls matching the provided parameters.
+/// Supports CSV and XLSX exports through the `Accept` header.
 #[oasgen(skip(db), tags("Haul"))]
 #[tracing::instrument(skip(db))]
 pub async fn hauls<T: Database + Send + Sync + 'static>(
     db: web::Data<T>,
     params: Query<HaulsParams>,
-) -> Result<ResponseOrStream<Haul>> {
+) -> Result<TabularResponse<Haul>> {
     let query: HaulsQuery = params.into_inner().into();
 
     let response = stream_response! {
@@ -125,6 +126,11 @@ pub struct Haul {
     pub fiskeridir_vessel_id: Option<FiskeridirVesselId>,
     pub vessel_name: Option<String>,
     pub call_sign: CallSign,
+    /// Estimated value of the catches based on the Råfisklaget weekly sale prices of the most
+    /// recent week with sales at or before the end of the haul.
+    pub estimated_value: Option<f64>,
+    /// Sea temperature at the haul in degrees celsius.
+    pub water_temperature: Option<f64>,
 }
 
 #[serde_as]
@@ -224,6 +230,8 @@ impl From<kyogre_core::Haul> for Haul {
             catches,
             vessel_name,
             call_sign,
+            estimated_value,
+            water_temperature,
         } = v;
 
         Haul {
@@ -243,6 +251,8 @@ impl From<kyogre_core::Haul> for Haul {
             call_sign,
             stop_latitude,
             stop_longitude,
+            estimated_value,
+            water_temperature,
         }
     }
 }
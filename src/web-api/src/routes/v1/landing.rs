@@ -17,7 +17,7 @@ use tracing::error;
 use crate::{
     Cache, Database,
     error::Result,
-    response::{Response, ResponseOrStream, StreamResponse},
+    response::{Response, StreamResponse, TabularResponse},
     routes::utils::*,
     stream_response,
 };
@@ -59,12 +59,13 @@ pub struct LandingMatrixParams {
 }
 
 /// Returns all landings matching the provided parameters.
+/// Supports CSV and XLSX exports through the `Accept` header.
 #[oasgen(skip(db), tags("Landing"))]
 #[tracing::instrument(skip(db))]
 pub async fn landings<T: Database + Send + Sync + 'static>(
     db: web::Data<T>,
     params: Query<LandingsParams>,
-) -> Result<ResponseOrStream<Landing>> {
+) -> Result<TabularResponse<Landing>> {
     let query: LandingsQuery = params.into_inner().into();
 
     let response = stream_response! {
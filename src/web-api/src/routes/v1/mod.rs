@@ -1,14 +1,19 @@
 pub mod ais;
 pub mod ais_vms;
+pub mod data_quality;
 pub mod delivery_point;
 pub mod fishing_facility;
+pub mod fuel_backtest;
 pub mod fuel_measurement;
 pub mod gear;
+pub mod geofence;
 pub mod haul;
 pub mod landing;
+pub mod ocean_climate;
 pub mod org;
 pub mod price;
 pub mod species;
+pub mod time_series;
 pub mod trip;
 pub mod user;
 pub mod user_haul;
@@ -0,0 +1,76 @@
+use actix_web::web;
+use chrono::{DateTime, Duration, Utc};
+use kyogre_core::{
+    CatchLocationId, CatchLocationOceanClimate, CatchLocationOceanClimateQuery,
+    MAX_OCEAN_CLIMATE_RANGE_DAYS,
+};
+use oasgen::{OaSchema, oasgen};
+use serde::{Deserialize, Serialize};
+use serde_qs::actix::QsQuery as Query;
+
+use crate::{
+    error::{
+        Result,
+        error::{DateRangeTooLongSnafu, StartAfterEndSnafu},
+    },
+    response::StreamResponse,
+    stream_response, *,
+};
+
+#[derive(Default, Debug, Deserialize, Serialize, OaSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OceanClimateParams {
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub depths: Option<Vec<i32>>,
+    pub catch_location_ids: Option<Vec<CatchLocationId>>,
+}
+
+/// Returns the ocean climate of each catch location and depth averaged over the given time
+/// range, defaults to the last day. The time range cannot exceed 31 days.
+#[oasgen(skip(db), tags("OceanClimate"))]
+#[tracing::instrument(skip(db))]
+pub async fn ocean_climate<T: Database + Send + Sync + 'static>(
+    db: web::Data<T>,
+    params: Query<OceanClimateParams>,
+) -> Result<StreamResponse<CatchLocationOceanClimate>> {
+    let query: CatchLocationOceanClimateQuery = params.into_inner().into();
+
+    if query.start > query.end {
+        return StartAfterEndSnafu {
+            start: query.start,
+            end: query.end,
+        }
+        .fail();
+    }
+    if query.end - query.start > Duration::days(MAX_OCEAN_CLIMATE_RANGE_DAYS) {
+        return DateRangeTooLongSnafu {
+            start: query.start,
+            end: query.end,
+            max_days: MAX_OCEAN_CLIMATE_RANGE_DAYS,
+        }
+        .fail();
+    }
+
+    Ok(stream_response! {
+        db.ocean_climate(query)
+    })
+}
+
+impl From<OceanClimateParams> for CatchLocationOceanClimateQuery {
+    fn from(v: OceanClimateParams) -> Self {
+        let OceanClimateParams {
+            start,
+            end,
+            depths,
+            catch_location_ids,
+        } = v;
+
+        Self {
+            start: start.unwrap_or_else(|| Utc::now() - Duration::days(1)),
+            end: end.unwrap_or_else(Utc::now),
+            depths,
+            catch_location_ids,
+        }
+    }
+}
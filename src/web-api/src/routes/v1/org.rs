@@ -1,10 +1,16 @@
-use super::vessel::FuelParams;
+use super::vessel::{EmissionReportParams, FuelParams};
 use crate::error::error::ObjectNotFoundSnafu;
-use crate::{Database, error::Result, extractors::BwProfile, response::Response};
+use crate::{
+    Database,
+    error::Result,
+    extractors::BwProfile,
+    response::{Response, TabularResponse},
+};
 use actix_web::web::{self, Path};
 use fiskeridir_rs::{CallSign, OrgId};
 use kyogre_core::{
-    DateTimeRangeWithDefaultTimeSpan, FuelEntry, Object, OrgBenchmarkQuery, OrgBenchmarks,
+    DateTimeRangeWithDefaultTimeSpan, EmissionReport, FuelEntry, Object, OrgBenchmarkQuery,
+    OrgBenchmarks,
 };
 use oasgen::{OaSchema, oasgen};
 use serde::{Deserialize, Serialize};
@@ -24,6 +30,10 @@ pub struct OrgBenchmarkPath {
 pub struct OrgFuelPath {
     pub org_id: OrgId,
 }
+#[derive(Debug, Clone, OaSchema, Deserialize)]
+pub struct OrgEmissionsPath {
+    pub org_id: OrgId,
+}
 
 /// Returns organization benchmarks for the given organization id (Breg org id).
 /// This will include benchmarks for all vessels associated with the organization.
@@ -51,6 +61,7 @@ pub async fn benchmarks<T: Database + 'static>(
 /// given org, if no date range is given the last 30 days
 /// are returned.
 /// This is not based on trips and is the full fuel consumption estimate for the given date range.
+/// Supports CSV and XLSX exports through the `Accept` header.
 #[oasgen(skip(db), tags("Org"))]
 #[tracing::instrument(skip(db), fields(user_id = profile.tracing_id()))]
 pub async fn fuel<T: Database + Send + Sync + 'static>(
@@ -58,12 +69,37 @@ pub async fn fuel<T: Database + Send + Sync + 'static>(
     profile: BwProfile,
     params: Query<FuelParams>,
     path: Path<OrgFuelPath>,
-) -> Result<Response<Vec<FuelEntry>>> {
+) -> Result<TabularResponse<FuelEntry>> {
     let call_sign = profile.call_sign(db.as_ref()).await?;
     let query = params.into_inner().to_query(call_sign);
 
     let org_id = path.into_inner().org_id;
     match db.fuel_estimation_by_org(&query, org_id).await? {
+        Some(b) => Ok(Response::new(b).into()),
+        None => ObjectNotFoundSnafu {
+            object: Object::Org(org_id),
+        }
+        .fail(),
+    }
+}
+
+/// Returns the CO2, NOx and SOx emissions of all vessels associated with the given org for the
+/// given year, if no year is given the current year is returned.
+/// Measured and estimated emissions are reported separately both per vessel and for the org as a
+/// whole.
+#[oasgen(skip(db), tags("Org"))]
+#[tracing::instrument(skip(db), fields(user_id = profile.tracing_id()))]
+pub async fn emissions<T: Database + Send + Sync + 'static>(
+    db: web::Data<T>,
+    profile: BwProfile,
+    params: Query<EmissionReportParams>,
+    path: Path<OrgEmissionsPath>,
+) -> Result<Response<EmissionReport>> {
+    let call_sign = profile.call_sign(db.as_ref()).await?;
+    let query = params.into_inner().to_query(call_sign);
+
+    let org_id = path.into_inner().org_id;
+    match db.emission_report_by_org(&query, org_id).await? {
         Some(b) => Ok(Response::new(b)),
         None => ObjectNotFoundSnafu {
             object: Object::Org(org_id),
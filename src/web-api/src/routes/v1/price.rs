@@ -1,5 +1,5 @@
 use actix_web::web;
-use kyogre_core::PriceQuery;
+use kyogre_core::{PriceQuery, PriceSeries, PriceSeriesQuery};
 use oasgen::oasgen;
 use serde_qs::web::QsQuery as Query;
 
@@ -14,3 +14,16 @@ pub async fn price<T: Database + 'static>(
     let price = db.price(params.into_inner()).await?;
     Ok(Response::new(price))
 }
+
+/// Returns the weekly prices of the given price group for the most recent weeks with sales,
+/// followed by a forecast of the upcoming weeks with a 95% prediction interval.
+/// The forecast is empty if the price group has too few weeks with sales.
+#[oasgen(skip(db), tags("Price"))]
+#[tracing::instrument(skip(db))]
+pub async fn price_series<T: Database + 'static>(
+    db: web::Data<T>,
+    params: Query<PriceSeriesQuery>,
+) -> Result<Response<PriceSeries>> {
+    let series = db.price_series(params.into_inner()).await?;
+    Ok(Response::new(series))
+}
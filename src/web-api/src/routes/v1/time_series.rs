@@ -0,0 +1,208 @@
+use actix_web::web;
+use chrono::{DateTime, Utc};
+use fiskeridir_rs::{GearGroup, SpeciesGroup, VesselLengthGroup};
+use kyogre_core::{
+    ActiveHaulsFilter, ActiveLandingFilter, CatchLocationId, FiskeridirVesselId,
+    HaulsTimeSeriesQuery, LandingTimeSeriesQuery, TimeBucket, TimeSeriesGroup,
+};
+use oasgen::{OaSchema, oasgen};
+use serde::{Deserialize, Serialize};
+use serde_qs::actix::QsQuery as Query;
+use serde_with::{DisplayFromStr, serde_as};
+use snafu::OptionExt;
+
+use super::{
+    haul::{self, HaulsMatrixParams},
+    landing::{self, LandingMatrixParams},
+};
+use crate::{
+    Cache,
+    error::{Result, error::MatrixCacheUnavailableSnafu},
+    response::Response,
+};
+
+/// Accepts the same filters as the hauls matrix.
+#[serde_as]
+#[derive(Default, Debug, Clone, Deserialize, Serialize, OaSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HaulsTimeSeriesParams {
+    pub months: Option<Vec<u32>>,
+    pub catch_locations: Option<Vec<CatchLocationId>>,
+    #[serde_as(as = "Option<Vec<DisplayFromStr>>")]
+    pub gear_group_ids: Option<Vec<GearGroup>>,
+    #[serde_as(as = "Option<Vec<DisplayFromStr>>")]
+    pub species_group_ids: Option<Vec<SpeciesGroup>>,
+    #[serde_as(as = "Option<Vec<DisplayFromStr>>")]
+    pub vessel_length_groups: Option<Vec<VesselLengthGroup>>,
+    pub fiskeridir_vessel_ids: Option<Vec<FiskeridirVesselId>>,
+    pub bycatch_percentage: Option<f64>,
+    pub majority_species_group: Option<bool>,
+    /// Defaults to `month`
+    pub bucket: Option<TimeBucket>,
+    pub group_by: Option<Vec<TimeSeriesGroup>>,
+}
+
+/// Accepts the same filters as the landing matrix.
+#[serde_as]
+#[derive(Default, Debug, Clone, Deserialize, Serialize, OaSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LandingTimeSeriesParams {
+    pub months: Option<Vec<u32>>,
+    pub catch_locations: Option<Vec<CatchLocationId>>,
+    #[serde_as(as = "Option<Vec<DisplayFromStr>>")]
+    pub gear_group_ids: Option<Vec<GearGroup>>,
+    #[serde_as(as = "Option<Vec<DisplayFromStr>>")]
+    pub species_group_ids: Option<Vec<SpeciesGroup>>,
+    #[serde_as(as = "Option<Vec<DisplayFromStr>>")]
+    pub vessel_length_groups: Option<Vec<VesselLengthGroup>>,
+    pub fiskeridir_vessel_ids: Option<Vec<FiskeridirVesselId>>,
+    /// Defaults to `month`
+    pub bucket: Option<TimeBucket>,
+    pub group_by: Option<Vec<TimeSeriesGroup>>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, Serialize, OaSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeSeriesPoint {
+    pub bucket: DateTime<Utc>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub gear_group_id: Option<GearGroup>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub species_group_id: Option<SpeciesGroup>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub vessel_length_group: Option<VesselLengthGroup>,
+    pub catch_location_id: Option<CatchLocationId>,
+    pub fiskeridir_vessel_id: Option<FiskeridirVesselId>,
+    pub living_weight: f64,
+}
+
+/// Returns the summed haul living weights per time bucket, optionally grouped by additional
+/// dimensions. Time series are only served from the matrix cache.
+#[oasgen(skip(cache), tags("Haul"))]
+#[tracing::instrument(skip(cache))]
+pub async fn hauls_time_series<S: Cache>(
+    cache: web::Data<Option<S>>,
+    params: Query<HaulsTimeSeriesParams>,
+) -> Result<Response<Vec<TimeSeriesPoint>>> {
+    let cache = cache.as_ref().context(MatrixCacheUnavailableSnafu)?;
+
+    let series = cache
+        .hauls_time_series(&params.into_inner().into())
+        .await?
+        .into_iter()
+        .map(TimeSeriesPoint::from)
+        .collect();
+
+    Ok(Response::new(series))
+}
+
+/// Returns the summed landing living weights per time bucket, optionally grouped by additional
+/// dimensions. Time series are only served from the matrix cache.
+#[oasgen(skip(cache), tags("Landing"))]
+#[tracing::instrument(skip(cache))]
+pub async fn landing_time_series<S: Cache>(
+    cache: web::Data<Option<S>>,
+    params: Query<LandingTimeSeriesParams>,
+) -> Result<Response<Vec<TimeSeriesPoint>>> {
+    let cache = cache.as_ref().context(MatrixCacheUnavailableSnafu)?;
+
+    let series = cache
+        .landing_time_series(&params.into_inner().into())
+        .await?
+        .into_iter()
+        .map(TimeSeriesPoint::from)
+        .collect();
+
+    Ok(Response::new(series))
+}
+
+impl From<HaulsTimeSeriesParams> for HaulsTimeSeriesQuery {
+    fn from(value: HaulsTimeSeriesParams) -> Self {
+        let HaulsTimeSeriesParams {
+            months,
+            catch_locations,
+            gear_group_ids,
+            species_group_ids,
+            vessel_length_groups,
+            fiskeridir_vessel_ids,
+            bycatch_percentage,
+            majority_species_group,
+            bucket,
+            group_by,
+        } = value;
+
+        let params = HaulsMatrixParams {
+            months,
+            catch_locations,
+            gear_group_ids,
+            species_group_ids,
+            vessel_length_groups,
+            fiskeridir_vessel_ids,
+            bycatch_percentage,
+            majority_species_group,
+        };
+
+        Self {
+            // The active filter is ignored for time series
+            filter: haul::matrix_params_to_query(params, ActiveHaulsFilter::Date),
+            bucket: bucket.unwrap_or_default(),
+            group_by: group_by.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<LandingTimeSeriesParams> for LandingTimeSeriesQuery {
+    fn from(value: LandingTimeSeriesParams) -> Self {
+        let LandingTimeSeriesParams {
+            months,
+            catch_locations,
+            gear_group_ids,
+            species_group_ids,
+            vessel_length_groups,
+            fiskeridir_vessel_ids,
+            bucket,
+            group_by,
+        } = value;
+
+        let params = LandingMatrixParams {
+            months,
+            catch_locations,
+            gear_group_ids,
+            species_group_ids,
+            vessel_length_groups,
+            fiskeridir_vessel_ids,
+        };
+
+        Self {
+            // The active filter is ignored for time series
+            filter: landing::matrix_params_to_query(params, ActiveLandingFilter::Date),
+            bucket: bucket.unwrap_or_default(),
+            group_by: group_by.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<kyogre_core::TimeSeriesPoint> for TimeSeriesPoint {
+    fn from(value: kyogre_core::TimeSeriesPoint) -> Self {
+        let kyogre_core::TimeSeriesPoint {
+            bucket,
+            gear_group_id,
+            species_group_id,
+            vessel_length_group,
+            catch_location_id,
+            fiskeridir_vessel_id,
+            living_weight,
+        } = value;
+
+        Self {
+            bucket,
+            gear_group_id,
+            species_group_id,
+            vessel_length_group,
+            catch_location_id,
+            fiskeridir_vessel_id,
+            living_weight,
+        }
+    }
+}
@@ -6,7 +6,8 @@ use fiskeridir_rs::{CallSign, GearGroup, VesselLengthGroup};
 use kyogre_core::{
     AverageEeoiQuery, AverageFuiQuery, AverageTripBenchmarks, AverageTripBenchmarksQuery,
     DateTimeRange, EeoiQuery, FiskeridirVesselId, FuiQuery, Mean, OptionalDateTimeRange, Ordering,
-    TripBenchmarksQuery, TripId, TripWithBenchmark,
+    PEER_BENCHMARKS_DEFAULT_PEERS, PEER_BENCHMARKS_MAX_PEERS, PEER_BENCHMARKS_MIN_PEERS,
+    PeerBenchmarks, PeerBenchmarksQuery, TripBenchmarksQuery, TripId, TripWithBenchmark,
 };
 use oasgen::{OaSchema, oasgen};
 use serde::{Deserialize, Serialize};
@@ -36,6 +37,15 @@ pub struct FuiParams {
     pub range: OptionalDateTimeRange,
 }
 
+#[derive(Default, Debug, Deserialize, Serialize, OaSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerBenchmarksParams {
+    #[serde(flatten)]
+    pub range: OptionalDateTimeRange,
+    /// Defaults to 10 and is clamped between 5 and 100.
+    pub num_peers: Option<u32>,
+}
+
 #[serde_as]
 #[derive(Default, Debug, Deserialize, Serialize, OaSchema)]
 #[serde(rename_all = "camelCase")]
@@ -106,6 +116,24 @@ pub async fn benchmarks<T: Database>(
     Ok(Response::new(benchmarks))
 }
 
+/// Compares the trip benchmarks of the vessel associated with the authenticated user against its
+/// most similar vessels for the given period.
+/// Peers are selected by length group, gear group, fishery and engine power, and only their
+/// aggregated benchmarks are returned.
+#[oasgen(skip(db), tags("Trip"))]
+#[tracing::instrument(skip(db), fields(user_id = profile.tracing_id()))]
+pub async fn peers<T: Database>(
+    db: web::Data<T>,
+    profile: BwProfile,
+    params: Query<PeerBenchmarksParams>,
+) -> Result<Response<PeerBenchmarks>> {
+    let call_sign = profile.call_sign(db.as_ref()).await?;
+    let query = params.into_inner().into_query(call_sign.clone());
+
+    let benchmarks = db.peer_benchmarks(query).await?;
+    Ok(Response::new(benchmarks))
+}
+
 /// Returns the FUI of the logged in user for the given period.
 #[oasgen(skip(db), tags("Trip"))]
 #[tracing::instrument(skip(db), fields(user_id = profile.tracing_id()))]
@@ -249,6 +277,20 @@ impl TripBenchmarksParams {
     }
 }
 
+impl PeerBenchmarksParams {
+    fn into_query(self, call_sign: CallSign) -> PeerBenchmarksQuery {
+        let Self { range, num_peers } = self;
+
+        PeerBenchmarksQuery {
+            call_sign,
+            range,
+            num_peers: num_peers
+                .unwrap_or(PEER_BENCHMARKS_DEFAULT_PEERS)
+                .clamp(PEER_BENCHMARKS_MIN_PEERS, PEER_BENCHMARKS_MAX_PEERS),
+        }
+    }
+}
+
 impl FuiParams {
     fn into_query(self, call_sign: CallSign) -> FuiQuery {
         let Self { range } = self;
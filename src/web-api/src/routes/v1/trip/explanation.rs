@@ -0,0 +1,221 @@
+use super::VesselEvent;
+use crate::{
+    Database,
+    error::{Result, error::ObjectNotFoundSnafu},
+    extractors::AdminBwProfile,
+    response::Response,
+};
+use actix_web::web::{self, Path};
+use chrono::{DateTime, Utc};
+use kyogre_core::{
+    FiskeridirVesselId, Object, PrecisionAttempt, PrecisionBoundary, PrecisionDirection,
+    PrecisionId, ProcessingStatus, PrunedTripPosition, TripAssemblerId, TripId,
+    TripPositionLayerId, TripsConflictStrategy,
+};
+use oasgen::{OaSchema, oasgen};
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
+
+#[derive(Debug, Deserialize, OaSchema)]
+pub struct TripExplanationPath {
+    pub trip_id: TripId,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, OaSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TripExplanation {
+    pub trip_id: TripId,
+    pub fiskeridir_vessel_id: FiskeridirVesselId,
+    #[serde_as(as = "DisplayFromStr")]
+    pub trip_assembler_id: TripAssemblerId,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub period_extended_start: DateTime<Utc>,
+    pub period_extended_end: DateTime<Utc>,
+    pub period_precision_start: Option<DateTime<Utc>>,
+    pub period_precision_end: Option<DateTime<Utc>>,
+    pub landing_coverage_start: DateTime<Utc>,
+    pub landing_coverage_end: DateTime<Utc>,
+    /// The conflict strategy the trip assembler used when the trip was created, not set for trips
+    /// created before we started recording it.
+    pub conflict_strategy: Option<TripConflictStrategy>,
+    pub start_vessel_event_id: Option<u64>,
+    pub end_vessel_event_id: Option<u64>,
+    pub events: Vec<VesselEvent>,
+    pub precision_status: ProcessingStatus,
+    pub start_precision: Option<TripPrecision>,
+    pub end_precision: Option<TripPrecision>,
+    /// The outcome of each precision implementation run on the trip, in the order they were run.
+    /// Implementations after the first one to succeed for the start or end of the trip are not
+    /// run.
+    pub precision_attempts: Vec<TripPrecisionAttempt>,
+    pub position_layers_status: ProcessingStatus,
+    pub track_coverage: Option<f64>,
+    pub pruned_positions: Vec<TripPrunedPositions>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, OaSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TripConflictStrategy {
+    pub strategy: String,
+    /// The conflict for `Replace` and the start of the replaced range for `ReplaceRange`.
+    pub start: Option<DateTime<Utc>>,
+    /// The end of the replaced range for `ReplaceRange`.
+    pub end: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, OaSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TripPrecisionAttempt {
+    pub precision_id: PrecisionId,
+    pub boundary: PrecisionBoundary,
+    /// The new start or end of the trip found by the implementation, not set if it did not find
+    /// anything.
+    pub timestamp: Option<DateTime<Utc>>,
+    pub direction: Option<PrecisionDirection>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, OaSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TripPrecision {
+    pub precision_id: PrecisionId,
+    pub direction: PrecisionDirection,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, OaSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TripPrunedPositions {
+    pub trip_position_layer_id: TripPositionLayerId,
+    /// The positions removed by the layer.
+    pub positions: serde_json::Value,
+    /// Layer specific details on why the positions were removed.
+    pub value: serde_json::Value,
+}
+
+/// Returns how the given trip was assembled, which vessel events it is based on, the outcome of
+/// the trip precision implementations and which positions were removed by each position layer.
+/// Intended for investigating trips that look wrong and requires the admin role.
+#[oasgen(skip(db), tags("Trip"))]
+#[tracing::instrument(skip(db), fields(user_id = profile.tracing_id()))]
+pub async fn explanation<T: Database + 'static>(
+    db: web::Data<T>,
+    profile: AdminBwProfile,
+    path: Path<TripExplanationPath>,
+) -> Result<Response<TripExplanation>> {
+    match db.trip_explanation(path.trip_id).await? {
+        Some(e) => Ok(Response::new(e.into())),
+        None => ObjectNotFoundSnafu {
+            object: Object::Trip(path.trip_id),
+        }
+        .fail(),
+    }
+}
+
+impl From<kyogre_core::TripExplanation> for TripExplanation {
+    fn from(value: kyogre_core::TripExplanation) -> Self {
+        let kyogre_core::TripExplanation {
+            trip_id,
+            vessel_id,
+            assembler_id,
+            period,
+            period_extended,
+            period_precision,
+            landing_coverage,
+            conflict_strategy,
+            start_vessel_event_id,
+            end_vessel_event_id,
+            events,
+            precision_status,
+            start_precision,
+            end_precision,
+            precision_attempts,
+            position_layers_status,
+            track_coverage,
+            pruned_positions,
+        } = value;
+
+        Self {
+            trip_id,
+            fiskeridir_vessel_id: vessel_id,
+            trip_assembler_id: assembler_id,
+            start: period.start(),
+            end: period.end(),
+            period_extended_start: period_extended.start(),
+            period_extended_end: period_extended.end(),
+            period_precision_start: period_precision.as_ref().map(|p| p.start()),
+            period_precision_end: period_precision.as_ref().map(|p| p.end()),
+            landing_coverage_start: landing_coverage.start(),
+            landing_coverage_end: landing_coverage.end(),
+            conflict_strategy: conflict_strategy.map(TripConflictStrategy::from),
+            start_vessel_event_id,
+            end_vessel_event_id,
+            events: events.into_iter().map(VesselEvent::from).collect(),
+            precision_status,
+            start_precision: start_precision.map(|p| TripPrecision {
+                precision_id: p.id,
+                direction: p.direction,
+            }),
+            end_precision: end_precision.map(|p| TripPrecision {
+                precision_id: p.id,
+                direction: p.direction,
+            }),
+            precision_attempts: precision_attempts
+                .into_iter()
+                .map(TripPrecisionAttempt::from)
+                .collect(),
+            position_layers_status,
+            track_coverage,
+            pruned_positions: pruned_positions
+                .into_iter()
+                .map(TripPrunedPositions::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<TripsConflictStrategy> for TripConflictStrategy {
+    fn from(value: TripsConflictStrategy) -> Self {
+        let (start, end) = value.timestamps();
+
+        Self {
+            strategy: value.to_string(),
+            start,
+            end,
+        }
+    }
+}
+
+impl From<PrecisionAttempt> for TripPrecisionAttempt {
+    fn from(value: PrecisionAttempt) -> Self {
+        let PrecisionAttempt {
+            id,
+            boundary,
+            timestamp,
+            direction,
+        } = value;
+
+        Self {
+            precision_id: id,
+            boundary,
+            timestamp,
+            direction,
+        }
+    }
+}
+
+impl From<PrunedTripPosition> for TripPrunedPositions {
+    fn from(value: PrunedTripPosition) -> Self {
+        let PrunedTripPosition {
+            positions,
+            value,
+            trip_layer,
+        } = value;
+
+        Self {
+            trip_position_layer_id: trip_layer,
+            positions,
+            value,
+        }
+    }
+}
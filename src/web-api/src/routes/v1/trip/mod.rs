@@ -2,7 +2,7 @@ use super::fishing_facility::FishingFacility;
 use crate::{
     error::Result,
     extractors::OptionBwProfile,
-    response::{Response, ResponseOrStream, StreamResponse, ais_unfold},
+    response::{Response, StreamResponse, TabularResponse, TrackResponse, ais_unfold},
     routes::v1::{ais_vms::AisVmsPosition, haul::HaulCatch},
     stream_response, *,
 };
@@ -14,8 +14,8 @@ use fiskeridir_rs::{
 };
 use futures::TryStreamExt;
 use kyogre_core::{
-    CatchLocationId, FiskeridirVesselId, HasTrack, HaulId, OptionalDateTimeRange, Ordering,
-    Pagination, Tra, TripAssemblerId, TripId, TripSorting, Trips, TripsQuery, UserHaul,
+    AisVmsParams, CatchLocationId, FiskeridirVesselId, HasTrack, HaulId, OptionalDateTimeRange,
+    Ordering, Pagination, Tra, TripAssemblerId, TripId, TripSorting, Trips, TripsQuery, UserHaul,
     VesselEventType,
 };
 use oasgen::{OaSchema, oasgen};
@@ -24,6 +24,7 @@ use serde_qs::actix::QsQuery as Query;
 use serde_with::{DisplayFromStr, serde_as};
 
 pub mod benchmarks;
+pub mod explanation;
 
 #[serde_as]
 #[derive(Default, Debug, Deserialize, Serialize, OaSchema)]
@@ -63,16 +64,22 @@ pub struct CurrentTripPositionsPath {
     pub fiskeridir_vessel_id: FiskeridirVesselId,
 }
 
+#[derive(Debug, Deserialize, OaSchema)]
+pub struct TripTrackPath {
+    pub trip_id: TripId,
+}
+
 /// Returns all trips matching the provided parameters.
 /// All vessels below 15m have significantly reduced trip data quality as they do not report
 /// ERS POR and DEP messages.
+/// Supports CSV and XLSX exports through the `Accept` header.
 #[oasgen(skip(db, meilisearch), tags("Trip"))]
 #[tracing::instrument(skip(db), fields(user_id = profile.tracing_id()))]
 pub async fn trips<T: Database + Send + Sync + 'static>(
     db: web::Data<T>,
     profile: OptionBwProfile,
     params: Query<TripsParameters>,
-) -> Result<ResponseOrStream<Trip>> {
+) -> Result<TabularResponse<Trip>> {
     let read_fishing_facility = profile.read_fishing_facilities();
 
     let query = TripsQuery::from(params.into_inner());
@@ -111,19 +118,46 @@ pub async fn current_trip<T: Database + 'static>(
 
 /// Returns the current trip of the given vessel, which is determined by the vessel's last reported DEP message.
 /// All vessels below 15m will not have a current trip as they do not report DEP messages.
+/// Supports GeoJSON and GPX output through the `Accept` header.
 #[oasgen(skip(db), tags("Trip"))]
 #[tracing::instrument(skip(db), fields(user_id = user.tracing_id()))]
 pub async fn current_trip_positions<T: Database + Send + Sync + 'static>(
     db: web::Data<T>,
     path: Path<CurrentTripPositionsPath>,
     user: UserAuth,
-) -> Result<StreamResponse<AisVmsPosition>> {
-    Ok(stream_response! {
+) -> Result<TrackResponse<AisVmsPosition>> {
+    let name = format!("current_trip_{}", path.fiskeridir_vessel_id);
+
+    let response = stream_response! {
         ais_unfold(
             db.current_trip_positions(path.fiskeridir_vessel_id, user.ais_permission())
                 .map_ok(AisVmsPosition::from),
         )
-    })
+    };
+
+    Ok(TrackResponse::new(response, name))
+}
+
+/// Returns the combined AIS/VMS track of the given trip.
+/// AIS data for vessels under 15m are restricted to authenticated users with sufficient permissions.
+/// Supports GeoJSON and GPX output through the `Accept` header.
+#[oasgen(skip(db), tags("Trip"))]
+#[tracing::instrument(skip(db), fields(user_id = user.tracing_id()))]
+pub async fn track<T: Database + Send + Sync + 'static>(
+    db: web::Data<T>,
+    path: Path<TripTrackPath>,
+    user: UserAuth,
+) -> Result<TrackResponse<AisVmsPosition>> {
+    let trip_id = path.into_inner().trip_id;
+
+    let response = stream_response! {
+        ais_unfold(
+            db.ais_vms_positions(AisVmsParams::Trip(trip_id), user.ais_permission())
+                .map_ok(AisVmsPosition::from),
+        )
+    };
+
+    Ok(TrackResponse::new(response, format!("trip_{trip_id}")))
 }
 
 #[serde_as]
@@ -193,6 +227,8 @@ pub struct CurrentTrip {
     pub target_species_fiskeridir_id: Option<i32>,
     pub hauls: Vec<TripsDetailedHaul>,
     pub fishing_facilities: Vec<FishingFacility>,
+    /// Sum of the estimated value of the trip's hauls, based on Råfisklaget weekly sale prices.
+    pub estimated_value: Option<f64>,
 }
 
 #[serde_as]
@@ -272,6 +308,9 @@ pub struct TripsDetailedHaul {
     pub config: Option<serde_json::Value>,
     pub start_fuel_liter: Option<u32>,
     pub end_fuel_liter: Option<u32>,
+    /// Estimated value of the catches based on Råfisklaget weekly sale prices, only set for the
+    /// hauls of the current trip.
+    pub estimated_value: Option<f64>,
 }
 
 impl TripsDetailedHaul {
@@ -377,6 +416,7 @@ impl From<kyogre_core::TripsDetailedHaul> for TripsDetailedHaul {
             config,
             start_fuel_liter,
             end_fuel_liter,
+            estimated_value,
         } = v;
 
         Self {
@@ -401,6 +441,7 @@ impl From<kyogre_core::TripsDetailedHaul> for TripsDetailedHaul {
             config,
             start_fuel_liter,
             end_fuel_liter,
+            estimated_value,
         }
     }
 }
@@ -457,6 +498,7 @@ impl From<kyogre_core::CurrentTrip> for CurrentTrip {
             target_species_fiskeridir_id,
             hauls,
             fishing_facilities,
+            estimated_value,
         } = v;
 
         Self {
@@ -467,6 +509,7 @@ impl From<kyogre_core::CurrentTrip> for CurrentTrip {
                 .into_iter()
                 .map(FishingFacility::from)
                 .collect(),
+            estimated_value,
         }
     }
 }
@@ -573,6 +616,9 @@ impl PartialEq<kyogre_core::Haul> for TripsDetailedHaul {
             catches,
             vessel_name,
             call_sign,
+            // Only set for the hauls of the current trip
+            estimated_value: _,
+            water_temperature: _,
         } = other;
 
         // If we are comparing these two, we assume that id is set
@@ -608,6 +654,7 @@ impl PartialEq<TripsDetailedHaul> for UserHaul {
             total_living_weight_kg: _,
             config,
             gear,
+            estimated_value: _,
         } = self;
 
         other.start_fuel_liter.unwrap() == *start_fuel_liter
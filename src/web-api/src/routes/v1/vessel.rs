@@ -8,13 +8,14 @@ use crate::{
     stream_response,
 };
 use actix_web::web::{self, Path};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Utc};
 use fiskeridir_rs::{CallSign, GearGroup, RegisterVesselOwner, SpeciesGroup, VesselLengthGroup};
 use futures::TryStreamExt;
 use kyogre_core::{
-    DEFAULT_LIVE_FUEL_THRESHOLD, EngineType, FisheryId, FiskeridirVesselId, FuelQuery,
-    LiveFuelQuery, Mmsi, NaiveDateRange, Object, Ordering, Pagination, VesselCurrentTrip,
-    VesselEventQuery, VesselEventType, VesselEvents,
+    DEFAULT_LIVE_FUEL_THRESHOLD, EmissionReport, EmissionReportQuery, EngineType, FisheryId,
+    FiskeridirVesselId, FuelEstimationModel, FuelQuery, FuelType, LiveFuelQuery, Mmsi,
+    NaiveDateRange, Object, Ordering, Pagination, VesselCurrentTrip, VesselEventQuery,
+    VesselEventType, VesselEvents,
 };
 use kyogre_core::{LiveFuel, UpdateVessel};
 use oasgen::{OaSchema, oasgen};
@@ -31,6 +32,13 @@ pub struct FuelParams {
     pub range: NaiveDateRange<30>,
 }
 
+#[derive(Default, Debug, Clone, Deserialize, Serialize, OaSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EmissionReportParams {
+    /// Defaults to the current year.
+    pub year: Option<i32>,
+}
+
 #[derive(Default, Debug, Clone, Deserialize, Serialize, OaSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct LiveFuelParams {
@@ -141,6 +149,23 @@ pub async fn fuel<T: Database + Send + Sync + 'static>(
     Ok(Response::new(db.fuel_estimation(&query).await?))
 }
 
+/// Returns the CO2, NOx and SOx emissions of the vessel associated with the authenticated user for
+/// the given year, if no year is given the current year is returned.
+/// Emissions are computed from the fuel measurements reported by the vessel where available and
+/// from our fuel estimates otherwise, using the emission factors of the vessel's fuel type.
+#[oasgen(skip(db), tags("Vessel"))]
+#[tracing::instrument(skip(db), fields(user_id = profile.tracing_id()))]
+pub async fn emissions<T: Database + Send + Sync + 'static>(
+    db: web::Data<T>,
+    profile: BwProfile,
+    params: Query<EmissionReportParams>,
+) -> Result<Response<EmissionReport>> {
+    let call_sign = profile.call_sign(db.as_ref()).await?;
+    let query = params.into_inner().to_query(call_sign);
+
+    Ok(Response::new(db.emission_report(&query).await?))
+}
+
 #[oasgen(skip(db), tags("Vessel"))]
 #[tracing::instrument(skip(db), fields(user_id = profile.tracing_id()))]
 pub async fn live_fuel<T: Database + Send + Sync + 'static>(
@@ -206,6 +231,10 @@ pub struct FiskeridirVessel {
     pub engine_rpm: Option<u32>,
     pub degree_of_electrification: Option<f64>,
     pub service_speed: Option<f64>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub fuel_type: Option<FuelType>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub fuel_estimation_model: Option<FuelEstimationModel>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, OaSchema, PartialEq)]
@@ -226,6 +255,16 @@ impl LiveFuelParams {
     }
 }
 
+impl EmissionReportParams {
+    pub fn to_query(self, call_sign: CallSign) -> EmissionReportQuery {
+        let Self { year } = self;
+        EmissionReportQuery {
+            call_sign,
+            year: year.unwrap_or_else(|| Utc::now().year()),
+        }
+    }
+}
+
 impl FuelParams {
     pub fn to_query(self, call_sign: CallSign) -> FuelQuery {
         let Self { range } = self;
@@ -304,6 +343,8 @@ impl From<kyogre_core::FiskeridirVessel> for FiskeridirVessel {
             engine_version: _,
             degree_of_electrification,
             service_speed,
+            fuel_type,
+            fuel_estimation_model,
         } = value;
 
         FiskeridirVessel {
@@ -326,6 +367,8 @@ impl From<kyogre_core::FiskeridirVessel> for FiskeridirVessel {
             engine_rpm,
             degree_of_electrification,
             service_speed,
+            fuel_type,
+            fuel_estimation_model,
         }
     }
 }
@@ -352,6 +395,8 @@ impl PartialEq<fiskeridir_rs::Vessel> for FiskeridirVessel {
             engine_rpm: _,
             degree_of_electrification: _,
             service_speed: _,
+            fuel_type: _,
+            fuel_estimation_model: _,
         } = self;
 
         Some(*id) == other.id
@@ -401,6 +446,8 @@ impl PartialEq<kyogre_core::FiskeridirVessel> for FiskeridirVessel {
             engine_rpm,
             degree_of_electrification,
             service_speed,
+            fuel_type,
+            fuel_estimation_model,
         } = self;
 
         *id == other.id
@@ -420,6 +467,8 @@ impl PartialEq<kyogre_core::FiskeridirVessel> for FiskeridirVessel {
             && *engine_rpm == other.engine_rpm
             && *degree_of_electrification == other.degree_of_electrification
             && *service_speed == other.service_speed
+            && *fuel_type == other.fuel_type
+            && *fuel_estimation_model == other.fuel_estimation_model
     }
 }
 
@@ -454,6 +503,8 @@ impl PartialEq<UpdateVessel> for Vessel {
             engine_rpm,
             degree_of_electrification,
             service_speed,
+            fuel_type,
+            fuel_estimation_model,
         } = other;
 
         self.fiskeridir.engine_power == *engine_power
@@ -466,6 +517,8 @@ impl PartialEq<UpdateVessel> for Vessel {
             && self.fiskeridir.engine_rpm == *engine_rpm
             && self.fiskeridir.degree_of_electrification == *degree_of_electrification
             && self.fiskeridir.service_speed == *service_speed
+            && self.fiskeridir.fuel_type == *fuel_type
+            && self.fiskeridir.fuel_estimation_model == *fuel_estimation_model
     }
 }
 
@@ -1,7 +1,7 @@
 use crate::{
     Database,
     error::Result,
-    response::{StreamResponse, ais_unfold},
+    response::{StreamResponse, TrackResponse, ais_unfold},
     stream_response,
 };
 use actix_web::web::{self, Path};
@@ -27,20 +27,25 @@ pub struct VmsPath {
 
 /// Returns the VMS track for the given vessel mactching the given filter if any.
 /// If no time filter is provided the track of the last 24 hours are returned.
+/// Supports GeoJSON and GPX output through the `Accept` header.
 #[oasgen(skip(db), tags("Vms"))]
 #[tracing::instrument(skip(db))]
 pub async fn vms_positions<T: Database + Send + Sync + 'static>(
     db: web::Data<T>,
     params: Query<VmsParameters>,
     path: Path<VmsPath>,
-) -> Result<StreamResponse<VmsPosition>> {
+) -> Result<TrackResponse<VmsPosition>> {
     let range = params.into_inner().range.into();
-    Ok(stream_response! {
+    let name = format!("vms_{}", path.call_sign);
+
+    let response = stream_response! {
         ais_unfold(
             db.vms_positions(&path.call_sign, &range)
                 .map_ok(VmsPosition::from),
         )
-    })
+    };
+
+    Ok(TrackResponse::new(response, name))
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, OaSchema)]
@@ -1,7 +1,10 @@
 use actix_web::web;
 use chrono::{DateTime, Duration, Utc};
 use futures::TryStreamExt;
-use kyogre_core::{Weather, WeatherLocationId, WeatherQuery};
+use kyogre_core::{
+    CatchLocationId, MAX_WEATHER_FORECAST_HORIZON_HOURS, Weather, WeatherForecast,
+    WeatherForecastQuery, WeatherLocationId, WeatherQuery,
+};
 use oasgen::{OaSchema, oasgen};
 use serde::{Deserialize, Serialize};
 use serde_qs::actix::QsQuery as Query;
@@ -31,6 +34,29 @@ pub async fn weather<T: Database + Send + Sync + 'static>(
     }
 }
 
+#[derive(Default, Debug, Deserialize, Serialize, OaSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WeatherForecastParams {
+    pub catch_location_ids: Option<Vec<CatchLocationId>>,
+    /// The number of hours after the reference time of the forecast run to return, capped at
+    /// 72 hours.
+    pub horizon_hours: Option<u32>,
+}
+
+/// Returns the hourly forecast of the latest forecast run for each catch location.
+#[oasgen(skip(db), tags("Weather"))]
+#[tracing::instrument(skip(db))]
+pub async fn weather_forecasts<T: Database + Send + Sync + 'static>(
+    db: web::Data<T>,
+    params: Query<WeatherForecastParams>,
+) -> StreamResponse<WeatherForecast> {
+    let query = params.into_inner().into();
+
+    stream_response! {
+        db.weather_forecasts(query)
+    }
+}
+
 #[oasgen(skip(db), tags("Weather"))]
 #[tracing::instrument(skip(db))]
 pub async fn weather_locations<T: Database + Send + Sync + 'static>(
@@ -75,3 +101,19 @@ impl From<WeatherParams> for WeatherQuery {
         }
     }
 }
+
+impl From<WeatherForecastParams> for WeatherForecastQuery {
+    fn from(v: WeatherForecastParams) -> Self {
+        let WeatherForecastParams {
+            catch_location_ids,
+            horizon_hours,
+        } = v;
+
+        Self {
+            catch_location_ids,
+            horizon_hours: horizon_hours
+                .unwrap_or(MAX_WEATHER_FORECAST_HORIZON_HOURS)
+                .min(MAX_WEATHER_FORECAST_HORIZON_HOURS),
+        }
+    }
+}
@@ -20,6 +20,16 @@ pub struct Settings {
 pub struct Duckdb {
     pub ip: String,
     pub port: u16,
+    /// Additional matrix cache replicas, requests are balanced across all replicas including the
+    /// one given by `ip` and `port`.
+    #[serde(default)]
+    pub replicas: Vec<DuckdbReplica>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DuckdbReplica {
+    pub ip: String,
+    pub port: u16,
 }
 
 #[derive(Debug, Clone, Deserialize)]
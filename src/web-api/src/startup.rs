@@ -43,7 +43,9 @@ impl App {
 
         let duck_db = match &settings.duck_db_api {
             Some(duckdb) => {
-                let adapter = Client::new(&duckdb.ip, duckdb.port).await.unwrap();
+                let replicas = std::iter::once((&duckdb.ip, duckdb.port))
+                    .chain(duckdb.replicas.iter().map(|r| (&r.ip, r.port)));
+                let adapter = Client::with_replicas(replicas).await.unwrap();
                 Some(adapter)
             }
             _ => None,
@@ -128,16 +130,28 @@ where
                 "/trips/current/{fiskeridir_vessel_id}/positions",
                 get().to(routes::v1::trip::current_trip_positions::<T>),
             )
+            .route(
+                "/trips/{trip_id}/track",
+                get().to(routes::v1::trip::track::<T>),
+            )
             .route("/hauls", get().to(routes::v1::haul::hauls::<T>))
             .route(
                 "/hauls_matrix/{active_filter}",
                 get().to(routes::v1::haul::hauls_matrix::<T, S>),
             )
+            .route(
+                "/hauls_time_series",
+                get().to(routes::v1::time_series::hauls_time_series::<S>),
+            )
             .route("/landings", get().to(routes::v1::landing::landings::<T>))
             .route(
                 "/landing_matrix/{active_filter}",
                 get().to(routes::v1::landing::landing_matrix::<T, S>),
             )
+            .route(
+                "/landing_time_series",
+                get().to(routes::v1::time_series::landing_time_series::<S>),
+            )
             .route(
                 "/delivery_points",
                 get().to(routes::v1::delivery_point::delivery_points::<T>),
@@ -155,10 +169,18 @@ where
                 get().to(routes::v1::ais_vms::ais_vms_positions::<T>),
             )
             .route("/weather", get().to(routes::v1::weather::weather::<T>))
+            .route(
+                "/weather_forecasts",
+                get().to(routes::v1::weather::weather_forecasts::<T>),
+            )
             .route(
                 "/weather_locations",
                 get().to(routes::v1::weather::weather_locations::<T>),
             )
+            .route(
+                "/ocean_climate",
+                get().to(routes::v1::ocean_climate::ocean_climate::<T>),
+            )
             .route(
                 "/trip/benchmarks/average",
                 get().to(routes::v1::trip::benchmarks::average::<T>),
@@ -171,7 +193,11 @@ where
                 "/trip/benchmarks/average_eeoi",
                 get().to(routes::v1::trip::benchmarks::average_eeoi::<T>),
             )
-            .route("/price", get().to(routes::v1::price::price::<T>));
+            .route("/price", get().to(routes::v1::price::price::<T>))
+            .route(
+                "/price/series",
+                get().to(routes::v1::price::price_series::<T>),
+            );
 
         if let Some(guard) = bw_state.guard() {
             scope = scope
@@ -203,6 +229,12 @@ where
                         .guard(guard.clone())
                         .to(routes::v1::vessel::live_fuel::<T>),
                 )
+                .route(
+                    "/vessel/emissions",
+                    get()
+                        .guard(guard.clone())
+                        .to(routes::v1::vessel::emissions::<T>),
+                )
                 .route(
                     "/fuel_measurements",
                     get()
@@ -233,6 +265,48 @@ where
                         .guard(guard.clone())
                         .to(routes::v1::fuel_measurement::delete_fuel_measurements::<T>),
                 )
+                .route(
+                    "/fuel_backtest",
+                    get()
+                        .guard(guard.clone())
+                        .to(routes::v1::fuel_backtest::fuel_backtest::<T>),
+                )
+                .route(
+                    "/data_quality",
+                    get()
+                        .guard(guard.clone())
+                        .to(routes::v1::data_quality::data_quality::<T>),
+                )
+                .route(
+                    "/trips/{trip_id}/explanation",
+                    get()
+                        .guard(guard.clone())
+                        .to(routes::v1::trip::explanation::explanation::<T>),
+                )
+                .route(
+                    "/geofences",
+                    get()
+                        .guard(guard.clone())
+                        .to(routes::v1::geofence::geofences::<T>),
+                )
+                .route(
+                    "/geofences",
+                    post()
+                        .guard(guard.clone())
+                        .to(routes::v1::geofence::create_geofence::<T>),
+                )
+                .route(
+                    "/geofences/events",
+                    get()
+                        .guard(guard.clone())
+                        .to(routes::v1::geofence::geofence_events::<T>),
+                )
+                .route(
+                    "/geofences/{geofence_id}",
+                    delete()
+                        .guard(guard.clone())
+                        .to(routes::v1::geofence::delete_geofence::<T>),
+                )
                 .route(
                     "/vessels",
                     put()
@@ -303,6 +377,12 @@ where
                     "/org/{org_id}/fuel",
                     get().guard(guard.clone()).to(routes::v1::org::fuel::<T>),
                 )
+                .route(
+                    "/org/{org_id}/emissions",
+                    get()
+                        .guard(guard.clone())
+                        .to(routes::v1::org::emissions::<T>),
+                )
                 .route(
                     "/trip/benchmarks",
                     get()
@@ -320,6 +400,12 @@ where
                     get()
                         .guard(guard.clone())
                         .to(routes::v1::trip::benchmarks::eeoi::<T>),
+                )
+                .route(
+                    "/trip/benchmarks/peers",
+                    get()
+                        .guard(guard.clone())
+                        .to(routes::v1::trip::benchmarks::peers::<T>),
                 );
         }
 
@@ -220,3 +220,36 @@ async fn test_current_trip_updates_correctly() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn test_current_trip_includes_estimated_value_of_hauls() {
+    test(|mut helper, builder| async move {
+        let state = builder.vessels(1).dep(1).hauls(2).build().await;
+        let haul = &state.hauls[0];
+
+        helper
+            .builder()
+            .await
+            .haul_weekly_sale(haul, haul.stop_timestamp)
+            .build()
+            .await;
+
+        helper.app.login_user();
+        let trip = helper
+            .app
+            .get_current_trip(state.vessels[0].fiskeridir.id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let sum = trip
+            .hauls
+            .iter()
+            .map(|h| h.estimated_value.unwrap())
+            .sum::<f64>();
+
+        assert_eq!(trip.hauls.len(), 2);
+        assert!((trip.estimated_value.unwrap() - sum).abs() < 1e-6);
+    })
+    .await;
+}
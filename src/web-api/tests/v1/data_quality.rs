@@ -0,0 +1,108 @@
+use super::helper::test;
+use fiskeridir_rs::{DataFile, FieldQuality};
+use http_client::StatusCode;
+use kyogre_core::{NewFileDataQuality, ScraperFileHashInboundPort};
+use web_api::{error::ErrorDiscriminants, routes::v1::data_quality::DataQualityParams};
+
+fn data_quality(file: DataFile, rows: u64, rejected_rows: u64) -> NewFileDataQuality {
+    NewFileDataQuality {
+        file_id: file.id(),
+        file_hash: "hash".into(),
+        dataset: file.to_string(),
+        year: file.year(),
+        rows,
+        rejected_rows,
+        fields: FieldQuality {
+            coerced: 1,
+            defaulted: 2,
+            rejected: 3,
+        },
+    }
+}
+
+#[tokio::test]
+async fn test_data_quality_requires_admin_role() {
+    test(|mut helper, _builder| async move {
+        helper
+            .app
+            .login_user_with_policies_and_roles(vec![], vec![]);
+
+        let error = helper
+            .app
+            .data_quality(DataQualityParams::default())
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.status, StatusCode::FORBIDDEN);
+        assert_eq!(error.error, ErrorDiscriminants::InsufficientPermissions);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_data_quality_returns_latest_quality_per_file() {
+    test(|mut helper, _builder| async move {
+        let db = helper.adapter();
+        db.set_data_quality(&data_quality(DataFile::ErsDca { year: 2024 }, 10, 1))
+            .await
+            .unwrap();
+        db.set_data_quality(&data_quality(DataFile::ErsDca { year: 2024 }, 20, 2))
+            .await
+            .unwrap();
+        db.set_data_quality(&data_quality(DataFile::Landings { year: 2023 }, 5, 0))
+            .await
+            .unwrap();
+
+        helper.app.login_user();
+
+        let quality = helper
+            .app
+            .data_quality(DataQualityParams::default())
+            .await
+            .unwrap();
+
+        assert_eq!(quality.len(), 2);
+        assert_eq!(quality[0].dataset, "ers_dca");
+        assert_eq!(quality[0].year, 2024);
+        assert_eq!(quality[0].rows, 20);
+        assert_eq!(quality[0].rejected_rows, 2);
+        assert_eq!(quality[0].coerced_fields, 1);
+        assert_eq!(quality[0].defaulted_fields, 2);
+        assert_eq!(quality[0].rejected_fields, 3);
+        assert_eq!(quality[1].dataset, "landings");
+        assert_eq!(quality[1].year, 2023);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_data_quality_filters_by_dataset_and_year() {
+    test(|mut helper, _builder| async move {
+        let db = helper.adapter();
+        for file in [
+            DataFile::ErsDca { year: 2023 },
+            DataFile::ErsDca { year: 2024 },
+            DataFile::Landings { year: 2024 },
+        ] {
+            db.set_data_quality(&data_quality(file, 10, 0))
+                .await
+                .unwrap();
+        }
+
+        helper.app.login_user();
+
+        let quality = helper
+            .app
+            .data_quality(DataQualityParams {
+                datasets: Some(vec!["ers_dca".into()]),
+                years: Some(vec![2024]),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(quality.len(), 1);
+        assert_eq!(quality[0].dataset, "ers_dca");
+        assert_eq!(quality[0].year, 2024);
+    })
+    .await;
+}
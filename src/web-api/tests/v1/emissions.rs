@@ -0,0 +1,199 @@
+use super::helper::test;
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use engine::*;
+use fiskeridir_rs::OrgId;
+use float_cmp::approx_eq;
+use kyogre_core::{CreateFuelMeasurement, Emissions, FuelType, TestHelperOutbound, UpdateVessel};
+use web_api::routes::v1::vessel::EmissionReportParams;
+
+fn measurements(start: DateTime<Utc>) -> [CreateFuelMeasurement; 2] {
+    [
+        CreateFuelMeasurement {
+            timestamp: start,
+            fuel_liter: 3000.,
+            fuel_after_liter: None,
+        },
+        CreateFuelMeasurement {
+            timestamp: start + Duration::days(1),
+            fuel_liter: 1000.,
+            fuel_after_liter: None,
+        },
+    ]
+}
+
+fn start() -> DateTime<Utc> {
+    Utc.from_utc_datetime(&NaiveDateTime::new(
+        NaiveDate::from_ymd_opt(2020, 3, 12).unwrap(),
+        NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
+    ))
+}
+
+#[tokio::test]
+async fn test_emissions_converts_measured_fuel_with_diesel_factors_by_default() {
+    test(|mut helper, builder| async move {
+        builder.vessels(1).set_logged_in().build().await;
+        helper.app.login_user();
+
+        helper
+            .app
+            .create_fuel_measurements(&measurements(start()))
+            .await
+            .unwrap();
+
+        let report = helper
+            .app
+            .get_vessel_emissions(EmissionReportParams { year: Some(2020) })
+            .await
+            .unwrap();
+
+        let expected = Emissions::new(2000., FuelType::Diesel);
+
+        assert_eq!(report.year, 2020);
+        assert_eq!(report.vessels.len(), 1);
+        assert_eq!(report.vessels[0].fuel_type, FuelType::Diesel);
+        assert!(approx_eq!(f64, report.measured.fuel_liter, 2000.));
+        assert!(approx_eq!(f64, report.measured.co2_tonn, expected.co2_tonn));
+        assert!(approx_eq!(f64, report.measured.nox_kg, expected.nox_kg));
+        assert!(approx_eq!(f64, report.measured.sox_kg, expected.sox_kg));
+        assert!(approx_eq!(f64, report.estimated.fuel_liter, 0.));
+        assert_eq!(report.total, report.measured);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_emissions_uses_the_fuel_type_of_the_vessel() {
+    test(|mut helper, builder| async move {
+        builder.vessels(1).set_logged_in().build().await;
+        helper.app.login_user();
+
+        helper
+            .app
+            .update_vessel(&UpdateVessel {
+                fuel_type: Some(FuelType::Lng),
+                ..UpdateVessel::test_new()
+            })
+            .await
+            .unwrap();
+        helper
+            .app
+            .create_fuel_measurements(&measurements(start()))
+            .await
+            .unwrap();
+
+        let report = helper
+            .app
+            .get_vessel_emissions(EmissionReportParams { year: Some(2020) })
+            .await
+            .unwrap();
+
+        let expected = Emissions::new(2000., FuelType::Lng);
+
+        assert_eq!(report.vessels[0].fuel_type, FuelType::Lng);
+        assert!(approx_eq!(f64, report.measured.co2_tonn, expected.co2_tonn));
+        assert!(approx_eq!(f64, report.measured.sox_kg, 0.));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_emissions_only_includes_fuel_within_the_given_year() {
+    test(|mut helper, builder| async move {
+        builder.vessels(1).set_logged_in().build().await;
+        helper.app.login_user();
+
+        helper
+            .app
+            .create_fuel_measurements(&measurements(start()))
+            .await
+            .unwrap();
+
+        let report = helper
+            .app
+            .get_vessel_emissions(EmissionReportParams { year: Some(2021) })
+            .await
+            .unwrap();
+
+        assert_eq!(report.vessels.len(), 1);
+        assert_eq!(report.total, Emissions::default());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_emissions_reports_fuel_estimates_as_estimated() {
+    test(|mut helper, builder| async move {
+        let start = start();
+        let end = start + Duration::days(10);
+        let fuel_processor = builder.processors.estimator.clone();
+
+        builder
+            .trip_data_increment(Duration::hours(6))
+            .vessels(1)
+            .set_engine_building_year()
+            .set_logged_in()
+            .trips(1)
+            .modify(|t| {
+                t.trip_specification.set_start(start);
+                t.trip_specification.set_end(end);
+            })
+            .ais_vms_positions(40)
+            .build()
+            .await;
+
+        helper.app.login_user();
+        fuel_processor.run_single(None).await.unwrap();
+
+        let report = helper
+            .app
+            .get_vessel_emissions(EmissionReportParams { year: Some(2020) })
+            .await
+            .unwrap();
+
+        let estimate = helper
+            .adapter()
+            .sum_fuel_estimates(
+                NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2020, 12, 31).unwrap(),
+                &[],
+                None,
+            )
+            .await;
+
+        assert!(estimate > 0.0);
+        assert!(approx_eq!(f64, report.estimated.fuel_liter, estimate));
+        assert!(approx_eq!(f64, report.measured.fuel_liter, 0.));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_org_emissions_includes_all_vessels_in_org() {
+    test(|mut helper, builder| async move {
+        let org_id = OrgId::test_new(1);
+
+        builder
+            .vessels(2)
+            .set_org_id_of_owner(org_id)
+            .set_logged_in()
+            .build()
+            .await;
+        helper.app.login_user();
+
+        helper
+            .app
+            .create_fuel_measurements(&measurements(start()))
+            .await
+            .unwrap();
+
+        let report = helper
+            .app
+            .get_org_emissions(org_id, EmissionReportParams { year: Some(2020) })
+            .await
+            .unwrap();
+
+        assert_eq!(report.vessels.len(), 2);
+        assert!(approx_eq!(f64, report.measured.fuel_liter, 2000.));
+    })
+    .await;
+}
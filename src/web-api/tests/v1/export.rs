@@ -0,0 +1,163 @@
+use super::helper::test;
+use chrono::{Duration, Utc};
+use engine::*;
+use fiskeridir_rs::GearGroup;
+use kyogre_core::{CreateFuelMeasurement, Ordering};
+use serde_json::Value;
+use web_api::{
+    excel::decode_excel,
+    response::Tabular,
+    routes::v1::{
+        fuel_measurement::FuelMeasurementsParams,
+        haul::{Haul, HaulsParams},
+        landing::{Landing, LandingsParams},
+        trip::{Trip, TripsParameters},
+    },
+};
+
+#[tokio::test]
+async fn test_hauls_csv_export_has_stable_headers_and_one_row_per_haul() {
+    test(|helper, builder| async move {
+        let state = builder.hauls(3).build().await;
+
+        let csv = helper
+            .app
+            .get_csv("hauls", Some(&HaulsParams::default()))
+            .await
+            .unwrap();
+
+        let lines: Vec<_> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], Haul::HEADERS.join(","));
+        for haul in state.hauls {
+            assert!(
+                lines[1..]
+                    .iter()
+                    .any(|l| l.starts_with(&format!("{},", haul.id)))
+            );
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_landings_csv_export_applies_filters() {
+    test(|helper, builder| async move {
+        let state = builder
+            .landings(3)
+            .modify_idx(|i, v| {
+                if i == 0 {
+                    v.landing.gear.group = GearGroup::Seine;
+                }
+            })
+            .build()
+            .await;
+
+        let params = LandingsParams {
+            gear_group_ids: Some(vec![GearGroup::Seine]),
+            ordering: Some(Ordering::Asc),
+            ..Default::default()
+        };
+
+        let csv = helper.app.get_csv("landings", Some(&params)).await.unwrap();
+
+        let lines: Vec<_> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], Landing::HEADERS.join(","));
+        assert!(lines[1].starts_with(&format!("{},", state.landings[0].id)));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_trips_csv_export_returns_one_row_per_trip() {
+    test(|helper, builder| async move {
+        let state = builder.vessels(1).trips(2).build().await;
+
+        let csv = helper
+            .app
+            .get_csv("trips", Some(&TripsParameters::default()))
+            .await
+            .unwrap();
+
+        let lines: Vec<_> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], Trip::HEADERS.join(","));
+        for trip in state.trips {
+            assert!(
+                lines[1..]
+                    .iter()
+                    .any(|l| l.starts_with(&format!("{},", trip.trip_id)))
+            );
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_fuel_measurements_csv_export_returns_measurements_of_logged_in_vessel() {
+    test(|mut helper, builder| async move {
+        builder.vessels(1).set_logged_in().build().await;
+        helper.app.login_user();
+
+        let now = Utc::now();
+        let body = &[
+            CreateFuelMeasurement {
+                timestamp: now,
+                fuel_liter: 1000.,
+                fuel_after_liter: None,
+            },
+            CreateFuelMeasurement {
+                timestamp: now - Duration::days(1),
+                fuel_liter: 2000.,
+                fuel_after_liter: Some(2500.),
+            },
+        ];
+        helper.app.create_fuel_measurements(body).await.unwrap();
+
+        let csv = helper
+            .app
+            .get_csv(
+                "fuel_measurements",
+                Some(&FuelMeasurementsParams::default()),
+            )
+            .await
+            .unwrap();
+
+        let lines: Vec<_> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "id,timestamp,fuel,fuelAfter");
+        assert!(lines[1..].iter().any(|l| l.ends_with(",2000,2500")));
+        assert!(lines[1..].iter().any(|l| l.ends_with(",1000,")));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_hauls_xlsx_export_has_header_row_and_one_row_per_haul() {
+    test(|helper, builder| async move {
+        builder.hauls(2).build().await;
+
+        let xlsx = helper
+            .app
+            .get_xlsx("hauls", Some(&HaulsParams::default()))
+            .await
+            .unwrap();
+
+        let rows: Vec<Vec<Value>> = decode_excel(xlsx).unwrap();
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows[0],
+            Haul::HEADERS
+                .iter()
+                .map(|h| Value::String(h.to_string()))
+                .collect::<Vec<_>>()
+        );
+    })
+    .await;
+}
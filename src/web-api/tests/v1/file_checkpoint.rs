@@ -0,0 +1,71 @@
+use super::helper::test;
+use fiskeridir_rs::DataFile;
+use kyogre_core::{
+    FileCheckpoint, FileParseError, ScraperFileHashInboundPort, ScraperFileHashOutboundPort,
+};
+
+fn checkpoint(file_hash: &str, row_offset: u64) -> FileCheckpoint {
+    FileCheckpoint {
+        file_id: DataFile::ErsDca { year: 2024 }.id(),
+        file_hash: file_hash.into(),
+        row_offset,
+        chunk_hash: row_offset.to_string(),
+    }
+}
+
+fn parse_error(file_hash: &str, line: u64) -> FileParseError {
+    FileParseError {
+        file_id: DataFile::ErsDca { year: 2024 }.id(),
+        file_hash: file_hash.into(),
+        line,
+        error: "invalid row".into(),
+    }
+}
+
+#[tokio::test]
+async fn test_file_checkpoint_is_updated_and_removed_when_file_is_added() {
+    test(|helper, _builder| async move {
+        let db = helper.adapter();
+        let id = DataFile::ErsDca { year: 2024 }.id();
+
+        db.set_checkpoint(&checkpoint("a", 10), vec![])
+            .await
+            .unwrap();
+        db.set_checkpoint(&checkpoint("a", 20), vec![])
+            .await
+            .unwrap();
+        assert_eq!(db.checkpoint(&id).await.unwrap(), Some(checkpoint("a", 20)));
+
+        db.add(&id, "a".into()).await.unwrap();
+        assert_eq!(db.checkpoint(&id).await.unwrap(), None);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_file_parse_errors_are_only_kept_for_latest_file_version() {
+    test(|helper, _builder| async move {
+        let db = helper.adapter();
+        let id = DataFile::ErsDca { year: 2024 }.id();
+
+        db.set_checkpoint(
+            &checkpoint("a", 10),
+            vec![parse_error("a", 3), parse_error("a", 7)],
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            db.parse_errors(&id).await.unwrap(),
+            vec![parse_error("a", 3), parse_error("a", 7)]
+        );
+
+        db.set_checkpoint(&checkpoint("b", 10), vec![parse_error("b", 5)])
+            .await
+            .unwrap();
+        assert_eq!(
+            db.parse_errors(&id).await.unwrap(),
+            vec![parse_error("b", 5)]
+        );
+    })
+    .await;
+}
@@ -0,0 +1,256 @@
+use super::helper::{TestHelper, test};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use engine::*;
+use http_client::StatusCode;
+use kyogre_core::{
+    CreateFuelMeasurement, EmpiricalFuelModel, FiskeridirVesselId, FuelEstimation,
+    FuelEstimationModel, ProcessingStatus, TestHelperOutbound,
+};
+use web_api::{error::ErrorDiscriminants, routes::v1::fuel_backtest::FuelBacktestParams};
+
+/// Builds a logged in vessel with a single trip and two fuel measurements during the trip, which
+/// yields a single measured range of 1000 liters. Returns the vessel id and the start of the trip.
+async fn vessel_with_fuel_measurements(
+    helper: &mut TestHelper,
+    builder: TestStateBuilder,
+) -> (FiskeridirVesselId, DateTime<Utc>) {
+    let start = Utc.from_utc_datetime(&NaiveDateTime::new(
+        NaiveDate::from_ymd_opt(2020, 3, 12).unwrap(),
+        NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+    ));
+
+    let state = builder
+        .trip_data_increment(Duration::hours(6))
+        .vessels(1)
+        .set_engine_building_year()
+        .set_logged_in()
+        .trips(1)
+        .modify(|t| {
+            t.trip_specification.set_start(start);
+            t.trip_specification.set_end(start + Duration::days(10));
+        })
+        .ais_vms_positions(40)
+        .build()
+        .await;
+
+    helper.app.login_user();
+
+    let body = vec![
+        CreateFuelMeasurement {
+            timestamp: start + Duration::days(1),
+            fuel_liter: 2000.,
+            fuel_after_liter: None,
+        },
+        CreateFuelMeasurement {
+            timestamp: start + Duration::days(3),
+            fuel_liter: 1000.,
+            fuel_after_liter: None,
+        },
+    ];
+    helper.app.create_fuel_measurements(&body).await.unwrap();
+
+    (state.vessels[0].fiskeridir.id, start)
+}
+
+#[tokio::test]
+async fn test_fuel_backtest_requires_admin_role() {
+    test(|mut helper, _builder| async move {
+        helper
+            .app
+            .login_user_with_policies_and_roles(vec![], vec![]);
+
+        let error = helper
+            .app
+            .fuel_backtest(FuelBacktestParams::default())
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.status, StatusCode::FORBIDDEN);
+        assert_eq!(error.error, ErrorDiscriminants::InsufficientPermissions);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_fuel_backtest_is_empty_without_fuel_measurements() {
+    test(|mut helper, builder| async move {
+        builder.vessels(1).ais_vms_positions(10).build().await;
+        helper.app.login_user();
+
+        helper.run_processors().await;
+
+        let metrics = helper
+            .app
+            .fuel_backtest(FuelBacktestParams::default())
+            .await
+            .unwrap();
+
+        assert!(metrics.is_empty());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_fuel_backtest_returns_metrics_per_vessel_and_across_vessels() {
+    test(|mut helper, builder| async move {
+        let (vessel_id, _) = vessel_with_fuel_measurements(&mut helper, builder).await;
+
+        helper.run_processors().await;
+
+        let metrics = helper
+            .app
+            .fuel_backtest(FuelBacktestParams::default())
+            .await
+            .unwrap();
+
+        let all = metrics
+            .iter()
+            .find(|m| m.fiskeridir_vessel_id.is_none() && m.model == FuelEstimationModel::Maru)
+            .unwrap();
+        let vessel = metrics
+            .iter()
+            .find(|m| {
+                m.fiskeridir_vessel_id == Some(vessel_id) && m.model == FuelEstimationModel::Maru
+            })
+            .unwrap();
+
+        assert_eq!(vessel.num_ranges, 1);
+        assert_eq!(vessel.measured_liter, 1000.);
+        assert!(vessel.estimated_liter > 0.);
+        assert_eq!(all.num_ranges, vessel.num_ranges);
+        assert_eq!(all.measured_liter, vessel.measured_liter);
+        assert_eq!(all.estimated_liter, vessel.estimated_liter);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_fuel_backtest_filters_by_vessel() {
+    test(|mut helper, builder| async move {
+        let (vessel_id, _) = vessel_with_fuel_measurements(&mut helper, builder).await;
+
+        helper.run_processors().await;
+
+        let metrics = helper
+            .app
+            .fuel_backtest(FuelBacktestParams {
+                fiskeridir_vessel_id: Some(vessel_id),
+            })
+            .await
+            .unwrap();
+
+        assert!(!metrics.is_empty());
+        assert!(
+            metrics
+                .iter()
+                .all(|m| m.fiskeridir_vessel_id == Some(vessel_id))
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_empirical_fuel_model_is_only_refitted_when_fuel_measurements_change() {
+    test(|mut helper, builder| async move {
+        let (vessel_id, start) = vessel_with_fuel_measurements(&mut helper, builder).await;
+
+        let adapter = helper.adapter();
+
+        assert!(
+            adapter
+                .changed_fuel_measurements_checksum(vessel_id)
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        helper.run_processors().await;
+
+        assert!(
+            adapter
+                .changed_fuel_measurements_checksum(vessel_id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let body = vec![CreateFuelMeasurement {
+            timestamp: start + Duration::days(5),
+            fuel_liter: 500.,
+            fuel_after_liter: None,
+        }];
+        helper.app.create_fuel_measurements(&body).await.unwrap();
+
+        assert!(
+            adapter
+                .changed_fuel_measurements_checksum(vessel_id)
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        helper.run_processors().await;
+
+        assert!(
+            adapter
+                .changed_fuel_measurements_checksum(vessel_id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_changed_empirical_fuel_model_resets_fuel_estimates() {
+    test(|helper, builder| async move {
+        let state = builder
+            .vessels(1)
+            .set_logged_in()
+            .trips(1)
+            .ais_vms_positions(3)
+            .build()
+            .await;
+
+        let vessel_id = state.vessels[0].fiskeridir.id;
+        let adapter = helper.adapter();
+        let model = EmpiricalFuelModel {
+            idle_liter_per_hour: 10.,
+            load_liter_per_hour: 100.,
+            haul_liter_per_hour: 20.,
+            num_measurements: 10,
+        };
+
+        adapter
+            .set_empirical_fuel_model(vessel_id, Some(model), "unchanged", false)
+            .await
+            .unwrap();
+
+        assert!(
+            adapter
+                .fuel_estimates_with_status(ProcessingStatus::Successful)
+                .await
+                > 0
+        );
+
+        adapter
+            .set_empirical_fuel_model(vessel_id, Some(model), "changed", true)
+            .await
+            .unwrap();
+
+        assert!(
+            adapter
+                .fuel_estimates_with_status(ProcessingStatus::Unprocessed)
+                .await
+                > 0
+        );
+        assert_eq!(
+            adapter
+                .fuel_estimates_with_status(ProcessingStatus::Successful)
+                .await,
+            0
+        );
+    })
+    .await;
+}
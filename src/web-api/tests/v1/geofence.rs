@@ -0,0 +1,332 @@
+use super::helper::test;
+use chrono::{Duration, Utc};
+use http_client::StatusCode;
+use kyogre_core::{
+    BarentswatchUserId, GeofenceAreaType, GeofenceEventType, GeofenceId, ScraperInboundPort,
+    UpdateUser,
+};
+use web_api::{
+    error::ErrorDiscriminants,
+    routes::v1::geofence::{CreateGeofence, GeofenceEventsParams, GeofencePoint},
+};
+
+fn square(latitude: f64, longitude: f64) -> Vec<GeofencePoint> {
+    vec![
+        GeofencePoint {
+            latitude,
+            longitude,
+        },
+        GeofencePoint {
+            latitude: latitude + 1.,
+            longitude,
+        },
+        GeofencePoint {
+            latitude: latitude + 1.,
+            longitude: longitude + 1.,
+        },
+        GeofencePoint {
+            latitude,
+            longitude: longitude + 1.,
+        },
+    ]
+}
+
+fn polygon_geofence(name: &str) -> CreateGeofence {
+    CreateGeofence {
+        name: name.into(),
+        polygon: Some(square(70., 20.)),
+        catch_location_id: None,
+        fishing_facility_tool_id: None,
+    }
+}
+
+#[tokio::test]
+async fn test_create_and_get_geofences() {
+    test(|mut helper, _builder| async move {
+        helper.app.login_user();
+
+        let first = helper
+            .app
+            .create_geofence(&polygon_geofence("first"))
+            .await
+            .unwrap();
+        let second = helper
+            .app
+            .create_geofence(&polygon_geofence("second"))
+            .await
+            .unwrap();
+
+        let geofences = helper.app.geofences().await.unwrap();
+
+        assert_eq!(first.area_type, GeofenceAreaType::Polygon);
+        assert_eq!(geofences, vec![first, second]);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_geofences_are_not_returned_for_other_users() {
+    test(|mut helper, _builder| async move {
+        helper.app.login_user();
+        helper
+            .app
+            .create_geofence(&polygon_geofence("first"))
+            .await
+            .unwrap();
+
+        helper
+            .app
+            .login_user_with_id(BarentswatchUserId::test_new());
+        let geofences = helper.app.geofences().await.unwrap();
+
+        assert!(geofences.is_empty());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_delete_geofence() {
+    test(|mut helper, _builder| async move {
+        helper.app.login_user();
+
+        let geofence = helper
+            .app
+            .create_geofence(&polygon_geofence("first"))
+            .await
+            .unwrap();
+
+        helper.app.delete_geofence(geofence.id).await.unwrap();
+
+        let geofences = helper.app.geofences().await.unwrap();
+        assert!(geofences.is_empty());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_delete_geofence_fails_on_non_existing_id() {
+    test(|mut helper, _builder| async move {
+        helper.app.login_user();
+
+        let error = helper
+            .app
+            .delete_geofence(GeofenceId::test_new(1))
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.status, StatusCode::NOT_FOUND);
+        assert_eq!(error.error, ErrorDiscriminants::ObjectNotFound);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_create_geofence_fails_with_too_few_points() {
+    test(|mut helper, _builder| async move {
+        helper.app.login_user();
+
+        let mut geofence = polygon_geofence("first");
+        geofence.polygon.as_mut().unwrap().truncate(2);
+
+        let error = helper.app.create_geofence(&geofence).await.unwrap_err();
+
+        assert_eq!(error.status, StatusCode::BAD_REQUEST);
+        assert_eq!(error.error, ErrorDiscriminants::InvalidGeofencePolygon);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_create_geofence_fails_with_out_of_range_coordinate() {
+    test(|mut helper, _builder| async move {
+        helper.app.login_user();
+
+        let mut geofence = polygon_geofence("first");
+        geofence.polygon.as_mut().unwrap()[1].latitude = 91.;
+
+        let error = helper.app.create_geofence(&geofence).await.unwrap_err();
+
+        assert_eq!(error.status, StatusCode::BAD_REQUEST);
+        assert_eq!(error.error, ErrorDiscriminants::InvalidGeofenceCoordinate);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_create_geofence_fails_with_multiple_areas() {
+    test(|mut helper, _builder| async move {
+        helper.app.login_user();
+
+        let mut geofence = polygon_geofence("first");
+        geofence.catch_location_id = Some("01-01".parse().unwrap());
+
+        let error = helper.app.create_geofence(&geofence).await.unwrap_err();
+
+        assert_eq!(error.status, StatusCode::BAD_REQUEST);
+        assert_eq!(error.error, ErrorDiscriminants::InvalidGeofenceArea);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_geofence_events_are_recorded_for_followed_vessels() {
+    test(|mut helper, builder| async move {
+        helper.app.login_user_with_full_ais_permissions();
+
+        let start = Utc::now() - Duration::hours(1);
+        let state = builder
+            .vessels(1)
+            .ais_positions(3)
+            .modify_idx(|i, v| {
+                v.position.msgtime = start + Duration::minutes(i as i64);
+                // Outside, inside and then outside of the geofence
+                v.position.latitude = if i == 1 { 70.5 } else { 69. };
+                v.position.longitude = 20.5;
+            })
+            .build()
+            .await;
+
+        let vessel_id = state.vessels[0].fiskeridir.id;
+
+        helper
+            .app
+            .update_user(UpdateUser {
+                following: Some(vec![vessel_id]),
+                fuel_consent: None,
+                selected_vessel: None,
+            })
+            .await
+            .unwrap();
+
+        let geofence = helper
+            .app
+            .create_geofence(&polygon_geofence("first"))
+            .await
+            .unwrap();
+
+        helper.run_processors().await;
+
+        let events = helper
+            .app
+            .geofence_events(GeofenceEventsParams::default())
+            .await
+            .unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event_type, GeofenceEventType::Exit);
+        assert_eq!(events[0].timestamp, state.ais_positions[2].msgtime);
+        assert_eq!(events[1].event_type, GeofenceEventType::Enter);
+        assert_eq!(events[1].timestamp, state.ais_positions[1].msgtime);
+        assert!(
+            events
+                .iter()
+                .all(|e| e.geofence_id == geofence.id && e.fiskeridir_vessel_id == vessel_id)
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_geofence_events_are_not_recorded_for_vessels_that_are_not_followed() {
+    test(|mut helper, builder| async move {
+        helper.app.login_user();
+
+        let start = Utc::now() - Duration::hours(1);
+        builder
+            .vessels(1)
+            .ais_positions(3)
+            .modify_idx(|i, v| {
+                v.position.msgtime = start + Duration::minutes(i as i64);
+                v.position.latitude = if i == 1 { 70.5 } else { 69. };
+                v.position.longitude = 20.5;
+            })
+            .build()
+            .await;
+
+        helper
+            .app
+            .create_geofence(&polygon_geofence("first"))
+            .await
+            .unwrap();
+
+        helper.run_processors().await;
+
+        let events = helper
+            .app
+            .geofence_events(GeofenceEventsParams::default())
+            .await
+            .unwrap();
+
+        assert!(events.is_empty());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_geofence_events_are_recorded_for_late_vms_positions() {
+    test(|mut helper, builder| async move {
+        helper.app.login_user_with_full_ais_permissions();
+
+        let start = Utc::now() - Duration::hours(1);
+        let state = builder
+            .vessels(1)
+            .vms_positions(2)
+            .modify_idx(|i, v| {
+                v.position.timestamp = start + Duration::minutes(2 * i as i64);
+                v.position.latitude = Some(69.);
+                v.position.longitude = Some(20.5);
+            })
+            .build()
+            .await;
+
+        let vessel_id = state.vessels[0].fiskeridir.id;
+
+        helper
+            .app
+            .update_user(UpdateUser {
+                following: Some(vec![vessel_id]),
+                fuel_consent: None,
+                selected_vessel: None,
+            })
+            .await
+            .unwrap();
+
+        helper
+            .app
+            .create_geofence(&polygon_geofence("first"))
+            .await
+            .unwrap();
+
+        helper.run_processors().await;
+
+        let events = helper
+            .app
+            .geofence_events(GeofenceEventsParams::default())
+            .await
+            .unwrap();
+        assert!(events.is_empty());
+
+        // A position inside the geofence between the two processed positions
+        let late = start + Duration::minutes(1);
+        let mut position =
+            fiskeridir_rs::Vms::test_default(1, state.vms_positions[0].call_sign.clone(), late);
+        position.latitude = Some(70.5);
+        position.longitude = Some(20.5);
+        helper.adapter().add_vms(vec![position]).await.unwrap();
+
+        helper.run_processors().await;
+
+        let events = helper
+            .app
+            .geofence_events(GeofenceEventsParams::default())
+            .await
+            .unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event_type, GeofenceEventType::Exit);
+        assert_eq!(events[0].timestamp, start + Duration::minutes(2));
+        assert_eq!(events[1].event_type, GeofenceEventType::Enter);
+        assert_eq!(events[1].timestamp, late);
+    })
+    .await;
+}
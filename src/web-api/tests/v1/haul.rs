@@ -1,8 +1,11 @@
 use super::helper::test;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use engine::*;
 use fiskeridir_rs::{GearGroup, SpeciesGroup, VesselLengthGroup};
-use kyogre_core::{CatchLocationId, HaulsSorting, Ordering};
+use kyogre_core::{
+    CatchLocationId, HaulOceanClimate, HaulWeatherInbound, HaulWeatherOutput, HaulWeatherStatus,
+    HaulsSorting, Ordering,
+};
 use web_api::routes::v1::haul::HaulsParams;
 
 #[tokio::test]
@@ -291,3 +294,124 @@ async fn test_hauls_species_fiskeridir_defaults_to_zero() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn test_hauls_estimated_value_uses_weekly_sale_price() {
+    test(|helper, builder| async move {
+        let state = builder.hauls(1).build().await;
+        let haul = &state.hauls[0];
+        assert!(haul.estimated_value.is_none());
+
+        helper
+            .builder()
+            .await
+            .haul_weekly_sale(haul, haul.stop_timestamp)
+            .build()
+            .await;
+
+        let hauls = helper.app.get_hauls(Default::default()).await.unwrap();
+
+        // Test weekly sales have a price of 150 per kilo net quantity, and a living weight of 90%
+        // of the net quantity
+        let expected = haul.total_living_weight() as f64 * 150. / 0.9;
+
+        assert_eq!(hauls.len(), 1);
+        assert!((hauls[0].estimated_value.unwrap() - expected).abs() < 1e-6);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_hauls_estimated_value_uses_most_recent_weekly_sale_before_haul() {
+    test(|helper, builder| async move {
+        let state = builder.hauls(1).build().await;
+        let haul = &state.hauls[0];
+
+        helper
+            .builder()
+            .await
+            .haul_weekly_sale(haul, haul.stop_timestamp - Duration::weeks(3))
+            .build()
+            .await;
+
+        let hauls = helper.app.get_hauls(Default::default()).await.unwrap();
+
+        assert_eq!(hauls.len(), 1);
+        assert!(hauls[0].estimated_value.is_some());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_hauls_estimated_value_ignores_weekly_sales_older_than_max_price_age() {
+    test(|helper, builder| async move {
+        let state = builder.hauls(1).build().await;
+        let haul = &state.hauls[0];
+
+        // Weekly sale prices are only used for 8 weeks
+        helper
+            .builder()
+            .await
+            .haul_weekly_sale(haul, haul.stop_timestamp - Duration::weeks(9))
+            .build()
+            .await;
+
+        let hauls = helper.app.get_hauls(Default::default()).await.unwrap();
+
+        assert_eq!(hauls.len(), 1);
+        assert!(hauls[0].estimated_value.is_none());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_hauls_estimated_value_ignores_weekly_sales_after_haul() {
+    test(|helper, builder| async move {
+        let state = builder.hauls(1).build().await;
+        let haul = &state.hauls[0];
+
+        helper
+            .builder()
+            .await
+            .haul_weekly_sale(haul, haul.stop_timestamp + Duration::weeks(1))
+            .build()
+            .await;
+
+        let hauls = helper.app.get_hauls(Default::default()).await.unwrap();
+
+        assert_eq!(hauls.len(), 1);
+        assert!(hauls[0].estimated_value.is_none());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_hauls_returns_water_temperature() {
+    test(|helper, builder| async move {
+        let state = builder.hauls(1).build().await;
+
+        helper
+            .adapter()
+            .add_haul_weather(vec![HaulWeatherOutput {
+                haul_id: state.hauls[0].id,
+                weather: None,
+                ocean_climate: Some(HaulOceanClimate {
+                    water_speed: None,
+                    water_direction: None,
+                    salinity: None,
+                    water_temperature: Some(5.),
+                    ocean_climate_depth: None,
+                    sea_floor_depth: None,
+                }),
+                status: HaulWeatherStatus::Successful,
+            }])
+            .await
+            .unwrap();
+
+        let hauls = helper.app.get_hauls(HaulsParams::default()).await.unwrap();
+
+        assert_eq!(hauls.len(), 1);
+        assert_eq!(hauls[0].water_temperature, Some(5.));
+    })
+    .await;
+}
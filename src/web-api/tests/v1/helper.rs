@@ -55,7 +55,7 @@ impl TestHelper {
         processors.run().await.unwrap();
     }
     pub async fn run_engine_cycle(&self) {
-        let mut engine = engine(self.adapter().clone(), &self.db_settings).await;
+        let mut engine = engine(self.adapter().clone(), &self.db_settings, true).await;
 
         engine = engine.run_single().await;
         loop {
@@ -66,7 +66,7 @@ impl TestHelper {
         }
     }
     pub async fn builder(&self) -> TestStateBuilder {
-        let engine = engine(self.adapter().clone(), &self.db_settings).await;
+        let engine = engine(self.adapter().clone(), &self.db_settings, true).await;
 
         TestStateBuilder::new(
             Box::new(self.adapter().clone()),
@@ -233,6 +233,7 @@ where
                         cache_mode: adapter::CacheMode::ReturnError,
                         storage: CacheStorage::Memory,
                         refresh_interval: std::time::Duration::from_secs(100000),
+                        parquet_snapshots: None,
                     },
                     port: 0,
                 })
@@ -245,6 +246,7 @@ where
                     Some(Duckdb {
                         ip: ip.clone(),
                         port,
+                        replicas: vec![],
                     }),
                     Some(duckdb_rs::Client::new(ip, port).await.unwrap()),
                 )
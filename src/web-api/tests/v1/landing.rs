@@ -1,8 +1,10 @@
 use super::helper::test;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Utc};
 use engine::*;
 use fiskeridir_rs::{GearGroup, SpeciesGroup, VesselLengthGroup};
-use kyogre_core::{CatchLocationId, FiskeridirVesselId, LandingsSorting, Ordering};
+use kyogre_core::{
+    CatchLocationId, FiskeridirVesselId, LandingsSorting, Ordering, ScraperInboundPort,
+};
 use web_api::routes::v1::landing::LandingsParams;
 
 #[tokio::test]
@@ -269,6 +271,46 @@ async fn test_landing_deletion_only_deletes_removed_landings() {
     .await;
 }
 
+#[tokio::test]
+async fn test_landing_chunks_are_kept_until_removed_landings_are_deleted() {
+    test(|helper, builder| async move {
+        let vessel_id = FiskeridirVesselId::test_new(1);
+        let landing = fiskeridir_rs::Landing::test_default(1, Some(vessel_id));
+        let landing2 = fiskeridir_rs::Landing::test_default(2, Some(vessel_id));
+        let landing3 = fiskeridir_rs::Landing::test_default(3, Some(vessel_id));
+        let year = landing.landing_timestamp.year() as u32;
+        helper
+            .db
+            .add_landings(vec![landing.clone(), landing2.clone()])
+            .await;
+
+        helper
+            .db
+            .db
+            .add_landings_chunk(vec![landing3.clone()], year)
+            .await
+            .unwrap();
+
+        let landings = helper.db.landing_ids_of_vessel(vessel_id).await;
+        assert_eq!(3, landings.len());
+
+        helper
+            .db
+            .db
+            .delete_removed_landings(vec![landing.id.clone(), landing3.id.clone()], year)
+            .await
+            .unwrap();
+
+        let landings = helper.db.landing_ids_of_vessel(vessel_id).await;
+        assert_eq!(2, landings.len());
+        assert_eq!(landings[0], landing.id);
+        assert_eq!(landings[1], landing3.id);
+
+        builder.build().await;
+    })
+    .await;
+}
+
 #[tokio::test]
 async fn test_landings_filter_by_limit() {
     test(|helper, builder| async move {
@@ -4,24 +4,36 @@ pub mod barentswatch_helper;
 pub mod current_position;
 pub mod current_trip;
 pub mod current_trip_positions;
+pub mod data_quality;
 #[cfg(feature = "all-tests")]
 pub mod db_migrations;
 pub mod delivery_point;
+pub mod emissions;
+pub mod export;
+pub mod file_checkpoint;
 pub mod fishing_facility;
 pub mod fishing_predictions;
+pub mod fuel_backtest;
 pub mod fuel_measurement;
+pub mod geofence;
 pub mod haul;
 pub mod hauls_matrix;
 pub mod helper;
 pub mod landing;
 pub mod landing_matrix;
+pub mod ocean_climate;
 pub mod org;
+pub mod price;
 pub mod species;
 pub mod test_client;
+pub mod time_series;
+pub mod track_export;
 pub mod trip;
+pub mod trip_explanation;
 pub mod user;
 pub mod user_haul;
 pub mod vessel;
 pub mod vessel_event;
 pub mod vessel_org_fuel;
 pub mod vms;
+pub mod weather_forecast;
@@ -0,0 +1,152 @@
+use super::helper::test;
+use chrono::{Duration, TimeZone, Utc};
+use http_client::StatusCode;
+use kyogre_core::{
+    MAX_OCEAN_CLIMATE_RANGE_DAYS, NewOceanClimate, ScraperInboundPort, WEATHER_LOCATION_LATS_LONS,
+};
+use web_api::{error::ErrorDiscriminants, routes::v1::ocean_climate::OceanClimateParams};
+
+fn ocean_climate(depth: i32, temperature: f64) -> NewOceanClimate {
+    let (latitude, longitude, _) = WEATHER_LOCATION_LATS_LONS[0];
+    let mut v = NewOceanClimate::test_default(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+    v.latitude = latitude;
+    v.longitude = longitude;
+    v.depth = depth;
+    v.temperature = Some(temperature);
+    v
+}
+
+fn params() -> OceanClimateParams {
+    let timestamp = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    OceanClimateParams {
+        start: Some(timestamp - Duration::hours(1)),
+        end: Some(timestamp + Duration::hours(1)),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_ocean_climate_returns_climate_per_catch_location_and_depth() {
+    test(|helper, _builder| async move {
+        helper
+            .adapter()
+            .add_ocean_climate(vec![ocean_climate(0, 4.), ocean_climate(25, 6.)])
+            .await
+            .unwrap();
+
+        let climate = helper.app.ocean_climate(params()).await.unwrap();
+
+        assert!(!climate.is_empty());
+        assert!(
+            climate
+                .iter()
+                .all(|c| c.temperature == Some(if c.depth == 0 { 4. } else { 6. }))
+        );
+
+        let catch_location_id = &climate[0].catch_location_id;
+        let depths = climate
+            .iter()
+            .filter(|c| &c.catch_location_id == catch_location_id)
+            .map(|c| c.depth)
+            .collect::<Vec<_>>();
+        assert_eq!(depths, vec![0, 25]);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_ocean_climate_filters_by_depth_and_catch_location() {
+    test(|helper, _builder| async move {
+        helper
+            .adapter()
+            .add_ocean_climate(vec![ocean_climate(0, 4.), ocean_climate(25, 6.)])
+            .await
+            .unwrap();
+
+        let all = helper.app.ocean_climate(params()).await.unwrap();
+        let catch_location_id = all[0].catch_location_id.clone();
+
+        let climate = helper
+            .app
+            .ocean_climate(OceanClimateParams {
+                depths: Some(vec![25]),
+                catch_location_ids: Some(vec![catch_location_id.clone()]),
+                ..params()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(climate.len(), 1);
+        assert_eq!(climate[0].catch_location_id, catch_location_id);
+        assert_eq!(climate[0].depth, 25);
+        assert_eq!(climate[0].temperature, Some(6.));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_ocean_climate_excludes_climate_outside_time_range() {
+    test(|helper, _builder| async move {
+        helper
+            .adapter()
+            .add_ocean_climate(vec![ocean_climate(0, 4.)])
+            .await
+            .unwrap();
+
+        let timestamp = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let climate = helper
+            .app
+            .ocean_climate(OceanClimateParams {
+                start: Some(timestamp + Duration::hours(1)),
+                end: Some(timestamp + Duration::hours(2)),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        assert!(climate.is_empty());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_ocean_climate_fails_with_start_after_end() {
+    test(|helper, _builder| async move {
+        let timestamp = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let error = helper
+            .app
+            .ocean_climate(OceanClimateParams {
+                start: Some(timestamp + Duration::hours(1)),
+                end: Some(timestamp),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.status, StatusCode::BAD_REQUEST);
+        assert_eq!(error.error, ErrorDiscriminants::StartAfterEnd);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_ocean_climate_fails_with_too_long_range() {
+    test(|helper, _builder| async move {
+        let timestamp = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let error = helper
+            .app
+            .ocean_climate(OceanClimateParams {
+                start: Some(timestamp),
+                end: Some(
+                    timestamp + Duration::days(MAX_OCEAN_CLIMATE_RANGE_DAYS) + Duration::seconds(1),
+                ),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.status, StatusCode::BAD_REQUEST);
+        assert_eq!(error.error, ErrorDiscriminants::DateRangeTooLong);
+    })
+    .await;
+}
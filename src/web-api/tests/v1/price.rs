@@ -0,0 +1,119 @@
+use super::helper::test;
+use chrono::{Datelike, Duration, TimeZone, Utc};
+use fiskeridir_rs::{Condition, GearGroup, Quality, SpeciesFiskeridirId, VesselLengthGroup};
+use kyogre_core::{PRICE_SERIES_DEFAULT_WEEKS, PriceSeriesQuery};
+use processors::{PRICE_FORECAST_HORIZON_WEEKS, PRICE_FORECAST_MIN_WEEKS};
+
+fn query(weeks: Option<u32>) -> PriceSeriesQuery {
+    PriceSeriesQuery {
+        length_group: VesselLengthGroup::FifteenToTwentyOne,
+        gear_group: GearGroup::Trawl,
+        species: SpeciesFiskeridirId::test_new(1032),
+        condition: Condition::Levende,
+        quality: Quality::Superior,
+        weeks,
+    }
+}
+
+#[tokio::test]
+async fn test_price_series_returns_prices_ordered_by_week() {
+    test(|helper, builder| async move {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        builder.weekly_sales(5, start).build().await;
+
+        let series = helper.app.price_series(query(None)).await.unwrap();
+
+        assert_eq!(series.prices.len(), 5);
+        assert!(series.forecast.is_empty());
+        for (i, p) in series.prices.iter().enumerate() {
+            assert_eq!(p.year, 2024);
+            assert_eq!(p.week, i as i32 + 1);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_price_series_only_returns_the_most_recent_weeks() {
+    test(|helper, builder| async move {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        builder
+            .weekly_sales(PRICE_SERIES_DEFAULT_WEEKS as usize + 2, start)
+            .build()
+            .await;
+
+        let series = helper.app.price_series(query(None)).await.unwrap();
+        assert_eq!(series.prices.len(), PRICE_SERIES_DEFAULT_WEEKS as usize);
+        assert_eq!(series.prices[0].week, 3);
+
+        let series = helper.app.price_series(query(Some(3))).await.unwrap();
+        assert_eq!(series.prices.len(), 3);
+        assert_eq!(series.prices[0].year, 2024);
+        assert_eq!(series.prices[0].week, 52);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_price_series_does_not_forecast_groups_with_too_few_weeks() {
+    test(|helper, builder| async move {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        builder
+            .weekly_sales(PRICE_FORECAST_MIN_WEEKS - 1, start)
+            .build()
+            .await;
+
+        helper.run_processors().await;
+
+        let series = helper.app.price_series(query(None)).await.unwrap();
+        assert!(series.forecast.is_empty());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_price_series_does_not_forecast_groups_without_recent_sales() {
+    test(|helper, builder| async move {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        builder
+            .weekly_sales(PRICE_FORECAST_MIN_WEEKS, start)
+            .build()
+            .await;
+
+        helper.run_processors().await;
+
+        let series = helper.app.price_series(query(None)).await.unwrap();
+        assert!(!series.prices.is_empty());
+        assert!(series.forecast.is_empty());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_price_series_forecasts_the_weeks_after_the_current_week() {
+    test(|helper, builder| async move {
+        let start = Utc::now() - Duration::weeks(PRICE_FORECAST_MIN_WEEKS as i64);
+        builder
+            .weekly_sales(PRICE_FORECAST_MIN_WEEKS, start)
+            .build()
+            .await;
+
+        helper.run_processors().await;
+
+        let series = helper.app.price_series(query(None)).await.unwrap();
+        let last = series.prices.last().unwrap();
+        let now = Utc::now();
+
+        assert_eq!(series.forecast.len(), PRICE_FORECAST_HORIZON_WEEKS as usize);
+        for (i, f) in series.forecast.iter().enumerate() {
+            let week = (now + Duration::weeks(i as i64 + 1)).iso_week();
+            assert_eq!(f.year, week.year());
+            assert_eq!(f.week, week.week() as i32);
+            // The test weekly sales all have the same price
+            assert!((f.price - last.price).abs() < 1e-6);
+            assert!(f.lower_bound <= f.price);
+            assert!(f.upper_bound >= f.price);
+        }
+    })
+    .await;
+}
@@ -3,35 +3,45 @@ use fiskeridir_rs::{CallSign, OrgId};
 use http_client::{HttpClient, StatusCode};
 use kyogre_core::{
     ActiveHaulsFilter, ActiveLandingFilter, AverageTripBenchmarks, BarentswatchUserId,
-    CreateFuelMeasurement, DeleteFuelMeasurement, FiskeridirVesselId, FuelEntry, FuelMeasurement,
-    HaulEnd, HaulStart, LiveFuel, Mmsi, OrgBenchmarks, SpeciesFiskeridir, StartedUserHaul,
-    UpdateUser, UpdateUserHaul, UpdateVessel, UserHaul, UserHaulId, VesselBenchmarks,
+    CatchLocationOceanClimate, CreateFuelMeasurement, DeleteFuelMeasurement, EmissionReport,
+    FileDataQuality, FiskeridirVesselId, FuelBacktestMetrics, FuelEntry, FuelMeasurement,
+    GeofenceEvent, GeofenceId, HaulEnd, HaulStart, LiveFuel, Mmsi, OrgBenchmarks, PeerBenchmarks,
+    PriceSeries, PriceSeriesQuery, SpeciesFiskeridir, StartedUserHaul, TripId, UpdateUser,
+    UpdateUserHaul, UpdateVessel, UserHaul, UserHaulId, VesselBenchmarks, WeatherForecast,
 };
 use serde::{Serialize, de::DeserializeOwned};
 use std::{convert::TryInto, fmt::Debug};
 use web_api::{
     error::{ErrorDiscriminants, ErrorResponse},
     extractors::{BwPolicy, BwRole},
+    response::{GEOJSON_MIME, GPX_MIME, XLSX_MIME},
     routes::v1::{
         ais::{AisPosition, AisTrackParameters},
         ais_vms::{AisVmsParameters, AisVmsPosition, CurrentPosition, CurrentPositionParameters},
+        data_quality::DataQualityParams,
         delivery_point::DeliveryPoint,
         fishing_facility::{FishingFacilitiesParams, FishingFacility},
+        fuel_backtest::FuelBacktestParams,
         fuel_measurement::{FuelMeasurementsParams, UploadFuelMeasurement},
+        geofence::{CreateGeofence, Geofence, GeofenceEventsParams},
         haul::{Haul, HaulsMatrix, HaulsMatrixParams, HaulsParams},
         landing::{Landing, LandingMatrix, LandingMatrixParams, LandingsParams},
+        ocean_climate::OceanClimateParams,
         org::OrgBenchmarkParameters,
         species::{Species, SpeciesFao, SpeciesGroupDetailed, SpeciesMainGroupDetailed},
+        time_series::{HaulsTimeSeriesParams, LandingTimeSeriesParams, TimeSeriesPoint},
         trip::{
             CurrentTrip, Trip, TripsParameters,
             benchmarks::{
-                AverageEeoiParams, AverageTripBenchmarksParams, EeoiParams, TripBenchmarks,
-                TripBenchmarksParams,
+                AverageEeoiParams, AverageTripBenchmarksParams, EeoiParams, PeerBenchmarksParams,
+                TripBenchmarks, TripBenchmarksParams,
             },
+            explanation::TripExplanation,
         },
         user::User,
-        vessel::{FuelParams, LiveFuelParams, Vessel},
+        vessel::{EmissionReportParams, FuelParams, LiveFuelParams, Vessel},
         vms::{VmsParameters, VmsPosition},
+        weather::WeatherForecastParams,
     },
 };
 
@@ -103,6 +113,7 @@ impl ApiClient {
         method: Method,
         body: &impl Serialize,
         url_parameters: Option<&impl Serialize>,
+        accept: Option<&str>,
     ) -> http_client::Result<http_client::Response> {
         let path = path.as_ref();
         let mut request = match method {
@@ -117,6 +128,10 @@ impl ApiClient {
             request = request.header("Authorization", format!("Bearer {token}"));
         }
 
+        if let Some(accept) = accept {
+            request = request.header("Accept", accept);
+        }
+
         if let Some(cs) = &self.call_sign_override {
             #[derive(Serialize)]
             struct Cs {
@@ -137,7 +152,10 @@ impl ApiClient {
         body: &impl Serialize,
         url_parameters: Option<&impl Serialize>,
     ) -> Result<T, Error> {
-        match self.do_request(path, method, &body, url_parameters).await {
+        match self
+            .do_request(path, method, &body, url_parameters, None)
+            .await
+        {
             Ok(v) => {
                 let text = v.text().await.unwrap();
                 match serde_json::from_str::<T>(&text) {
@@ -149,6 +167,63 @@ impl ApiClient {
         }
     }
 
+    async fn export(
+        &self,
+        path: impl AsRef<str>,
+        accept: &str,
+        url_parameters: Option<&impl Serialize>,
+    ) -> Result<Vec<u8>, Error> {
+        match self
+            .do_request(path, Method::GET, &(), url_parameters, Some(accept))
+            .await
+        {
+            Ok(v) => Ok(v.bytes().await.unwrap().to_vec()),
+            Err(e) => Err(handle_request_failure(e)),
+        }
+    }
+
+    /// Performs a GET request to the given path requesting a CSV export.
+    pub async fn get_csv(
+        &self,
+        path: impl AsRef<str>,
+        url_parameters: Option<&impl Serialize>,
+    ) -> Result<String, Error> {
+        self.export(path, "text/csv", url_parameters)
+            .await
+            .map(|v| String::from_utf8(v).unwrap())
+    }
+
+    /// Performs a GET request to the given path requesting an XLSX export.
+    pub async fn get_xlsx(
+        &self,
+        path: impl AsRef<str>,
+        url_parameters: Option<&impl Serialize>,
+    ) -> Result<Vec<u8>, Error> {
+        self.export(path, XLSX_MIME, url_parameters).await
+    }
+
+    /// Performs a GET request to the given path requesting a GeoJSON track.
+    pub async fn get_geojson(
+        &self,
+        path: impl AsRef<str>,
+        url_parameters: Option<&impl Serialize>,
+    ) -> Result<serde_json::Value, Error> {
+        self.export(path, GEOJSON_MIME, url_parameters)
+            .await
+            .map(|v| serde_json::from_slice(&v).unwrap())
+    }
+
+    /// Performs a GET request to the given path requesting a GPX track.
+    pub async fn get_gpx(
+        &self,
+        path: impl AsRef<str>,
+        url_parameters: Option<&impl Serialize>,
+    ) -> Result<String, Error> {
+        self.export(path, GPX_MIME, url_parameters)
+            .await
+            .map(|v| String::from_utf8(v).unwrap())
+    }
+
     fn url(&self, route: &str) -> String {
         format!("{}/{}", self.address, route)
     }
@@ -226,6 +301,19 @@ impl ApiClient {
         )
         .await
     }
+    pub async fn get_org_emissions(
+        &self,
+        org_id: OrgId,
+        params: EmissionReportParams,
+    ) -> Result<EmissionReport, Error> {
+        self.send(
+            format!("org/{org_id}/emissions"),
+            Method::GET,
+            &(),
+            Some(&params),
+        )
+        .await
+    }
     pub async fn get_org_benchmarks(
         &self,
         org_id: OrgId,
@@ -253,6 +341,13 @@ impl ApiClient {
         self.send("trip/benchmarks/average", Method::GET, &(), Some(&params))
             .await
     }
+    pub async fn get_peer_benchmarks(
+        &self,
+        params: PeerBenchmarksParams,
+    ) -> Result<PeerBenchmarks, Error> {
+        self.send("trip/benchmarks/peers", Method::GET, &(), Some(&params))
+            .await
+    }
     pub async fn get_eeoi(&self, params: EeoiParams) -> Result<Option<f64>, Error> {
         self.send("trip/benchmarks/eeoi", Method::GET, &(), Some(&params))
             .await
@@ -322,6 +417,28 @@ impl ApiClient {
             .await
     }
 
+    pub async fn geofences(&self) -> Result<Vec<Geofence>, Error> {
+        self.send("geofences", Method::GET, &(), None::<&()>).await
+    }
+
+    pub async fn create_geofence(&self, geofence: &CreateGeofence) -> Result<Geofence, Error> {
+        self.send("geofences", Method::POST, &geofence, None::<&()>)
+            .await
+    }
+
+    pub async fn delete_geofence(&self, id: GeofenceId) -> Result<(), Error> {
+        self.send(format!("geofences/{id}"), Method::DELETE, &(), None::<&()>)
+            .await
+    }
+
+    pub async fn geofence_events(
+        &self,
+        params: GeofenceEventsParams,
+    ) -> Result<Vec<GeofenceEvent>, Error> {
+        self.send("geofences/events", Method::GET, &(), Some(&params))
+            .await
+    }
+
     pub async fn get_hauls(&self, params: HaulsParams) -> Result<Vec<Haul>, Error> {
         self.send("hauls", Method::GET, &(), Some(&params)).await
     }
@@ -354,6 +471,20 @@ impl ApiClient {
         )
         .await
     }
+    pub async fn get_hauls_time_series(
+        &self,
+        params: HaulsTimeSeriesParams,
+    ) -> Result<Vec<TimeSeriesPoint>, Error> {
+        self.send("hauls_time_series", Method::GET, &(), Some(&params))
+            .await
+    }
+    pub async fn get_landing_time_series(
+        &self,
+        params: LandingTimeSeriesParams,
+    ) -> Result<Vec<TimeSeriesPoint>, Error> {
+        self.send("landing_time_series", Method::GET, &(), Some(&params))
+            .await
+    }
 
     pub async fn get_trips(&self, params: TripsParameters) -> Result<Vec<Trip>, Error> {
         self.send("trips", Method::GET, &(), Some(&params)).await
@@ -365,6 +496,15 @@ impl ApiClient {
         self.send(format!("trips/current/{id}"), Method::GET, &(), None::<&()>)
             .await
     }
+    pub async fn get_trip_track(&self, trip_id: TripId) -> Result<Vec<AisVmsPosition>, Error> {
+        self.send(
+            format!("trips/{trip_id}/track"),
+            Method::GET,
+            &(),
+            None::<&()>,
+        )
+        .await
+    }
     pub async fn get_current_trip_positions(
         &self,
         id: FiskeridirVesselId,
@@ -415,6 +555,13 @@ impl ApiClient {
         self.send("vessel/fuel", Method::GET, &(), Some(&params))
             .await
     }
+    pub async fn get_vessel_emissions(
+        &self,
+        params: EmissionReportParams,
+    ) -> Result<EmissionReport, Error> {
+        self.send("vessel/emissions", Method::GET, &(), Some(&params))
+            .await
+    }
     pub async fn get_fuel_measurements(
         &self,
         params: FuelMeasurementsParams,
@@ -447,6 +594,47 @@ impl ApiClient {
         self.send("fuel_measurements", Method::DELETE, &body, None::<&()>)
             .await
     }
+    pub async fn fuel_backtest(
+        &self,
+        params: FuelBacktestParams,
+    ) -> Result<Vec<FuelBacktestMetrics>, Error> {
+        self.send("fuel_backtest", Method::GET, &(), Some(&params))
+            .await
+    }
+    pub async fn data_quality(
+        &self,
+        params: DataQualityParams,
+    ) -> Result<Vec<FileDataQuality>, Error> {
+        self.send("data_quality", Method::GET, &(), Some(&params))
+            .await
+    }
+    pub async fn price_series(&self, query: PriceSeriesQuery) -> Result<PriceSeries, Error> {
+        self.send("price/series", Method::GET, &(), Some(&query))
+            .await
+    }
+    pub async fn weather_forecasts(
+        &self,
+        params: WeatherForecastParams,
+    ) -> Result<Vec<WeatherForecast>, Error> {
+        self.send("weather_forecasts", Method::GET, &(), Some(&params))
+            .await
+    }
+    pub async fn ocean_climate(
+        &self,
+        params: OceanClimateParams,
+    ) -> Result<Vec<CatchLocationOceanClimate>, Error> {
+        self.send("ocean_climate", Method::GET, &(), Some(&params))
+            .await
+    }
+    pub async fn trip_explanation(&self, trip_id: TripId) -> Result<TripExplanation, Error> {
+        self.send(
+            format!("trips/{trip_id}/explanation"),
+            Method::GET,
+            &(),
+            None::<&()>,
+        )
+        .await
+    }
 }
 
 fn handle_request_failure(error: http_client::Error) -> Error {
@@ -0,0 +1,154 @@
+use super::helper::{test, test_with_matrix_cache};
+use chrono::{DateTime, Duration, Utc};
+use engine::*;
+use fiskeridir_rs::SpeciesGroup;
+use http_client::StatusCode;
+use kyogre_core::{TimeBucket, TimeSeriesGroup};
+use web_api::{
+    error::ErrorDiscriminants,
+    routes::v1::time_series::{HaulsTimeSeriesParams, LandingTimeSeriesParams},
+};
+
+#[tokio::test]
+async fn test_hauls_time_series_fails_without_matrix_cache() {
+    test(|helper, _builder| async move {
+        let error = helper
+            .app
+            .get_hauls_time_series(HaulsTimeSeriesParams::default())
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(error.error, ErrorDiscriminants::MatrixCacheUnavailable);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_hauls_time_series_sums_living_weight_per_month() {
+    test_with_matrix_cache(|helper, builder| async move {
+        let january: DateTime<Utc> = "2024-01-01T00:00:00Z".parse().unwrap();
+        let february: DateTime<Utc> = "2024-02-01T00:00:00Z".parse().unwrap();
+
+        builder
+            .vessels(1)
+            .hauls(3)
+            .modify_idx(|i, v| {
+                let (start, weight) = match i {
+                    0 => (january + Duration::days(9), 100),
+                    1 => (january + Duration::days(19), 50),
+                    _ => (february + Duration::days(9), 30),
+                };
+                v.dca.set_start_timestamp(start);
+                v.dca.set_stop_timestamp(start + Duration::hours(1));
+                v.dca.start_latitude = Some(70.536);
+                v.dca.start_longitude = Some(21.957);
+                v.dca.catch.species.living_weight = Some(weight);
+            })
+            .build()
+            .await;
+
+        helper.refresh_matrix_cache().await;
+
+        let series = helper
+            .app
+            .get_hauls_time_series(HaulsTimeSeriesParams::default())
+            .await
+            .unwrap();
+
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].bucket, january);
+        assert_eq!(series[0].living_weight as i64, 150);
+        assert_eq!(series[1].bucket, february);
+        assert_eq!(series[1].living_weight as i64, 30);
+        assert!(series.iter().all(|p| p.species_group_id.is_none()));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_landing_time_series_groups_by_species_group() {
+    test_with_matrix_cache(|helper, builder| async move {
+        let timestamp: DateTime<Utc> = "2013-01-10T00:00:00Z".parse().unwrap();
+
+        builder
+            .landings(3)
+            .modify_idx(|i, v| {
+                let (species_group, weight) = match i {
+                    0 => (SpeciesGroup::GreenlandHalibut, 20.0),
+                    1 => (SpeciesGroup::GoldenRedfish, 40.0),
+                    _ => (SpeciesGroup::GreenlandHalibut, 100.0),
+                };
+                v.landing.landing_timestamp = timestamp;
+                v.landing.product.species.group_code = species_group;
+                v.landing.product.living_weight = Some(weight);
+            })
+            .build()
+            .await;
+
+        helper.refresh_matrix_cache().await;
+
+        let series = helper
+            .app
+            .get_landing_time_series(LandingTimeSeriesParams {
+                bucket: Some(TimeBucket::Year),
+                group_by: Some(vec![TimeSeriesGroup::SpeciesGroup]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let weight = |species_group| {
+            series
+                .iter()
+                .find(|p| p.species_group_id == Some(species_group))
+                .unwrap()
+                .living_weight as i64
+        };
+
+        assert_eq!(series.len(), 2);
+        assert!(
+            series
+                .iter()
+                .all(|p| p.bucket == "2013-01-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap())
+        );
+        assert_eq!(weight(SpeciesGroup::GreenlandHalibut), 120);
+        assert_eq!(weight(SpeciesGroup::GoldenRedfish), 40);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_landing_time_series_applies_matrix_filters() {
+    test_with_matrix_cache(|helper, builder| async move {
+        builder
+            .landings(2)
+            .modify_idx(|i, v| {
+                v.landing.landing_timestamp = "2013-01-10T00:00:00Z".parse().unwrap();
+                if i == 0 {
+                    v.landing.product.species.group_code = SpeciesGroup::GreenlandHalibut;
+                    v.landing.product.living_weight = Some(20.0);
+                } else {
+                    v.landing.product.species.group_code = SpeciesGroup::GoldenRedfish;
+                    v.landing.product.living_weight = Some(40.0);
+                }
+            })
+            .build()
+            .await;
+
+        helper.refresh_matrix_cache().await;
+
+        let series = helper
+            .app
+            .get_landing_time_series(LandingTimeSeriesParams {
+                species_group_ids: Some(vec![SpeciesGroup::GoldenRedfish]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].living_weight as i64, 40);
+    })
+    .await;
+}
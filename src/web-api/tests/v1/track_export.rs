@@ -0,0 +1,163 @@
+use super::helper::test;
+use chrono::{Duration, TimeZone, Utc};
+use engine::*;
+use kyogre_core::DateTimeRangeWithDefaultTimeSpan;
+use web_api::routes::v1::{ais_vms::AisVmsParameters, vms::VmsParameters};
+
+#[tokio::test]
+async fn test_trip_track_returns_same_positions_as_ais_vms_by_trip() {
+    test(|helper, builder| async move {
+        let state = builder
+            .vessels(1)
+            .trips(1)
+            .ais_vms_positions(3)
+            .build()
+            .await;
+
+        let trip_id = state.trips[0].trip_id;
+
+        let track = helper.app.get_trip_track(trip_id).await.unwrap();
+        let positions = helper
+            .app
+            .get_ais_vms_positions(AisVmsParameters {
+                trip_id: Some(trip_id),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(track.len(), 3);
+        assert_eq!(track, positions);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_trip_track_geojson_contains_point_features_and_line_string() {
+    test(|helper, builder| async move {
+        let state = builder
+            .vessels(1)
+            .trips(1)
+            .ais_vms_positions(3)
+            .build()
+            .await;
+
+        let geojson = helper
+            .app
+            .get_geojson(
+                format!("trips/{}/track", state.trips[0].trip_id),
+                None::<&()>,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(geojson["type"], "FeatureCollection");
+
+        let features = geojson["features"].as_array().unwrap();
+        assert_eq!(features.len(), 4);
+
+        for (feature, position) in features.iter().zip(&state.ais_vms_positions) {
+            assert_eq!(feature["geometry"]["type"], "Point");
+            assert_eq!(
+                feature["geometry"]["coordinates"],
+                serde_json::json!([position.longitude, position.latitude])
+            );
+            assert!(feature["properties"].get("timestamp").is_some());
+            assert!(feature["properties"].get("lat").is_none());
+            assert!(feature["properties"].get("det").is_none());
+        }
+
+        let line = &features[3]["geometry"];
+        assert_eq!(line["type"], "LineString");
+        assert_eq!(line["coordinates"].as_array().unwrap().len(), 3);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_ais_vms_geojson_does_not_contain_line_string_for_single_position() {
+    test(|helper, builder| async move {
+        let state = builder.vessels(1).ais_vms_positions(1).build().await;
+
+        let pos = &state.ais_vms_positions[0];
+        let params = AisVmsParameters {
+            mmsi: state.vessels[0].mmsi(),
+            call_sign: state.vessels[0].fiskeridir.call_sign.clone(),
+            range: DateTimeRangeWithDefaultTimeSpan::test_new(
+                pos.timestamp - Duration::seconds(1),
+                pos.timestamp + Duration::seconds(1),
+            ),
+            trip_id: None,
+        };
+
+        let geojson = helper
+            .app
+            .get_geojson("ais_vms_positions", Some(&params))
+            .await
+            .unwrap();
+
+        let features = geojson["features"].as_array().unwrap();
+        assert_eq!(features.len(), 1);
+        assert_eq!(features[0]["geometry"]["type"], "Point");
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_vms_gpx_contains_one_track_point_per_position() {
+    test(|helper, builder| async move {
+        let state = builder.vessels(1).vms_positions(3).build().await;
+
+        let call_sign = state.vessels[0].fiskeridir.call_sign.clone().unwrap();
+        let params = VmsParameters {
+            range: DateTimeRangeWithDefaultTimeSpan::test_new(
+                state.vms_positions[0].timestamp - Duration::seconds(1),
+                state.vms_positions[2].timestamp + Duration::seconds(1),
+            ),
+        };
+
+        let gpx = helper
+            .app
+            .get_gpx(format!("vms/{}", call_sign.as_ref()), Some(&params))
+            .await
+            .unwrap();
+
+        assert!(gpx.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?><gpx"#));
+        assert!(gpx.ends_with("</trkseg></trk></gpx>"));
+        assert_eq!(gpx.matches("<trkpt ").count(), 3);
+        for position in &state.vms_positions {
+            assert!(gpx.contains(&format!(
+                r#"<trkpt lat="{}" lon="{}">"#,
+                position.latitude, position.longitude
+            )));
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_ais_vms_positions_defaults_to_json_for_unsupported_accept_header() {
+    test(|helper, builder| async move {
+        let state = builder.vessels(1).ais_vms_positions(2).build().await;
+
+        let params = AisVmsParameters {
+            mmsi: state.vessels[0].mmsi(),
+            call_sign: state.vessels[0].fiskeridir.call_sign.clone(),
+            range: DateTimeRangeWithDefaultTimeSpan::test_new(
+                Utc.timestamp_opt(0, 0).unwrap(),
+                Utc::now(),
+            ),
+            trip_id: None,
+        };
+
+        let csv = helper
+            .app
+            .get_csv("ais_vms_positions", Some(&params))
+            .await
+            .unwrap();
+
+        let positions: Vec<serde_json::Value> = serde_json::from_str(&csv).unwrap();
+        assert_eq!(positions.len(), 2);
+    })
+    .await;
+}
@@ -11,6 +11,7 @@ pub mod average;
 pub mod catch_value_per_fuel;
 pub mod eeoi;
 pub mod fuel_consumption;
+pub mod peers;
 pub mod weight_per_distance;
 pub mod weight_per_fuel;
 pub mod weight_per_hour;
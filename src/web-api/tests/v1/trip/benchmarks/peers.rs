@@ -0,0 +1,202 @@
+use crate::v1::helper::test;
+use chrono::Duration;
+use engine::*;
+use kyogre_core::PEER_BENCHMARKS_MIN_PEERS;
+use web_api::routes::v1::trip::benchmarks::PeerBenchmarksParams;
+
+#[tokio::test]
+async fn test_peer_benchmarks_ranks_vessel_against_peers() {
+    test(|mut helper, builder| async move {
+        let num_vessels = PEER_BENCHMARKS_MIN_PEERS as usize + 1;
+        builder
+            .trip_duration(Duration::hours(2))
+            .vessels(num_vessels)
+            .set_logged_in()
+            .trips(num_vessels)
+            .hauls(num_vessels)
+            .modify_idx(|i, v| {
+                v.dca.catch.species.living_weight = Some((i + 1) as u32 * 1_000);
+            })
+            .build()
+            .await;
+
+        helper.app.login_user();
+
+        let bench = helper
+            .app
+            .get_peer_benchmarks(Default::default())
+            .await
+            .unwrap();
+
+        // Each peer `i` has a weight of `(i + 1) * 1000` over a trip of 2 hours
+        let peer_average = (1..num_vessels).map(|i| (i + 1) * 500).sum::<usize>()
+            / PEER_BENCHMARKS_MIN_PEERS as usize;
+
+        let weight_per_hour = bench.weight_per_hour.unwrap();
+        assert_eq!(bench.num_peers, PEER_BENCHMARKS_MIN_PEERS);
+        assert_eq!(weight_per_hour.num_peers, PEER_BENCHMARKS_MIN_PEERS);
+        assert_eq!(weight_per_hour.value as i64, 500);
+        assert_eq!(weight_per_hour.peer_average as i64, peer_average as i64);
+        assert_eq!(weight_per_hour.percentile_rank, 0.);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_peer_benchmarks_counts_ties_as_half() {
+    test(|mut helper, builder| async move {
+        let num_vessels = PEER_BENCHMARKS_MIN_PEERS as usize + 1;
+        builder
+            .trip_duration(Duration::hours(2))
+            .vessels(num_vessels)
+            .set_logged_in()
+            .trips(num_vessels)
+            .hauls(num_vessels)
+            .build()
+            .await;
+
+        helper.app.login_user();
+
+        let bench = helper
+            .app
+            .get_peer_benchmarks(Default::default())
+            .await
+            .unwrap();
+
+        assert_eq!(bench.weight_per_hour.unwrap().percentile_rank, 50.);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_peer_benchmarks_are_none_with_fewer_than_the_minimum_peers() {
+    test(|mut helper, builder| async move {
+        let num_vessels = PEER_BENCHMARKS_MIN_PEERS as usize;
+        builder
+            .trip_duration(Duration::hours(2))
+            .vessels(num_vessels)
+            .set_logged_in()
+            .trips(num_vessels)
+            .hauls(num_vessels)
+            .build()
+            .await;
+
+        helper.app.login_user();
+
+        let bench = helper
+            .app
+            .get_peer_benchmarks(Default::default())
+            .await
+            .unwrap();
+
+        assert_eq!(bench.num_peers, PEER_BENCHMARKS_MIN_PEERS - 1);
+        assert!(bench.weight_per_hour.is_none());
+        assert!(bench.weight_per_distance.is_none());
+        assert!(bench.weight_per_fuel_liter.is_none());
+        assert!(bench.catch_value_per_fuel_liter.is_none());
+        assert!(bench.eeoi.is_none());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_peer_benchmarks_excludes_vessels_in_other_length_groups() {
+    test(|mut helper, builder| async move {
+        builder
+            .vessels(2)
+            .modify_idx(|i, v| {
+                if i == 1 {
+                    v.fiskeridir.length = 40.;
+                }
+            })
+            .set_logged_in()
+            .trips(2)
+            .hauls(2)
+            .build()
+            .await;
+
+        helper.app.login_user();
+
+        let bench = helper
+            .app
+            .get_peer_benchmarks(Default::default())
+            .await
+            .unwrap();
+
+        assert_eq!(bench.num_peers, 0);
+        assert!(bench.weight_per_hour.is_none());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_peer_benchmarks_picks_peers_with_the_closest_engine_power() {
+    test(|mut helper, builder| async move {
+        builder
+            .trip_duration(Duration::hours(2))
+            .vessels(PEER_BENCHMARKS_MIN_PEERS as usize + 2)
+            .modify_idx(|i, v| {
+                v.fiskeridir.engine_power = Some(match i {
+                    0 => 1_000,
+                    6 => 5_000,
+                    _ => 1_100,
+                });
+            })
+            .set_logged_in()
+            .trips(PEER_BENCHMARKS_MIN_PEERS as usize + 2)
+            .hauls(PEER_BENCHMARKS_MIN_PEERS as usize + 2)
+            .modify_idx(|i, v| {
+                v.dca.catch.species.living_weight = Some((i + 1) as u32 * 1_000);
+            })
+            .build()
+            .await;
+
+        helper.app.login_user();
+
+        let bench = helper
+            .app
+            .get_peer_benchmarks(PeerBenchmarksParams {
+                num_peers: Some(PEER_BENCHMARKS_MIN_PEERS),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let weight_per_hour = bench.weight_per_hour.unwrap();
+        assert_eq!(bench.num_peers, PEER_BENCHMARKS_MIN_PEERS);
+        assert_eq!(weight_per_hour.peer_average as i64, 2_000);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_peer_benchmarks_raises_num_peers_to_the_minimum() {
+    test(|mut helper, builder| async move {
+        builder
+            .trip_duration(Duration::hours(2))
+            .vessels(PEER_BENCHMARKS_MIN_PEERS as usize + 1)
+            .set_logged_in()
+            .trips(PEER_BENCHMARKS_MIN_PEERS as usize + 1)
+            .hauls(PEER_BENCHMARKS_MIN_PEERS as usize + 1)
+            .build()
+            .await;
+
+        helper.app.login_user();
+
+        let bench = helper
+            .app
+            .get_peer_benchmarks(PeerBenchmarksParams {
+                num_peers: Some(1),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(bench.num_peers, PEER_BENCHMARKS_MIN_PEERS);
+        assert_eq!(
+            bench.weight_per_hour.unwrap().num_peers,
+            PEER_BENCHMARKS_MIN_PEERS
+        );
+    })
+    .await;
+}
@@ -0,0 +1,217 @@
+use super::helper::test;
+use chrono::{Duration, TimeZone, Utc};
+use engine::*;
+use http_client::StatusCode;
+use kyogre_core::{
+    PrecisionBoundary, PrecisionDirection, PrecisionId, TripAssemblerId, TripId,
+    TripPositionLayerId, VesselEventType,
+};
+use web_api::error::ErrorDiscriminants;
+
+#[tokio::test]
+async fn test_trip_explanation_requires_admin_role() {
+    test(|mut helper, builder| async move {
+        let state = builder.vessels(1).trips(1).build().await;
+
+        helper
+            .app
+            .login_user_with_policies_and_roles(vec![], vec![]);
+
+        let error = helper
+            .app
+            .trip_explanation(state.trips[0].trip_id)
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.status, StatusCode::FORBIDDEN);
+        assert_eq!(error.error, ErrorDiscriminants::InsufficientPermissions);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_trip_explanation_returns_not_found_for_non_existing_trip() {
+    test(|mut helper, _builder| async move {
+        helper.app.login_user();
+
+        let error = helper
+            .app
+            .trip_explanation(TripId::test_new(1))
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.status, StatusCode::NOT_FOUND);
+        assert_eq!(error.error, ErrorDiscriminants::ObjectNotFound);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_trip_explanation_returns_events_used_by_the_assembler() {
+    test(|mut helper, builder| async move {
+        let state = builder
+            .vessels(1)
+            .trips(1)
+            .landings(1)
+            .hauls(1)
+            .build()
+            .await;
+
+        helper.app.login_user();
+
+        let trip = &state.trips[0];
+        let explanation = helper.app.trip_explanation(trip.trip_id).await.unwrap();
+
+        assert_eq!(explanation.trip_id, trip.trip_id);
+        assert_eq!(explanation.trip_assembler_id, TripAssemblerId::Ers);
+        assert!(explanation.conflict_strategy.is_some());
+
+        let event_ids = explanation
+            .events
+            .iter()
+            .map(|e| e.event_id)
+            .collect::<Vec<_>>();
+        assert!(event_ids.contains(&explanation.start_vessel_event_id.unwrap()));
+        assert!(event_ids.contains(&explanation.end_vessel_event_id.unwrap()));
+
+        for event_type in [
+            VesselEventType::ErsDep,
+            VesselEventType::ErsDca,
+            VesselEventType::Haul,
+            VesselEventType::ErsPor,
+            VesselEventType::Landing,
+        ] {
+            assert!(
+                explanation
+                    .events
+                    .iter()
+                    .any(|e| e.event_type == event_type)
+            );
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_trip_explanation_returns_conflict_strategy_with_timestamps() {
+    test(|mut helper, builder| async move {
+        let start = Utc.timestamp_opt(1000000, 0).unwrap();
+
+        // The second trip is prior to the first one and therefore conflicts with it
+        let state = builder
+            .vessels(1)
+            .trips(1)
+            .modify(|v| {
+                v.trip_specification.set_start(start + Duration::days(10));
+                v.trip_specification.set_end(start + Duration::days(11));
+            })
+            .new_cycle()
+            .trips(1)
+            .modify(|v| {
+                v.trip_specification.set_start(start);
+                v.trip_specification.set_end(start + Duration::days(1));
+            })
+            .build()
+            .await;
+
+        helper.app.login_user();
+
+        let trip = state.trips.iter().min_by_key(|t| t.period.start()).unwrap();
+        let explanation = helper.app.trip_explanation(trip.trip_id).await.unwrap();
+
+        let conflict_strategy = explanation.conflict_strategy.unwrap();
+        match conflict_strategy.strategy.as_str() {
+            "Replace" => {
+                assert!(conflict_strategy.start.unwrap() <= trip.period.start());
+                assert!(conflict_strategy.end.is_none());
+            }
+            "ReplaceRange" => {
+                assert!(conflict_strategy.start.unwrap() <= trip.period.start());
+                assert!(conflict_strategy.end.unwrap() >= trip.period.start());
+            }
+            v => panic!("unexpected conflict strategy: {v}"),
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_trip_explanation_returns_outcome_of_each_precision_implementation() {
+    test(|mut helper, builder| async move {
+        let state = builder
+            .vessels(1)
+            .trips(1)
+            .precision(PrecisionId::DistanceToShore)
+            .build()
+            .await;
+
+        helper.app.login_user();
+
+        let trip = &state.trips[0];
+        let period_precision = trip.period_precision.as_ref().unwrap();
+        let explanation = helper.app.trip_explanation(trip.trip_id).await.unwrap();
+
+        let start_precision = explanation.start_precision.unwrap();
+        assert_eq!(start_precision.precision_id, PrecisionId::DistanceToShore);
+        assert_eq!(start_precision.direction, PrecisionDirection::Extending);
+
+        for (boundary, timestamp) in [
+            (PrecisionBoundary::Start, period_precision.start()),
+            (PrecisionBoundary::End, period_precision.end()),
+        ] {
+            let attempts = explanation
+                .precision_attempts
+                .iter()
+                .filter(|a| a.boundary == boundary)
+                .collect::<Vec<_>>();
+
+            // Implementations are run until the first one succeeds
+            let (last, prior) = attempts.split_last().unwrap();
+            assert_eq!(last.precision_id, PrecisionId::DistanceToShore);
+            assert_eq!(last.timestamp, Some(timestamp));
+            assert_eq!(last.direction, Some(PrecisionDirection::Extending));
+            assert!(!prior.is_empty());
+            assert!(
+                prior
+                    .iter()
+                    .all(|a| a.timestamp.is_none() && a.direction.is_none())
+            );
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_trip_explanation_returns_pruned_positions() {
+    test(|mut helper, builder| async move {
+        let state = builder
+            .vessels(1)
+            .trips(1)
+            .ais_vms_positions(3)
+            .modify_idx(|i, v| match i {
+                0 => v.position.set_location(59.11, 38.32),
+                1 => v.position.set_location(85.11, 38.32),
+                2 => v.position.set_location(88.11, 38.32),
+                _ => unreachable!(),
+            })
+            .build()
+            .await;
+
+        helper.app.login_user();
+
+        let explanation = helper
+            .app
+            .trip_explanation(state.trips[0].trip_id)
+            .await
+            .unwrap();
+
+        let pruned = explanation
+            .pruned_positions
+            .iter()
+            .find(|p| p.trip_position_layer_id == TripPositionLayerId::UnrealisticSpeed)
+            .unwrap();
+        assert_eq!(pruned.positions.as_array().unwrap().len(), 2);
+        assert!(pruned.value.get("speed").is_some());
+    })
+    .await;
+}
@@ -0,0 +1,140 @@
+use super::helper::test;
+use chrono::{Duration, TimeZone, Utc};
+use kyogre_core::{NewWeatherForecast, ScraperInboundPort, TestHelperOutbound};
+use web_api::routes::v1::weather::WeatherForecastParams;
+
+#[tokio::test]
+async fn test_weather_forecasts_returns_the_latest_run_of_each_catch_location() {
+    test(|helper, _builder| async move {
+        let first_run = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let second_run = first_run + Duration::hours(6);
+
+        let forecasts = [first_run, second_run]
+            .into_iter()
+            .flat_map(|run| {
+                (0..4).map(move |h| NewWeatherForecast::test_default(run, run + Duration::hours(h)))
+            })
+            .collect();
+
+        helper
+            .adapter()
+            .add_weather_forecasts(forecasts)
+            .await
+            .unwrap();
+
+        let forecasts = helper
+            .app
+            .weather_forecasts(WeatherForecastParams::default())
+            .await
+            .unwrap();
+
+        assert!(!forecasts.is_empty());
+        assert!(forecasts.iter().all(|f| f.reference_time == second_run));
+
+        let catch_location_id = &forecasts[0].catch_location_id;
+        let horizons = forecasts
+            .iter()
+            .filter(|f| &f.catch_location_id == catch_location_id)
+            .map(|f| f.horizon_hours)
+            .collect::<Vec<_>>();
+        assert_eq!(horizons, vec![0, 1, 2, 3]);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_weather_forecasts_filters_by_horizon() {
+    test(|helper, _builder| async move {
+        let run = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+
+        let forecasts = [0, 24, 48, 72, 96]
+            .into_iter()
+            .map(|h| NewWeatherForecast::test_default(run, run + Duration::hours(h)))
+            .collect();
+
+        helper
+            .adapter()
+            .add_weather_forecasts(forecasts)
+            .await
+            .unwrap();
+
+        let forecasts = helper
+            .app
+            .weather_forecasts(WeatherForecastParams::default())
+            .await
+            .unwrap();
+        assert_eq!(forecasts.iter().map(|f| f.horizon_hours).max(), Some(72));
+
+        let forecasts = helper
+            .app
+            .weather_forecasts(WeatherForecastParams {
+                horizon_hours: Some(48),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(forecasts.iter().map(|f| f.horizon_hours).max(), Some(48));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_weather_forecasts_filters_by_catch_location() {
+    test(|helper, _builder| async move {
+        let run = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+
+        helper
+            .adapter()
+            .add_weather_forecasts(vec![NewWeatherForecast::test_default(run, run)])
+            .await
+            .unwrap();
+
+        let all = helper
+            .app
+            .weather_forecasts(WeatherForecastParams::default())
+            .await
+            .unwrap();
+        let catch_location_id = all[0].catch_location_id.clone();
+
+        let forecasts = helper
+            .app
+            .weather_forecasts(WeatherForecastParams {
+                catch_location_ids: Some(vec![catch_location_id.clone()]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(forecasts.len(), 1);
+        assert_eq!(forecasts[0].catch_location_id, catch_location_id);
+        assert_eq!(forecasts[0].wind_speed_10m, Some(12.0));
+        assert_eq!(forecasts[0].air_pressure_at_sea_level, Some(101000.0));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_adding_a_weather_forecast_run_deletes_older_runs() {
+    test(|helper, _builder| async move {
+        let first_run = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let second_run = first_run + Duration::hours(6);
+
+        for run in [first_run, second_run] {
+            let forecasts = (0..4)
+                .map(|h| NewWeatherForecast::test_default(run, run + Duration::hours(h)))
+                .collect();
+
+            helper
+                .adapter()
+                .add_weather_forecasts(forecasts)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            helper.adapter().weather_forecast_reference_times().await,
+            vec![second_run]
+        );
+    })
+    .await;
+}